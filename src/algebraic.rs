@@ -0,0 +1,923 @@
+//! Real and complex algebraic numbers
+//!
+//! A `RealAlgebraic` is a real root of an integer polynomial, stored as its minimal polynomial $f$ (primitive, with
+//! a positive leading coefficient) and an isolating interval $(l, u)$ with rational endpoints, in which $f$ has
+//! exactly one root. Rational numbers are the ones with $\deg f = 1$, and for them $l = u$ is the number itself.
+//! For $\deg f \geq 2$ the endpoints are never roots, since $f$ is irreducible, so $f(l)$ and $f(u)$ have opposite
+//! signs and bisecting keeps the half where the sign changes. That's `refine`, and `to_float` refines until both
+//! endpoints round to the same `Float`, which is then the correctly rounded value.
+//!
+//! Comparison is exact: two numbers are equal when they have the same minimal polynomial and their intervals
+//! share the root, and otherwise refining both eventually separates them. The arithmetic goes through resultants.
+//! If $f(\alpha) = 0$ and $g(\beta) = 0$ with $\deg g = n$, then
+//! $$
+//! \alpha + \beta \text{ is a root of } \mathrm{Res}\_y(f(y), g(x - y)), \qquad
+//! \alpha \beta \text{ is a root of } \mathrm{Res}\_y(f(y), y^n g(x / y)),
+//! $$
+//! and the result is the root of one of the irreducible factors picked out by interval arithmetic on the
+//! intervals of $\alpha$ and $\beta$. The degrees multiply, so long chains of operations get slow.
+//!
+//! Real roots are isolated with Sturm sequences, bisecting down from the Cauchy bound. `Polynomial::real_roots` is
+//! the same thing for polynomials over $\mathbb{Z}$ and $\mathbb{Q}$.
+//!
+//! A `ComplexAlgebraic` is $a + bi$ with real algebraic $a$ and $b$, which is all of $\overline{\mathbb{Q}}$.
+//!
+//! Complex roots of an irreducible $f$ of degree $n$ come from two resultants. The real part of a root $z$ is
+//! $(z + \bar{z})/2$, a root of $\mathrm{Res}\_y(f(y), f(2x - y))$. The differences $z - w$ of roots are the roots
+//! of $\mathrm{Res}\_y(f(y), f(x + y))$, which is $x^n$ times a polynomial in $x^2$, and $z - \bar{z} = 2bi$, so the
+//! imaginary parts $b > 0$ are among the real roots of that polynomial at $x^2 = -4b^2$. Interval arithmetic on
+//! $f(a + bi)$ throws out the wrong pairs $(a, b)$ until there are as many left as pairs of nonreal roots.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::algebraic::RealAlgebraic;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::{Integer, Rational};
+//!
+//! let z = |v: Vec<i64>| Polynomial::from(v.into_iter().map(Integer::from).collect::<Vec<_>>());
+//! let roots = z(vec![-2, 0, 1]).real_roots();
+//! let sqrt2 = roots[1].clone();
+//! assert_eq!(sqrt2.clone() * sqrt2.clone(), RealAlgebraic::from(Integer::from(2)));
+//!
+//! let sqrt3 = RealAlgebraic::new(&z(vec![-3, 0, 1]), Rational::from(0), Rational::from(2)).unwrap();
+//! let sum = sqrt2.clone() + sqrt3;
+//! assert_eq!(sum.minimal_polynomial(), &z(vec![1, 0, -10, 0, 1]));
+//! assert!(sum > RealAlgebraic::from(Rational::from((314, 100))));
+//! // correctly rounded, so it agrees with the f64 square root
+//! assert_eq!(sqrt2.to_float(53).to_f64(), 2_f64.sqrt());
+//! ```
+use crate::error::ArithmeticError;
+use crate::factor::factor_over_z;
+use crate::poly::Polynomial;
+use crate::structure::{CommRing, EuclideanDomain, Field, GcdDomain, IntegralDomain, Monoid};
+use crate::{Integer, Rational};
+use core::cmp::Ordering;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+use rug::Float;
+
+/// A real algebraic number: the root of `poly` in the interval from `lower` to `upper`.
+#[derive(Clone, Debug)]
+pub struct RealAlgebraic {
+    poly: Polynomial<Integer>,
+    lower: Rational,
+    upper: Rational,
+}
+
+/// $f(x)$ for a rational $x$.
+fn eval_at(f: &Polynomial<Integer>, x: &Rational) -> Rational {
+    f.coeffs_take(f.deg() as usize + 1)
+        .iter()
+        .rev()
+        .fold(Rational::new(), |acc, c| acc * x + c)
+}
+
+/// Divides out the content and makes the leading coefficient positive.
+fn normalized(f: Polynomial<Integer>) -> Polynomial<Integer> {
+    let coeffs = f.coeffs_take(f.deg() as usize + 1);
+    let mut c = coeffs.iter().fold(Integer::new(), |acc, a| acc.gcd(a));
+    if f.lead_coeff() < 0 {
+        c = -c;
+    }
+    Polynomial::from(coeffs.into_iter().map(|a| a / &c).collect::<Vec<Integer>>())
+}
+
+/// $f(y)$ as a polynomial in $y$ with constant coefficients in $\mathbb{Z}[x]$.
+fn lift(f: &Polynomial<Integer>) -> Polynomial<Polynomial<Integer>> {
+    Polynomial::from(
+        f.coeffs_take(f.deg() as usize + 1)
+            .into_iter()
+            .map(|c| Polynomial::from(vec![c]))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// The Sturm sequence of a squarefree $f$, over $\mathbb{Q}$.
+fn sturm(f: &Polynomial<Integer>) -> Vec<Polynomial<Rational>> {
+    let f = Polynomial::from(
+        f.coeffs_take(f.deg() as usize + 1)
+            .into_iter()
+            .map(Rational::from)
+            .collect::<Vec<_>>(),
+    );
+    let mut seq = vec![f.clone(), f.derivative()];
+    loop {
+        let r = seq[seq.len() - 2].div_rem(&seq[seq.len() - 1]).1;
+        if r.is_zero() {
+            return seq;
+        }
+        seq.push(-r);
+    }
+}
+
+/// The number of sign changes of the Sturm sequence at $x$, skipping zeros.
+fn sign_changes(seq: &[Polynomial<Rational>], x: &Rational) -> usize {
+    let signs: Vec<Ordering> = seq
+        .iter()
+        .map(|g| g.eval(x.clone()).cmp0())
+        .filter(|s| *s != Ordering::Equal)
+        .collect();
+    signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+/// Isolating intervals for the real roots of an irreducible $f$ of degree at least 2. All the roots are in
+/// $(-B, B)$ for the Cauchy bound $B = 1 + \max\_i |a\_i / a\_n|$, and Sturm's theorem counts the ones in each half.
+/// Left halves go first, so the roots come out in increasing order.
+fn isolate(f: &Polynomial<Integer>) -> Vec<RealAlgebraic> {
+    let seq = sturm(f);
+    let coeffs = f.coeffs_take(f.deg() as usize + 1);
+    let (lead, rest) = coeffs.split_last().unwrap();
+    let bound: Rational = rest
+        .iter()
+        .map(|a| Rational::from((a.clone().abs(), lead.clone())))
+        .fold(Rational::new(), |acc, a| acc.max(a))
+        + 1;
+    let mut intervals = vec![(-bound.clone(), bound)];
+    let mut roots = vec![];
+    while let Some((a, b)) = intervals.pop() {
+        match sign_changes(&seq, &a) - sign_changes(&seq, &b) {
+            0 => {}
+            1 => roots.push(RealAlgebraic {
+                poly: f.clone(),
+                lower: a,
+                upper: b,
+            }),
+            _ => {
+                let mid: Rational = Rational::from(&a + &b) / 2;
+                intervals.push((mid.clone(), b));
+                intervals.push((a, mid));
+            }
+        }
+    }
+    roots
+}
+
+/// $f(s)$ by Horner's rule, for $s \in \mathbb{Z}[x][y]$.
+fn substitute(
+    f: &Polynomial<Integer>,
+    s: &Polynomial<Polynomial<Integer>>,
+) -> Polynomial<Polynomial<Integer>> {
+    lift(f)
+        .coeffs_take(f.deg() as usize + 1)
+        .into_iter()
+        .rev()
+        .fold(Polynomial::zero(), |acc, c| {
+            acc * s.clone() + Polynomial::from(vec![c])
+        })
+}
+
+/// The product of two intervals.
+fn interval_mul(a: &(Rational, Rational), b: &(Rational, Rational)) -> (Rational, Rational) {
+    let products = [
+        Rational::from(&a.0 * &b.0),
+        Rational::from(&a.0 * &b.1),
+        Rational::from(&a.1 * &b.0),
+        Rational::from(&a.1 * &b.1),
+    ];
+    let lower = products.iter().min().unwrap().clone();
+    let upper = products.iter().max().unwrap().clone();
+    (lower, upper)
+}
+
+/// Whether $f(a + bi)$ can be 0 for $a$ and $b$ in their intervals, by interval arithmetic on Horner's rule.
+fn may_vanish(f: &Polynomial<Integer>, a: &RealAlgebraic, b: &RealAlgebraic) -> bool {
+    let (x, y) = (
+        (a.lower.clone(), a.upper.clone()),
+        (b.lower.clone(), b.upper.clone()),
+    );
+    let zero = (Rational::new(), Rational::new());
+    let (re, im) = f.coeffs_take(f.deg() as usize + 1).iter().rev().fold(
+        (zero.clone(), zero),
+        |(re, im), c| {
+            let (re_x, im_y) = (interval_mul(&re, &x), interval_mul(&im, &y));
+            let (re_y, im_x) = (interval_mul(&re, &y), interval_mul(&im, &x));
+            (
+                (
+                    Rational::from(&re_x.0 - &im_y.1) + c,
+                    Rational::from(&re_x.1 - &im_y.0) + c,
+                ),
+                (
+                    Rational::from(&re_y.0 + &im_x.0),
+                    Rational::from(&re_y.1 + &im_x.1),
+                ),
+            )
+        },
+    );
+    re.0 <= 0 && re.1 >= 0 && im.0 <= 0 && im.1 >= 0
+}
+
+/// The roots $a + bi$ with $b > 0$ of an irreducible $f$ with `real` real roots, see the module docs.
+fn upper_roots(f: &Polynomial<Integer>, real: usize) -> Vec<ComplexAlgebraic> {
+    let n = f.deg() as usize;
+    if real == n {
+        return vec![];
+    }
+    let z = |v: Vec<i64>| Polynomial::from(v.into_iter().map(Integer::from).collect::<Vec<_>>());
+    let two_x_minus_y = Polynomial::from(vec![z(vec![0, 2]), z(vec![-1])]);
+    let x_plus_y = Polynomial::from(vec![z(vec![0, 1]), z(vec![1])]);
+    let sums = lift(f).resultant(&substitute(f, &two_x_minus_y));
+    let differences = lift(f).resultant(&substitute(f, &x_plus_y));
+    // the coefficient of x^(n + 2m) in the differences goes to x^(2m), times (-4)^m
+    let coeffs = differences.coeffs_take(differences.deg() as usize + 1);
+    let mut squares = vec![Integer::new(); coeffs.len() - n];
+    let mut scale = Integer::from(1);
+    for m in 0..=(coeffs.len() - 1 - n) / 2 {
+        squares[2 * m] = Integer::from(&coeffs[n + 2 * m] * &scale);
+        scale *= -4;
+    }
+
+    let mut re = RealAlgebraic::roots_of(&sums);
+    let mut im: Vec<RealAlgebraic> = RealAlgebraic::roots_of(&Polynomial::from(squares))
+        .into_iter()
+        .filter(|b| b.cmp0() == Ordering::Greater)
+        .collect();
+    let mut pairs: Vec<(usize, usize)> = (0..re.len())
+        .flat_map(|i| (0..im.len()).map(move |j| (i, j)))
+        .collect();
+    loop {
+        pairs.retain(|(i, j)| may_vanish(f, &re[*i], &im[*j]));
+        if pairs.len() == (n - real) / 2 {
+            return pairs
+                .into_iter()
+                .map(|(i, j)| ComplexAlgebraic::new(re[i].clone(), im[j].clone()))
+                .collect();
+        }
+        re.iter_mut().for_each(RealAlgebraic::bisect);
+        im.iter_mut().for_each(RealAlgebraic::bisect);
+    }
+}
+
+impl RealAlgebraic {
+    /// The root of $f$ in the open interval $(l, u)$, if there's exactly one (not counting multiplicity).
+    pub fn new(f: &Polynomial<Integer>, lower: Rational, upper: Rational) -> Option<Self> {
+        let (lower, upper) = (Self::from(lower), Self::from(upper));
+        let mut roots: Vec<Self> = Self::roots_of(f)
+            .into_iter()
+            .filter(|r| *r > lower && *r < upper)
+            .collect();
+        match roots.len() {
+            1 => roots.pop(),
+            _ => None,
+        }
+    }
+
+    /// The distinct real roots of $f$, in increasing order. Panics if $f = 0$.
+    pub fn roots_of(f: &Polynomial<Integer>) -> Vec<Self> {
+        assert!(!f.is_zero(), "every number is a root of 0");
+        let mut roots: Vec<Self> = factor_over_z(f)
+            .1
+            .into_iter()
+            .flat_map(|(g, _)| match g.deg() {
+                1 => {
+                    let c = g.coeffs_take(2);
+                    vec![Self::from(Rational::from((-c[0].clone(), c[1].clone())))]
+                }
+                _ => isolate(&g),
+            })
+            .collect();
+        roots.sort();
+        roots
+    }
+
+    /// The minimal polynomial over $\mathbb{Z}$: irreducible, primitive, and with a positive leading coefficient.
+    pub fn minimal_polynomial(&self) -> &Polynomial<Integer> {
+        &self.poly
+    }
+
+    /// The degree of the minimal polynomial, which is $[\mathbb{Q}(\alpha) : \mathbb{Q}]$.
+    pub fn degree(&self) -> usize {
+        self.poly.deg() as usize
+    }
+
+    /// The current isolating interval. It only gets smaller with `refine`.
+    pub fn interval(&self) -> (&Rational, &Rational) {
+        (&self.lower, &self.upper)
+    }
+
+    pub fn is_rational(&self) -> bool {
+        self.degree() == 1
+    }
+
+    pub fn to_rational(&self) -> Option<Rational> {
+        self.is_rational().then(|| self.lower.clone())
+    }
+
+    /// Halves the interval, keeping the half where $f$ changes sign. Rationals are already exact.
+    fn bisect(&mut self) {
+        if self.is_rational() {
+            return;
+        }
+        let mid: Rational = Rational::from(&self.lower + &self.upper) / 2;
+        if eval_at(&self.poly, &mid).cmp0() == eval_at(&self.poly, &self.lower).cmp0() {
+            self.lower = mid;
+        } else {
+            self.upper = mid;
+        }
+    }
+
+    /// Refines until 0 isn't in the closed interval, which doesn't terminate for 0 itself.
+    fn avoid_zero(&mut self) {
+        while self.lower <= 0 && self.upper >= 0 {
+            self.bisect();
+        }
+    }
+
+    /// Bisects until the interval has width at most `width`, which should be positive.
+    pub fn refine(&mut self, width: &Rational) {
+        while Rational::from(&self.upper - &self.lower) > *width {
+            self.bisect();
+        }
+    }
+
+    /// The sign, as an `Ordering` against 0 like `Rational::cmp0`.
+    pub fn cmp0(&self) -> Ordering {
+        if self.is_rational() {
+            return self.lower.cmp0();
+        }
+        let mut a = self.clone();
+        a.avoid_zero();
+        a.lower.cmp0().max(a.upper.cmp0())
+    }
+
+    /// The value rounded to the nearest `Float` with `prec` bits.
+    pub fn to_float(&self, prec: u32) -> Float {
+        let mut a = self.clone();
+        loop {
+            let (lower, upper) = (
+                Float::with_val(prec, &a.lower),
+                Float::with_val(prec, &a.upper),
+            );
+            if lower == upper {
+                return lower;
+            }
+            a.bisect();
+        }
+    }
+
+    /// The root of $h$ that `op` on the intervals of $a$ and $b$ converges to. Interval arithmetic always
+    /// contains the true value, so the right root is never thrown out, and refining everything eventually
+    /// separates it from the others.
+    fn select<F>(h: &Polynomial<Integer>, mut a: Self, mut b: Self, op: F) -> Self
+    where
+        F: Fn(&Self, &Self) -> (Rational, Rational),
+    {
+        let mut candidates = Self::roots_of(h);
+        loop {
+            let (lower, upper) = op(&a, &b);
+            candidates.retain(|c| c.lower <= upper && lower <= c.upper);
+            if candidates.len() == 1 {
+                return candidates.pop().unwrap();
+            }
+            a.bisect();
+            b.bisect();
+            candidates.iter_mut().for_each(Self::bisect);
+        }
+    }
+}
+
+impl From<Rational> for RealAlgebraic {
+    fn from(r: Rational) -> Self {
+        Self {
+            poly: Polynomial::from(vec![-r.numer().clone(), r.denom().clone()]),
+            lower: r.clone(),
+            upper: r,
+        }
+    }
+}
+
+impl From<Integer> for RealAlgebraic {
+    fn from(n: Integer) -> Self {
+        Self::from(Rational::from(n))
+    }
+}
+
+/// Same minimal polynomial, and the intersection of the intervals contains the root.
+impl PartialEq for RealAlgebraic {
+    fn eq(&self, other: &Self) -> bool {
+        if self.poly != other.poly {
+            return false;
+        }
+        if self.is_rational() {
+            return self.lower == other.lower;
+        }
+        let lower = (&self.lower).max(&other.lower);
+        let upper = (&self.upper).min(&other.upper);
+        lower < upper && eval_at(&self.poly, lower).cmp0() != eval_at(&self.poly, upper).cmp0()
+    }
+}
+
+impl Eq for RealAlgebraic {}
+
+impl PartialOrd for RealAlgebraic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Refines both until the intervals are disjoint. They only touch at an endpoint of an irrational number, which
+/// isn't the number itself.
+impl Ord for RealAlgebraic {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        let (mut a, mut b) = (self.clone(), other.clone());
+        loop {
+            if a.upper <= b.lower {
+                return Ordering::Less;
+            }
+            if b.upper <= a.lower {
+                return Ordering::Greater;
+            }
+            a.bisect();
+            b.bisect();
+        }
+    }
+}
+
+impl Zero for RealAlgebraic {
+    fn zero() -> Self {
+        Self::from(Rational::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_rational() && self.lower == 0
+    }
+}
+
+impl One for RealAlgebraic {
+    fn one() -> Self {
+        Self::from(Rational::from(1))
+    }
+}
+
+/// $-\alpha$ is a root of $f(-x)$.
+impl Neg for RealAlgebraic {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let coeffs = self.poly.coeffs_take(self.degree() + 1);
+        let poly = Polynomial::from(
+            coeffs
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| if i % 2 == 1 { -c } else { c })
+                .collect::<Vec<Integer>>(),
+        );
+        Self {
+            poly: normalized(poly),
+            lower: -self.upper,
+            upper: -self.lower,
+        }
+    }
+}
+
+impl Add for RealAlgebraic {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_rational(), rhs.to_rational()) {
+            return Self::from(a + b);
+        }
+        // g(x - y) by Horner's rule in x - y
+        let x_minus_y = Polynomial::from(vec![
+            Polynomial::from(vec![Integer::new(), Integer::from(1)]),
+            Polynomial::from(vec![Integer::from(-1)]),
+        ]);
+        let g = lift(&rhs.poly)
+            .coeffs_take(rhs.degree() + 1)
+            .into_iter()
+            .rev()
+            .fold(Polynomial::zero(), |acc, c| {
+                acc * x_minus_y.clone() + Polynomial::from(vec![c])
+            });
+        let h = lift(&self.poly).resultant(&g);
+        Self::select(&h, self, rhs, |a, b| {
+            (
+                Rational::from(&a.lower + &b.lower),
+                Rational::from(&a.upper + &b.upper),
+            )
+        })
+    }
+}
+
+impl Sub for RealAlgebraic {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Mul for RealAlgebraic {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        if let (Some(a), Some(b)) = (self.to_rational(), rhs.to_rational()) {
+            return Self::from(a * b);
+        }
+        // y^n g(x/y) = sum of g_i x^i y^(n - i), whose leading coefficient g_0 isn't 0
+        let n = rhs.degree();
+        let coeffs = rhs.poly.coeffs_take(n + 1);
+        let g = Polynomial::from(
+            (0..=n)
+                .map(|k| {
+                    let mut c = vec![Integer::new(); n - k];
+                    c.push(coeffs[n - k].clone());
+                    Polynomial::from(c)
+                })
+                .collect::<Vec<_>>(),
+        );
+        let h = lift(&self.poly).resultant(&g);
+        Self::select(&h, self, rhs, |a, b| {
+            let products = [
+                Rational::from(&a.lower * &b.lower),
+                Rational::from(&a.lower * &b.upper),
+                Rational::from(&a.upper * &b.lower),
+                Rational::from(&a.upper * &b.upper),
+            ];
+            let lower = products.iter().min().unwrap().clone();
+            let upper = products.iter().max().unwrap().clone();
+            (lower, upper)
+        })
+    }
+}
+
+impl Div for RealAlgebraic {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv().expect("divide by zero")
+    }
+}
+
+impl Monoid for RealAlgebraic {}
+impl CommRing for RealAlgebraic {}
+
+/// The normal numbers are 0 and 1, like for `Rational`.
+impl IntegralDomain for RealAlgebraic {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.clone() / other.clone())
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => self.clone(),
+        }
+    }
+}
+
+impl GcdDomain for RealAlgebraic {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl EuclideanDomain for RealAlgebraic {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "divide by zero");
+        (self.clone() / other.clone(), Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+/// $1/\alpha$ is a root of the reversed polynomial $x^n f(1/x)$, in $(1/u, 1/l)$ once 0 is out of $(l, u)$.
+impl Field for RealAlgebraic {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        if self.is_zero() {
+            return Err(ArithmeticError::DivideByZero);
+        }
+        if let Some(r) = self.to_rational() {
+            return Ok(Self::from(r.recip()));
+        }
+        let mut a = self.clone();
+        a.avoid_zero();
+        let mut coeffs = a.poly.coeffs_take(a.degree() + 1);
+        coeffs.reverse();
+        Ok(Self {
+            poly: normalized(Polynomial::from(coeffs)),
+            lower: a.upper.recip(),
+            upper: a.lower.recip(),
+        })
+    }
+}
+
+/// A complex algebraic number $a + bi$, with $a$ and $b$ real algebraic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComplexAlgebraic {
+    re: RealAlgebraic,
+    im: RealAlgebraic,
+}
+
+impl ComplexAlgebraic {
+    pub fn new(re: RealAlgebraic, im: RealAlgebraic) -> Self {
+        Self { re, im }
+    }
+
+    /// The imaginary unit $i$.
+    pub fn i() -> Self {
+        Self::new(RealAlgebraic::zero(), RealAlgebraic::one())
+    }
+
+    pub fn re(&self) -> &RealAlgebraic {
+        &self.re
+    }
+
+    pub fn im(&self) -> &RealAlgebraic {
+        &self.im
+    }
+
+    pub fn is_real(&self) -> bool {
+        self.im.is_zero()
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.re.clone(), -self.im.clone())
+    }
+
+    /// $|z|^2 = a^2 + b^2$, which is real algebraic when $z$ is (unlike $|z|$, which needs a square root).
+    pub fn norm(&self) -> RealAlgebraic {
+        self.re.clone() * self.re.clone() + self.im.clone() * self.im.clone()
+    }
+
+    /// The distinct complex roots of $f$, sorted by real part and then imaginary part. Panics if $f = 0$.
+    pub fn roots_of(f: &Polynomial<Integer>) -> Vec<Self> {
+        assert!(!f.is_zero(), "every number is a root of 0");
+        let mut roots: Vec<Self> = factor_over_z(f)
+            .1
+            .into_iter()
+            .flat_map(|(g, _)| {
+                let real = RealAlgebraic::roots_of(&g);
+                let upper = upper_roots(&g, real.len());
+                real.into_iter()
+                    .map(Self::from)
+                    .chain(upper.iter().map(Self::conjugate))
+                    .chain(upper.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        roots.sort_by(|z, w| z.re.cmp(&w.re).then_with(|| z.im.cmp(&w.im)));
+        roots
+    }
+}
+
+impl From<RealAlgebraic> for ComplexAlgebraic {
+    fn from(re: RealAlgebraic) -> Self {
+        Self::new(re, RealAlgebraic::zero())
+    }
+}
+
+impl Zero for ComplexAlgebraic {
+    fn zero() -> Self {
+        Self::from(RealAlgebraic::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+}
+
+impl One for ComplexAlgebraic {
+    fn one() -> Self {
+        Self::from(RealAlgebraic::one())
+    }
+}
+
+impl Add for ComplexAlgebraic {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Neg for ComplexAlgebraic {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Sub for ComplexAlgebraic {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for ComplexAlgebraic {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let re = self.re.clone() * rhs.re.clone() - self.im.clone() * rhs.im.clone();
+        let im = self.re * rhs.im + self.im * rhs.re;
+        Self::new(re, im)
+    }
+}
+
+impl Div for ComplexAlgebraic {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv().expect("divide by zero")
+    }
+}
+
+impl Monoid for ComplexAlgebraic {}
+impl CommRing for ComplexAlgebraic {}
+
+/// The normal numbers are 0 and 1.
+impl IntegralDomain for ComplexAlgebraic {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.clone() / other.clone())
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => self.clone(),
+        }
+    }
+}
+
+impl GcdDomain for ComplexAlgebraic {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl EuclideanDomain for ComplexAlgebraic {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "divide by zero");
+        (self.clone() / other.clone(), Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+/// $1/z = \bar{z} / |z|^2$.
+impl Field for ComplexAlgebraic {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        let n = self.norm().inv()?;
+        let z = self.conjugate();
+        Ok(Self::new(z.re * n.clone(), z.im * n))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zpoly;
+
+    fn q(a: i64, b: i64) -> RealAlgebraic {
+        RealAlgebraic::from(Rational::from((a, b)))
+    }
+
+    fn sqrt(n: i64) -> RealAlgebraic {
+        RealAlgebraic::new(
+            &zpoly(vec![-n, 0, 1]),
+            Rational::new(),
+            Rational::from(n + 1),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roots() {
+        // (x^2 - 2)(2x - 1)(x^2 + 1)
+        let f = zpoly(vec![-2, 0, 1]) * zpoly(vec![-1, 2]) * zpoly(vec![1, 0, 1]);
+        let roots = f.real_roots();
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[0], -sqrt(2));
+        assert_eq!(roots[1], q(1, 2));
+        assert_eq!(roots[2], sqrt(2));
+        assert!(roots.windows(2).all(|w| w[0] < w[1]));
+        // x^3 - x - 1 has one real root, the plastic number
+        let rho = &zpoly(vec![-1, -1, 0, 1]).real_roots()[0];
+        assert_eq!(rho.degree(), 3);
+        assert_eq!(rho.to_float(30).to_f64() as f32, 1.324_718);
+        let over_q = Polynomial::from(vec![
+            Rational::from((-1, 4)),
+            Rational::new(),
+            Rational::from(1),
+        ]);
+        assert_eq!(over_q.real_roots(), vec![q(-1, 2), q(1, 2)]);
+        assert!(zpoly(vec![1, 0, 1]).real_roots().is_empty());
+        assert_eq!(
+            RealAlgebraic::new(
+                &zpoly(vec![-2, 0, 1]),
+                Rational::from(-2),
+                Rational::from(2)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let (a, b) = (sqrt(2), sqrt(3));
+        assert!(a < b);
+        assert!(a > q(1414, 1000) && a < q(1415, 1000));
+        assert_eq!(a.cmp0(), Ordering::Greater);
+        assert_eq!((-a.clone()).cmp0(), Ordering::Less);
+        assert_eq!(RealAlgebraic::zero().cmp0(), Ordering::Equal);
+        // the same number with different intervals
+        let mut c = a.clone();
+        c.refine(&Rational::from((1, 1000)));
+        assert_eq!(c, a);
+        assert!(Rational::from(c.interval().1 - c.interval().0) <= Rational::from((1, 1000)));
+        assert_eq!(sqrt(4), q(2, 1));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b) = (sqrt(2), sqrt(3));
+        assert_eq!(a.clone() * a.clone(), q(2, 1));
+        assert_eq!(a.clone() * b.clone(), sqrt(6));
+        assert_eq!(a.clone() - a.clone(), RealAlgebraic::zero());
+        let s = a.clone() + b.clone();
+        assert_eq!(s.minimal_polynomial(), &zpoly(vec![1, 0, -10, 0, 1]));
+        // (sqrt 2 + sqrt 3)^2 = 5 + 2 sqrt 6
+        assert_eq!(s.clone() * s.clone(), q(5, 1) + q(2, 1) * sqrt(6));
+        // 1/(sqrt 3 - sqrt 2) = sqrt 3 + sqrt 2
+        assert_eq!((b.clone() - a.clone()).inv().unwrap(), s);
+        assert_eq!(q(1, 1) / a.clone(), a.clone() / q(2, 1));
+        assert!(RealAlgebraic::zero().inv().is_err());
+        assert_eq!(a.clone() + q(1, 2) - a, q(1, 2));
+        assert_eq!(s.to_float(53).to_f64(), 2_f64.sqrt() + 3_f64.sqrt());
+    }
+
+    #[test]
+    fn test_complex() {
+        let i = ComplexAlgebraic::i();
+        assert_eq!(i.clone() * i.clone(), -ComplexAlgebraic::one());
+        // the primitive cube root of unity (-1 + sqrt(-3))/2
+        let w = ComplexAlgebraic::new(q(-1, 2), sqrt(3) * q(1, 2));
+        assert_eq!(w.clone() * w.clone() * w.clone(), ComplexAlgebraic::one());
+        assert_eq!(w.clone() * w.clone(), w.conjugate());
+        assert_eq!(w.norm(), RealAlgebraic::one());
+        let z = ComplexAlgebraic::new(sqrt(2), q(1, 1));
+        assert_eq!(z.clone() / z.clone(), ComplexAlgebraic::one());
+        assert!((z.clone() * z.conjugate()).is_real());
+    }
+
+    #[test]
+    fn test_complex_roots() {
+        let c = |a: RealAlgebraic, b: RealAlgebraic| ComplexAlgebraic::new(a, b);
+        // (x^2 + 1)(x - 2)
+        let roots = (zpoly(vec![1, 0, 1]) * zpoly(vec![-2, 1])).roots();
+        assert_eq!(
+            roots,
+            vec![
+                -ComplexAlgebraic::i(),
+                ComplexAlgebraic::i(),
+                c(q(2, 1), q(0, 1))
+            ]
+        );
+        // the cube roots of unity
+        let w = c(q(-1, 2), sqrt(3) * q(1, 2));
+        assert_eq!(
+            zpoly(vec![-1, 0, 0, 1]).roots(),
+            vec![w.conjugate(), w, ComplexAlgebraic::one()]
+        );
+        // the primitive 8th roots of unity, with two roots for each real part
+        let roots = zpoly(vec![1, 0, 0, 0, 1]).roots();
+        assert_eq!(roots.len(), 4);
+        let h = sqrt(2) * q(1, 2);
+        assert_eq!(roots[0], c(-h.clone(), -h.clone()));
+        assert_eq!(roots[3], c(h.clone(), h.clone()));
+        assert!(roots
+            .iter()
+            .all(|z| z.clone() * z.clone() * z.clone() * z.clone() == -ComplexAlgebraic::one()));
+        // x^3 - x - 1 has one real root and a pair of complex ones
+        let roots = zpoly(vec![-1, -1, 0, 1]).roots();
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots.iter().filter(|z| z.is_real()).count(), 1);
+        assert_eq!(roots[0], roots[1].conjugate());
+        let f = Polynomial::from(vec![
+            Rational::from((1, 4)),
+            Rational::new(),
+            Rational::from(1),
+        ]);
+        assert_eq!(f.roots(), vec![c(q(0, 1), q(-1, 2)), c(q(0, 1), q(1, 2))]);
+    }
+}
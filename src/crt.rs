@@ -0,0 +1,273 @@
+//! The Chinese remainder theorem
+//!
+//! In a Euclidean domain $R$, the congruences $x \equiv a\_i \pmod{m\_i}$ have a common solution exactly when
+//! $a\_i \equiv a\_j \pmod{\gcd(m\_i, m\_j)}$ for all $i, j$, and then it's unique mod $\mathrm{lcm}(m\_i)$. `crt`
+//! merges them two at a time: with $g = \gcd(m, n) = sm + tn$, the solution of $x \equiv a \pmod m$ and
+//! $x \equiv b \pmod n$ is $x = a + sm(b - a)/g$, which only exists when $g \mid b - a$.
+//!
+//! For pairwise coprime moduli with product $M$ this is the isomorphism $R/M \cong \prod R/m\_i$, and the inverse
+//! map is linear: $x = \sum a\_i e\_i$ for the basis $e\_i \equiv 1 \pmod{m\_i}$, $e\_i \equiv 0 \pmod{m\_j}$. That's
+//! worth precomputing in a `CrtBasis` when the same moduli get used over and over (like in modular algorithms).
+//! For $\mathbb{Z}/mn \cong \mathbb{Z}/m \times \mathbb{Z}/n$ with the moduli known at compile time, `crt_homo`
+//! and `crt_inverse_homo` give the two directions as `Homo`s, which are ring isomorphisms too.
+//!
+//! Over $F[x]$, the CRT is interpolation: the moduli $x - c\_i$ give the polynomial with values $a\_i$ at $c\_i$.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::crt::{crt, crt_homo, CrtBasis};
+//! use bored_algebra::products::product::DirectProduct;
+//! use bored_algebra::zmod::Zmod;
+//! use bored_algebra::Integer;
+//!
+//! let ints = |v: Vec<i64>| v.into_iter().map(Integer::from).collect::<Vec<_>>();
+//! // x = 2 mod 3, 3 mod 5, 2 mod 7
+//! let (x, m) = crt(&ints(vec![2, 3, 2]), &ints(vec![3, 5, 7])).unwrap();
+//! assert_eq!((x, m), (Integer::from(23), Integer::from(105)));
+//! // the moduli don't need to be coprime, if the residues agree: x = 5 mod 6 and 3 mod 4
+//! assert_eq!(crt(&ints(vec![5, 3]), &ints(vec![6, 4])).unwrap(), (Integer::from(11), Integer::from(12)));
+//! assert!(crt(&ints(vec![5, 2]), &ints(vec![6, 4])).is_err());
+//!
+//! let basis = CrtBasis::new(&ints(vec![3, 5, 7])).unwrap();
+//! assert_eq!(basis.combine(&ints(vec![2, 3, 2])), 23);
+//!
+//! let split = crt_homo::<15, 3, 5>();
+//! assert_eq!(split.apply(Zmod::new(7)), DirectProduct(Zmod::new(1), Zmod::new(2)));
+//! ```
+use crate::error::ArithmeticError;
+use crate::module::Homo;
+use crate::products::product::DirectProduct;
+use crate::structure::{ext_gcd, EuclideanDomain};
+use crate::zmod::Zmod;
+use crate::Integer;
+
+/// $x \bmod m$, as the remainder of division by the normal $m$.
+fn reduce<R: EuclideanDomain>(x: &R, m: &R) -> R {
+    x.div_rem(m).1
+}
+
+/// The $x$ with $x \equiv a\_i \pmod{m\_i}$, with the normal $\mathrm{lcm}(m\_i)$ that makes it unique, as
+/// $(x, \mathrm{lcm})$. $x$ is reduced mod the lcm. Fails with `NoSolution` if the residues disagree mod the gcd of
+/// two moduli. Panics if the lengths differ or a modulus is zero.
+pub fn crt<R: EuclideanDomain>(residues: &[R], moduli: &[R]) -> Result<(R, R), ArithmeticError> {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "need a residue for each modulus"
+    );
+    let (mut x, mut m) = (R::zero(), R::one());
+    for (b, n) in residues.iter().zip(moduli) {
+        assert!(!n.is_zero(), "the moduli can't be zero");
+        let (g, s, _) = ext_gcd(&m, n);
+        let q = (b.clone() - x.clone())
+            .div_exact(&g)
+            .ok_or(ArithmeticError::NoSolution)?;
+        let lcm = (m.div_exact(&g).unwrap() * n.clone()).normalize();
+        x = reduce(&(x + m * s * q), &lcm);
+        m = lcm;
+    }
+    Ok((x, m))
+}
+
+/// The CRT basis for pairwise coprime moduli $m\_1, \ldots, m\_k$ with product $M$: the $e\_i$ with
+/// $e\_i \equiv 1 \pmod{m\_i}$ and $e\_i \equiv 0 \pmod{m\_j}$ for $j \neq i$, which is
+/// $e\_i = (M/m\_i) \cdot ((M/m\_i)^{-1} \bmod m\_i)$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrtBasis<R: EuclideanDomain> {
+    moduli: Vec<R>,
+    modulus: R,
+    basis: Vec<R>,
+}
+
+impl<R: EuclideanDomain> CrtBasis<R> {
+    /// The basis for nonzero `moduli`, or `NotCoprime` if they aren't pairwise coprime.
+    pub fn new(moduli: &[R]) -> Result<Self, ArithmeticError> {
+        assert!(
+            moduli.iter().all(|m| !m.is_zero()),
+            "the moduli can't be zero"
+        );
+        let modulus = moduli
+            .iter()
+            .fold(R::one(), |acc, m| acc * m.clone())
+            .normalize();
+        let mut basis = vec![];
+        for m in moduli {
+            let cofactor = modulus.div_exact(m).unwrap();
+            let (g, s, _) = ext_gcd(&cofactor, m);
+            if !g.is_one() {
+                return Err(ArithmeticError::NotCoprime);
+            }
+            basis.push(reduce(&(cofactor * s), &modulus));
+        }
+        Ok(Self {
+            moduli: moduli.to_vec(),
+            modulus,
+            basis,
+        })
+    }
+
+    pub fn moduli(&self) -> &[R] {
+        &self.moduli
+    }
+
+    /// $M$, the normal product of the moduli.
+    pub fn modulus(&self) -> &R {
+        &self.modulus
+    }
+
+    /// $e\_1, \ldots, e\_k$.
+    pub fn basis(&self) -> &[R] {
+        &self.basis
+    }
+
+    /// The $x$ mod $M$ with $x \equiv a\_i \pmod{m\_i}$, which is $\sum a\_i e\_i$. Panics unless there's a residue
+    /// for each modulus.
+    pub fn combine(&self, residues: &[R]) -> R {
+        assert_eq!(
+            residues.len(),
+            self.moduli.len(),
+            "need a residue for each modulus"
+        );
+        let x = residues
+            .iter()
+            .zip(&self.basis)
+            .fold(R::zero(), |acc, (a, e)| acc + a.clone() * e.clone());
+        reduce(&x, &self.modulus)
+    }
+
+    /// $(x \bmod m\_1, \ldots, x \bmod m\_k)$, the other direction.
+    pub fn split(&self, x: &R) -> Vec<R> {
+        self.moduli.iter().map(|m| reduce(x, m)).collect()
+    }
+}
+
+const fn gcd_word(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Carries the compile time check that $\mathbb{Z}/MN \cong \mathbb{Z}/M \times \mathbb{Z}/N$.
+struct Splitting<const MN: u64, const M: u64, const N: u64>;
+
+impl<const MN: u64, const M: u64, const N: u64> Splitting<MN, M, N> {
+    const ASSERT_SPLITS: () = assert!(
+        M as u128 * N as u128 == MN as u128 && gcd_word(M, N) == 1,
+        "Z/MN is only Z/M x Z/N for coprime M and N with product MN"
+    );
+}
+
+/// The isomorphism $\mathbb{Z}/MN \to \mathbb{Z}/M \times \mathbb{Z}/N$, $x \mapsto (x \bmod M, x \bmod N)$, for
+/// coprime $M$ and $N$. That's checked at compile time.
+pub fn crt_homo<const MN: u64, const M: u64, const N: u64>(
+) -> Homo<Integer, Zmod<MN>, DirectProduct<Zmod<M>, Zmod<N>>> {
+    let () = Splitting::<MN, M, N>::ASSERT_SPLITS;
+    Homo::new(Box::new(|x: Zmod<MN>| {
+        DirectProduct(Zmod::new(x.value()), Zmod::new(x.value()))
+    }))
+}
+
+/// The inverse of `crt_homo`, $(a, b) \mapsto a e\_1 + b e\_2$ with the CRT basis $e\_1, e\_2$ of $\mathbb{Z}/MN$.
+pub fn crt_inverse_homo<const MN: u64, const M: u64, const N: u64>(
+) -> Homo<Integer, DirectProduct<Zmod<M>, Zmod<N>>, Zmod<MN>> {
+    let () = Splitting::<MN, M, N>::ASSERT_SPLITS;
+    let basis = CrtBasis::new(&[Integer::from(M), Integer::from(N)]).unwrap();
+    let e: Vec<Zmod<MN>> = basis.basis().iter().map(Zmod::from).collect();
+    Homo::new(Box::new(move |x: DirectProduct<Zmod<M>, Zmod<N>>| {
+        Zmod::new(x.0.value()) * e[0] + Zmod::new(x.1.value()) * e[1]
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::poly::Polynomial;
+    use crate::test_helpers::ints;
+    use crate::Rational;
+
+    fn qpoly(v: Vec<i64>) -> Polynomial<Rational> {
+        Polynomial::from(v.into_iter().map(Rational::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_crt_integers() {
+        let moduli = ints(vec![4, 9, 25, 7]);
+        let basis = CrtBasis::new(&moduli).unwrap();
+        assert_eq!(*basis.modulus(), 6300);
+        for x in (0..6300).step_by(37).map(Integer::from) {
+            let residues = basis.split(&x);
+            assert_eq!(basis.combine(&residues), x);
+            assert_eq!(crt(&residues, &moduli).unwrap(), (x, Integer::from(6300)));
+        }
+        for (i, e) in basis.basis().iter().enumerate() {
+            let expected = (0..4).map(|j| (i == j) as i64).collect();
+            assert_eq!(basis.split(e), ints(expected));
+        }
+        // negative residues and moduli
+        assert_eq!(
+            crt(&ints(vec![-1, -1]), &ints(vec![-5, 7])).unwrap(),
+            (Integer::from(34), Integer::from(35))
+        );
+        assert!(matches!(
+            CrtBasis::new(&ints(vec![6, 10])),
+            Err(ArithmeticError::NotCoprime)
+        ));
+
+        // non-coprime moduli: x = 3 mod 10, 8 mod 15, 13 mod 20 is x = 53 mod 60
+        assert_eq!(
+            crt(&ints(vec![3, 8, 13]), &ints(vec![10, 15, 20])).unwrap(),
+            (Integer::from(53), Integer::from(60))
+        );
+        assert!(matches!(
+            crt(&ints(vec![3, 8, 14]), &ints(vec![10, 15, 20])),
+            Err(ArithmeticError::NoSolution)
+        ));
+        assert_eq!(
+            crt::<Integer>(&[], &[]).unwrap(),
+            (Integer::new(), Integer::from(1))
+        );
+    }
+
+    #[test]
+    fn test_crt_polynomials() {
+        // interpolation: the values 1, 2, 5 at 0, 1, 2 give x^2 + 1 (up to constant moduli)
+        let residues = vec![qpoly(vec![1]), qpoly(vec![2]), qpoly(vec![5])];
+        let moduli = vec![qpoly(vec![0, 1]), qpoly(vec![-1, 1]), qpoly(vec![-4, 2])];
+        let (f, m) = crt(&residues, &moduli).unwrap();
+        assert_eq!(f, qpoly(vec![1, 0, 1]));
+        assert_eq!(m, qpoly(vec![0, 2, -3, 1]));
+        let basis = CrtBasis::new(&moduli).unwrap();
+        assert_eq!(basis.combine(&residues), f);
+        // x = 1 mod x^2 - 1 and x = x mod x^2 - x need to agree mod x - 1, and do
+        let residues = vec![qpoly(vec![1]), qpoly(vec![0, 1])];
+        let moduli = vec![qpoly(vec![-1, 0, 1]), qpoly(vec![0, -1, 1])];
+        let (f, m) = crt(&residues, &moduli).unwrap();
+        assert_eq!(m, qpoly(vec![0, -1, 0, 1]));
+        assert_eq!(f.div_rem(&moduli[0]).1, residues[0]);
+        assert_eq!(f.div_rem(&moduli[1]).1, residues[1]);
+        assert!(matches!(
+            crt(&[qpoly(vec![1]), qpoly(vec![2])], &moduli),
+            Err(ArithmeticError::NoSolution)
+        ));
+    }
+
+    #[test]
+    fn test_homo() {
+        let split = crt_homo::<60, 4, 15>();
+        let join = crt_inverse_homo::<60, 4, 15>();
+        for x in (0..60).map(Zmod::<60>::new) {
+            let y = split.apply(x);
+            assert_eq!(y, DirectProduct(Zmod::new(x.value()), Zmod::new(x.value())));
+            assert_eq!(join.apply(y), x);
+            // a ring homomorphism
+            let z = Zmod::new(7);
+            assert_eq!(split.apply(x * z), split.apply(x) * split.apply(z));
+            assert_eq!(split.apply(x + z), split.apply(x) + split.apply(z));
+        }
+        assert_eq!(
+            join.apply(DirectProduct(Zmod::new(1), Zmod::new(0))),
+            Zmod::new(45)
+        );
+    }
+}
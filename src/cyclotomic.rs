@@ -0,0 +1,413 @@
+//! Cyclotomic polynomials and fields
+//!
+//! The $n$th cyclotomic polynomial $\Phi\_n$ is the minimal polynomial of a primitive $n$th root of unity $\zeta\_n$.
+//! From $x^n - 1 = \prod\_{d \mid n} \Phi\_d$ and Möbius inversion,
+//! $$
+//! \Phi\_n(x) = \prod\_{d \mid n} (x^d - 1)^{\mu(n/d)},
+//! $$
+//! which is how `cyclotomic` computes it: multiply by the binomials with $\mu = 1$, then divide by the ones with
+//! $\mu = -1$. It has degree $\varphi(n)$, integer coefficients, and is irreducible over $\mathbb{Q}$. The results are
+//! cached (per thread), since the same ones tend to be asked for over and over.
+//!
+//! The field $\mathbb{Q}(\zeta\_n) = \mathbb{Q}[x]/(\Phi\_n)$ is Galois over $\mathbb{Q}$, with
+//! $(\mathbb{Z}/n)^\times \cong \mathrm{Gal}(\mathbb{Q}(\zeta\_n)/\mathbb{Q})$ by $a \mapsto (\sigma\_a: \zeta\_n
+//! \mapsto \zeta\_n^a)$, so $\sigma\_{-1}$ is complex conjugation. Its roots of unity are the $\mathrm{lcm}(n, 2)$th
+//! ones, so for odd $n$ it's the same field as $\mathbb{Q}(\zeta\_{2n})$.
+//!
+//! For a prime $p$ and a character $\chi$ of $\mathbb{F}\_p^\times$, the Gauss sum is
+//! $g(\chi) = \sum\_{a \neq 0} \chi(a) \zeta\_p^a$ and the Jacobi sum of two characters is
+//! $J(\chi\_1, \chi\_2) = \sum\_{a \neq 0, 1} \chi\_1(a) \chi\_2(1 - a)$. The characters are the $\chi\_k$ with
+//! $\chi\_k(g^j) = \zeta\_{p-1}^{kj}$ for the smallest primitive root $g$ mod $p$, and they take the value 0 at 0, even
+//! the trivial one. When $\chi\_1$, $\chi\_2$ and $\chi\_1 \chi\_2$ are nontrivial,
+//! $J(\chi\_1, \chi\_2) = g(\chi\_1) g(\chi\_2) / g(\chi\_1 \chi\_2)$ and $|J(\chi\_1, \chi\_2)|^2 = p$, which is how
+//! to write $p \equiv 1 \pmod 4$ as a sum of two squares with quartic characters.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::cyclotomic::{cyclotomic, cyclotomic_index, CyclotomicField};
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::Integer;
+//! use bored_algebra::number_field::NumberFieldElement;
+//! use bored_algebra::Rational;
+//!
+//! let phi12 = cyclotomic(12);
+//! assert_eq!(phi12, Polynomial::from(vec![1, 0, -1, 0, 1].into_iter().map(Integer::from).collect::<Vec<_>>()));
+//! assert_eq!(cyclotomic_index(&phi12), Some(12));
+//!
+//! // the quadratic Gauss sum mod 5 is sqrt(5)
+//! let k = CyclotomicField::new(5);
+//! let g = k.gauss_sum(5, 2).unwrap();
+//! assert_eq!(g.clone() * g, NumberFieldElement::constant(Rational::from(5)));
+//!
+//! // 13 = 3^2 + 2^2, from the Jacobi sum of a quartic character with itself in Q(i)
+//! let k = CyclotomicField::new(4);
+//! let j = k.jacobi_sum(13, 3, 3).unwrap();
+//! assert_eq!(j.norm(), 13);
+//! assert_eq!(j.coords(), vec![Rational::from(3), Rational::from(-2)]);
+//! ```
+use crate::ntheory::arith::{divisors, euler_phi_up_to, mobius};
+use crate::ntheory::prime::is_prime;
+use crate::ntheory::units::primitive_root;
+use crate::number_field::{NumberField, NumberFieldElement};
+use crate::poly::Polynomial;
+use crate::{Integer, Rational};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static CYCLOTOMIC: RefCell<HashMap<u64, Polynomial<Integer>>> = RefCell::new(HashMap::new());
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+/// $f \cdot (x^d - 1)$, densely.
+fn mul_binomial(f: &[Integer], d: usize) -> Vec<Integer> {
+    (0..f.len() + d)
+        .map(|i| {
+            let shifted = match i >= d {
+                true => f[i - d].clone(),
+                false => Integer::new(),
+            };
+            match f.get(i) {
+                Some(c) => shifted - c,
+                None => shifted,
+            }
+        })
+        .collect()
+}
+
+/// $f / (x^d - 1)$, densely, for an $f$ it divides: the coefficients of the quotient $q$ come from the top down, by
+/// $f\_i = q\_{i-d} - q\_i$.
+fn div_binomial(f: &[Integer], d: usize) -> Vec<Integer> {
+    let mut q = vec![Integer::new(); f.len() - d];
+    for i in (d..f.len()).rev() {
+        q[i - d] = match q.get(i) {
+            Some(c) => Integer::from(&f[i] + c),
+            None => f[i].clone(),
+        };
+    }
+    q
+}
+
+/// The cyclotomic polynomial $\Phi\_n$ for $n > 0$, by the Möbius product formula.
+pub fn cyclotomic(n: u64) -> Polynomial<Integer> {
+    assert!(n > 0, "there's no 0th cyclotomic polynomial");
+    if let Some(f) = CYCLOTOMIC.with(|cache| cache.borrow().get(&n).cloned()) {
+        return f;
+    }
+    let (mut num, mut den) = (vec![], vec![]);
+    for d in divisors(&Integer::from(n)) {
+        let d = d.to_u64().unwrap();
+        match mobius(&Integer::from(n / d)) {
+            1 => num.push(d as usize),
+            -1 => den.push(d as usize),
+            _ => {}
+        }
+    }
+    let mut f = vec![Integer::from(1)];
+    for d in num {
+        f = mul_binomial(&f, d);
+    }
+    for d in den {
+        f = div_binomial(&f, d);
+    }
+    let f = Polynomial::from(f);
+    CYCLOTOMIC.with(|cache| cache.borrow_mut().insert(n, f.clone()));
+    f
+}
+
+/// The $n$ with $f = \Phi\_n$, if there is one. Since $\varphi(n) \geq \sqrt{n/2}$, only $n \leq 2 \deg(f)^2$ need
+/// to be tried, and only the ones with $\varphi(n) = \deg f$ get compared.
+pub fn cyclotomic_index(f: &Polynomial<Integer>) -> Option<u64> {
+    let d = f.deg();
+    if d == 0 || f.lead_coeff() != 1 || f.coeffs_take(1)[0].clone().abs() != 1 {
+        return None;
+    }
+    let bound = (2 * d * d).max(2);
+    let phi = euler_phi_up_to(bound as usize);
+    (1..=bound).find(|&n| phi[n as usize] == d && cyclotomic(n) == *f)
+}
+
+/// Whether $f = \Phi\_n$ for some $n$.
+pub fn is_cyclotomic(f: &Polynomial<Integer>) -> bool {
+    cyclotomic_index(f).is_some()
+}
+
+/// The discrete logarithms of $1, \ldots, p - 1$ to the smallest primitive root mod a prime $p$, at their indices.
+fn discrete_logs(p: u64) -> Vec<u64> {
+    let g = primitive_root(&Integer::from(p)).unwrap().to_u64().unwrap();
+    let mut logs = vec![0; p as usize];
+    let mut power = 1;
+    for j in 0..p - 1 {
+        logs[power as usize] = j;
+        power = (power as u128 * g as u128 % p as u128) as u64;
+    }
+    logs
+}
+
+/// The field $\mathbb{Q}(\zeta\_n)$, with $\zeta\_n$ as the generator. See the module docs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CyclotomicField {
+    n: u64,
+    field: NumberField,
+}
+
+impl CyclotomicField {
+    /// $\mathbb{Q}(\zeta\_n) = \mathbb{Q}[x]/(\Phi\_n)$ for $n > 0$.
+    pub fn new(n: u64) -> Self {
+        let phi = cyclotomic(n);
+        let phi = phi
+            .coeffs_take(phi.deg() as usize + 1)
+            .into_iter()
+            .map(Rational::from)
+            .collect::<Vec<_>>();
+        Self {
+            n,
+            field: NumberField::new_unchecked(&Polynomial::from(phi)),
+        }
+    }
+
+    /// $n$.
+    pub fn order(&self) -> u64 {
+        self.n
+    }
+
+    pub fn field(&self) -> &NumberField {
+        &self.field
+    }
+
+    /// $\zeta\_n$.
+    pub fn zeta(&self) -> NumberFieldElement {
+        self.field.generator()
+    }
+
+    /// $\zeta\_m^k$ as (sign, exponent of $\zeta\_n$), if it's in the field: $\zeta\_m^k$ is a root of unity of order
+    /// $m/\gcd(m, k)$, which needs to divide $L = \mathrm{lcm}(n, 2)$. For odd $n$,
+    /// $\zeta\_{2n}^j = (-1)^j \zeta\_n^{j(n+1)/2}$.
+    fn root_exponent(&self, m: u64, k: u64) -> Option<(bool, u64)> {
+        let n = self.n;
+        let k = k % m;
+        let g = gcd(m, k);
+        let (m, k) = (m / g, k / g);
+        let l = if n.is_multiple_of(2) { n } else { 2 * n };
+        if !l.is_multiple_of(m) {
+            return None;
+        }
+        let j = (k as u128 * (l / m) as u128 % l as u128) as u64;
+        match l == n {
+            true => Some((false, j)),
+            false => Some((
+                j % 2 == 1,
+                (j as u128 * (n as u128 + 1) / 2 % n as u128) as u64,
+            )),
+        }
+    }
+
+    /// $\sum c\_j \zeta\_n^j$, from the coefficients at the exponents $0 \leq j < n$.
+    fn sum_of_powers(&self, c: Vec<Rational>) -> NumberFieldElement {
+        self.field.element(&Polynomial::from(c))
+    }
+
+    /// $\zeta\_m^k$, if it's in the field, which it is exactly when its order divides $\mathrm{lcm}(n, 2)$.
+    pub fn root_of_unity(&self, m: u64, k: u64) -> Option<NumberFieldElement> {
+        assert!(m > 0, "there are no 0th roots of unity");
+        let (negative, j) = self.root_exponent(m, k)?;
+        let mut c = vec![Rational::new(); self.n as usize];
+        c[j as usize] = Rational::from(if negative { -1 } else { 1 });
+        Some(self.sum_of_powers(c))
+    }
+
+    /// The units $a$ mod $n$, which are the automorphisms $\sigma\_a$.
+    pub fn galois_group(&self) -> Vec<u64> {
+        (0..self.n).filter(|&a| gcd(a, self.n) == 1).collect()
+    }
+
+    /// $\sigma\_a(x)$, where $\sigma\_a(\zeta\_n) = \zeta\_n^a$. Panics unless $a$ is a unit mod $n$.
+    pub fn automorphism(&self, a: u64, x: &NumberFieldElement) -> NumberFieldElement {
+        let n = self.n;
+        assert!(gcd(a % n, n) == 1, "{} isn't a unit mod {}", a, n);
+        if let Some(k) = x.field() {
+            assert!(*k == self.field, "not an element of Q(zeta_{})", n);
+        }
+        let mut c = vec![Rational::new(); n as usize];
+        for (i, ci) in x.coords().into_iter().enumerate() {
+            let j = (i as u128 * a as u128 % n as u128) as usize;
+            c[j] += ci;
+        }
+        self.sum_of_powers(c)
+    }
+
+    /// The Gauss sum $g(\chi\_k) = \sum\_{a \neq 0} \chi\_k(a) \zeta\_p^a$ for a prime $p$, or `None` if $\zeta\_p$
+    /// or the values of $\chi\_k$ aren't in the field. $\mathbb{Q}(\zeta\_{p(p-1)})$ has all of them.
+    pub fn gauss_sum(&self, p: u64, k: u64) -> Option<NumberFieldElement> {
+        assert!(is_prime(&Integer::from(p)), "{} isn't prime", p);
+        let logs = discrete_logs(p);
+        let mut c = vec![Rational::new(); self.n as usize];
+        for a in 1..p {
+            let (s1, e1) = self.root_exponent(p - 1, (k % (p - 1)) * logs[a as usize])?;
+            let (s2, e2) = self.root_exponent(p, a)?;
+            let j = ((e1 + e2) % self.n) as usize;
+            c[j] += if s1 ^ s2 { -1 } else { 1 };
+        }
+        Some(self.sum_of_powers(c))
+    }
+
+    /// The Jacobi sum $J(\chi\_{k\_1}, \chi\_{k\_2}) = \sum\_{a \neq 0, 1} \chi\_{k\_1}(a) \chi\_{k\_2}(1 - a)$ for a
+    /// prime $p$, or `None` if the values of the characters aren't in the field. $\mathbb{Q}(\zeta\_{p-1})$ has all
+    /// of them.
+    pub fn jacobi_sum(&self, p: u64, k1: u64, k2: u64) -> Option<NumberFieldElement> {
+        assert!(is_prime(&Integer::from(p)), "{} isn't prime", p);
+        let logs = discrete_logs(p);
+        let mut c = vec![Rational::new(); self.n as usize];
+        for a in 2..p {
+            let (s1, e1) = self.root_exponent(p - 1, (k1 % (p - 1)) * logs[a as usize])?;
+            let (s2, e2) =
+                self.root_exponent(p - 1, (k2 % (p - 1)) * logs[(p + 1 - a) as usize])?;
+            let j = ((e1 + e2) % self.n) as usize;
+            c[j] += if s1 ^ s2 { -1 } else { 1 };
+        }
+        Some(self.sum_of_powers(c))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zpoly;
+    use num_traits::identities::One;
+
+    fn rational(a: i64) -> NumberFieldElement {
+        NumberFieldElement::constant(Rational::from(a))
+    }
+
+    #[test]
+    fn test_cyclotomic() {
+        assert_eq!(cyclotomic(1), zpoly(vec![-1, 1]));
+        assert_eq!(cyclotomic(2), zpoly(vec![1, 1]));
+        assert_eq!(cyclotomic(6), zpoly(vec![1, -1, 1]));
+        assert_eq!(cyclotomic(8), zpoly(vec![1, 0, 0, 0, 1]));
+        assert_eq!(cyclotomic(9), zpoly(vec![1, 0, 0, 1, 0, 0, 1]));
+        // the first one with a coefficient other than 0, 1 and -1
+        let phi105 = cyclotomic(105);
+        assert_eq!(phi105.deg(), 48);
+        assert_eq!(phi105.coeffs_take(49)[7], -2);
+        assert!(phi105.coeffs_take(49).iter().all(|c| c.clone().abs() <= 2));
+        // x^n - 1 is the product of the Phi_d for d | n
+        for n in 1..=40 {
+            let product = divisors(&Integer::from(n))
+                .iter()
+                .fold(Polynomial::one(), |acc, d| {
+                    acc * cyclotomic(d.to_u64().unwrap())
+                });
+            let mut binomial = vec![0; n as usize + 1];
+            binomial[0] = -1;
+            binomial[n as usize] = 1;
+            assert_eq!(product, zpoly(binomial));
+        }
+
+        assert_eq!(cyclotomic_index(&zpoly(vec![1, 1, 1])), Some(3));
+        assert_eq!(cyclotomic_index(&zpoly(vec![1, 0, 1])), Some(4));
+        assert_eq!(cyclotomic_index(&cyclotomic(60)), Some(60));
+        assert!(is_cyclotomic(&zpoly(vec![-1, 1])));
+        assert!(!is_cyclotomic(&zpoly(vec![2, 0, 1])));
+        assert!(!is_cyclotomic(&zpoly(vec![1, 1, 0, 1])));
+        assert!(!is_cyclotomic(&zpoly(vec![1])));
+    }
+
+    #[test]
+    fn test_field() {
+        let k = CyclotomicField::new(12);
+        let zeta = k.zeta();
+        assert_eq!(k.galois_group(), vec![1, 5, 7, 11]);
+        for a in k.galois_group() {
+            assert_eq!(k.automorphism(a, &zeta), k.root_of_unity(12, a).unwrap());
+            // sigma_a sigma_b = sigma_ab, and they're ring homomorphisms
+            let x = zeta.clone() + rational(3) * zeta.clone() * zeta.clone();
+            let y = zeta.clone() - rational(2);
+            for b in k.galois_group() {
+                assert_eq!(
+                    k.automorphism(a, &k.automorphism(b, &x)),
+                    k.automorphism(a * b, &x)
+                );
+            }
+            assert_eq!(
+                k.automorphism(a, &(x.clone() * y.clone())),
+                k.automorphism(a, &x) * k.automorphism(a, &y)
+            );
+        }
+        // complex conjugation: zeta + zeta^-1 = sqrt(3) is real
+        let sqrt3 = zeta.clone() + k.automorphism(11, &zeta);
+        assert_eq!(k.automorphism(11, &sqrt3), sqrt3);
+        assert_eq!(sqrt3.clone() * sqrt3, rational(3));
+        // the roots of unity in Q(zeta_5) are the 10th ones
+        let k = CyclotomicField::new(5);
+        let minus_one = k.root_of_unity(2, 1).unwrap();
+        assert_eq!(minus_one, rational(-1));
+        let zeta10 = k.root_of_unity(10, 1).unwrap();
+        assert_eq!(zeta10.clone() * zeta10.clone(), k.zeta());
+        let phi10 = cyclotomic(10)
+            .coeffs_take(5)
+            .into_iter()
+            .map(Rational::from);
+        assert_eq!(
+            zeta10.minimal_polynomial(),
+            Polynomial::from(phi10.collect::<Vec<_>>())
+        );
+        assert!(k.root_of_unity(4, 1).is_none());
+        assert_eq!(k.root_of_unity(20, 4), Some(k.zeta()));
+    }
+
+    #[test]
+    fn test_gauss_jacobi() {
+        // quadratic Gauss sums: g^2 = (-1)^((p - 1)/2) p
+        for p in [3_u64, 5, 7, 11, 13] {
+            let k = CyclotomicField::new(p);
+            let g = k.gauss_sum(p, (p - 1) / 2).unwrap();
+            let sign = if p % 4 == 1 { 1 } else { -1 };
+            assert_eq!(g.clone() * g, rational(sign * p as i64));
+            // the trivial character gives -1
+            assert_eq!(k.gauss_sum(p, 0).unwrap(), rational(-1));
+        }
+        assert!(CyclotomicField::new(5).gauss_sum(7, 3).is_none());
+        assert!(CyclotomicField::new(5).gauss_sum(5, 1).is_none());
+
+        // all the characters mod 7 live in Q(zeta_42)
+        let p = 7;
+        let k = CyclotomicField::new(42);
+        let g: Vec<NumberFieldElement> = (0..p - 1).map(|i| k.gauss_sum(p, i).unwrap()).collect();
+        for a in 1..p - 1 {
+            // g(chi) g(chi^-1) = chi(-1) p, and chi_a(-1) = (-1)^a
+            let sign = if a % 2 == 0 { 1 } else { -1 };
+            assert_eq!(
+                g[a as usize].clone() * g[(p - 1 - a) as usize].clone(),
+                rational(sign * p as i64)
+            );
+            for b in 1..p - 1 {
+                let j = k.jacobi_sum(p, a, b).unwrap();
+                match (a + b) % (p - 1) {
+                    0 => assert_eq!(j, rational(-sign)),
+                    c => {
+                        assert_eq!(
+                            j.clone() * g[c as usize].clone(),
+                            g[a as usize].clone() * g[b as usize].clone()
+                        );
+                        assert_eq!(j.clone() * k.automorphism(41, &j), rational(p as i64));
+                    }
+                }
+            }
+        }
+        assert_eq!(k.jacobi_sum(p, 0, 0).unwrap(), rational(p as i64 - 2));
+        // cubic characters mod 7 only need Q(zeta_3)
+        let j = CyclotomicField::new(3).jacobi_sum(7, 2, 2).unwrap();
+        assert_eq!(j.norm(), 7);
+        // and so do the sextic ones, since -zeta_3^2 is a primitive 6th root of unity, but Q(i) doesn't
+        assert!(CyclotomicField::new(3).jacobi_sum(7, 1, 2).is_some());
+        assert!(CyclotomicField::new(4).jacobi_sum(7, 1, 2).is_none());
+    }
+}
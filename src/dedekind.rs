@@ -0,0 +1,1240 @@
+//! Ideals in rings of integers
+//!
+//! The ring of integers $\mathcal{O}\_K$ of a number field is a Dedekind domain: its nonzero fractional ideals form
+//! a group under multiplication, and every one factors uniquely into prime ideals. A `FractionalIdeal` is a
+//! $\mathbb{Z}$-lattice in $K$, kept in Hermite normal form over the integral basis $\omega\_1, \ldots, \omega\_n$, so
+//! equal ideals have equal bases. Products and sums are spans of products and unions, and the inverse
+//! $I^{-1} = \\{x \in K : xI \subseteq \mathcal{O}\_K\\}$ is the dual of a lattice made from the multiplication table.
+//!
+//! The primes above $p$ come from Dedekind–Kummer when $p$ doesn't divide the index of $\mathbb{Z}[d\theta]$: if
+//! $g = \prod h\_i^{e\_i}$ mod $p$ for the minimal polynomial $g$ of $d\theta$, then
+//! $p\mathcal{O}\_K = \prod \mathfrak{p}\_i^{e\_i}$ with $\mathfrak{p}\_i = (p, h\_i(d\theta))$. Otherwise, the
+//! $p$-radical $J = \prod \mathfrak{p}\_i$ is split by elements $\beta$ with $\beta^p \equiv \beta$ mod $J$: the
+//! $J + (\beta - c)$ for $c \in \mathbb{F}\_p$ are the products of the $\mathfrak{p}\_i$ with $\beta \equiv c$ mod
+//! $\mathfrak{p}\_i$, and $J$ is prime when the only such $\beta$ are in $\mathbb{F}\_p + J$.
+//!
+//! The class group is generated by the primes of norm at most the Minkowski bound
+//! $$
+//! M\_K = \frac{n!}{n^n} \left(\frac{4}{\pi}\right)^{r\_2} \sqrt{|d\_K|},
+//! $$
+//! and the relations between them come from $p\mathcal{O}\_K$ and from small elements with smooth norms. Then each
+//! class that could still be trivial gets a principal ideal test, which is what makes the answer certain.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::dedekind::{FractionalIdeal, RingOfIntegers};
+//! use bored_algebra::number_field::NumberField;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::{Integer, Rational};
+//!
+//! // Z[sqrt(-5)], where 6 = 2 * 3 = (1 + sqrt(-5))(1 - sqrt(-5))
+//! let k = NumberField::new(&Polynomial::from(vec![Rational::from(5), Rational::new(), Rational::from(1)])).unwrap();
+//! let o = RingOfIntegers::new(&k);
+//! let p2 = &o.primes_above(&Integer::from(2))[0];
+//! assert_eq!(p2.ramification_index(), 2);
+//! let a = k.element(&Polynomial::from(vec![Rational::from(1), Rational::from(1)]));
+//! let factors = FractionalIdeal::principal(&o, &a).factor();
+//! assert_eq!(factors.len(), 2);
+//! assert_eq!(factors[0], (p2.clone(), 1));
+//! assert_eq!(o.class_number(), 2);
+//! ```
+use crate::factor::factor_mod_p;
+use crate::matrix::Matrix;
+use crate::ntheory::factor::factor_integer;
+use crate::number_field::{
+    hermite_normal_form, left_kernel_mod_p, pow_mod_p, solve_triangular, NumberField,
+    NumberFieldElement,
+};
+use crate::poly::Polynomial;
+use crate::{Integer, Rational};
+use rug::ops::{Pow, RemRounding};
+
+use core::cmp::Ordering;
+use core::ops::{Add, Div, Mul};
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct Inner {
+    field: NumberField,
+    /// The integral basis, in coordinates over the power basis.
+    basis: Vec<Vec<Rational>>,
+    /// `table[i][j]` is $\omega\_i \omega\_j$ in coordinates over the integral basis.
+    table: Vec<Vec<Vec<Integer>>>,
+    discriminant: Integer,
+}
+
+/// The ring of integers of a number field, with the integral basis from `NumberField::integral_basis`. It's cheap
+/// to clone.
+#[derive(Clone, Debug)]
+pub struct RingOfIntegers {
+    inner: Rc<Inner>,
+}
+
+impl PartialEq for RingOfIntegers {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner) || self.inner.field == other.inner.field
+    }
+}
+
+impl Eq for RingOfIntegers {}
+
+impl RingOfIntegers {
+    pub fn new(field: &NumberField) -> Self {
+        let basis: Vec<Vec<Rational>> = field.integral_basis().iter().map(|w| w.coords()).collect();
+        let table = field.multiplication_table(&basis);
+        let discriminant = field.lattice_discriminant(&basis);
+        Self {
+            inner: Rc::new(Inner {
+                field: field.clone(),
+                basis,
+                table,
+                discriminant,
+            }),
+        }
+    }
+
+    pub fn field(&self) -> &NumberField {
+        &self.inner.field
+    }
+
+    pub fn degree(&self) -> usize {
+        self.inner.basis.len()
+    }
+
+    /// The integral basis $\omega\_1, \ldots, \omega\_n$.
+    pub fn basis(&self) -> Vec<NumberFieldElement> {
+        self.inner
+            .basis
+            .iter()
+            .map(|w| self.field().from_coords(w))
+            .collect()
+    }
+
+    /// The discriminant $d\_K$.
+    pub fn discriminant(&self) -> Integer {
+        self.inner.discriminant.clone()
+    }
+
+    /// The coordinates of $\alpha$ over the integral basis, which are integers exactly when $\alpha$ is.
+    pub fn coords(&self, a: &NumberFieldElement) -> Vec<Rational> {
+        let mut c = a.coords();
+        c.resize(self.degree(), Rational::new());
+        solve_triangular(&self.inner.basis, &c)
+    }
+
+    /// $\sum\_i c\_i \omega\_i$.
+    pub fn element(&self, c: &[Rational]) -> NumberFieldElement {
+        let n = self.degree();
+        let coords: Vec<Rational> = (0..n)
+            .map(|k| {
+                c.iter()
+                    .zip(self.inner.basis.iter())
+                    .fold(Rational::new(), |acc, (ci, w)| {
+                        acc + Rational::from(ci * &w[k])
+                    })
+            })
+            .collect();
+        self.field().from_coords(&coords)
+    }
+
+    /// $xy$ in coordinates over the integral basis.
+    fn mul_coords(&self, x: &[Rational], y: &[Rational]) -> Vec<Rational> {
+        let mut z = vec![Rational::new(); self.degree()];
+        for (xi, row) in x.iter().zip(self.inner.table.iter()) {
+            if *xi == 0 {
+                continue;
+            }
+            for (yj, t) in y.iter().zip(row.iter()) {
+                let c = Rational::from(xi * yj);
+                for (zk, tk) in z.iter_mut().zip(t.iter()) {
+                    *zk += Rational::from(&c * tk);
+                }
+            }
+        }
+        z
+    }
+
+    /// The $k$th unit vector, which is $\omega\_k$.
+    fn unit(&self, k: usize) -> Vec<Integer> {
+        let mut e = vec![Integer::new(); self.degree()];
+        e[k] = Integer::from(1);
+        e
+    }
+
+    /// $d$ such that $d\theta$ is integral, and the index of $\mathbb{Z}[d\theta]$ in $\mathcal{O}\_K$.
+    fn monogenic_index(&self) -> (Integer, Integer) {
+        let n = self.degree();
+        let d = self
+            .field()
+            .modulus()
+            .coeffs_take(n + 1)
+            .iter()
+            .fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+        // the basis is triangular over the power basis, so the index is a ratio of diagonals
+        let index = self
+            .inner
+            .basis
+            .iter()
+            .enumerate()
+            .fold(Rational::from(1), |acc, (i, w)| {
+                acc * Rational::from(d.clone().pow(i as u32)) / w[i].clone()
+            });
+        (d, index.numer().abs_ref().into())
+    }
+
+    /// The prime ideals above $p$, with their ramification indices and residue degrees, so that
+    /// $\sum e\_i f\_i = n$. `p` should be prime.
+    pub fn primes_above(&self, p: &Integer) -> Vec<PrimeIdeal> {
+        let n = self.degree();
+        let (d, index) = self.monogenic_index();
+        let ideals = if !index.is_divisible(p) {
+            // Dedekind-Kummer, with the minimal polynomial d^n f(x/d) of d theta
+            let g: Vec<Integer> = self
+                .field()
+                .modulus()
+                .coeffs_take(n + 1)
+                .iter()
+                .enumerate()
+                .map(|(k, c)| {
+                    (c * Rational::from(d.clone().pow((n - k) as u32)))
+                        .numer()
+                        .clone()
+                })
+                .collect();
+            factor_mod_p(&Polynomial::from(g), p)
+                .into_iter()
+                .map(|(h, _)| {
+                    let h = Polynomial::from(
+                        h.coeffs()
+                            .iter()
+                            .enumerate()
+                            .map(|(k, c)| Rational::from(c * d.clone().pow(k as u32)))
+                            .collect::<Vec<_>>(),
+                    );
+                    let h = self.coords(&self.field().element(&h));
+                    let mut p_coords = vec![Rational::new(); n];
+                    p_coords[0] = Rational::from(p);
+                    FractionalIdeal::generated(self, &[p_coords, h])
+                })
+                .collect()
+        } else {
+            self.split(self.radical(p), p)
+        };
+        let po = FractionalIdeal::principal(self, &self.field().from_coords(&[Rational::from(p)]));
+        ideals
+            .into_iter()
+            .map(|ideal| {
+                let mut f = 0;
+                let mut norm = ideal.norm().numer().clone();
+                while norm > 1 {
+                    norm /= p;
+                    f += 1;
+                }
+                let e = po.power_valuation(&ideal) as usize;
+                PrimeIdeal {
+                    p: p.clone(),
+                    ideal,
+                    e,
+                    f,
+                }
+            })
+            .collect()
+    }
+
+    /// The $p$-radical $\\{x \in \mathcal{O}\_K : x^q \in p\mathcal{O}\_K\\}$ for $q = p^j \geq n$, which is the
+    /// product of the primes above $p$.
+    fn radical(&self, p: &Integer) -> FractionalIdeal {
+        let n = self.degree();
+        let one = self.unit(0);
+        let mut q = p.clone();
+        while q < n {
+            q *= p;
+        }
+        let frobenius: Vec<Vec<Integer>> = (0..n)
+            .map(|i| pow_mod_p(&self.inner.table, &one, &self.unit(i), &q, p))
+            .collect();
+        let mut rows = left_kernel_mod_p(&frobenius, p);
+        rows.extend((0..n).map(|i| self.unit(i).into_iter().map(|x| x * p).collect()));
+        FractionalIdeal::from_lattice(self, &to_rational(&rows))
+    }
+
+    /// Splits a product $J$ of distinct primes above $p$ into the primes.
+    fn split(&self, j: FractionalIdeal, p: &Integer) -> Vec<FractionalIdeal> {
+        let n = self.degree();
+        let one = self.unit(0);
+        let rank = |rows: &[Vec<Integer>]| rows.len() - left_kernel_mod_p(rows, p).len();
+        let j_rows: Vec<Vec<Integer>> = j
+            .basis
+            .iter()
+            .map(|row| row.iter().map(|x| x.numer().clone().rem_euc(p)).collect())
+            .collect();
+
+        // the x with x^p - x in J, which is linear mod p
+        let mut rows: Vec<Vec<Integer>> = (0..n)
+            .map(|i| {
+                let x = pow_mod_p(&self.inner.table, &one, &self.unit(i), p, p);
+                x.into_iter()
+                    .zip(self.unit(i))
+                    .map(|(a, b)| (a - b).rem_euc(p))
+                    .collect()
+            })
+            .collect();
+        rows.extend(j_rows.iter().cloned());
+        let fixed: Vec<Vec<Integer>> = left_kernel_mod_p(&rows, p)
+            .into_iter()
+            .map(|mut u| {
+                u.truncate(n);
+                u
+            })
+            .collect();
+        // O/J is a product of one finite field for each prime
+        if rank(&fixed) - rank(&j_rows) <= 1 {
+            return vec![j];
+        }
+
+        let mut trivial = j_rows;
+        trivial.push(one.clone());
+        let trivial_rank = rank(&trivial);
+        let beta = fixed
+            .into_iter()
+            .find(|u| {
+                let mut rows = trivial.clone();
+                rows.push(u.clone());
+                rank(&rows) > trivial_rank
+            })
+            .unwrap();
+        let beta: Vec<Rational> = beta.iter().map(Rational::from).collect();
+        // beta is c mod each prime, for roots c of its characteristic polynomial
+        let charpoly = self.element(&beta).characteristic_polynomial();
+        let charpoly = Polynomial::from(
+            charpoly
+                .coeffs()
+                .iter()
+                .map(|c| c.numer().clone())
+                .collect::<Vec<_>>(),
+        );
+        let mut primes = vec![];
+        for (h, _) in factor_mod_p(&charpoly, p) {
+            if h.deg() != 1 {
+                continue;
+            }
+            let c = (-h.coeffs()[0].clone()).rem_euc(p);
+            let mut shifted = beta.clone();
+            shifted[0] -= Rational::from(c);
+            let part = j.clone() + FractionalIdeal::generated(self, &[shifted]);
+            if part.norm() != 1 {
+                primes.extend(self.split(part, p));
+            }
+        }
+        primes
+    }
+
+    /// $\lfloor M\_K \rfloor$ for the Minkowski bound $M\_K$, with $4/\pi$ rounded up to $191/150$. Every ideal
+    /// class contains an integral ideal of norm at most $M\_K$.
+    pub fn minkowski_bound(&self) -> Integer {
+        let n = self.degree() as u32;
+        let (_, r2) = self.field().signature();
+        let factorial = Integer::from(Integer::factorial(n));
+        let bound_squared = Rational::from((factorial.square(), Integer::from(n).pow(2 * n)))
+            * Rational::from((191, 150)).pow(2 * r2 as u32)
+            * Rational::from(self.inner.discriminant.clone().abs());
+        Integer::from(bound_squared.numer() / bound_squared.denom()).sqrt()
+    }
+
+    /// The class group, as its invariant factors $d\_1 \mid d\_2 \mid \cdots$ with
+    /// $\mathrm{Cl}(K) \cong \bigoplus\_i \mathbb{Z}/d\_i$, so the trivial group is empty.
+    ///
+    /// The generators are the primes of norm at most $M\_K$, and the relations come from the primes above each $p$
+    /// and from elements $\sum c\_i \omega\_i$ in growing boxes $|c\_i \omega\_i| \lesssim b$ whose norms factor over
+    /// the generators. It stops when the relations have full rank, the order hasn't changed since $b$ was half as
+    /// big, and the relations between relations have given $r\_1 + r\_2 - 1$ independent units.
+    ///
+    /// That gives a group $G$ which $\mathrm{Cl}(K)$ is a quotient of, and the answer is certified by checking that
+    /// no relation is missing: if one were, some class of prime order $\ell \mid |G|$ would be principal. So for
+    /// each such $\ell$, every class in $G[\ell]$ gets a principal ideal test, which is a bounded search thanks to
+    /// the units (see `is_principal`), and any principal one is added as a relation.
+    ///
+    /// TODO: this is only practical for small degrees and discriminants. A proper version would sieve for
+    /// relations.
+    pub fn class_group(&self) -> Vec<Integer> {
+        let bound = self.minkowski_bound();
+        let roots = self.embeddings();
+        let (r1, r2) = self.field().signature();
+        let mut base: Vec<PrimeIdeal> = vec![];
+        let mut relations: Vec<Vec<Integer>> = vec![];
+        // the relations with the log embeddings of their elements, and the units that come from them
+        let mut found: Vec<(Vec<Integer>, Vec<f64>)> = vec![];
+        let mut echelon = vec![];
+        let mut units = vec![];
+        let mut p = Integer::from(2);
+        let mut rational_primes = vec![];
+        while p <= bound {
+            let above = self.primes_above(&p);
+            let start = base.len();
+            base.extend(above.iter().filter(|q| q.norm() <= bound).cloned());
+            if base.len() - start == above.len() {
+                // p O is principal
+                let mut relation = vec![Integer::new(); start];
+                relation.extend(above.iter().map(|q| Integer::from(q.e)));
+                relations.push(relation.clone());
+                found.push((relation, vec![p.to_f64().ln(); roots.len()]));
+            }
+            rational_primes.push(p.clone());
+            p.next_prime_mut();
+        }
+        let k = base.len();
+        if k == 0 {
+            return vec![];
+        }
+        for relation in relations.iter_mut() {
+            relation.resize(k, Integer::new());
+        }
+        for (relation, log) in found.drain(..) {
+            let mut relation = relation;
+            relation.resize(k, Integer::new());
+            add_relation(&mut echelon, &mut units, relation, log);
+        }
+        // powers[i][j] is base[i]^(j + 1), for valuations
+        let mut powers: Vec<Vec<FractionalIdeal>> =
+            base.iter().map(|q| vec![q.ideal.clone()]).collect();
+
+        // the box is stretched so that each c_i w_i has about the same size, which is what makes norms small
+        let sizes: Vec<f64> = self.basis().iter().map(root_bound).collect();
+        let largest = sizes.iter().cloned().fold(0.0, f64::max);
+        let ranges = |b: i64| -> Vec<i64> {
+            sizes
+                .iter()
+                .map(|s| (b as f64 * largest / s).ceil() as i64)
+                .collect()
+        };
+
+        let mut previous = None;
+        let mut changed = 0;
+        for b in 1_i64.. {
+            let (inner, outer) = (ranges(b - 1), ranges(b));
+            let count = outer.iter().map(|r| 2 * r + 1).product::<i64>();
+            for index in 0..count {
+                let mut c = vec![];
+                let mut rest = index;
+                for r in outer.iter() {
+                    c.push(rest % (2 * r + 1) - r);
+                    rest /= 2 * r + 1;
+                }
+                // the shell of the box, up to sign
+                let first = c.iter().find(|x| **x != 0);
+                let in_inner = c.iter().zip(inner.iter()).all(|(x, r)| x.abs() <= *r);
+                if in_inner || first.is_none_or(|x| *x < 0) {
+                    continue;
+                }
+                let c: Vec<Rational> = c.into_iter().map(Rational::from).collect();
+                let element = self.element(&c);
+                let mut norm = element.norm().numer().clone().abs();
+                let mut exponents = vec![];
+                for p in rational_primes.iter() {
+                    let mut e = 0;
+                    while norm.is_divisible(p) {
+                        norm /= p;
+                        e += 1;
+                    }
+                    exponents.push(e);
+                }
+                if norm != 1 {
+                    continue;
+                }
+                // the valuations at the primes in the base, which must account for the whole norm
+                let mut relation = vec![Integer::new(); k];
+                let mut smooth = true;
+                for (p, e) in rational_primes.iter().zip(exponents) {
+                    let mut counted = 0;
+                    for (i, q) in base.iter().enumerate().filter(|(_, q)| q.p == *p) {
+                        let mut v = 0;
+                        while counted + (v + 1) * q.f <= e {
+                            if powers[i].len() <= v {
+                                let next = powers[i][v - 1].clone() * q.ideal.clone();
+                                powers[i].push(next);
+                            }
+                            if !powers[i][v].contains_coords(&c) {
+                                break;
+                            }
+                            v += 1;
+                        }
+                        counted += v * q.f;
+                        relation[i] = Integer::from(v);
+                    }
+                    smooth &= counted == e;
+                }
+                if smooth {
+                    relations.push(relation.clone());
+                    let log = log_embedding(&element, &roots);
+                    add_relation(&mut echelon, &mut units, relation, log);
+                }
+            }
+            let h = Matrix::from(relations.clone()).hermite_normal_form();
+            relations = (0..h.rows()).map(|i| h.row(i).to_vec()).collect();
+            if relations.len() < k {
+                continue;
+            }
+            let order = (0..k).fold(Integer::from(1), |acc, i| acc * h.get(i, i));
+            if previous.as_ref() != Some(&order) {
+                previous = Some(order);
+                changed = b;
+            } else if b >= 2 * changed + 2 && independent(&units).len() == r1 + r2 - 1 {
+                break;
+            }
+        }
+
+        let units = independent(&units);
+        while let Some(relation) = self.missing_relation(&base, &relations, &roots, &units) {
+            relations.push(relation);
+            let h = Matrix::from(relations).hermite_normal_form();
+            relations = (0..h.rows()).map(|i| h.row(i).to_vec()).collect();
+        }
+        Matrix::from(relations)
+            .elementary_divisors()
+            .into_iter()
+            .filter(|d| *d != 1)
+            .collect()
+    }
+
+    /// The class number $h\_K = |\mathrm{Cl}(K)|$, from `class_group`.
+    pub fn class_number(&self) -> Integer {
+        self.class_group()
+            .into_iter()
+            .fold(Integer::from(1), |acc, d| acc * d)
+    }
+
+    /// The roots $\theta\_i$ of the defining polynomial as complex floats, one from each pair of complex
+    /// conjugates, so that $\alpha \mapsto \sigma\_i(\alpha)$ are the $r\_1 + r\_2$ embeddings up to conjugation.
+    fn embeddings(&self) -> Vec<(f64, f64)> {
+        self.field()
+            .modulus()
+            .roots()
+            .into_iter()
+            .filter(|z| z.im().cmp0() != Ordering::Less)
+            .map(|z| (z.re().to_float(53).to_f64(), z.im().to_float(53).to_f64()))
+            .collect()
+    }
+
+    /// A relation $v$ with $\prod\_i P\_i^{v\_i}$ principal that isn't in the span of `relations`, which are in
+    /// Hermite normal form with full rank. Those are the classes of prime order in the group they define, so it's
+    /// enough to try the $v = xH/\ell$ with $x$ in the left kernel of $H$ mod $\ell$, up to scalars.
+    fn missing_relation(
+        &self,
+        base: &[PrimeIdeal],
+        relations: &[Vec<Integer>],
+        roots: &[(f64, f64)],
+        units: &[Vec<f64>],
+    ) -> Option<Vec<Integer>> {
+        let k = base.len();
+        let order = (0..k).fold(Integer::from(1), |acc, i| acc * &relations[i][i]);
+        if order == 1 {
+            return None;
+        }
+        for (l, _) in factor_integer(&order) {
+            let kernel = left_kernel_mod_p(&relations[..k], &l);
+            let m = l.to_usize().unwrap();
+            for index in 1..m.pow(kernel.len() as u32) {
+                // the coefficients of x in base l, up to scalars: the first nonzero one is 1
+                let digits: Vec<Integer> = (0..kernel.len())
+                    .map(|j| Integer::from(index / m.pow(j as u32) % m))
+                    .collect();
+                if digits.iter().find(|d| **d != 0) != Some(&Integer::from(1)) {
+                    continue;
+                }
+                let x: Vec<Integer> = (0..k)
+                    .map(|i| {
+                        kernel
+                            .iter()
+                            .zip(digits.iter())
+                            .fold(Integer::new(), |acc, (row, d)| {
+                                acc + Integer::from(&row[i] * d)
+                            })
+                            % &l
+                    })
+                    .collect();
+                let v: Vec<Integer> = (0..k)
+                    .map(|j| {
+                        x.iter()
+                            .zip(relations.iter())
+                            .fold(Integer::new(), |acc, (xi, row)| {
+                                acc + Integer::from(xi * &row[j])
+                            })
+                            / &l
+                    })
+                    .collect();
+                let ideal = base
+                    .iter()
+                    .zip(v.iter())
+                    .fold(FractionalIdeal::unit(self), |acc, (q, e)| {
+                        acc * q.ideal.pow(e.to_i64().unwrap())
+                    });
+                if self.is_principal(&ideal, roots, units) {
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether $I$ is principal, given a full rank set of units $\varepsilon\_j$ by their log embeddings.
+    ///
+    /// After scaling, $I$ is integral with norm $N$, and it's principal exactly when it has an element of norm
+    /// $\pm N$. A generator can be multiplied by a product of the $\varepsilon\_j$ to move its log embedding into a
+    /// fundamental domain around $\frac{1}{n}\log N$, where
+    /// $\log |\sigma\_i(\alpha)| \leq \frac{1}{n}\log N + \frac{1}{2}\sum\_j |\log |\sigma\_i(\varepsilon\_j)||$. That bounds
+    /// the coordinates of $\alpha$ over a basis of $I$, so the search is finite.
+    fn is_principal(
+        &self,
+        ideal: &FractionalIdeal,
+        roots: &[(f64, f64)],
+        units: &[Vec<f64>],
+    ) -> bool {
+        let d = ideal.denominator();
+        let ideal = FractionalIdeal::from_lattice(self, &ideal.scaled_basis(&d));
+        let norm = ideal.norm().numer().clone();
+        let n = self.degree();
+        // a little slack for rounding
+        let bounds: Vec<f64> = (0..roots.len())
+            .map(|i| {
+                let spread: f64 = units.iter().map(|u| u[i].abs()).sum();
+                (norm.to_f64().ln() / n as f64 + spread / 2.0).exp() * (1.0 + 1e-6) + 1e-6
+            })
+            .collect();
+        // the real coordinates of the embeddings of the basis, and the bounds on each
+        let basis = ideal.basis();
+        let embedded: Vec<Vec<(f64, f64)>> = roots
+            .iter()
+            .map(|root| basis.iter().map(|b| embed(b, *root)).collect())
+            .collect();
+        let mut rows = vec![];
+        let mut row_bounds = vec![];
+        for ((root, bound), values) in roots.iter().zip(bounds.iter()).zip(embedded.iter()) {
+            rows.push(values.iter().map(|v| v.0).collect::<Vec<_>>());
+            row_bounds.push(*bound);
+            if root.1 != 0.0 {
+                rows.push(values.iter().map(|v| v.1).collect());
+                row_bounds.push(*bound);
+            }
+        }
+        let m = Matrix::from(
+            rows.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|x| Rational::from_f64(*x).unwrap())
+                        .collect()
+                })
+                .collect::<Vec<Vec<Rational>>>(),
+        );
+        let inverse = m
+            .inverse()
+            .expect("the embeddings of a basis are independent");
+        let ranges: Vec<i64> = (0..n)
+            .map(|k| {
+                let c: f64 = (0..n)
+                    .map(|j| inverse.get(k, j).to_f64().abs() * row_bounds[j])
+                    .sum();
+                (c * (1.0 + 1e-6)).floor() as i64
+            })
+            .collect();
+        let count = ranges.iter().map(|r| 2 * r + 1).product::<i64>();
+        for index in 0..count {
+            let mut c = vec![];
+            let mut rest = index;
+            for r in ranges.iter() {
+                c.push(rest % (2 * r + 1) - r);
+                rest /= 2 * r + 1;
+            }
+            if c.iter().all(|x| *x == 0) {
+                continue;
+            }
+            // the embeddings in floats first, and the exact norm only for the few that pass
+            let mut close = true;
+            let mut log_norm = 0.0;
+            for ((root, bound), values) in roots.iter().zip(bounds.iter()).zip(embedded.iter()) {
+                let (re, im) = values
+                    .iter()
+                    .zip(c.iter())
+                    .fold((0.0, 0.0), |(re, im), (v, x)| {
+                        (re + v.0 * *x as f64, im + v.1 * *x as f64)
+                    });
+                let size = re.hypot(im);
+                close &= size <= *bound * (1.0 + 1e-6);
+                log_norm += if root.1 == 0.0 {
+                    size.ln()
+                } else {
+                    2.0 * size.ln()
+                };
+            }
+            if !close || (log_norm - norm.to_f64().ln()).abs() > 1e-3 {
+                continue;
+            }
+            let a = basis
+                .iter()
+                .zip(c.iter())
+                .fold(self.field().from_coords(&[]), |acc, (b, x)| {
+                    acc + b.clone() * self.field().from_coords(&[Rational::from(*x)])
+                });
+            if a.norm().numer().clone().abs() == norm {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// $\sigma(\alpha) = g(\theta)$ for $\alpha = g(\theta)$ and a root $\theta$, as a complex float.
+fn embed(a: &NumberFieldElement, root: (f64, f64)) -> (f64, f64) {
+    let g = a.value();
+    g.coeffs_take(g.deg() as usize + 1)
+        .iter()
+        .rev()
+        .fold((0.0, 0.0), |(re, im), c| {
+            (
+                re * root.0 - im * root.1 + c.to_f64(),
+                re * root.1 + im * root.0,
+            )
+        })
+}
+
+/// $(\log |\sigma\_1(\alpha)|, \ldots, \log |\sigma\_{r\_1 + r\_2}(\alpha)|)$.
+fn log_embedding(a: &NumberFieldElement, roots: &[(f64, f64)]) -> Vec<f64> {
+    roots
+        .iter()
+        .map(|root| {
+            let (re, im) = embed(a, *root);
+            re.hypot(im).ln()
+        })
+        .collect()
+}
+
+fn dot(u: &[f64], v: &[f64]) -> f64 {
+    u.iter().zip(v).map(|(x, y)| x * y).sum()
+}
+
+/// Adds a relation and the log embedding of its element to a basis in echelon form. If it's a combination of the
+/// ones before, the same combination of their elements has no prime factors, so it's a unit.
+fn add_relation(
+    echelon: &mut Vec<(Vec<Integer>, Vec<f64>)>,
+    units: &mut Vec<Vec<f64>>,
+    mut v: Vec<Integer>,
+    mut log: Vec<f64>,
+) {
+    // the rows are sorted by their pivots, so clearing one pivot never brings back an earlier one
+    for (b, log_b) in echelon.iter() {
+        let pivot = b.iter().position(|x| *x != 0).unwrap();
+        if v[pivot] == 0 {
+            continue;
+        }
+        let g = Integer::from(v[pivot].gcd_ref(&b[pivot]));
+        let (s, t) = (Integer::from(&b[pivot] / &g), Integer::from(&v[pivot] / &g));
+        v = v
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| Integer::from(&s * x) - Integer::from(&t * y))
+            .collect();
+        log = log
+            .iter()
+            .zip(log_b.iter())
+            .map(|(x, y)| s.to_f64() * x - t.to_f64() * y)
+            .collect();
+    }
+    match v.iter().position(|x| *x != 0) {
+        None => add_unit(units, log),
+        Some(pivot) => {
+            let at =
+                echelon.partition_point(|(b, _)| b.iter().position(|x| *x != 0).unwrap() < pivot);
+            echelon.insert(at, (v, log));
+        }
+    }
+}
+
+/// Adds the log embedding of a unit, and makes the list shorter by subtracting multiples of one unit from another
+/// (which are still units) while that helps. Roots of unity, with log embedding 0, are dropped.
+fn add_unit(units: &mut Vec<Vec<f64>>, log: Vec<f64>) {
+    const TINY: f64 = 1e-6;
+    units.push(log);
+    loop {
+        let mut changed = false;
+        for i in 0..units.len() {
+            for j in 0..units.len() {
+                let size = dot(&units[j], &units[j]);
+                if i == j || size < TINY {
+                    continue;
+                }
+                let t = (dot(&units[i], &units[j]) / size).round();
+                let reduced: Vec<f64> = units[i]
+                    .iter()
+                    .zip(&units[j])
+                    .map(|(x, y)| x - t * y)
+                    .collect();
+                if dot(&reduced, &reduced) < dot(&units[i], &units[i]) * (1.0 - TINY) {
+                    units[i] = reduced;
+                    changed = true;
+                }
+            }
+        }
+        units.retain(|u| dot(u, u) >= TINY);
+        if !changed {
+            break;
+        }
+    }
+    units.sort_by(|u, v| dot(u, u).total_cmp(&dot(v, v)));
+    units.truncate(units.first().map_or(0, |u| u.len()) + 2);
+}
+
+/// The shortest units that are independent, by Gram-Schmidt.
+fn independent(units: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut chosen: Vec<Vec<f64>> = vec![];
+    let mut orthogonal: Vec<Vec<f64>> = vec![];
+    for u in units {
+        let mut w = u.clone();
+        for o in orthogonal.iter() {
+            let t = dot(&w, o) / dot(o, o);
+            w = w.iter().zip(o).map(|(x, y)| x - t * y).collect();
+        }
+        if dot(&w, &w) > 1e-6 * dot(u, u) {
+            chosen.push(u.clone());
+            orthogonal.push(w);
+        }
+    }
+    chosen
+}
+
+/// A bound $2 \max\_k |a\_{n-k}|^{1/k}$ on the absolute values of the conjugates of $\alpha$, from its
+/// characteristic polynomial $x^n + a\_{n-1}x^{n-1} + \cdots + a\_0$.
+fn root_bound(a: &NumberFieldElement) -> f64 {
+    let c = a.characteristic_polynomial().coeffs();
+    let n = c.len() - 1;
+    (1..=n)
+        .map(|k| 2.0 * c[n - k].to_f64().abs().powf(1.0 / k as f64))
+        .fold(0.0, f64::max)
+}
+
+fn to_rational(rows: &[Vec<Integer>]) -> Vec<Vec<Rational>> {
+    rows.iter()
+        .map(|row| row.iter().map(Rational::from).collect())
+        .collect()
+}
+
+/// A nonzero fractional ideal of $\mathcal{O}\_K$, a finitely generated $\mathcal{O}\_K$-submodule of $K$.
+/// # Example:
+/// ```
+/// use bored_algebra::dedekind::{FractionalIdeal, RingOfIntegers};
+/// use bored_algebra::number_field::NumberField;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::Rational;
+///
+/// // Z[i]
+/// let k = NumberField::new(&Polynomial::from(vec![Rational::from(1), Rational::new(), Rational::from(1)])).unwrap();
+/// let o = RingOfIntegers::new(&k);
+/// let i = k.generator();
+/// let a = FractionalIdeal::principal(&o, &(i.clone() + k.from_coords(&[Rational::from(2)])));
+/// assert_eq!(a.norm(), 5);
+/// assert!(a.is_integral() && !a.inverse().is_integral());
+/// assert_eq!(a.clone() * a.inverse(), FractionalIdeal::unit(&o));
+/// // (2 + i) + (2 - i) = (1), since they're coprime
+/// let b = FractionalIdeal::principal(&o, &(k.from_coords(&[Rational::from(2)]) - i));
+/// assert_eq!(a + b, FractionalIdeal::unit(&o));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FractionalIdeal {
+    ring: RingOfIntegers,
+    /// A lower triangular Hermite normal form over the integral basis.
+    basis: Vec<Vec<Rational>>,
+}
+
+impl FractionalIdeal {
+    /// The lattice spanned by `rows`, in coordinates over the integral basis, which should be an ideal.
+    fn from_lattice(ring: &RingOfIntegers, rows: &[Vec<Rational>]) -> Self {
+        let basis = hermite_normal_form(rows);
+        assert_eq!(
+            basis.len(),
+            ring.degree(),
+            "the zero ideal isn't a fractional ideal"
+        );
+        Self {
+            ring: ring.clone(),
+            basis,
+        }
+    }
+
+    /// The ideal generated by elements in coordinates over the integral basis.
+    fn generated(ring: &RingOfIntegers, generators: &[Vec<Rational>]) -> Self {
+        let n = ring.degree();
+        let rows: Vec<Vec<Rational>> = generators
+            .iter()
+            .flat_map(|g| (0..n).map(move |j| ring.mul_coords(g, &to_rational(&[ring.unit(j)])[0])))
+            .collect();
+        Self::from_lattice(ring, &rows)
+    }
+
+    /// The ideal generated by `generators`. Panics if they're all zero.
+    pub fn new(ring: &RingOfIntegers, generators: &[NumberFieldElement]) -> Self {
+        let generators: Vec<Vec<Rational>> = generators.iter().map(|g| ring.coords(g)).collect();
+        Self::generated(ring, &generators)
+    }
+
+    /// $(\alpha) = \alpha\mathcal{O}\_K$. Panics if $\alpha = 0$.
+    pub fn principal(ring: &RingOfIntegers, a: &NumberFieldElement) -> Self {
+        Self::new(ring, core::slice::from_ref(a))
+    }
+
+    /// $\mathcal{O}\_K$ itself, the identity for multiplication.
+    pub fn unit(ring: &RingOfIntegers) -> Self {
+        let rows: Vec<Vec<Integer>> = (0..ring.degree()).map(|i| ring.unit(i)).collect();
+        Self::from_lattice(ring, &to_rational(&rows))
+    }
+
+    pub fn ring(&self) -> &RingOfIntegers {
+        &self.ring
+    }
+
+    /// A $\mathbb{Z}$-basis.
+    pub fn basis(&self) -> Vec<NumberFieldElement> {
+        self.basis.iter().map(|b| self.ring.element(b)).collect()
+    }
+
+    /// The norm $N(I) = [\mathcal{O}\_K : I]$, extended multiplicatively to fractional ideals.
+    pub fn norm(&self) -> Rational {
+        self.basis
+            .iter()
+            .enumerate()
+            .fold(Rational::from(1), |acc, (i, b)| acc * b[i].clone())
+            .abs()
+    }
+
+    /// Whether $I \subseteq \mathcal{O}\_K$.
+    pub fn is_integral(&self) -> bool {
+        self.basis.iter().flatten().all(|x| *x.denom() == 1)
+    }
+
+    pub fn contains(&self, a: &NumberFieldElement) -> bool {
+        self.contains_coords(&self.ring.coords(a))
+    }
+
+    fn contains_coords(&self, c: &[Rational]) -> bool {
+        solve_triangular(&self.basis, c)
+            .iter()
+            .all(|x| *x.denom() == 1)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.basis.iter().all(|b| other.contains_coords(b))
+    }
+
+    /// $I^{-1} = \\{x \in K : xI \subseteq \mathcal{O}\_K\\}$.
+    ///
+    /// $x = \sum\_k c\_k \omega\_k$ is in $I^{-1}$ when the coordinates of each $\omega\_k b\_j$, dotted with $c$, are
+    /// integers, so $I^{-1}$ is the dual of the lattice $\Lambda$ spanned by those coordinate vectors (indexed by $k$).
+    /// If the rows of $H$ are a basis of $\Lambda$, the dual basis is the columns of $H^{-1}$.
+    pub fn inverse(&self) -> Self {
+        let n = self.ring.degree();
+        let rows: Vec<Vec<Rational>> = self
+            .basis
+            .iter()
+            .flat_map(|b| {
+                let products: Vec<Vec<Rational>> = (0..n)
+                    .map(|k| {
+                        self.ring
+                            .mul_coords(&to_rational(&[self.ring.unit(k)])[0], b)
+                    })
+                    .collect();
+                (0..n).map(move |l| products.iter().map(|x| x[l].clone()).collect())
+            })
+            .collect();
+        let h = Matrix::from(hermite_normal_form(&rows))
+            .inverse()
+            .unwrap()
+            .transpose();
+        let dual: Vec<Vec<Rational>> = (0..n).map(|i| h.row(i).to_vec()).collect();
+        Self::from_lattice(&self.ring, &dual)
+    }
+
+    /// $I^k$, for any integer $k$.
+    pub fn pow(&self, k: i64) -> Self {
+        let base = match k < 0 {
+            true => self.inverse(),
+            false => self.clone(),
+        };
+        (0..k.unsigned_abs()).fold(Self::unit(&self.ring), |acc, _| acc * base.clone())
+    }
+
+    /// The largest $k$ with $I \subseteq P^k$, for integral $I$ and $P$.
+    fn power_valuation(&self, p: &Self) -> i64 {
+        let mut k = 0;
+        let mut power = p.clone();
+        while self.is_subset(&power) {
+            k += 1;
+            power = power * p.clone();
+        }
+        k
+    }
+
+    /// The exponent $v\_P(I)$ of $P$ in the factorization of $I$.
+    pub fn valuation(&self, p: &PrimeIdeal) -> i64 {
+        // dI is integral, and v_P(d) = e v_p(d)
+        let d = self.denominator();
+        let scaled = Self::from_lattice(&self.ring, &self.scaled_basis(&d));
+        let mut vd = 0;
+        let mut rest = d;
+        while rest.is_divisible(&p.p) {
+            rest /= &p.p;
+            vd += 1;
+        }
+        scaled.power_valuation(&p.ideal) - vd * p.e as i64
+    }
+
+    /// The least $d > 0$ with $dI \subseteq \mathcal{O}\_K$.
+    fn denominator(&self) -> Integer {
+        self.basis
+            .iter()
+            .flatten()
+            .fold(Integer::from(1), |acc, x| acc.lcm(x.denom()))
+    }
+
+    fn scaled_basis(&self, d: &Integer) -> Vec<Vec<Rational>> {
+        self.basis
+            .iter()
+            .map(|row| row.iter().map(|x| Rational::from(x * d)).collect())
+            .collect()
+    }
+
+    /// The factorization $I = \prod P\_i^{k\_i}$ into prime ideals, ordered by the primes under them, with the
+    /// $k\_i \neq 0$.
+    pub fn factor(&self) -> Vec<(PrimeIdeal, i64)> {
+        let d = self.denominator();
+        let norm = Self::from_lattice(&self.ring, &self.scaled_basis(&d)).norm();
+        // a prime in the factorization lies over a prime dividing d or N(dI)
+        let mut primes: Vec<Integer> = [d, norm.numer().clone()]
+            .iter()
+            .filter(|x| **x != 1)
+            .flat_map(|x| factor_integer(x).into_iter().map(|(p, _)| p))
+            .collect();
+        primes.sort();
+        primes.dedup();
+        let mut factors = vec![];
+        for p in primes {
+            for q in self.ring.primes_above(&p) {
+                let v = self.valuation(&q);
+                if v != 0 {
+                    factors.push((q, v));
+                }
+            }
+        }
+        factors
+    }
+}
+
+/// The product $IJ$, spanned by the products of the bases.
+impl Mul for FractionalIdeal {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        assert_eq!(self.ring, rhs.ring, "ideals of different rings");
+        let rows: Vec<Vec<Rational>> = self
+            .basis
+            .iter()
+            .flat_map(|a| rhs.basis.iter().map(|b| self.ring.mul_coords(a, b)))
+            .collect();
+        Self::from_lattice(&self.ring, &rows)
+    }
+}
+
+/// $IJ^{-1}$.
+impl Div for FractionalIdeal {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+/// The sum $I + J$, which for integral ideals is their gcd.
+impl Add for FractionalIdeal {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        assert_eq!(self.ring, rhs.ring, "ideals of different rings");
+        let mut rows = self.basis;
+        rows.extend(rhs.basis);
+        Self::from_lattice(&self.ring, &rows)
+    }
+}
+
+/// A prime ideal $P$ above $p$, with $p\mathcal{O}\_K = P^e \cdots$ and $\mathcal{O}\_K / P = \mathbb{F}\_{p^f}$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimeIdeal {
+    p: Integer,
+    ideal: FractionalIdeal,
+    e: usize,
+    f: usize,
+}
+
+impl PrimeIdeal {
+    /// The rational prime $p$ under $P$.
+    pub fn p(&self) -> &Integer {
+        &self.p
+    }
+
+    pub fn ideal(&self) -> &FractionalIdeal {
+        &self.ideal
+    }
+
+    /// The ramification index $e$.
+    pub fn ramification_index(&self) -> usize {
+        self.e
+    }
+
+    /// The residue degree $f$.
+    pub fn residue_degree(&self) -> usize {
+        self.f
+    }
+
+    /// $N(P) = p^f$.
+    pub fn norm(&self) -> Integer {
+        self.p.clone().pow(self.f as u32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quadratic_form::BinaryQuadraticForm;
+
+    fn ring(coeffs: Vec<i64>) -> RingOfIntegers {
+        let f = Polynomial::from(coeffs.into_iter().map(Rational::from).collect::<Vec<_>>());
+        RingOfIntegers::new(&NumberField::new(&f).unwrap())
+    }
+
+    fn splitting(o: &RingOfIntegers, p: i64) -> Vec<(usize, usize)> {
+        let primes = o.primes_above(&Integer::from(p));
+        assert_eq!(primes.iter().map(|q| q.e * q.f).sum::<usize>(), o.degree());
+        let mut ef: Vec<(usize, usize)> = primes.iter().map(|q| (q.e, q.f)).collect();
+        ef.sort();
+        ef
+    }
+
+    #[test]
+    fn test_primes() {
+        let gaussian = ring(vec![1, 0, 1]);
+        assert_eq!(splitting(&gaussian, 2), vec![(2, 1)]);
+        assert_eq!(splitting(&gaussian, 3), vec![(1, 2)]);
+        assert_eq!(splitting(&gaussian, 5), vec![(1, 1), (1, 1)]);
+        // Z[sqrt(5)] has index 2, so 2 takes the general method
+        let golden = ring(vec![-5, 0, 1]);
+        assert_eq!(splitting(&golden, 2), vec![(1, 2)]);
+        assert_eq!(splitting(&golden, 5), vec![(2, 1)]);
+        assert_eq!(splitting(&golden, 11), vec![(1, 1), (1, 1)]);
+        // Dedekind's field, where 2 splits completely but divides every index
+        let dedekind = ring(vec![-8, -2, -1, 1]);
+        assert_eq!(splitting(&dedekind, 2), vec![(1, 1), (1, 1), (1, 1)]);
+        let cube_root = ring(vec![-2, 0, 0, 1]);
+        assert_eq!(splitting(&cube_root, 3), vec![(3, 1)]);
+        assert_eq!(splitting(&cube_root, 5), vec![(1, 1), (1, 2)]);
+        // x^2 - 5/4 is Q(sqrt(5)) again, where theta isn't integral
+        let f = Polynomial::from(vec![
+            Rational::from((-5, 4)),
+            Rational::new(),
+            Rational::from(1),
+        ]);
+        let halved = RingOfIntegers::new(&NumberField::new(&f).unwrap());
+        assert_eq!(splitting(&halved, 2), vec![(1, 2)]);
+        assert_eq!(splitting(&halved, 11), vec![(1, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_ideals() {
+        let o = ring(vec![5, 0, 1]);
+        let k = o.field().clone();
+        let c = |a: i64, b: i64| k.from_coords(&[Rational::from(a), Rational::from(b)]);
+        let p2 = FractionalIdeal::new(&o, &[c(2, 0), c(1, 1)]);
+        let p3 = FractionalIdeal::new(&o, &[c(3, 0), c(1, 1)]);
+        assert_eq!(p2.norm(), 2);
+        assert_eq!(p2.pow(2), FractionalIdeal::principal(&o, &c(2, 0)));
+        assert_eq!(
+            p2.clone() * p3.clone(),
+            FractionalIdeal::principal(&o, &c(1, 1))
+        );
+        assert_eq!(p2.clone() + p3.clone(), FractionalIdeal::unit(&o));
+        assert!(p2.contains(&c(3, 1)) && !p2.contains(&c(1, 0)));
+        let inv = p2.inverse();
+        assert_eq!(inv.norm(), Rational::from((1, 2)));
+        assert_eq!(inv, p2.clone() / FractionalIdeal::principal(&o, &c(2, 0)));
+        assert_eq!(p2.pow(-2).pow(-1), p2.pow(2));
+
+        let six = FractionalIdeal::principal(&o, &c(6, 0));
+        let factors = six.factor();
+        assert_eq!(factors.len(), 3);
+        assert_eq!(
+            factors[0],
+            (o.primes_above(&Integer::from(2))[0].clone(), 2)
+        );
+        assert_eq!(factors.iter().map(|(_, v)| v).sum::<i64>(), 4);
+        let quotient = (six / p3.pow(3)).factor();
+        assert_eq!(
+            quotient
+                .iter()
+                .map(|(q, v)| (q.p().to_i64().unwrap(), *v))
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (3, -2), (3, 1)]
+        );
+
+        // the same in Dedekind's field, through the general method
+        let o = ring(vec![-8, -2, -1, 1]);
+        let primes = o.primes_above(&Integer::from(2));
+        let product = primes
+            .iter()
+            .fold(FractionalIdeal::unit(&o), |acc, q| acc * q.ideal().clone());
+        assert_eq!(
+            product,
+            FractionalIdeal::principal(&o, &o.field().from_coords(&[Rational::from(2)]))
+        );
+        for q in primes.iter() {
+            assert_eq!(
+                q.ideal().clone() * q.ideal().inverse(),
+                FractionalIdeal::unit(&o)
+            );
+        }
+    }
+
+    #[test]
+    fn test_class_group() {
+        let class_group = |coeffs: Vec<i64>| {
+            ring(coeffs)
+                .class_group()
+                .into_iter()
+                .map(|d| d.to_i64().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert!(class_group(vec![1, 0, 1]).is_empty());
+        assert_eq!(class_group(vec![5, 0, 1]), vec![2]);
+        assert_eq!(class_group(vec![23, 0, 1]), vec![3]);
+        assert_eq!(class_group(vec![14, 0, 1]), vec![4]);
+        assert_eq!(class_group(vec![21, 0, 1]), vec![2, 2]);
+        assert_eq!(class_group(vec![47, 0, 1]), vec![5]);
+        assert_eq!(class_group(vec![-10, 0, 1]), vec![2]);
+        assert!(class_group(vec![-2, 0, 0, 1]).is_empty());
+        assert_eq!(ring(vec![-8, -2, -1, 1]).class_number(), 1);
+        assert_eq!(ring(vec![5, 0, 1]).minkowski_bound(), 2);
+        assert_eq!(class_group(vec![26, 0, 1]), vec![6]);
+        assert_eq!(class_group(vec![65, 0, 1]), vec![2, 4]);
+        // the fundamental unit 224 + 15 sqrt(223) is big, but 15 + sqrt(223) has norm 2
+        assert_eq!(class_group(vec![-223, 0, 1]), vec![3]);
+        assert_eq!(class_group(vec![-11, 0, 0, 1]), vec![2]);
+        assert_eq!(class_group(vec![-82, 0, 1]), vec![4]);
+        assert_eq!(class_group(vec![7, 0, 0, 1]), vec![3]);
+    }
+
+    #[test]
+    fn test_class_number() {
+        // for imaginary quadratic fields, the reduced forms give the exact class number
+        for d in [
+            1_i64, 2, 3, 5, 6, 7, 11, 13, 15, 17, 19, 23, 26, 30, 31, 35, 39, 41,
+        ] {
+            let disc = if d % 4 == 3 { -d } else { -4 * d };
+            let h = BinaryQuadraticForm::class_number(&Integer::from(disc));
+            assert_eq!(ring(vec![d, 0, 1]).class_number(), h);
+        }
+    }
+}
@@ -9,6 +9,18 @@ pub enum ArithmeticError {
     OutOfBounds,
     #[error("algorithm {alg:?} is not defined for element {el:?}")]
     AlgNotApplicable { alg: String, el: u64 },
+    #[error("input is not homogeneous")]
+    NotHomogeneous,
+    #[error("element is not a unit")]
+    NotUnit,
+    #[error("polynomial is not irreducible")]
+    NotIrreducible,
+    #[error("moduli are not coprime")]
+    NotCoprime,
+    #[error("congruences have no common solution")]
+    NoSolution,
+    #[error("residue field is not separable")]
+    NotSeparable,
     //future idea:
     //
     //    there are many cases where the user claims an operation satisfies a certain property, like
@@ -0,0 +1,667 @@
+//! Factoring polynomials
+//!
+//! Univariate polynomials over $\mathbb{Z}$ and $\mathbb{Q}$ are factored with the Berlekamp-Zassenhaus
+//! approach: factor modulo a small prime $p$ (Cantor-Zassenhaus), Hensel lift the factors to $p^l$ (see `hensel`) past a
+//! bound on the coefficients of the true factors, and then try products of subsets of the lifted factors.
+//! This is exponential in the worst case (Swinnerton-Dyer polynomials) but fast for everything else.
+//!
+//! The trait `FactorField` is for coefficient fields with a factoring algorithm for $F[x]$, which are $\mathbb{Q}$
+//! and $\mathbb{F}\_p$ (as `Zmod<P>`). Anything generic that needs to factor (primary decomposition, for example)
+//! asks for it.
+//!
+//! TODO: van Hoeij's lattice recombination, to get rid of the exponential subset search.
+use crate::gcd::GcdField;
+use crate::hensel::dense_lift_factors;
+use crate::poly::Polynomial;
+use crate::zmod::Zmod;
+use crate::{Integer, Rational};
+use rug::ops::{Pow, RemRounding};
+use rug::rand::RandState;
+
+use num_traits::identities::Zero;
+
+/// Fields $F$ where polynomials in $F[x]$ can be factored into irreducibles.
+pub trait FactorField: GcdField {
+    /// Zero or a prime $p$. Fields of characteristic $p$ need to be $\mathbb{F}\_p$ itself, where every element
+    /// is its own $p$th power, since that's how multivariate factoring takes $p$th roots.
+    const CHARACTERISTIC: u64;
+
+    /// The monic irreducible factors of `f` with their multiplicities, sorted by degree. The leading
+    /// coefficient is dropped, so constants have no factors. Panics if `f` is zero.
+    fn factor_poly(f: &Polynomial<Self>) -> Vec<(Polynomial<Self>, usize)>;
+}
+
+/// Factors over $\mathbb{Q}$ by clearing denominators and factoring over $\mathbb{Z}$.
+impl FactorField for Rational {
+    const CHARACTERISTIC: u64 = 0;
+
+    fn factor_poly(f: &Polynomial<Rational>) -> Vec<(Polynomial<Rational>, usize)> {
+        let integral = primitive(&f.coeffs_take(f.deg() as usize + 1));
+        factor_over_z(&Polynomial::from(integral))
+            .1
+            .into_iter()
+            .map(|(g, e)| {
+                let g: Vec<Rational> = g.coeffs().into_iter().map(Rational::from).collect();
+                (Polynomial::from(g).monic(), e)
+            })
+            .collect()
+    }
+}
+
+/// Factors over $\mathbb{F}\_p$ with `factor_mod_p`, for prime $P$ (checked when this gets compiled).
+impl<const P: u64> FactorField for Zmod<P> {
+    const CHARACTERISTIC: u64 = P;
+
+    fn factor_poly(f: &Polynomial<Zmod<P>>) -> Vec<(Polynomial<Zmod<P>>, usize)> {
+        let () = Self::ASSERT_PRIME;
+        let lift: Vec<Integer> = f
+            .coeffs()
+            .iter()
+            .map(|c| Integer::from(c.value()))
+            .collect();
+        factor_mod_p(&Polynomial::from(lift), &Integer::from(P))
+            .into_iter()
+            .map(|(g, e)| {
+                let g: Vec<Zmod<P>> = g.coeffs().iter().map(Zmod::from).collect();
+                (Polynomial::from(g), e)
+            })
+            .collect()
+    }
+}
+
+/// Factors a polynomial over $\mathbb{Z}$ as its content (with the sign of the leading coefficient) times
+/// primitive irreducible factors with positive leading coefficients, given with their multiplicities and
+/// sorted by degree. Panics if `f` is zero.
+/// # Example:
+/// ```
+/// use bored_algebra::factor::factor_over_z;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::Integer;
+///
+/// // 2x^3 - 2 = 2 (x - 1)(x^2 + x + 1)
+/// let f = Polynomial::from(vec![Integer::from(-2), Integer::new(), Integer::new(), Integer::from(2)]);
+/// let (content, factors) = factor_over_z(&f);
+/// assert_eq!(content, 2);
+/// assert_eq!(factors.len(), 2);
+/// assert_eq!(factors[1].0.coeffs(), vec![Integer::from(1), Integer::from(1), Integer::from(1)]);
+/// ```
+pub fn factor_over_z(f: &Polynomial<Integer>) -> (Integer, Vec<(Polynomial<Integer>, usize)>) {
+    assert!(!f.is_zero(), "cannot factor zero");
+    let f = trim(f.coeffs());
+    let mut content = f.iter().fold(Integer::new(), |acc, c| acc.gcd(c));
+    if f.last().unwrap() < &0 {
+        content = -content;
+    }
+    let f: Vec<Integer> = f.iter().map(|c| Integer::from(c / &content)).collect();
+    let mut factors = vec![];
+    for (g, e) in squarefree_over_z(&f) {
+        for h in zassenhaus(&g) {
+            factors.push((Polynomial::from(h), e));
+        }
+    }
+    factors.sort_by(|(g, _), (h, _)| {
+        g.deg()
+            .cmp(&h.deg())
+            .then_with(|| g.coeffs().cmp(&h.coeffs()))
+    });
+    (content, factors)
+}
+
+/// The squarefree decomposition of a primitive polynomial with positive leading coefficient, by
+/// Yun's algorithm over $\mathbb{Q}$, as primitive integer polynomials.
+fn squarefree_over_z(f: &[Integer]) -> Vec<(Vec<Integer>, usize)> {
+    let to_q =
+        |f: &[Integer]| Polynomial::from(f.iter().map(Rational::from).collect::<Vec<Rational>>());
+    let f = to_q(f);
+    let mut result = vec![];
+    let mut c = f.gcd(&f.clone().derivative());
+    let mut w = f / c.clone();
+    let mut i = 1;
+    while w.deg() > 0 {
+        let y = w.gcd(&c);
+        let z = w.clone() / y.clone();
+        if z.deg() > 0 {
+            result.push((primitive(&z.coeffs_take(z.deg() as usize + 1)), i));
+        }
+        c = c / y.clone();
+        w = y;
+        i += 1;
+    }
+    result
+}
+
+/// The primitive integer polynomial with positive leading coefficient that is a rational multiple of `f`.
+fn primitive(f: &[Rational]) -> Vec<Integer> {
+    let denom = f.iter().fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+    let f: Vec<Integer> = f
+        .iter()
+        .map(|c| c.numer() * Integer::from(&denom / c.denom()))
+        .collect();
+    let mut content = f.iter().fold(Integer::new(), |acc, c| acc.gcd(c));
+    if f.last().unwrap() < &0 {
+        content = -content;
+    }
+    trim(f.iter().map(|c| Integer::from(c / &content)).collect())
+}
+
+/// Factors a squarefree primitive polynomial with positive leading coefficient over $\mathbb{Z}$.
+fn zassenhaus(f: &[Integer]) -> Vec<Vec<Integer>> {
+    let n = f.len() - 1;
+    if n <= 1 {
+        return vec![f.to_vec()];
+    }
+    let lc = f[n].clone();
+    // a few primes keeping f squarefree, and use the one giving the fewest factors
+    let mut p = Integer::from(2);
+    let mut best: Option<(Integer, Vec<Vec<Integer>>)> = None;
+    let mut tried = 0;
+    while tried < 3 {
+        p.next_prime_mut();
+        if lc.is_divisible(&p) {
+            continue;
+        }
+        let fp = reduce(f, &p);
+        if gcd_mod(&fp, &derivative_mod(&fp, &p), &p).len() > 1 {
+            continue;
+        }
+        tried += 1;
+        let factors: Vec<Vec<Integer>> = factor_squarefree_mod_p(&monic_mod(&fp, &p), &p);
+        if best.as_ref().is_none_or(|(_, b)| factors.len() < b.len()) {
+            best = Some((p.clone(), factors));
+        }
+    }
+    let (p, factors) = best.unwrap();
+    if factors.len() == 1 {
+        return vec![f.to_vec()];
+    }
+
+    // Landau-Mignotte: factors of f have coefficients at most 2^n |f|_2, and the lifted factors
+    // are multiplied by lc(f) before comparing
+    let max = f.iter().map(|c| c.clone().abs()).max().unwrap();
+    let bound = ((Integer::from(n + 1).sqrt() + 1u32) * max * lc.clone().abs()) << n as u32;
+    let mut modulus = p.clone();
+    let mut l = 1;
+    while modulus <= Integer::from(&bound * 2u32) {
+        modulus *= &p;
+        l += 1;
+    }
+    let lifted = dense_lift_factors(f, &factors, &p, l);
+
+    // try products of subsets of the lifted factors, smallest subsets first
+    let mut result = vec![];
+    let mut f = f.to_vec();
+    let mut remaining: Vec<Vec<Integer>> = lifted;
+    let mut size = 1;
+    'outer: while 2 * size <= remaining.len() {
+        let mut subset: Vec<usize> = (0..size).collect();
+        loop {
+            let lc = f.last().unwrap().clone();
+            let candidate = subset
+                .iter()
+                .fold(vec![lc], |acc, &i| mul_mod(&acc, &remaining[i], &modulus));
+            let candidate = primitive_int(&symmetric(&candidate, &modulus));
+            if let Some(q) = div_exact_z(&f, &candidate) {
+                result.push(candidate);
+                f = q;
+                remaining = remaining
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !subset.contains(i))
+                    .map(|(_, g)| g)
+                    .collect();
+                continue 'outer;
+            }
+            if !next_subset(&mut subset, remaining.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    result.push(f);
+    result
+}
+
+/// Steps `subset` (increasing indices below `n`) to the next subset of the same size in
+/// lexicographic order. Returns false when there are none left.
+pub(crate) fn next_subset(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    for i in (0..k).rev() {
+        if subset[i] < n - k + i {
+            subset[i] += 1;
+            for j in i + 1..k {
+                subset[j] = subset[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Exact division over $\mathbb{Z}$, or `None` if `g` does not divide `f`.
+fn div_exact_z(f: &[Integer], g: &[Integer]) -> Option<Vec<Integer>> {
+    let (n, k) = (f.len(), g.len());
+    if n < k {
+        return None;
+    }
+    let lead = g.last().unwrap();
+    let mut r = f.to_vec();
+    let mut q = vec![Integer::new(); n - k + 1];
+    for i in (0..=n - k).rev() {
+        if !r[i + k - 1].is_divisible(lead) {
+            return None;
+        }
+        let c = Integer::from(&r[i + k - 1] / lead);
+        for (j, d) in g.iter().enumerate() {
+            r[i + j] -= Integer::from(&c * d);
+        }
+        q[i] = c;
+    }
+    match r.iter().all(|c| c.is_zero()) {
+        true => Some(trim(q)),
+        false => None,
+    }
+}
+
+fn primitive_int(f: &[Integer]) -> Vec<Integer> {
+    let content = f.iter().fold(Integer::new(), |acc, c| acc.gcd(c));
+    let f: Vec<Integer> = f.iter().map(|c| Integer::from(c / &content)).collect();
+    match f.last().unwrap() < &0 {
+        true => f.into_iter().map(|c| -c).collect(),
+        false => f,
+    }
+}
+
+/// Coefficients in $(-m/2, m/2]$.
+fn symmetric(f: &[Integer], m: &Integer) -> Vec<Integer> {
+    let half = Integer::from(m >> 1);
+    f.iter()
+        .map(|c| match *c > half {
+            true => Integer::from(c - m),
+            false => c.clone(),
+        })
+        .collect()
+}
+
+/*
+ * Dense polynomials over Z/m, as coefficient vectors (constant term first) without trailing zeros,
+ * with coefficients in [0, m). Division needs the leading coefficient of the divisor to be a unit.
+ */
+
+fn trim(mut f: Vec<Integer>) -> Vec<Integer> {
+    while f.last().is_some_and(|c| c.is_zero()) {
+        f.pop();
+    }
+    f
+}
+
+pub(crate) fn reduce(f: &[Integer], m: &Integer) -> Vec<Integer> {
+    trim(f.iter().map(|c| Integer::from(c.rem_euc(m))).collect())
+}
+
+pub(crate) fn add_mod(a: &[Integer], b: &[Integer], m: &Integer) -> Vec<Integer> {
+    let n = a.len().max(b.len());
+    let zero = Integer::new();
+    reduce(
+        &(0..n)
+            .map(|i| Integer::from(a.get(i).unwrap_or(&zero) + b.get(i).unwrap_or(&zero)))
+            .collect::<Vec<Integer>>(),
+        m,
+    )
+}
+
+pub(crate) fn sub_mod(a: &[Integer], b: &[Integer], m: &Integer) -> Vec<Integer> {
+    let n = a.len().max(b.len());
+    let zero = Integer::new();
+    reduce(
+        &(0..n)
+            .map(|i| Integer::from(a.get(i).unwrap_or(&zero) - b.get(i).unwrap_or(&zero)))
+            .collect::<Vec<Integer>>(),
+        m,
+    )
+}
+
+pub(crate) fn mul_mod(a: &[Integer], b: &[Integer], m: &Integer) -> Vec<Integer> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut c = vec![Integer::new(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            c[i + j] += Integer::from(x * y);
+        }
+    }
+    reduce(&c, m)
+}
+
+pub(crate) fn scale_mod(a: &[Integer], c: &Integer, m: &Integer) -> Vec<Integer> {
+    reduce(
+        &a.iter()
+            .map(|x| Integer::from(x * c))
+            .collect::<Vec<Integer>>(),
+        m,
+    )
+}
+
+pub(crate) fn div_rem_mod(
+    a: &[Integer],
+    b: &[Integer],
+    m: &Integer,
+) -> (Vec<Integer>, Vec<Integer>) {
+    let k = b.len();
+    let inv = Integer::from(
+        b[k - 1]
+            .invert_ref(m)
+            .expect("leading coefficient is not a unit"),
+    );
+    let mut r = a.to_vec();
+    if r.len() < k {
+        return (vec![], reduce(&r, m));
+    }
+    let mut q = vec![Integer::new(); r.len() - k + 1];
+    for i in (0..=r.len() - k).rev() {
+        let c = Integer::from(&r[i + k - 1] * &inv).rem_euc(m);
+        if !c.is_zero() {
+            for (j, d) in b.iter().enumerate() {
+                r[i + j] -= Integer::from(&c * d);
+                r[i + j] = Integer::from((&r[i + j]).rem_euc(m));
+            }
+        }
+        q[i] = c;
+    }
+    r.truncate(k - 1);
+    (trim(q), trim(r))
+}
+
+fn monic_mod(a: &[Integer], p: &Integer) -> Vec<Integer> {
+    match a.last() {
+        Some(c) => scale_mod(a, &Integer::from(c.invert_ref(p).unwrap()), p),
+        None => vec![],
+    }
+}
+
+pub(crate) fn gcd_mod(a: &[Integer], b: &[Integer], p: &Integer) -> Vec<Integer> {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    while !b.is_empty() {
+        let r = div_rem_mod(&a, &b, p).1;
+        a = b;
+        b = r;
+    }
+    monic_mod(&a, p)
+}
+
+/// Returns $(g, s, t)$ with $g = sa + tb$ the monic gcd.
+pub(crate) fn ext_gcd_mod(
+    a: &[Integer],
+    b: &[Integer],
+    p: &Integer,
+) -> (Vec<Integer>, Vec<Integer>, Vec<Integer>) {
+    let (mut r0, mut r1) = (a.to_vec(), b.to_vec());
+    let (mut s0, mut s1) = (vec![Integer::from(1)], vec![]);
+    let (mut t0, mut t1) = (vec![], vec![Integer::from(1)]);
+    while !r1.is_empty() {
+        let (q, r) = div_rem_mod(&r0, &r1, p);
+        let s = sub_mod(&s0, &mul_mod(&q, &s1, p), p);
+        let t = sub_mod(&t0, &mul_mod(&q, &t1, p), p);
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+    let inv = Integer::from(r0.last().unwrap().invert_ref(p).unwrap());
+    (
+        scale_mod(&r0, &inv, p),
+        scale_mod(&s0, &inv, p),
+        scale_mod(&t0, &inv, p),
+    )
+}
+
+pub(crate) fn derivative_mod(a: &[Integer], p: &Integer) -> Vec<Integer> {
+    reduce(
+        &a.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| Integer::from(c * i))
+            .collect::<Vec<Integer>>(),
+        p,
+    )
+}
+
+/// `base`$^e$ modulo `f` and $p$.
+fn pow_mod(base: &[Integer], e: &Integer, f: &[Integer], p: &Integer) -> Vec<Integer> {
+    let mut result = vec![Integer::from(1)];
+    let base = div_rem_mod(base, f, p).1;
+    for i in (0..e.significant_bits()).rev() {
+        result = div_rem_mod(&mul_mod(&result, &result, p), f, p).1;
+        if e.get_bit(i) {
+            result = div_rem_mod(&mul_mod(&result, &base, p), f, p).1;
+        }
+    }
+    div_rem_mod(&result, f, p).1
+}
+
+/// Factors a polynomial over $\mathbb{F}\_p$, where the coefficients of `f` are integers standing for their
+/// residues mod the prime $p$. Returns the monic irreducible factors (with coefficients in $[0, p)$) with
+/// their multiplicities, sorted by degree. Panics if `f` is zero mod $p$.
+/// # Example:
+/// ```
+/// use bored_algebra::factor::factor_mod_p;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::Integer;
+///
+/// // x^2 + 1 = (x + 2)(x + 3) mod 5
+/// let f = Polynomial::from(vec![Integer::from(1), Integer::new(), Integer::from(1)]);
+/// let factors = factor_mod_p(&f, &Integer::from(5));
+/// assert_eq!(factors.len(), 2);
+/// assert_eq!(factors[0].0.coeffs(), vec![Integer::from(2), Integer::from(1)]);
+/// ```
+pub fn factor_mod_p(f: &Polynomial<Integer>, p: &Integer) -> Vec<(Polynomial<Integer>, usize)> {
+    let f = reduce(&f.coeffs(), p);
+    assert!(!f.is_empty(), "cannot factor zero");
+    dense_factor_mod_p(&f, p)
+        .into_iter()
+        .map(|(g, e)| (Polynomial::from(g), e))
+        .collect()
+}
+
+/// `factor_mod_p` on coefficient vectors.
+fn dense_factor_mod_p(f: &[Integer], p: &Integer) -> Vec<(Vec<Integer>, usize)> {
+    let f = monic_mod(&reduce(f, p), p);
+    let mut factors = vec![];
+    for (g, e) in squarefree_mod_p(&f, p) {
+        for h in factor_squarefree_mod_p(&g, p) {
+            factors.push((h, e));
+        }
+    }
+    factors.sort_by(|(g, _), (h, _)| g.len().cmp(&h.len()).then_with(|| g.cmp(h)));
+    factors
+}
+
+/// The squarefree decomposition of a monic polynomial over $\mathbb{F}\_p$. Where the derivative
+/// vanishes, the polynomial is a $p$th power, and since $a^p = a$ in $\mathbb{F}\_p$ its $p$th root just
+/// divides the exponents by $p$.
+fn squarefree_mod_p(f: &[Integer], p: &Integer) -> Vec<(Vec<Integer>, usize)> {
+    let q = p.to_usize().unwrap();
+    let pth_root = |f: &[Integer]| -> Vec<Integer> { f.iter().step_by(q).cloned().collect() };
+    let mut result = vec![];
+    if f.len() <= 1 {
+        return result;
+    }
+    let df = derivative_mod(f, p);
+    if df.is_empty() {
+        return squarefree_mod_p(&pth_root(f), p)
+            .into_iter()
+            .map(|(g, e)| (g, e * q))
+            .collect();
+    }
+    let mut c = gcd_mod(f, &df, p);
+    let mut w = div_rem_mod(f, &c, p).0;
+    let mut i = 1;
+    while w.len() > 1 {
+        let y = gcd_mod(&w, &c, p);
+        let z = div_rem_mod(&w, &y, p).0;
+        if z.len() > 1 {
+            result.push((z, i));
+        }
+        c = div_rem_mod(&c, &y, p).0;
+        w = y;
+        i += 1;
+    }
+    if c.len() > 1 {
+        result.extend(
+            squarefree_mod_p(&pth_root(&c), p)
+                .into_iter()
+                .map(|(g, e)| (g, e * q)),
+        );
+    }
+    result
+}
+
+/// Cantor-Zassenhaus: splits a monic squarefree polynomial over $\mathbb{F}\_p$ by distinct-degree
+/// factorization, then equal-degree splitting.
+fn factor_squarefree_mod_p(f: &[Integer], p: &Integer) -> Vec<Vec<Integer>> {
+    let x = vec![Integer::new(), Integer::from(1)];
+    let mut rng = RandState::new();
+    let mut result = vec![];
+    let mut rest = f.to_vec();
+    let mut h = x.clone();
+    let mut d = 1;
+    while rest.len() > 2 * d {
+        h = pow_mod(&h, p, &rest, p);
+        let g = gcd_mod(&sub_mod(&h, &x, p), &rest, p);
+        if g.len() > 1 {
+            rest = div_rem_mod(&rest, &g, p).0;
+            h = div_rem_mod(&h, &rest, p).1;
+            result.extend(equal_degree(&g, d, p, &mut rng));
+        }
+        d += 1;
+    }
+    if rest.len() > 1 {
+        result.push(rest);
+    }
+    result
+}
+
+/// Splits a monic product of distinct irreducibles of degree `d` over $\mathbb{F}\_p$.
+fn equal_degree(f: &[Integer], d: usize, p: &Integer, rng: &mut RandState) -> Vec<Vec<Integer>> {
+    let n = f.len() - 1;
+    if n == d {
+        return vec![f.to_vec()];
+    }
+    loop {
+        let a: Vec<Integer> = trim(
+            (0..n)
+                .map(|_| Integer::from(p.random_below_ref(rng)))
+                .collect(),
+        );
+        if a.len() <= 1 {
+            continue;
+        }
+        let b = if *p == 2 {
+            // the trace a + a^2 + ... + a^(2^(d-1))
+            let mut power = a.clone();
+            let mut trace = a.clone();
+            for _ in 1..d {
+                power = div_rem_mod(&mul_mod(&power, &power, p), f, p).1;
+                trace = add_mod(&trace, &power, p);
+            }
+            trace
+        } else {
+            let e = (p.clone().pow(d as u32) - 1u32) >> 1;
+            sub_mod(&pow_mod(&a, &e, f, p), &[Integer::from(1)], p)
+        };
+        let g = gcd_mod(&b, f, p);
+        if g.len() > 1 && g.len() < f.len() {
+            let mut factors = equal_degree(&g, d, p, rng);
+            factors.extend(equal_degree(&div_rem_mod(f, &g, p).0, d, p, rng));
+            return factors;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::{ints, zpoly};
+
+    #[test]
+    fn test_mod_p() {
+        let p = Integer::from(5);
+        // x^4 - 1 = (x - 1)(x - 2)(x - 3)(x - 4) mod 5
+        let factors = dense_factor_mod_p(&ints(vec![-1, 0, 0, 0, 1]), &p);
+        assert_eq!(factors.len(), 4);
+        assert!(factors.iter().all(|(g, e)| g.len() == 2 && *e == 1));
+        // x^5 + 1 = (x + 1)^5 mod 5
+        assert_eq!(
+            dense_factor_mod_p(&ints(vec![1, 0, 0, 0, 0, 1]), &p),
+            vec![(ints(vec![1, 1]), 5)]
+        );
+        // x^4 + x + 1 is irreducible mod 2, and x^2 + x = x(x + 1)
+        let two = Integer::from(2);
+        assert_eq!(
+            dense_factor_mod_p(&ints(vec![1, 1, 0, 0, 1]), &two).len(),
+            1
+        );
+        assert_eq!(dense_factor_mod_p(&ints(vec![0, 1, 1]), &two).len(), 2);
+        // (x^2 + 1)^2 (x^2 + x + 1) mod 3: x^2 + 1 is irreducible mod 3
+        let three = Integer::from(3);
+        let f = mul_mod(
+            &mul_mod(&ints(vec![1, 0, 1]), &ints(vec![1, 0, 1]), &three),
+            &ints(vec![1, 1, 1]),
+            &three,
+        );
+        let factors = dense_factor_mod_p(&f, &three);
+        assert!(factors.contains(&(ints(vec![1, 0, 1]), 2)));
+        assert!(factors.contains(&(ints(vec![2, 1]), 2))); // x^2 + x + 1 = (x - 1)^2 mod 3
+    }
+
+    #[test]
+    fn test_over_z() {
+        // x^4 + 1 is irreducible over Z but splits mod every prime
+        let (c, factors) = factor_over_z(&zpoly(vec![1, 0, 0, 0, 1]));
+        assert_eq!((c, factors.len()), (Integer::from(1), 1));
+        // -6x^4 + 6 = -6 (x - 1)(x + 1)(x^2 + 1)
+        let (c, factors) = factor_over_z(&zpoly(vec![6, 0, 0, 0, -6]));
+        assert_eq!(c, -6);
+        assert_eq!(
+            factors,
+            vec![
+                (zpoly(vec![-1, 1]), 1),
+                (zpoly(vec![1, 1]), 1),
+                (zpoly(vec![1, 0, 1]), 1)
+            ]
+        );
+        // (2x + 3)^2 (x^3 - 2)
+        let f = zpoly(vec![3, 2]) * zpoly(vec![3, 2]) * zpoly(vec![-2, 0, 0, 1]);
+        let (_, factors) = factor_over_z(&f);
+        assert_eq!(
+            factors,
+            vec![(zpoly(vec![3, 2]), 2), (zpoly(vec![-2, 0, 0, 1]), 1)]
+        );
+        // x^8 - 1 = (x - 1)(x + 1)(x^2 + 1)(x^4 + 1)
+        let (_, factors) = factor_over_z(&zpoly(vec![-1, 0, 0, 0, 0, 0, 0, 0, 1]));
+        assert_eq!(factors.len(), 4);
+    }
+
+    #[test]
+    fn test_over_q() {
+        // x^2 - 1/4 = (x - 1/2)(x + 1/2)
+        let f = Polynomial::from(vec![
+            Rational::from((-1, 4)),
+            Rational::new(),
+            Rational::from(1),
+        ]);
+        let factors = Rational::factor_poly(&f);
+        assert_eq!(
+            factors,
+            vec![
+                (
+                    Polynomial::from(vec![Rational::from((-1, 2)), Rational::from(1)]),
+                    1
+                ),
+                (
+                    Polynomial::from(vec![Rational::from((1, 2)), Rational::from(1)]),
+                    1
+                ),
+            ]
+        );
+    }
+}
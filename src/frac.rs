@@ -0,0 +1,380 @@
+//! Fields of fractions
+//!
+//! `Frac<R>` is a fraction $a/b$ over a gcd domain $R$, always kept reduced: $\gcd(a, b) = 1$ and $b$ normal (see
+//! `IntegralDomain::normal_unit`), with $0 = 0/1$. So every element has exactly one representation and equality
+//! is just equality of numerators and denominators. `Frac<Integer>` is $\mathbb{Q}$ (use `Rational` for that,
+//! it's much faster), and `Frac<Polynomial<Integer>>` is the field $\mathbb{Q}(x)$ of rational functions, with
+//! positive leading coefficients in the denominators.
+//!
+//! Rational functions can be evaluated, with poles reported as `DivideByZero`, and split into partial fractions.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::frac::Frac;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::{Integer, Rational};
+//!
+//! let p = |c: Vec<i64>| Polynomial::from(c.into_iter().map(Integer::from).collect::<Vec<_>>());
+//! // (x^2 - 1)/(2x - 2) = (x + 1)/2
+//! let f = Frac::new(p(vec![-1, 0, 1]), p(vec![-2, 2])).unwrap();
+//! assert_eq!(f.numer(), &p(vec![1, 1]));
+//! assert_eq!(f.denom(), &p(vec![2]));
+//! assert_eq!(f.eval(&Rational::from(3)).unwrap(), 2);
+//!
+//! // 1/(x^2 - 1) has a pole at 1
+//! let g = Frac::new(p(vec![1]), p(vec![-1, 0, 1])).unwrap();
+//! assert!(g.eval(&Rational::from(1)).is_err());
+//! ```
+use crate::error::ArithmeticError;
+use crate::factor::factor_over_z;
+use crate::module::ModType;
+use crate::poly::{int_pow, Polynomial};
+use crate::structure::{CommRing, EuclideanDomain, Field, GcdDomain, IntegralDomain, Monoid};
+use crate::{Integer, Rational};
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+
+/// A reduced fraction over the gcd domain `R`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frac<R: GcdDomain> {
+    num: R,
+    den: R,
+}
+
+impl<R: GcdDomain> Frac<R> {
+    /// The fraction `num/den`, reduced, or `DivideByZero` if `den` is zero.
+    pub fn new(num: R, den: R) -> Result<Self, ArithmeticError> {
+        match den.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => Ok(Self::reduced(num, den)),
+        }
+    }
+
+    /// Divides out the gcd and the normal unit of the denominator, which has to be nonzero.
+    fn reduced(num: R, den: R) -> Self {
+        if num.is_zero() {
+            return Self::zero();
+        }
+        let g = num.gcd(&den);
+        let (num, den) = (num.div_exact(&g).unwrap(), den.div_exact(&g).unwrap());
+        let u = den.normal_unit();
+        Self {
+            num: num.div_exact(&u).unwrap(),
+            den: den.div_exact(&u).unwrap(),
+        }
+    }
+
+    pub fn numer(&self) -> &R {
+        &self.num
+    }
+
+    pub fn denom(&self) -> &R {
+        &self.den
+    }
+
+    /// Whether this is in $R$, i.e. the denominator is 1.
+    pub fn is_integral(&self) -> bool {
+        self.den.is_one()
+    }
+}
+
+/// $a = a/1$.
+impl<R: GcdDomain> From<R> for Frac<R> {
+    fn from(a: R) -> Self {
+        Self {
+            num: a,
+            den: R::one(),
+        }
+    }
+}
+
+impl<R: GcdDomain> Zero for Frac<R> {
+    fn zero() -> Self {
+        Self::from(R::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+}
+
+impl<R: GcdDomain> One for Frac<R> {
+    fn one() -> Self {
+        Self::from(R::one())
+    }
+
+    fn is_one(&self) -> bool {
+        self.num.is_one() && self.den.is_one()
+    }
+}
+
+/// $a/b + c/d = (a (d/g) + c (b/g)) / (b (d/g))$ with $g = \gcd(b, d)$, which keeps the products small.
+impl<R: GcdDomain> Add for Frac<R> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let g = self.den.gcd(&rhs.den);
+        let (b, d) = (
+            self.den.div_exact(&g).unwrap(),
+            rhs.den.div_exact(&g).unwrap(),
+        );
+        Self::reduced(self.num * d.clone() + rhs.num * b, self.den * d)
+    }
+}
+
+impl<R: GcdDomain> Neg for Frac<R> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl<R: GcdDomain> Sub for Frac<R> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+/// Cross-cancels before multiplying: $\frac{a}{b} \cdot \frac{c}{d} = \frac{(a/g\_1)(c/g\_2)}{(b/g\_2)(d/g\_1)}$
+/// with $g\_1 = \gcd(a, d)$ and $g\_2 = \gcd(c, b)$.
+impl<R: GcdDomain> Mul for Frac<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        let (g1, g2) = (self.num.gcd(&rhs.den), rhs.num.gcd(&self.den));
+        let num = self.num.div_exact(&g1).unwrap() * rhs.num.div_exact(&g2).unwrap();
+        let den = self.den.div_exact(&g2).unwrap() * rhs.den.div_exact(&g1).unwrap();
+        Self::reduced(num, den)
+    }
+}
+
+/// Panics when dividing by zero, like `Rational`. `Field::inv` doesn't.
+impl<R: GcdDomain> Div for Frac<R> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * Field::inv(&rhs).expect("divide by zero")
+    }
+}
+
+impl<R: GcdDomain> ModType<Frac<R>> for Frac<R> {
+    fn mod_mul(lhs: Self, rhs: Self) -> Self {
+        lhs * rhs
+    }
+}
+
+impl<R: GcdDomain> Monoid for Frac<R> {}
+impl<R: GcdDomain> CommRing for Frac<R> {}
+
+impl<R: GcdDomain> IntegralDomain for Frac<R> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.clone() / other.clone())
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => self.clone(),
+        }
+    }
+}
+
+impl<R: GcdDomain> GcdDomain for Frac<R> {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl<R: GcdDomain> EuclideanDomain for Frac<R> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self.clone() / other.clone(), Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+impl<R: GcdDomain> Field for Frac<R> {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        match self.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => Ok(Self::reduced(self.den.clone(), self.num.clone())),
+        }
+    }
+}
+
+fn to_rational(f: &Polynomial<Integer>) -> Polynomial<Rational> {
+    Polynomial::from(
+        f.coeffs_take(f.deg() as usize + 1)
+            .into_iter()
+            .map(Rational::from)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Clears the denominators of the coefficients.
+impl From<Polynomial<Rational>> for Frac<Polynomial<Integer>> {
+    fn from(f: Polynomial<Rational>) -> Self {
+        let coeffs = f.coeffs_take(f.deg() as usize + 1);
+        let lcm = coeffs
+            .iter()
+            .fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+        let num = coeffs
+            .iter()
+            .map(|c| c.numer() * Integer::from(&lcm / c.denom()))
+            .collect::<Vec<_>>();
+        Self::reduced(Polynomial::from(num), Polynomial::from(vec![lcm]))
+    }
+}
+
+/// Rational functions in $\mathbb{Q}(x)$.
+impl Frac<Polynomial<Integer>> {
+    /// The value at $a$, or `DivideByZero` if $a$ is a pole. Since the fraction is reduced, the poles are
+    /// exactly the roots of the denominator.
+    pub fn eval(&self, a: &Rational) -> Result<Rational, ArithmeticError> {
+        let den = to_rational(&self.den).eval(a.clone());
+        match den.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => Ok(to_rational(&self.num).eval(a.clone()) / den),
+        }
+    }
+
+    /// The partial fraction decomposition
+    /// $$
+    /// \frac{a}{b} = p + \sum\_{i} \sum\_{j=1}^{e\_i} \frac{c\_{ij}}{q\_i^j},
+    /// $$
+    /// where $b = c \prod\_i q\_i^{e\_i}$ factors over $\mathbb{Z}$ and $\deg c\_{ij} < \deg q\_i$. Returns $p$ and
+    /// the nonzero terms as $(c\_{ij}, q\_i, j)$, grouped by factor (in the order of `factor_over_z`) with $j$
+    /// increasing. The $q\_i$ are primitive with positive leading coefficients, and the numerators are over
+    /// $\mathbb{Q}$.
+    #[allow(clippy::type_complexity)]
+    pub fn partial_fractions(
+        &self,
+    ) -> (
+        Polynomial<Rational>,
+        Vec<(Polynomial<Rational>, Polynomial<Integer>, usize)>,
+    ) {
+        let (num, den) = (to_rational(&self.num), to_rational(&self.den));
+        let (p, r) = num.div_rem(&den);
+        let mut terms = vec![];
+        if r.is_zero() {
+            return (p, terms);
+        }
+        for (q, e) in factor_over_z(&self.den).1 {
+            let q_rat = to_rational(&q);
+            let power = int_pow(q_rat.clone(), e);
+            // r/b = sum of r_i/q_i^e_i, with r_i = r (b/q_i^e_i)^{-1} mod q_i^e_i
+            let rest = den.div_rem(&power).0;
+            let (_, s, _) = rest.ext_gcd(&power);
+            let mut r_i = (r.clone() * s).div_rem(&power).1;
+            // then the q_i-adic digits of r_i
+            let mut digits = vec![];
+            for j in (1..=e).rev() {
+                let (quot, rem) = r_i.div_rem(&q_rat);
+                if !rem.is_zero() {
+                    digits.push((rem, q.clone(), j));
+                }
+                r_i = quot;
+            }
+            terms.extend(digits.into_iter().rev());
+        }
+        (p, terms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zpoly;
+
+    fn poly_q(coeffs: Vec<(i64, i64)>) -> Polynomial<Rational> {
+        Polynomial::from(coeffs.into_iter().map(Rational::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_integer() {
+        let q = |a: i64, b: i64| Frac::new(Integer::from(a), Integer::from(b)).unwrap();
+        assert_eq!(q(6, -4).numer(), &-3);
+        assert_eq!(q(6, -4).denom(), &2);
+        assert_eq!(q(1, 6) + q(1, 3), q(1, 2));
+        assert_eq!(q(3, 4) - q(3, 4), Frac::zero());
+        assert_eq!(q(4, 9) * q(3, 8), q(1, 6));
+        assert_eq!(q(2, 3) / q(-4, 9), q(-3, 2));
+        assert_eq!(Field::inv(&q(-2, 5)).unwrap(), q(-5, 2));
+        assert!(Field::inv(&Frac::<Integer>::zero()).is_err());
+        assert!(Frac::new(Integer::from(1), Integer::new()).is_err());
+        assert!(q(4, 2).is_integral());
+    }
+
+    #[test]
+    fn test_rational_functions() {
+        // 1/(x - 1) - 1/(x + 1) = 2/(x^2 - 1)
+        let a = Frac::new(zpoly(vec![1]), zpoly(vec![-1, 1])).unwrap();
+        let b = Frac::new(zpoly(vec![1]), zpoly(vec![1, 1])).unwrap();
+        let c = a.clone() - b.clone();
+        assert_eq!(
+            c,
+            Frac::new(zpoly(vec![-2]), zpoly(vec![1, 0, -1])).unwrap()
+        );
+        assert_eq!(c.denom(), &zpoly(vec![-1, 0, 1]));
+        assert_eq!(
+            c.clone() / a.clone(),
+            Frac::new(zpoly(vec![2]), zpoly(vec![1, 1])).unwrap()
+        );
+        assert_eq!(c.eval(&Rational::from(3)).unwrap(), Rational::from((1, 4)));
+        assert!(c.eval(&Rational::from(-1)).is_err());
+        // the common factor goes away, so 1 isn't a pole of (x - 1)/(x^2 - 1)
+        let d = Frac::new(zpoly(vec![-1, 1]), zpoly(vec![-1, 0, 1])).unwrap();
+        assert_eq!(d.eval(&Rational::from(1)).unwrap(), Rational::from((1, 2)));
+        assert_eq!(
+            Frac::from(poly_q(vec![(1, 2), (0, 1), (2, 3)])),
+            Frac::new(zpoly(vec![3, 0, 4]), zpoly(vec![6])).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_partial_fractions() {
+        // (x^4 + 1)/(x^3 - x^2) = x + 1 + 2/(x - 1) - 1/x - 1/x^2
+        let f = Frac::new(zpoly(vec![1, 0, 0, 0, 1]), zpoly(vec![0, 0, -1, 1])).unwrap();
+        let (p, terms) = f.partial_fractions();
+        assert_eq!(p, poly_q(vec![(1, 1), (1, 1)]));
+        assert_eq!(
+            terms,
+            vec![
+                (poly_q(vec![(2, 1)]), zpoly(vec![-1, 1]), 1),
+                (poly_q(vec![(-1, 1)]), zpoly(vec![0, 1]), 1),
+                (poly_q(vec![(-1, 1)]), zpoly(vec![0, 1]), 2),
+            ]
+        );
+
+        // x^3/((2x + 1)(x^2 + 1)^2), checked by adding the terms back up
+        let den = zpoly(vec![1, 2]) * zpoly(vec![1, 0, 1]) * zpoly(vec![1, 0, 1]);
+        let f = Frac::new(zpoly(vec![0, 0, 0, 1]), den).unwrap();
+        let (p, terms) = f.partial_fractions();
+        assert!(p.is_zero());
+        assert_eq!(terms.len(), 3);
+        let sum = terms.into_iter().fold(Frac::zero(), |acc, (c, q, j)| {
+            acc + Frac::from(c) / Frac::from(int_pow(q, j))
+        });
+        assert_eq!(sum, f);
+
+        let (p, terms) = Frac::from(zpoly(vec![1, 2, 3])).partial_fractions();
+        assert!(terms.is_empty());
+        assert_eq!(p, poly_q(vec![(1, 1), (2, 1), (3, 1)]));
+    }
+}
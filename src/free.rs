@@ -0,0 +1,172 @@
+//! Free modules
+//!
+//! An element of the free module $R^n$ is a vector of coordinates in $R$. As with `MPolynomial`, the rank
+//! $n$ is not fixed: coordinates past the end of the vector are zero, so that `Free<R>` can implement
+//! `Zero`.
+use crate::module::{ModType, RingType};
+use core::ops::{Add, Neg, Sub};
+
+use num_traits::identities::Zero;
+
+/// An element $(r\_0, r\_1, \ldots, r\_{n-1})$ of a free module $R^n$.
+/// # Example:
+/// ```
+/// use bored_algebra::free::Free;
+/// use bored_algebra::module::ModType;
+///
+/// let v = Free::from(vec![1_i64, 0, 2]);
+/// let w = Free::from(vec![0_i64, 3]);
+/// assert_eq!(v.clone() + w, Free::from(vec![1, 3, 2]));
+/// assert_eq!(Free::mod_mul(2, v), Free::from(vec![2, 0, 4, 0, 0]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Free<R> {
+    coords: Vec<R>,
+}
+
+impl<R: RingType> Free<R> {
+    /// The standard basis vector $e\_i$.
+    pub fn basis(i: usize) -> Self {
+        let mut coords = vec![R::zero(); i + 1];
+        coords[i] = R::one();
+        Self { coords }
+    }
+
+    /// The $i$th coordinate.
+    pub fn get(&self, i: usize) -> R {
+        self.coords.get(i).cloned().unwrap_or_else(R::zero)
+    }
+
+    /// Sets the $i$th coordinate, extending the vector with zeros if needed.
+    pub fn set(&mut self, i: usize, r: R) {
+        if i >= self.coords.len() {
+            self.coords.resize(i + 1, R::zero());
+        }
+        self.coords[i] = r;
+    }
+
+    /// The coordinates, possibly with trailing zeros.
+    pub fn coords(&self) -> &[R] {
+        &self.coords
+    }
+
+    /// The number of stored coordinates. Every coordinate past this is zero.
+    pub fn len(&self) -> usize {
+        self.coords.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coords.is_empty()
+    }
+
+    /// Indices of the nonzero coordinates.
+    pub fn support(&self) -> impl Iterator<Item = usize> + '_ {
+        self.coords
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| !r.is_zero())
+            .map(|(i, _)| i)
+    }
+
+    /// Multiplies every coordinate by `r`.
+    pub fn scale(&self, r: &R) -> Self {
+        Self {
+            coords: self.coords.iter().map(|a| r.clone() * a.clone()).collect(),
+        }
+    }
+
+    /// Applies `f` to every coordinate.
+    pub fn map<S: RingType>(&self, f: impl Fn(&R) -> S) -> Free<S> {
+        Free {
+            coords: self.coords.iter().map(f).collect(),
+        }
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(R, R) -> R) -> Self {
+        let n = self.len().max(other.len());
+        Self {
+            coords: (0..n).map(|i| f(self.get(i), other.get(i))).collect(),
+        }
+    }
+}
+
+impl<R: RingType> From<Vec<R>> for Free<R> {
+    fn from(coords: Vec<R>) -> Self {
+        Self { coords }
+    }
+}
+
+/// Two vectors are equal if all their coordinates are, counting missing ones as zero.
+impl<R: RingType> PartialEq for Free<R> {
+    fn eq(&self, other: &Self) -> bool {
+        (0..self.len().max(other.len())).all(|i| self.get(i) == other.get(i))
+    }
+}
+
+impl<R: RingType> Eq for Free<R> {}
+
+impl<R: RingType> Zero for Free<R> {
+    fn zero() -> Self {
+        Self { coords: vec![] }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coords.iter().all(|r| r.is_zero())
+    }
+}
+
+impl<R: RingType> Add for Free<R> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| a + b)
+    }
+}
+
+impl<R: RingType> Neg for Free<R> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            coords: self.coords.into_iter().map(|a| -a).collect(),
+        }
+    }
+}
+
+impl<R: RingType> Sub for Free<R> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| a - b)
+    }
+}
+
+impl<R: RingType> ModType<R> for Free<R> {
+    fn mod_mul(r: R, m: Self) -> Self {
+        m.scale(&r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Free::from(vec![1, 2, 0, 0]), Free::from(vec![1, 2]));
+        assert_ne!(Free::from(vec![1, 2, 0, 3]), Free::from(vec![1, 2]));
+        assert!(Free::from(vec![0, 0]).is_zero());
+        assert_eq!(Free::<i64>::zero(), Free::from(vec![0]));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let v = Free::from(vec![1, 2, 3]);
+        let w = Free::<i64>::basis(4);
+        assert_eq!(v.clone() - w.clone(), Free::from(vec![1, 2, 3, 0, -1]));
+        assert_eq!(-v.clone() + v.clone(), Free::zero());
+        assert_eq!(v.support().collect::<Vec<usize>>(), vec![0, 1, 2]);
+        assert_eq!(w.get(4), 1);
+        assert_eq!(w.get(100), 0);
+    }
+}
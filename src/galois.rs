@@ -0,0 +1,851 @@
+//! Finite fields
+//!
+//! $\mathrm{GF}(p^n)$ is $\mathbb{F}\_p[x]/(f)$ for a monic irreducible $f$ of degree $n$, and a `GaloisField<P>` is
+//! an element of it: a polynomial over `Zmod<P>` of degree less than $n$, together with the modulus $f$. As with
+//! `ZmodDyn`, `zero()` and `one()` can't know $f$, so they give elements of the prime field without a modulus,
+//! which pick one up from whatever they're combined with.
+//!
+//! Different irreducibles of the same degree give isomorphic fields, but the elements aren't compatible: mixing
+//! them panics.
+//!
+//! Things that enumerate the field (primitive element search, discrete logs, Zech logarithms) are only meant for
+//! small fields.
+//!
+//! `factor` factors polynomials over $\mathrm{GF}(q)$, $q = p^n$, like `factor_mod_p` does over $\mathbb{F}\_p$:
+//! a squarefree decomposition, where $p$th roots of coefficients are $a^{q/p}$, and then Cantor-Zassenhaus.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::galois::GaloisField;
+//! use num_traits::One;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::zmod::Zmod;
+//!
+//! // GF(4) = F_2[x]/(x^2 + x + 1)
+//! type F2 = Zmod<2>;
+//! let f = Polynomial::from(vec![F2::new(1), F2::new(1), F2::new(1)]);
+//! let a = GaloisField::generator(&f);
+//! assert_eq!(a.clone() * a.clone(), a.clone() + GaloisField::one());
+//! assert_eq!(a.pow(&3.into()), GaloisField::one());
+//! assert_eq!(a.trace(), F2::new(1));
+//! ```
+use crate::error::ArithmeticError;
+use crate::module::ModType;
+use crate::ntheory::factor::factor_integer;
+use crate::poly::Polynomial;
+use crate::structure::Field;
+use crate::zmod::Zmod;
+use crate::Integer;
+use rug::ops::Pow;
+use rug::rand::RandState;
+
+use core::fmt;
+use core::fmt::Display;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The distinct prime factors of $n$.
+fn prime_factors(n: &Integer) -> Vec<Integer> {
+    factor_integer(n).into_iter().map(|(p, _)| p).collect()
+}
+
+fn reduce<F: Field>(a: &Polynomial<F>, f: &Polynomial<F>) -> Polynomial<F> {
+    a.div_rem(f).1
+}
+
+/// $a^e \bmod f$, by repeated squaring.
+pub(crate) fn pow_mod<F: Field>(
+    a: &Polynomial<F>,
+    e: &Integer,
+    f: &Polynomial<F>,
+) -> Polynomial<F> {
+    let mut result = reduce(&Polynomial::one(), f);
+    let base = reduce(a, f);
+    for i in (0..e.significant_bits()).rev() {
+        result = reduce(&(result.clone() * result), f);
+        if e.get_bit(i) {
+            result = reduce(&(result * base.clone()), f);
+        }
+    }
+    result
+}
+
+/// Rabin's irreducibility test: $f$ of degree $n$ is irreducible over $\mathbb{F}\_p$ exactly when $f$ divides
+/// $x^{p^n} - x$, and $\gcd(x^{p^{n/q}} - x, f) = 1$ for every prime $q \mid n$.
+/// # Example:
+/// ```
+/// use bored_algebra::galois::is_irreducible;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::zmod::Zmod;
+///
+/// type F2 = Zmod<2>;
+/// let (zero, one) = (F2::new(0), F2::new(1));
+/// // x^4 + x + 1 is irreducible mod 2, but x^4 + x^2 + 1 = (x^2 + x + 1)^2
+/// assert!(is_irreducible(&Polynomial::from(vec![one, one, zero, zero, one])));
+/// assert!(!is_irreducible(&Polynomial::from(vec![one, zero, one, zero, one])));
+/// ```
+pub fn is_irreducible<const P: u64>(f: &Polynomial<Zmod<P>>) -> bool {
+    let n = f.deg();
+    if n == 0 {
+        return false;
+    }
+    let x = Polynomial::from(vec![Zmod::zero(), Zmod::one()]);
+    let p = Integer::from(P);
+    // the powers x^(p^k) mod f, each the p-th power of the last
+    let mut frobenius = vec![reduce(&x, f)];
+    for k in 1..=n as usize {
+        frobenius.push(pow_mod(&frobenius[k - 1], &p, f));
+    }
+    if frobenius[n as usize] != reduce(&x, f) {
+        return false;
+    }
+    prime_factors(&Integer::from(n)).iter().all(|q| {
+        let k = (n / q.to_u64().unwrap()) as usize;
+        (frobenius[k].clone() - x.clone()).gcd(f).deg() == 0
+    })
+}
+
+/// An element of the finite field $\mathbb{F}\_p[x]/(f)$, or of $\mathbb{F}\_p$ if it doesn't have a modulus yet.
+#[derive(Clone, Debug)]
+pub struct GaloisField<const P: u64> {
+    value: Polynomial<Zmod<P>>, // reduced mod the modulus
+    modulus: Option<Rc<Polynomial<Zmod<P>>>>,
+}
+
+impl<const P: u64> GaloisField<P> {
+    /// The class of `value` in $\mathbb{F}\_p[x]/(f)$. Panics unless $f$ is monic of positive degree.
+    ///
+    /// $f$ should be irreducible, but that isn't checked, since it isn't cheap: see `is_irreducible`. With a
+    /// reducible $f$ this is a ring with zero divisors, and `inverse` fails on them.
+    pub fn new(value: Polynomial<Zmod<P>>, modulus: &Polynomial<Zmod<P>>) -> Self {
+        assert!(
+            modulus.deg() > 0 && modulus.lead_coeff().is_one(),
+            "the modulus needs to be monic of positive degree"
+        );
+        Self::with_modulus(value, Some(Rc::new(modulus.clone())))
+    }
+
+    /// The class of $x$, which generates the field over $\mathbb{F}\_p$.
+    pub fn generator(modulus: &Polynomial<Zmod<P>>) -> Self {
+        Self::new(Polynomial::from(vec![Zmod::zero(), Zmod::one()]), modulus)
+    }
+
+    /// The element of the prime field $\mathbb{F}\_p$ given by `a`, without a modulus.
+    pub fn constant(a: Zmod<P>) -> Self {
+        Self::with_modulus(Polynomial::from(vec![a]), None)
+    }
+
+    fn with_modulus(value: Polynomial<Zmod<P>>, modulus: Option<Rc<Polynomial<Zmod<P>>>>) -> Self {
+        let value = match &modulus {
+            Some(f) => reduce(&value, f),
+            None => value,
+        };
+        Self { value, modulus }
+    }
+
+    /// The representative polynomial, of degree less than that of the modulus.
+    pub fn value(&self) -> &Polynomial<Zmod<P>> {
+        &self.value
+    }
+
+    pub fn modulus(&self) -> Option<&Polynomial<Zmod<P>>> {
+        self.modulus.as_deref()
+    }
+
+    /// The degree $n$ of the field over $\mathbb{F}\_p$, which is 1 without a modulus.
+    pub fn degree(&self) -> u64 {
+        self.modulus.as_ref().map_or(1, |f| f.deg())
+    }
+
+    /// The number of elements of the field, $p^n$.
+    pub fn field_size(&self) -> Integer {
+        Integer::from(P).pow(self.degree() as u32)
+    }
+
+    fn common_modulus(&self, other: &Self) -> Option<Rc<Polynomial<Zmod<P>>>> {
+        match (&self.modulus, &other.modulus) {
+            (None, m) | (m, None) => m.clone(),
+            (Some(f), Some(g)) => {
+                assert!(Rc::ptr_eq(f, g) || f == g, "elements of different fields");
+                Some(f.clone())
+            }
+        }
+    }
+
+    /// `self` to the power of $e \geq 0$.
+    pub fn pow(&self, e: &Integer) -> Self {
+        let value = match &self.modulus {
+            Some(f) => pow_mod(&self.value, e, f),
+            None => Polynomial::from(vec![self.value.lead_coeff().pow(e.to_u64().unwrap())]),
+        };
+        Self::with_modulus(value, self.modulus.clone())
+    }
+
+    /// The inverse, from the extended Euclidean algorithm: if $sa + tf = 1$ then $s = a^{-1}$. Fails with
+    /// `NotUnit` for zero, or for a zero divisor if the modulus isn't irreducible.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        let f = match &self.modulus {
+            Some(f) => f,
+            None => {
+                let inv = self.value.lead_coeff().inverse()?;
+                return Ok(Self::constant(inv));
+            }
+        };
+        let (g, s, _) = self.value.ext_gcd(f);
+        match g.is_one() {
+            true => Ok(Self::with_modulus(s, self.modulus.clone())),
+            false => Err(ArithmeticError::NotUnit),
+        }
+    }
+
+    /// The Frobenius automorphism $a \mapsto a^p$, which generates the Galois group over $\mathbb{F}\_p$.
+    pub fn frobenius(&self) -> Self {
+        self.pow(&Integer::from(P))
+    }
+
+    /// The conjugates $a, a^p, \ldots, a^{p^{n-1}}$.
+    pub fn conjugates(&self) -> Vec<Self> {
+        let mut conjugates = vec![self.clone()];
+        for _ in 1..self.degree() {
+            let next = conjugates.last().unwrap().frobenius();
+            conjugates.push(next);
+        }
+        conjugates
+    }
+
+    /// The trace to $\mathbb{F}\_p$, the sum of the conjugates.
+    pub fn trace(&self) -> Zmod<P> {
+        let sum = self
+            .conjugates()
+            .into_iter()
+            .fold(Self::zero(), |acc, a| acc + a);
+        sum.value.coeffs()[0]
+    }
+
+    /// The norm to $\mathbb{F}\_p$, the product of the conjugates.
+    pub fn norm(&self) -> Zmod<P> {
+        let product = self
+            .conjugates()
+            .into_iter()
+            .fold(Self::one(), |acc, a| acc * a);
+        product.value.coeffs()[0]
+    }
+
+    /// The order of `self` in the multiplicative group, the smallest $k > 0$ with $a^k = 1$. Panics for zero.
+    pub fn multiplicative_order(&self) -> Integer {
+        assert!(!self.is_zero(), "zero has no multiplicative order");
+        let mut order: Integer = self.field_size() - 1;
+        for q in prime_factors(&order.clone()) {
+            while order.is_divisible(&q) && self.pow(&Integer::from(&order / &q)).is_one() {
+                order /= &q;
+            }
+        }
+        order
+    }
+
+    /// Returns true if `self` generates the multiplicative group.
+    pub fn is_primitive(&self) -> bool {
+        !self.is_zero() && self.multiplicative_order() == self.field_size() - 1
+    }
+
+    /// The element whose coefficients are the base $p$ digits of `index`, least significant first.
+    fn from_index(index: u64, modulus: &Rc<Polynomial<Zmod<P>>>) -> Self {
+        let mut coeffs = vec![];
+        let mut i = index;
+        for _ in 0..modulus.deg() {
+            coeffs.push(Zmod::new(i % P));
+            i /= P;
+        }
+        Self::with_modulus(Polynomial::from(coeffs), Some(modulus.clone()))
+    }
+
+    /// The inverse of `from_index`.
+    fn index(&self) -> u64 {
+        self.value
+            .coeffs_take(self.value.deg() as usize + 1)
+            .iter()
+            .rev()
+            .fold(0, |acc, c| acc * P + c.value())
+    }
+
+    /// The first primitive element, trying $x$ first and then the elements in the order of their base $p$
+    /// digits. Panics if the modulus isn't irreducible.
+    pub fn primitive_element(modulus: &Polynomial<Zmod<P>>) -> Self {
+        let x = Self::generator(modulus);
+        if x.is_primitive() {
+            return x;
+        }
+        let size = x.field_size().to_u64().expect("field too big to search");
+        (1..size)
+            .map(|i| Self::from_index(i, x.modulus.as_ref().unwrap()))
+            .find(|a| a.is_primitive())
+            .expect("the modulus isn't irreducible")
+    }
+
+    /// The discrete logarithm of `self` to the base $b$: the smallest $k \geq 0$ with $b^k = $ `self`, or `None` if
+    /// there isn't one. This is baby-step giant-step, taking about $\sqrt{p^n}$ time and memory.
+    pub fn discrete_log(&self, base: &Self) -> Option<Integer> {
+        if self.is_zero() || base.is_zero() {
+            return None;
+        }
+        let order = base.multiplicative_order().to_u64().expect("field too big");
+        let m = (order as f64).sqrt().ceil() as u64;
+        // baby steps b^j, keeping the smallest j for each value
+        let mut table = HashMap::new();
+        let mut power = Self::one();
+        for j in 0..m {
+            table.entry(power.index()).or_insert(j);
+            power = power * base.clone();
+        }
+        // giant steps a b^(-im)
+        let step = base.pow(&Integer::from(m)).inverse().unwrap();
+        let mut gamma = self.clone();
+        for i in 0..m {
+            if let Some(j) = table.get(&gamma.index()) {
+                return Some(Integer::from(i * m + j));
+            }
+            gamma = gamma * step.clone();
+        }
+        None
+    }
+}
+
+impl<const P: u64> PartialEq for GaloisField<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.common_modulus(other);
+        self.value == other.value
+    }
+}
+
+impl<const P: u64> Eq for GaloisField<P> {}
+
+impl<const P: u64> Zero for GaloisField<P> {
+    fn zero() -> Self {
+        Self::constant(Zmod::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<const P: u64> One for GaloisField<P> {
+    fn one() -> Self {
+        Self::constant(Zmod::one())
+    }
+
+    fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+}
+
+impl<const P: u64> Add for GaloisField<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let modulus = self.common_modulus(&rhs);
+        Self {
+            value: self.value + rhs.value,
+            modulus,
+        }
+    }
+}
+
+impl<const P: u64> Neg for GaloisField<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            value: -self.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<const P: u64> Sub for GaloisField<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let modulus = self.common_modulus(&rhs);
+        Self {
+            value: self.value - rhs.value,
+            modulus,
+        }
+    }
+}
+
+impl<const P: u64> Mul for GaloisField<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let modulus = self.common_modulus(&rhs);
+        Self::with_modulus(self.value * rhs.value, modulus)
+    }
+}
+
+/// Panics if `rhs` is zero.
+impl<const P: u64> Div for GaloisField<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        let modulus = self.common_modulus(&rhs);
+        let rhs = Self::with_modulus(rhs.value, modulus);
+        self * rhs.inverse().expect("divide by zero")
+    }
+}
+
+impl<const P: u64> ModType<GaloisField<P>> for GaloisField<P> {
+    fn mod_mul(r: GaloisField<P>, m: GaloisField<P>) -> GaloisField<P> {
+        r * m
+    }
+}
+
+/// $\mathrm{GF}(p^n)$ as a $\mathbb{Z}$-module.
+impl<const P: u64> ModType<Integer> for GaloisField<P> {
+    fn mod_mul(r: Integer, m: GaloisField<P>) -> GaloisField<P> {
+        GaloisField::constant(Zmod::from(&r)) * m
+    }
+}
+
+/// Writes the representative as a polynomial in $a$, the class of $x$.
+impl<const P: u64> Display for GaloisField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms: Vec<String> = self
+            .value
+            .coeffs_take(self.value.deg() as usize + 1)
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(k, c)| match (k, c.value()) {
+                (0, c) => format!("{}", c),
+                (1, 1) => "a".to_string(),
+                (1, c) => format!("{}a", c),
+                (k, 1) => format!("a^{}", k),
+                (k, c) => format!("{}a^{}", c, k),
+            })
+            .collect();
+        match terms.is_empty() {
+            true => write!(f, "0"),
+            false => write!(f, "{}", terms.join(" + ")),
+        }
+    }
+}
+
+/// Zech logarithms for a small field: with $\alpha$ primitive, every nonzero element is a power $\alpha^i$, and
+/// $Z(i)$ is defined by $1 + \alpha^i = \alpha^{Z(i)}$. Then multiplication is addition of logarithms, and
+/// addition is $\alpha^i + \alpha^j = \alpha^{i + Z(j - i)}$, so arithmetic only needs table lookups.
+#[derive(Clone, Debug)]
+pub struct ZechTable<const P: u64> {
+    primitive: GaloisField<P>,
+    powers: Vec<GaloisField<P>>,
+    logs: HashMap<u64, u64>,
+    zech: Vec<Option<u64>>,
+}
+
+impl<const P: u64> ZechTable<P> {
+    /// Makes the tables for $\mathbb{F}\_p[x]/(f)$ with the first primitive element. Panics if the field has more
+    /// than $2^{24}$ elements.
+    pub fn new(modulus: &Polynomial<Zmod<P>>) -> Self {
+        let primitive = GaloisField::primitive_element(modulus);
+        let size = primitive.field_size();
+        assert!(size <= 1 << 24, "field too big for Zech logarithms");
+        let order = size.to_u64().unwrap() - 1;
+        let mut powers = vec![GaloisField::one() * primitive.clone().pow(&Integer::new())];
+        for i in 1..order as usize {
+            powers.push(powers[i - 1].clone() * primitive.clone());
+        }
+        let logs: HashMap<u64, u64> = powers
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.index(), i as u64))
+            .collect();
+        let zech = powers
+            .iter()
+            .map(|a| logs.get(&(a.clone() + GaloisField::one()).index()).copied())
+            .collect();
+        Self {
+            primitive,
+            powers,
+            logs,
+            zech,
+        }
+    }
+
+    /// The primitive element $\alpha$ the logarithms are to.
+    pub fn primitive(&self) -> &GaloisField<P> {
+        &self.primitive
+    }
+
+    /// The $i$ with $\alpha^i = a$, or `None` for zero.
+    pub fn log(&self, a: &GaloisField<P>) -> Option<u64> {
+        self.logs.get(&a.index()).copied()
+    }
+
+    /// $\alpha^i$.
+    pub fn exp(&self, i: u64) -> GaloisField<P> {
+        self.powers[(i % self.powers.len() as u64) as usize].clone()
+    }
+
+    /// The Zech logarithm $Z(i)$, or `None` if $1 + \alpha^i = 0$.
+    pub fn zech(&self, i: u64) -> Option<u64> {
+        self.zech[(i % self.zech.len() as u64) as usize]
+    }
+
+    /// The logarithm of $\alpha^i \alpha^j$.
+    pub fn mul_logs(&self, i: u64, j: u64) -> u64 {
+        (i + j) % self.powers.len() as u64
+    }
+
+    /// The logarithm of $\alpha^i + \alpha^j$, or `None` if it's zero.
+    pub fn add_logs(&self, i: u64, j: u64) -> Option<u64> {
+        let n = self.powers.len() as u64;
+        let d = (j % n + n - i % n) % n;
+        self.zech(d).map(|z| self.mul_logs(i, z))
+    }
+}
+
+/// The monic irreducible factors of `f` over $\mathbb{F}\_p[x]/(m)$ for the modulus $m$, with their multiplicities,
+/// sorted by degree. The coefficients can be in that field or in $\mathbb{F}\_p$. Panics if `f` is zero, or if a
+/// coefficient belongs to a different field.
+/// # Example:
+/// ```
+/// use bored_algebra::galois::{factor, GaloisField};
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::zmod::Zmod;
+/// use num_traits::One;
+///
+/// // x^2 + x + 1 is irreducible over F_2, but splits over GF(4) = F_2[w]/(w^2 + w + 1)
+/// type F2 = Zmod<2>;
+/// let m = Polynomial::from(vec![F2::new(1), F2::new(1), F2::new(1)]);
+/// let one = GaloisField::<2>::one();
+/// let f = Polynomial::from(vec![one.clone(), one.clone(), one.clone()]);
+/// let factors = factor(&f, &m);
+/// assert_eq!(factors.len(), 2);
+/// assert!(factors.iter().all(|(g, e)| g.deg() == 1 && *e == 1));
+/// ```
+pub fn factor<const P: u64>(
+    f: &Polynomial<GaloisField<P>>,
+    modulus: &Polynomial<Zmod<P>>,
+) -> Vec<(Polynomial<GaloisField<P>>, usize)> {
+    assert!(!f.is_zero(), "cannot factor zero");
+    let one = GaloisField::new(Polynomial::one(), modulus);
+    let f = Polynomial::from(
+        f.coeffs_take(f.deg() as usize + 1)
+            .into_iter()
+            .map(|c| c * one.clone())
+            .collect::<Vec<_>>(),
+    )
+    .monic();
+    let q = one.field_size();
+    let mut rng = RandState::new();
+    let mut factors = vec![];
+    for (g, e) in squarefree(&f, &q) {
+        for h in distinct_degree(&g, &one, &mut rng) {
+            factors.push((h, e));
+        }
+    }
+    factors.sort_by_key(|(g, _)| g.deg());
+    factors
+}
+
+/// The squarefree decomposition of a monic polynomial over $\mathrm{GF}(q)$. Where the derivative vanishes the
+/// polynomial is a $p$th power, and its $p$th root takes every $p$th coefficient to the power $q/p$.
+#[allow(clippy::type_complexity)]
+fn squarefree<const P: u64>(
+    f: &Polynomial<GaloisField<P>>,
+    q: &Integer,
+) -> Vec<(Polynomial<GaloisField<P>>, usize)> {
+    let p = P as usize;
+    let pth_root = |f: &Polynomial<GaloisField<P>>| {
+        let e = Integer::from(q / P);
+        Polynomial::from(
+            f.coeffs_take(f.deg() as usize + 1)
+                .iter()
+                .step_by(p)
+                .map(|c| c.pow(&e))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let mut result = vec![];
+    if f.deg() == 0 {
+        return result;
+    }
+    let df = f.clone().derivative();
+    if df.is_zero() {
+        return squarefree(&pth_root(f), q)
+            .into_iter()
+            .map(|(g, e)| (g, e * p))
+            .collect();
+    }
+    let mut c = f.gcd(&df);
+    let mut w = f.div_rem(&c).0;
+    let mut i = 1;
+    while w.deg() > 0 {
+        let y = w.gcd(&c);
+        let z = w.div_rem(&y).0;
+        if z.deg() > 0 {
+            result.push((z, i));
+        }
+        c = c.div_rem(&y).0;
+        w = y;
+        i += 1;
+    }
+    if c.deg() > 0 {
+        result.extend(
+            squarefree(&pth_root(&c), q)
+                .into_iter()
+                .map(|(g, e)| (g, e * p)),
+        );
+    }
+    result
+}
+
+/// Splits a monic squarefree polynomial over the field of `one` into products of irreducibles of each degree $d$,
+/// from $\gcd(x^{q^d} - x, f)$, and those with `equal_degree`.
+fn distinct_degree<const P: u64>(
+    f: &Polynomial<GaloisField<P>>,
+    one: &GaloisField<P>,
+    rng: &mut RandState,
+) -> Vec<Polynomial<GaloisField<P>>> {
+    let q = one.field_size();
+    let x = Polynomial::from(vec![GaloisField::zero(), one.clone()]);
+    let mut result = vec![];
+    let mut rest = f.clone();
+    let mut h = x.clone();
+    let mut d = 1;
+    while rest.deg() >= 2 * d {
+        h = pow_mod(&h, &q, &rest);
+        let g = (h.clone() - x.clone()).gcd(&rest);
+        if g.deg() > 0 {
+            rest = rest.div_rem(&g).0;
+            h = reduce(&h, &rest);
+            result.extend(equal_degree(&g, d, one, rng));
+        }
+        d += 1;
+    }
+    if rest.deg() > 0 {
+        result.push(rest);
+    }
+    result
+}
+
+/// Splits a monic product of distinct irreducibles of degree `d` over $\mathrm{GF}(q)$: for a random $a$,
+/// $\gcd(a^{(q^d - 1)/2} - 1, f)$ is a proper factor about half the time, and in characteristic 2 the trace
+/// $a + a^2 + \cdots + a^{2^{nd - 1}}$ does the same.
+fn equal_degree<const P: u64>(
+    f: &Polynomial<GaloisField<P>>,
+    d: u64,
+    one: &GaloisField<P>,
+    rng: &mut RandState,
+) -> Vec<Polynomial<GaloisField<P>>> {
+    if f.deg() == d {
+        return vec![f.clone()];
+    }
+    let modulus = one.modulus.clone();
+    let mut random = || {
+        let digits = (0..one.degree())
+            .map(|_| Zmod::new(Integer::from(P).random_below(rng).to_u64().unwrap()))
+            .collect::<Vec<_>>();
+        GaloisField::with_modulus(Polynomial::from(digits), modulus.clone())
+    };
+    loop {
+        let a = Polynomial::from((0..f.deg()).map(|_| random()).collect::<Vec<_>>());
+        if a.deg() == 0 {
+            continue;
+        }
+        let b = if P == 2 {
+            let mut power = a.clone();
+            let mut trace = a.clone();
+            for _ in 1..one.degree() * d {
+                power = reduce(&(power.clone() * power), f);
+                trace = trace + power.clone();
+            }
+            trace
+        } else {
+            let e = (one.field_size().pow(d as u32) - 1u32) >> 1;
+            pow_mod(&a, &e, f) - Polynomial::from(vec![one.clone()])
+        };
+        if b.is_zero() {
+            continue;
+        }
+        let g = b.gcd(f);
+        if g.deg() > 0 && g.deg() < f.deg() {
+            let mut factors = equal_degree(&g, d, one, rng);
+            factors.extend(equal_degree(&f.div_rem(&g).0, d, one, rng));
+            return factors;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::structure::Field;
+
+    fn poly<const P: u64>(coeffs: Vec<u64>) -> Polynomial<Zmod<P>> {
+        Polynomial::from(coeffs.into_iter().map(Zmod::new).collect::<Vec<Zmod<P>>>())
+    }
+
+    #[test]
+    fn test_irreducible() {
+        assert!(is_irreducible(&poly::<2>(vec![1, 1, 0, 0, 1])));
+        assert!(!is_irreducible(&poly::<2>(vec![1, 0, 1, 0, 1])));
+        // x^2 + 1 splits mod 5 but not mod 7
+        assert!(!is_irreducible(&poly::<5>(vec![1, 0, 1])));
+        assert!(is_irreducible(&poly::<7>(vec![1, 0, 1])));
+        // x^6 + x + 1 is irreducible mod 2, x^6 + x^5 + x^4 + x^3 + 1 = (x^2 + x + 1)(x^4 + x + 1)
+        assert!(is_irreducible(&poly::<2>(vec![1, 1, 0, 0, 0, 0, 1])));
+        assert!(!is_irreducible(&poly::<2>(vec![1, 0, 0, 1, 1, 1, 1])));
+        assert!(!is_irreducible(&poly::<3>(vec![2])));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // GF(9) = F_3[x]/(x^2 + 1)
+        let f = poly::<3>(vec![1, 0, 1]);
+        let i = GaloisField::generator(&f);
+        assert_eq!(i.clone() * i.clone(), -GaloisField::one());
+        let a = GaloisField::new(poly(vec![1, 2]), &f); // 1 + 2i
+        assert_eq!(a.clone() * a.inverse().unwrap(), GaloisField::one());
+        assert_eq!(GaloisField::one() / a.clone(), a.inverse().unwrap());
+        assert!(GaloisField::<3>::zero().inverse().is_err());
+        // every element is fixed by Frobenius^2, and the Frobenius is conjugation
+        assert_eq!(a.frobenius().frobenius(), a);
+        assert_eq!(a.frobenius(), GaloisField::new(poly(vec![1, 1]), &f));
+        // trace and norm of 1 + 2i are 2 and 1 + 4 = 5 = 2
+        assert_eq!(a.trace(), Zmod::new(2));
+        assert_eq!(a.norm(), Zmod::new(2));
+        assert_eq!(format!("{}", a), "2a + 1");
+        assert_eq!(
+            GaloisField::<3>::mod_mul(Integer::from(5), a.clone()),
+            a.clone() + a
+        );
+        // with a reducible modulus there are zero divisors
+        let g = poly::<3>(vec![2, 0, 1]);
+        assert!(GaloisField::new(poly(vec![1, 1]), &g).inverse().is_err());
+    }
+
+    #[test]
+    fn test_primitive() {
+        // x is not primitive in F_3[x]/(x^2 + 1), since x^4 = 1
+        let f = poly::<3>(vec![1, 0, 1]);
+        let i = GaloisField::generator(&f);
+        assert_eq!(i.multiplicative_order(), 4);
+        let alpha = GaloisField::primitive_element(&f);
+        assert_eq!(alpha.multiplicative_order(), 8);
+        let k = i.discrete_log(&alpha).unwrap();
+        assert!(k == 2 || k == 6);
+        assert_eq!(alpha.pow(&k), i);
+        // alpha isn't a power of i
+        assert_eq!(alpha.discrete_log(&i), None);
+        assert_eq!(
+            GaloisField::one().discrete_log(&alpha),
+            Some(Integer::new())
+        );
+        // x is primitive in GF(2^5) = F_2[x]/(x^5 + x^2 + 1)
+        let f = poly::<2>(vec![1, 0, 1, 0, 0, 1]);
+        let x = GaloisField::generator(&f);
+        assert!(x.is_primitive());
+        let a = x.pow(&Integer::from(29));
+        assert_eq!(a.discrete_log(&x), Some(Integer::from(29)));
+        // everything not in F_2 is primitive in GF(32), since 31 is prime
+        assert!((x + GaloisField::one()).is_primitive());
+    }
+
+    #[test]
+    fn test_zech() {
+        // GF(8) = F_2[x]/(x^3 + x + 1)
+        let f = poly::<2>(vec![1, 1, 0, 1]);
+        let table = ZechTable::new(&f);
+        let alpha = table.primitive().clone();
+        assert_eq!(alpha, GaloisField::generator(&f));
+        // 1 + a = a^3, so Z(1) = 3, and Z(0) is undefined in characteristic 2
+        assert_eq!(table.zech(1), Some(3));
+        assert_eq!(table.zech(0), None);
+        for i in 0..7 {
+            assert_eq!(table.log(&table.exp(i)), Some(i));
+            for j in 0..7 {
+                let sum = table.exp(i) + table.exp(j);
+                assert_eq!(table.add_logs(i, j), table.log(&sum));
+                assert_eq!(table.exp(table.mul_logs(i, j)), table.exp(i) * table.exp(j));
+            }
+        }
+        assert_eq!(table.log(&GaloisField::zero()), None);
+    }
+
+    #[test]
+    fn test_polynomials() {
+        // GF(4) = F_2[x]/(x^2 + x + 1), with w = x and w^2 = w + 1
+        let f = poly::<2>(vec![1, 1, 1]);
+        let (one, w) = (GaloisField::one(), GaloisField::generator(&f));
+        let linear = |a: GaloisField<2>| Polynomial::from(vec![a, one.clone()]);
+        let a = linear(w.clone()) * linear(one.clone());
+        let b = linear(w.clone()) * linear(w.clone() * w.clone());
+        assert_eq!(a.gcd(&b), linear(w.clone()));
+        let (q, r) = a.div_rem(&linear(w.clone()));
+        assert_eq!(q, linear(one.clone()));
+        assert!(r.is_zero());
+        assert_eq!(w.inv().unwrap(), w.clone() + one);
+    }
+
+    #[test]
+    fn test_factor() {
+        let linear = |a: GaloisField<2>| Polynomial::from(vec![a, GaloisField::one()]);
+        // GF(4) = F_2[x]/(x^2 + x + 1): (x^2 + x + 1)^2 (x + 1) = (x + 1)(x + w)^2 (x + w^2)^2
+        let m = poly::<2>(vec![1, 1, 1]);
+        let (one, w) = (GaloisField::one(), GaloisField::generator(&m));
+        let lift = |f: Polynomial<Zmod<2>>| {
+            Polynomial::from(
+                f.coeffs()
+                    .into_iter()
+                    .map(GaloisField::constant)
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let f = lift(m.clone() * m.clone() * poly::<2>(vec![1, 1]));
+        let factors = factor(&f, &m);
+        assert_eq!(factors.len(), 3);
+        assert!(factors.contains(&(linear(one.clone()), 1)));
+        assert!(factors.contains(&(linear(w.clone()), 2)));
+        assert!(factors.contains(&(linear(w.clone() * w.clone()), 2)));
+        // x^4 + w = (x + w)^4 is a 4th power, since w^4 = w, and x^2 + x + w is irreducible since w has trace 1
+        let zero = GaloisField::zero();
+        let f = Polynomial::from(vec![
+            w.clone(),
+            zero.clone(),
+            zero.clone(),
+            zero,
+            one.clone(),
+        ]);
+        assert_eq!(factor(&f, &m), vec![(linear(w.clone()), 4)]);
+        let g = Polynomial::from(vec![w.clone(), one.clone(), one.clone()]);
+        assert_eq!(
+            factor(&(g.clone() * linear(one.clone())), &m),
+            vec![(linear(one), 1), (g, 1)]
+        );
+        // GF(8) = F_2[a]/(a^3 + a + 1), where x^3 + x + 1 has the roots a, a^2, a^4
+        let m = poly::<2>(vec![1, 1, 0, 1]);
+        let a = GaloisField::generator(&m);
+        let factors = factor(&lift(m.clone()), &m);
+        assert_eq!(factors.len(), 3);
+        for k in [1, 2, 4] {
+            assert!(factors.contains(&(linear(a.pow(&Integer::from(k))), 1)));
+        }
+        // x^2 + 1 is irreducible over F_3, but it's (x - i)(x + i) over GF(9) = F_3[i]/(i^2 + 1)
+        let m = poly::<3>(vec![1, 0, 1]);
+        let i = GaloisField::generator(&m);
+        let f = Polynomial::from(vec![
+            GaloisField::one(),
+            GaloisField::zero(),
+            GaloisField::one(),
+        ]);
+        let factors = factor(&f, &m);
+        assert_eq!(factors.len(), 2);
+        assert!(factors.contains(&(Polynomial::from(vec![i.clone(), GaloisField::one()]), 1)));
+        assert!(factors.contains(&(Polynomial::from(vec![-i, GaloisField::one()]), 1)));
+    }
+}
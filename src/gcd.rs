@@ -0,0 +1,635 @@
+//! Greatest common divisors of multivariate polynomials
+//!
+//! Over $\mathbb{F}\_p$ this is Brown's dense interpolation algorithm: to find $\gcd(a, b)$ in
+//! $\mathbb{F}\_p[x\_0, \ldots, x\_k]$, evaluate $x\_k$ at enough points $\alpha$, find the gcds of the images
+//! recursively, and interpolate. An image of too high a degree comes from an unlucky point and gets thrown away.
+//! Over $\mathbb{Z}$ the same thing happens one level up, with primes in place of evaluation points and the
+//! Chinese remainder theorem in place of interpolation, and over $\mathbb{Q}$ the denominators are cleared first.
+//!
+//! A gcd is only defined up to units, so the images need a common normalization before they can be combined:
+//! they're scaled so that their leading coefficient is the gcd of the leading coefficients of $a$ and $b$, which
+//! is a multiple of the true one, and the extra factor is removed with a primitive part at the end.
+//!
+//! TODO: Zippel's sparse interpolation, which is much faster for sparse polynomials in many variables.
+use crate::factor::{div_rem_mod, gcd_mod, mul_mod, scale_mod};
+use crate::ideal::Ideal;
+use crate::mpoly::{GRevLex, Lex, MPolynomial, Monomial, MonomialOrder};
+use crate::structure::Field;
+use crate::zmod::Zmod;
+use crate::{Integer, Rational};
+use core::cmp::Ordering;
+use rug::ops::RemRounding;
+
+use num_traits::identities::{One, Zero};
+
+/// Fields $F$ with a way to find gcds in $F[x\_0, x\_1, \ldots]$.
+pub trait GcdField: Field {
+    /// The monic gcd of `a` and `b`, which is zero if both are zero. The default works over any field, as
+    /// $ab / \mathrm{lcm}(a, b)$ where the lcm generates $(a) \cap (b)$, but it's slow.
+    fn gcd_multivariate<O: MonomialOrder>(
+        a: &MPolynomial<Self, O>,
+        b: &MPolynomial<Self, O>,
+    ) -> MPolynomial<Self, O> {
+        if a.is_zero() || b.is_zero() {
+            return (a.clone() + b.clone()).monic();
+        }
+        if a.is_constant() || b.is_constant() {
+            return MPolynomial::one();
+        }
+        let n = a.nvars().max(b.nvars());
+        let (a, b) = (a.reorder::<GRevLex>(), b.reorder::<GRevLex>());
+        let lcm = Ideal::new(n, vec![a.clone()])
+            .intersect(&Ideal::new(n, vec![b.clone()]))
+            .groebner_basis()[0]
+            .clone();
+        (&a * &b).div_rem(&lcm).0.reorder::<O>().monic()
+    }
+}
+
+impl GcdField for Rational {
+    fn gcd_multivariate<O: MonomialOrder>(
+        a: &MPolynomial<Rational, O>,
+        b: &MPolynomial<Rational, O>,
+    ) -> MPolynomial<Rational, O> {
+        gcd_over_q(a, b)
+    }
+}
+
+/// Brown's algorithm through `gcd_mod_p`, for prime $P$ (checked when this gets compiled).
+impl<const P: u64> GcdField for Zmod<P> {
+    fn gcd_multivariate<O: MonomialOrder>(
+        a: &MPolynomial<Zmod<P>, O>,
+        b: &MPolynomial<Zmod<P>, O>,
+    ) -> MPolynomial<Zmod<P>, O> {
+        let () = Self::ASSERT_PRIME;
+        let lift = |f: &MPolynomial<Zmod<P>, O>| f.map_coeffs(|c| Integer::from(c.value()));
+        gcd_mod_p(&lift(a), &lift(b), &Integer::from(P)).map_coeffs(|c| Zmod::from(c))
+    }
+}
+
+/// The monic gcd of two polynomials over a `GcdField`.
+/// # Example:
+/// ```
+/// use bored_algebra::gcd::gcd;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Rational;
+///
+/// type P = MPolynomial<Rational>;
+/// let (x, y) = (P::var(0), P::var(1));
+/// // gcd(x^2 - y^2, x^2 + 2xy + y^2) = x + y
+/// let a = x.clone() * x.clone() - y.clone() * y.clone();
+/// let b = (x.clone() + y.clone()) * (x.clone() + y.clone());
+/// assert_eq!(gcd(&a, &b), x + y);
+/// ```
+pub fn gcd<F: GcdField, O: MonomialOrder>(
+    a: &MPolynomial<F, O>,
+    b: &MPolynomial<F, O>,
+) -> MPolynomial<F, O> {
+    F::gcd_multivariate(a, b)
+}
+
+/// The monic gcd over $\mathbb{Q}$, from the gcd over $\mathbb{Z}$ of the polynomials with cleared denominators.
+pub fn gcd_over_q<O: MonomialOrder>(
+    a: &MPolynomial<Rational, O>,
+    b: &MPolynomial<Rational, O>,
+) -> MPolynomial<Rational, O> {
+    if a.is_zero() || b.is_zero() {
+        return (a.clone() + b.clone()).monic();
+    }
+    let clear = |f: &MPolynomial<Rational, O>| {
+        let denom = f
+            .terms()
+            .iter()
+            .fold(Integer::from(1), |acc, (_, c)| acc.lcm(c.denom()));
+        f.map_coeffs(|c| c.numer() * Integer::from(&denom / c.denom()))
+    };
+    gcd_over_z(&clear(a), &clear(b))
+        .map_coeffs(|c| Rational::from(c.clone()))
+        .monic()
+}
+
+/// The gcd over $\mathbb{Z}$, with a positive leading coefficient. This is zero if both are zero.
+/// # Example:
+/// ```
+/// use bored_algebra::gcd::gcd_over_z;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Integer;
+///
+/// // gcd(6xy + 6y, 4x^2 - 4) = 2(x + 1)
+/// let a: MPolynomial<Integer> = MPolynomial::from(vec![(vec![1, 1], Integer::from(6)), (vec![0, 1], Integer::from(6))]);
+/// let b = MPolynomial::from(vec![(vec![2], Integer::from(4)), (vec![], Integer::from(-4))]);
+/// let g = MPolynomial::from(vec![(vec![1], Integer::from(2)), (vec![], Integer::from(2))]);
+/// assert_eq!(gcd_over_z(&a, &b), g);
+/// ```
+pub fn gcd_over_z<O: MonomialOrder>(
+    a: &MPolynomial<Integer, O>,
+    b: &MPolynomial<Integer, O>,
+) -> MPolynomial<Integer, O> {
+    if a.is_zero() || b.is_zero() {
+        return positive(&(a.clone() + b.clone()));
+    }
+    let (ca, cb) = (content(a), content(b));
+    let c = Integer::from(ca.gcd_ref(&cb));
+    let a: MPolynomial<Integer, Lex> = a.reorder().map_coeffs(|x| Integer::from(x / &ca));
+    let b: MPolynomial<Integer, Lex> = b.reorder().map_coeffs(|x| Integer::from(x / &cb));
+    if a.is_constant() || b.is_constant() {
+        return MPolynomial::constant(c);
+    }
+    let k = a.nvars().max(b.nvars()) - 1;
+    let (la, lb) = (a.lead_coeff().unwrap(), b.lead_coeff().unwrap());
+    let gamma = Integer::from(la.gcd_ref(lb));
+
+    // the image mod the product of the primes so far, and its symmetric representative from last time
+    let mut image: Option<(MPolynomial<Integer, Lex>, Integer)> = None;
+    let mut last: Option<MPolynomial<Integer, Lex>> = None;
+    let mut p = Integer::from(1_u64 << 31);
+    loop {
+        p.next_prime_mut();
+        if la.is_divisible(&p) || lb.is_divisible(&p) {
+            continue;
+        }
+        let g =
+            brown(&reduce(&a, &p), &reduce(&b, &p), k, &p).expect("big primes have enough points");
+        if g.is_one() {
+            return MPolynomial::constant(c);
+        }
+        let g = reduce(&g.scale(&gamma), &p);
+        let (h, modulus) = match image.take() {
+            Some((h, m)) => {
+                match Lex::cmp(g.lead_monomial().unwrap(), h.lead_monomial().unwrap()) {
+                    Ordering::Greater => {
+                        image = Some((h, m));
+                        continue;
+                    }
+                    Ordering::Equal => (crt(&h, &m, &g, &p), m * &p),
+                    Ordering::Less => (g, p.clone()),
+                }
+            }
+            None => (g, p.clone()),
+        };
+        let half = Integer::from(&modulus >> 1);
+        let symmetric = h.map_coeffs(|x| match *x > half {
+            true => Integer::from(x - &modulus),
+            false => x.clone(),
+        });
+        image = Some((h, modulus));
+        // once the image stops changing it's probably right, and then dividing checks it
+        if last.as_ref() == Some(&symmetric) {
+            let d = content(&symmetric);
+            let g = symmetric.map_coeffs(|x| Integer::from(x / &d));
+            if divides_over_q(&g, &a) && divides_over_q(&g, &b) {
+                return positive(&g.scale(&c).reorder());
+            }
+        }
+        last = Some(symmetric);
+    }
+}
+
+/// The monic gcd over $\mathbb{F}\_p$, where the coefficients of `a` and `b` are integers standing for their
+/// residues mod the prime $p$. The result has coefficients in $[0, p)$, and is zero if both are zero mod $p$.
+///
+/// If $p$ is too small to have enough evaluation points, this falls back on a much slower primitive remainder
+/// sequence. TODO: evaluate in an extension field instead.
+/// # Example:
+/// ```
+/// use bored_algebra::gcd::gcd_mod_p;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Integer;
+///
+/// // x^2 - y^2 and x^2 + y^2 are coprime mod 7, but the same mod 2
+/// let a: MPolynomial<Integer> = MPolynomial::from(vec![(vec![2], Integer::from(1)), (vec![0, 2], Integer::from(-1))]);
+/// let b = MPolynomial::from(vec![(vec![2], Integer::from(1)), (vec![0, 2], Integer::from(1))]);
+/// assert!(gcd_mod_p(&a, &b, &Integer::from(7)).is_constant());
+/// assert_eq!(gcd_mod_p(&a, &b, &Integer::from(2)), b);
+/// ```
+pub fn gcd_mod_p<O: MonomialOrder>(
+    a: &MPolynomial<Integer, O>,
+    b: &MPolynomial<Integer, O>,
+    p: &Integer,
+) -> MPolynomial<Integer, O> {
+    let (a, b) = (
+        reduce(&a.reorder::<Lex>(), p),
+        reduce(&b.reorder::<Lex>(), p),
+    );
+    let g = match (a.is_zero() || b.is_zero(), a.nvars().max(b.nvars())) {
+        (true, _) => a + b,
+        (false, 0) => MPolynomial::one(),
+        // small primes can run out of points, but pseudo-remainders always work
+        (false, n) => brown(&a, &b, n - 1, p).unwrap_or_else(|| prs(&a, &b, n - 1, p)),
+    };
+    monic(&g.reorder(), p)
+}
+
+/// Brown's algorithm: the monic gcd of nonzero `a` and `b` in $\mathbb{F}\_p[x\_0, \ldots, x\_k]$, or `None` if
+/// there aren't enough points in $\mathbb{F}\_p$.
+fn brown(
+    a: &MPolynomial<Integer, Lex>,
+    b: &MPolynomial<Integer, Lex>,
+    k: usize,
+    p: &Integer,
+) -> Option<MPolynomial<Integer, Lex>> {
+    if k == 0 {
+        return Some(sparse(&gcd_mod(&dense(a, 0), &dense(b, 0), p), 0));
+    }
+    // split off the contents, which are in F_p[x_k]
+    let (ca, cb) = (content_in(a, k, p), content_in(b, k, p));
+    let c = sparse(&gcd_mod(&ca, &cb, p), k);
+    let (a, b) = (div_content(a, &ca, k, p), div_content(b, &cb, k, p));
+    let only_last = |f: &MPolynomial<Integer, Lex>| {
+        f.terms().iter().all(|(m, _)| (0..k).all(|i| m.exp(i) == 0))
+    };
+    if only_last(&a) || only_last(&b) {
+        return Some(c);
+    }
+    let (la, lb) = (lead_coeff_in(&a, k), lead_coeff_in(&b, k));
+    let gamma = gcd_mod(&la, &lb, p);
+    let bound = a.deg_in(k).min(b.deg_in(k)) as usize + gamma.len() - 1;
+
+    // the interpolated gcd so far, and the product of x_k - alpha over the points used
+    let mut image: Option<(MPolynomial<Integer, Lex>, Vec<Integer>)> = None;
+    let mut count = 0;
+    let mut alpha = Integer::new();
+    while alpha < *p {
+        let value = eval_dense(&gamma, &alpha, p);
+        if value.is_zero()
+            || eval_dense(&la, &alpha, p).is_zero()
+            || eval_dense(&lb, &alpha, p).is_zero()
+        {
+            alpha += 1;
+            continue;
+        }
+        let g = brown(&eval(&a, k, &alpha, p), &eval(&b, k, &alpha, p), k - 1, p)?;
+        if g.is_one() {
+            return Some(c);
+        }
+        let g = reduce(&g.scale(&value), p);
+        let x = vec![Integer::from(-&alpha).rem_euc(p), Integer::from(1)];
+        let (h, q) = match image.take() {
+            Some((h, q)) => {
+                match Lex::cmp(
+                    g.lead_monomial().unwrap(),
+                    &without(h.lead_monomial().unwrap(), k),
+                ) {
+                    Ordering::Greater => {
+                        image = Some((h, q));
+                        alpha += 1;
+                        continue;
+                    }
+                    Ordering::Equal => {
+                        // Newton interpolation: add the multiple of q that fixes the value at alpha
+                        let error = g - eval(&h, k, &alpha, p);
+                        let inv = Integer::from(eval_dense(&q, &alpha, p).invert_ref(p).unwrap());
+                        let h = reduce(&(h + &error * &sparse(&scale_mod(&q, &inv, p), k)), p);
+                        (h, mul_mod(&q, &x, p))
+                    }
+                    Ordering::Less => {
+                        count = 0;
+                        (g, x)
+                    }
+                }
+            }
+            None => (g, x),
+        };
+        count += 1;
+        alpha += 1;
+        if count > bound {
+            let g = div_content(&h, &content_in(&h, k, p), k, p);
+            if divides(&g, &a, p) && divides(&g, &b, p) {
+                return Some(monic(&(&c * &g), p));
+            }
+        }
+        image = Some((h, q));
+    }
+    None
+}
+
+/// The gcd by the primitive remainder sequence in $x\_k$, over $\mathbb{F}\_p[x\_0, \ldots, x\_{k-1}]$. This works
+/// for any $p$, but the intermediate polynomials get big.
+fn prs(
+    a: &MPolynomial<Integer, Lex>,
+    b: &MPolynomial<Integer, Lex>,
+    k: usize,
+    p: &Integer,
+) -> MPolynomial<Integer, Lex> {
+    if k == 0 {
+        return sparse(&gcd_mod(&dense(a, 0), &dense(b, 0), p), 0);
+    }
+    // the content of f as a polynomial in x_k, and its primitive part
+    let primitive = |f: &MPolynomial<Integer, Lex>| {
+        let c = (0..=f.deg_in(k))
+            .map(|e| coeff_in(f, k, e))
+            .filter(|c| !c.is_zero())
+            .fold(MPolynomial::zero(), |acc, c| match acc.is_zero() {
+                true => monic(&c, p),
+                false => prs(&acc, &c, k - 1, p),
+            });
+        (div_exact(f, &c, p).unwrap(), c)
+    };
+    let ((mut a, ca), (mut b, cb)) = (primitive(a), primitive(b));
+    let c = prs(&ca, &cb, k - 1, p);
+    if a.deg_in(k) < b.deg_in(k) {
+        (a, b) = (b, a);
+    }
+    while b.deg_in(k) > 0 {
+        // the pseudo-remainder of a by b
+        let (db, lb) = (b.deg_in(k), coeff_in(&b, k, b.deg_in(k)));
+        let mut r = a;
+        while !r.is_zero() && r.deg_in(k) >= db {
+            let dr = r.deg_in(k);
+            let mut exps = vec![0; k + 1];
+            exps[k] = dr - db;
+            let shifted =
+                (&coeff_in(&r, k, dr) * &b).mul_term(&Monomial::from(exps), &Integer::from(1));
+            r = reduce(&(&lb * &r - shifted), p);
+        }
+        if r.is_zero() {
+            return monic(&(&c * &b), p);
+        }
+        (a, b) = (b, primitive(&r).0);
+    }
+    c
+}
+
+/// The coefficient of $x\_k^e$ in $f$.
+fn coeff_in(f: &MPolynomial<Integer, Lex>, k: usize, e: u32) -> MPolynomial<Integer, Lex> {
+    MPolynomial::from_terms(
+        f.terms()
+            .iter()
+            .filter(|(m, _)| m.exp(k) == e)
+            .map(|(m, c)| (without(m, k), c.clone()))
+            .collect(),
+    )
+}
+
+/// The Chinese remainder theorem, coefficientwise: the polynomial congruent to $h$ mod $m$ and $g$ mod $p$, with
+/// coefficients in $[0, mp)$.
+fn crt(
+    h: &MPolynomial<Integer, Lex>,
+    m: &Integer,
+    g: &MPolynomial<Integer, Lex>,
+    p: &Integer,
+) -> MPolynomial<Integer, Lex> {
+    let inv = Integer::from(m.invert_ref(p).unwrap());
+    let mut monomials: Vec<&Monomial> = h.terms().iter().map(|(n, _)| n).collect();
+    monomials.extend(
+        g.terms()
+            .iter()
+            .map(|(n, _)| n)
+            .filter(|n| h.coeff(n).is_zero()),
+    );
+    MPolynomial::from_terms(
+        monomials
+            .into_iter()
+            .map(|n| {
+                let (x, y) = (h.coeff(n), g.coeff(n));
+                let t = ((y - &x) * &inv).rem_euc(p);
+                (n.clone(), x + t * m)
+            })
+            .collect(),
+    )
+}
+
+fn content<O: MonomialOrder>(f: &MPolynomial<Integer, O>) -> Integer {
+    f.terms()
+        .iter()
+        .fold(Integer::new(), |acc, (_, c)| acc.gcd(c))
+}
+
+fn positive<O: MonomialOrder>(f: &MPolynomial<Integer, O>) -> MPolynomial<Integer, O> {
+    match f.lead_coeff().is_some_and(|c| *c < 0) {
+        true => -f.clone(),
+        false => f.clone(),
+    }
+}
+
+fn divides_over_q(g: &MPolynomial<Integer, Lex>, f: &MPolynomial<Integer, Lex>) -> bool {
+    let to_q = |f: &MPolynomial<Integer, Lex>| f.map_coeffs(|c| Rational::from(c.clone()));
+    to_q(f).div_rem(&to_q(g)).1.is_zero()
+}
+
+/*
+ * Polynomials over F_p as MPolynomial<Integer, Lex> with coefficients in [0, p), and dense polynomials in
+ * the last variable x_k as in `factor`.
+ */
+
+fn reduce<O: MonomialOrder>(f: &MPolynomial<Integer, O>, p: &Integer) -> MPolynomial<Integer, O> {
+    f.map_coeffs(|c| Integer::from(c.rem_euc(p)))
+}
+
+fn monic<O: MonomialOrder>(f: &MPolynomial<Integer, O>, p: &Integer) -> MPolynomial<Integer, O> {
+    match f.lead_coeff() {
+        Some(c) => reduce(&f.scale(&Integer::from(c.invert_ref(p).unwrap())), p),
+        None => f.clone(),
+    }
+}
+
+/// $f / g$, if $g$ divides $f$ exactly.
+fn div_exact(
+    f: &MPolynomial<Integer, Lex>,
+    g: &MPolynomial<Integer, Lex>,
+    p: &Integer,
+) -> Option<MPolynomial<Integer, Lex>> {
+    let (n, d) = g.lead_term().unwrap();
+    let inv = Integer::from(d.invert_ref(p).unwrap());
+    let (mut q, mut r) = (vec![], f.clone());
+    while let Some((m, c)) = r.lead_term() {
+        let t = m.checked_div(n)?;
+        let c = Integer::from(c * &inv).rem_euc(p);
+        r = reduce(&(r.clone() - g.mul_term(&t, &c)), p);
+        q.push((t, c));
+    }
+    Some(MPolynomial::from_terms(q))
+}
+
+fn divides(g: &MPolynomial<Integer, Lex>, f: &MPolynomial<Integer, Lex>, p: &Integer) -> bool {
+    div_exact(f, g, p).is_some()
+}
+
+/// $f$ with $x\_k$ set to $\alpha$.
+fn eval(
+    f: &MPolynomial<Integer, Lex>,
+    k: usize,
+    alpha: &Integer,
+    p: &Integer,
+) -> MPolynomial<Integer, Lex> {
+    reduce(
+        &MPolynomial::from_terms(
+            f.terms()
+                .iter()
+                .map(|(m, c)| {
+                    let e = Integer::from(m.exp(k));
+                    (
+                        without(m, k),
+                        c * Integer::from(alpha.pow_mod_ref(&e, p).unwrap()),
+                    )
+                })
+                .collect(),
+        ),
+        p,
+    )
+}
+
+fn eval_dense(u: &[Integer], alpha: &Integer, p: &Integer) -> Integer {
+    u.iter()
+        .rev()
+        .fold(Integer::new(), |acc, c| (acc * alpha + c).rem_euc(p))
+}
+
+/// The monomial $m$ with $x\_k$ removed.
+fn without(m: &Monomial, k: usize) -> Monomial {
+    let mut exps = m.exps().to_vec();
+    if k < exps.len() {
+        exps[k] = 0;
+    }
+    Monomial::from(exps)
+}
+
+/// A polynomial in $x\_k$ alone as a dense coefficient vector.
+fn dense(f: &MPolynomial<Integer, Lex>, k: usize) -> Vec<Integer> {
+    let mut u = vec![Integer::new(); f.deg_in(k) as usize + 1];
+    for (m, c) in f.terms() {
+        u[m.exp(k) as usize] = c.clone();
+    }
+    while u.last().is_some_and(|c| c.is_zero()) {
+        u.pop();
+    }
+    u
+}
+
+fn sparse(u: &[Integer], k: usize) -> MPolynomial<Integer, Lex> {
+    MPolynomial::from_terms(
+        u.iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let mut exps = vec![0; k + 1];
+                exps[k] = i as u32;
+                (Monomial::from(exps), c.clone())
+            })
+            .collect(),
+    )
+}
+
+/// The coefficients of $f$ as a polynomial in $x\_0, \ldots, x\_{k-1}$ over $\mathbb{F}\_p[x\_k]$, largest first.
+fn coefficients_in(f: &MPolynomial<Integer, Lex>, k: usize) -> Vec<(Monomial, Vec<Integer>)> {
+    let mut groups: Vec<(Monomial, Vec<Integer>)> = vec![];
+    for (m, c) in f.terms() {
+        let n = without(m, k);
+        let e = m.exp(k) as usize;
+        // lex compares x_k last, so terms with the same coefficient are next to each other
+        if groups.last().is_none_or(|(l, _)| *l != n) {
+            groups.push((n, vec![]));
+        }
+        let u = &mut groups.last_mut().unwrap().1;
+        if u.len() <= e {
+            u.resize(e + 1, Integer::new());
+        }
+        u[e] = c.clone();
+    }
+    groups
+}
+
+fn lead_coeff_in(f: &MPolynomial<Integer, Lex>, k: usize) -> Vec<Integer> {
+    coefficients_in(f, k).swap_remove(0).1
+}
+
+fn content_in(f: &MPolynomial<Integer, Lex>, k: usize, p: &Integer) -> Vec<Integer> {
+    coefficients_in(f, k)
+        .iter()
+        .fold(vec![], |acc, (_, u)| gcd_mod(&acc, u, p))
+}
+
+/// Divides every coefficient of $f$ in $\mathbb{F}\_p[x\_k]$ by $u$.
+fn div_content(
+    f: &MPolynomial<Integer, Lex>,
+    u: &[Integer],
+    k: usize,
+    p: &Integer,
+) -> MPolynomial<Integer, Lex> {
+    coefficients_in(f, k)
+        .into_iter()
+        .fold(MPolynomial::zero(), |acc, (n, v)| {
+            let q = sparse(&div_rem_mod(&v, u, p).0, k);
+            acc + q.mul_term(&n, &Integer::from(1))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zmpoly;
+    use rug::ops::Pow;
+
+    type Z = MPolynomial<Integer>;
+
+    #[test]
+    fn test_mod_p() {
+        let p = Integer::from(101);
+        // (x + y z + 1)(x - z)^2 and (x + y z + 1)(y^2 - 3)
+        let g = zmpoly(vec![(vec![1], 1), (vec![0, 1, 1], 1), (vec![], 1)]);
+        let h = zmpoly(vec![(vec![1], 1), (vec![0, 0, 1], -1)]);
+        let a = &g * &(&h * &h);
+        let b = &g * &zmpoly(vec![(vec![0, 2], 1), (vec![], -3)]);
+        assert_eq!(gcd_mod_p(&a, &b, &p), g);
+        assert_eq!(gcd_mod_p(&a, &(&a * &h), &p), reduce(&a, &p));
+        // y^2 - 3 and x - z: coprime, and so are things that only differ by constants mod p
+        assert!(gcd_mod_p(&b, &(&h * &h), &p).is_one());
+        assert_eq!(
+            gcd_mod_p(&Z::zero(), &zmpoly(vec![(vec![1], 3)]), &p),
+            Z::var(0)
+        );
+        assert!(gcd_mod_p(&Z::zero(), &Z::zero(), &p).is_zero());
+        // x^2 + y^2 = (x + y)^2 mod 2
+        let a = zmpoly(vec![(vec![2], 1), (vec![0, 2], 1)]);
+        let b = zmpoly(vec![(vec![1, 1], 1), (vec![0, 2], 1)]);
+        let two = Integer::from(2);
+        assert_eq!(
+            gcd_mod_p(&a, &b, &two),
+            zmpoly(vec![(vec![1], 1), (vec![0, 1], 1)])
+        );
+        assert_eq!(gcd_mod_p(&a, &(&a * &b), &two), a);
+        // the same through the GcdField for Zmod<2>
+        let to_f2 = |f: &Z| f.map_coeffs(|c| Zmod::<2>::from(c));
+        let (a, b) = (to_f2(&a), to_f2(&b));
+        assert_eq!(gcd(&a, &b), MPolynomial::var(0) + MPolynomial::var(1));
+    }
+
+    #[test]
+    fn test_over_z() {
+        // contents, signs and leading coefficients that aren't 1
+        let g = zmpoly(vec![(vec![1, 1], 3), (vec![0, 0, 2], -2), (vec![], 5)]);
+        let a = &g * &zmpoly(vec![(vec![2], 4), (vec![0, 1], 2)]);
+        let b = &g * &zmpoly(vec![(vec![1, 0, 1], -6), (vec![0, 3], 9)]);
+        assert_eq!(gcd_over_z(&a, &b), g);
+        assert_eq!(gcd_over_z(&(-a.clone()), &a), a);
+        assert_eq!(
+            gcd_over_z(&zmpoly(vec![(vec![1], 4)]), &zmpoly(vec![(vec![], 6)])),
+            Z::constant(Integer::from(2))
+        );
+        // big coefficients: (2^40 x + 3^30 y)(x + y)^3 and (2^40 x + 3^30 y)(x - y)^2
+        let big = Z::from(vec![
+            (vec![1], Integer::from(1) << 40),
+            (vec![0, 1], Integer::from(3).pow(30)),
+        ]);
+        let (s, d) = (
+            zmpoly(vec![(vec![1], 1), (vec![0, 1], 1)]),
+            zmpoly(vec![(vec![1], 1), (vec![0, 1], -1)]),
+        );
+        let a = &big * &(&s * &(&s * &s));
+        let b = &big * &(&d * &d);
+        assert_eq!(gcd_over_z(&a, &b), big);
+    }
+
+    #[test]
+    fn test_over_q() {
+        type P = MPolynomial<Rational>;
+        let (x, y, z) = (P::var(0), P::var(1), P::var(2));
+        let half = P::constant(Rational::from((1, 2)));
+        // (x y - z/2)(x + y + z)^2 and (x y - z/2)(x + 1)
+        let g = x.clone() * y.clone() - half.clone() * z.clone();
+        let s = x.clone() + y.clone() + z.clone();
+        let a = g.clone() * s.clone() * s.clone();
+        let b = g.clone() * (x.clone() + P::one()) * half;
+        assert_eq!(gcd(&a, &b), g);
+        assert!(gcd(&s, &(x.clone() - y.clone())).is_one());
+    }
+}
@@ -0,0 +1,491 @@
+//! Grobner bases and syzygies
+//!
+//! Everything here works in a free module $R^n$ over $R = F[x\_0, x\_1, \ldots]$, since an ideal is just a
+//! submodule of $R^1$. Module elements are `Vector<F, O>`, and are compared with the
+//! "term over position" order: $m e\_i > n e\_j$ if $m > n$ in the order `O`, or if $m = n$ and $i < j$.
+//!
+//! The coefficients $F$ need to be a field, since reduction divides by leading coefficients.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::groebner::{groebner_basis, normal_form};
+//! use bored_algebra::mpoly::{Lex, MPolynomial};
+//! use bored_algebra::Rational;
+//!
+//! type P = MPolynomial<Rational, Lex>;
+//! let (x, y) = (P::var(0), P::var(1));
+//! let one = P::constant(Rational::from(1));
+//! // the circle x^2 + y^2 = 1 and the line x = y
+//! let gb = groebner_basis(&[x.clone() * x.clone() + y.clone() * y.clone() - one, x.clone() - y.clone()]);
+//! // lex eliminates x, leaving x - y and y^2 - 1/2
+//! assert_eq!(gb.len(), 2);
+//! assert_eq!(normal_form(&(x.clone() * x.clone()), &gb), P::constant(Rational::from((1, 2))));
+//! ```
+use crate::free::Free;
+use crate::mpoly::{MPolynomial, Monomial, MonomialOrder};
+use crate::structure::Field;
+use core::cmp::Ordering;
+
+use num_traits::identities::Zero;
+
+/// An element of the free module $R^n$, where $R = F[x\_0, x\_1, \ldots]$ with the order `O`.
+pub type Vector<F, O> = Free<MPolynomial<F, O>>;
+
+/// The leading term of a module element: its component, monomial and coefficient.
+pub fn lead_term<F: Field, O: MonomialOrder>(v: &Vector<F, O>) -> Option<(usize, &Monomial, &F)> {
+    let mut best: Option<(usize, &Monomial, &F)> = None;
+    for (i, p) in v.coords().iter().enumerate() {
+        if let Some((m, c)) = p.lead_term() {
+            match best {
+                // earlier components win ties
+                Some((_, n, _)) if O::cmp(m, n) != Ordering::Greater => (),
+                _ => best = Some((i, m, c)),
+            }
+        }
+    }
+    best
+}
+
+/// Compares two module monomials $m e\_i$ and $n e\_j$.
+fn cmp_module<O: MonomialOrder>(a: (usize, &Monomial), b: (usize, &Monomial)) -> Ordering {
+    O::cmp(a.1, b.1).then_with(|| b.0.cmp(&a.0))
+}
+
+/// Adds $c m e\_i$ to `v`.
+fn add_term<F: Field, O: MonomialOrder>(v: &mut Vector<F, O>, i: usize, m: &Monomial, c: &F) {
+    let p = v.get(i) + MPolynomial::term(m.clone(), c.clone());
+    v.set(i, p);
+}
+
+/// Subtracts $c m \cdot g$ from `v`.
+fn sub_multiple<F: Field, O: MonomialOrder>(
+    v: &Vector<F, O>,
+    m: &Monomial,
+    c: &F,
+    g: &Vector<F, O>,
+) -> Vector<F, O> {
+    let neg = -c.clone();
+    let n = v.len().max(g.len());
+    Free::from(
+        (0..n)
+            .map(|i| v.get(i) + g.get(i).mul_term(m, &neg))
+            .collect::<Vec<MPolynomial<F, O>>>(),
+    )
+}
+
+/// Scales `v` so its leading coefficient is one.
+fn make_monic<F: Field, O: MonomialOrder>(v: &Vector<F, O>) -> (Vector<F, O>, F) {
+    let inv = match lead_term(v) {
+        Some((_, _, c)) => F::one() / c.clone(),
+        None => F::one(),
+    };
+    (v.map(|p| p.scale(&inv)), inv)
+}
+
+/// Division with remainder by a list of module elements: returns quotients $q\_k$ and a remainder $r$ with
+/// $v = \sum\_k q\_k g\_k + r$, where no term of $r$ is divisible by a leading term of any $g\_k$.
+pub fn reduce_with_quotients<F: Field, O: MonomialOrder>(
+    v: &Vector<F, O>,
+    basis: &[Vector<F, O>],
+) -> (Vec<MPolynomial<F, O>>, Vector<F, O>) {
+    let leads: Vec<Option<(usize, Monomial, F)>> = basis
+        .iter()
+        .map(|g| lead_term(g).map(|(i, m, c)| (i, m.clone(), c.clone())))
+        .collect();
+    let mut quotients = vec![MPolynomial::zero(); basis.len()];
+    let mut p = v.clone();
+    let mut r = Free::zero();
+    while let Some((i, m, c)) = lead_term(&p).map(|(i, m, c)| (i, m.clone(), c.clone())) {
+        let divisor = leads.iter().enumerate().find_map(|(k, lead)| match lead {
+            Some((j, n, d)) if *j == i => m.checked_div(n).map(|t| (k, t, c.clone() / d.clone())),
+            _ => None,
+        });
+        match divisor {
+            Some((k, t, coeff)) => {
+                p = sub_multiple(&p, &t, &coeff, &basis[k]);
+                quotients[k] = quotients[k].clone() + MPolynomial::term(t, coeff);
+            }
+            None => {
+                add_term(&mut r, i, &m, &c);
+                add_term(&mut p, i, &m, &(-c));
+            }
+        }
+    }
+    (quotients, r)
+}
+
+/// The normal form of `v` with respect to `basis`. If `basis` is a Grobner basis, this is zero exactly
+/// when `v` is in the submodule generated by `basis`.
+pub fn module_normal_form<F: Field, O: MonomialOrder>(
+    v: &Vector<F, O>,
+    basis: &[Vector<F, O>],
+) -> Vector<F, O> {
+    reduce_with_quotients(v, basis).1
+}
+
+/// The S-vector of two elements with leading terms in the same component, along with the two
+/// monomials (and coefficients) that $g$ and $h$ were multiplied by.
+#[allow(clippy::type_complexity)]
+fn s_vector<F: Field, O: MonomialOrder>(
+    g: &Vector<F, O>,
+    h: &Vector<F, O>,
+) -> (Vector<F, O>, (Monomial, F), (Monomial, F)) {
+    let (_, m, c) = lead_term(g).unwrap();
+    let (_, n, d) = lead_term(h).unwrap();
+    let lcm = m.lcm(n);
+    let a = (lcm.checked_div(m).unwrap(), F::one() / c.clone());
+    let b = (lcm.checked_div(n).unwrap(), F::one() / d.clone());
+    let s = sub_multiple(&g.map(|p| p.mul_term(&a.0, &a.1)), &b.0, &b.1, h);
+    (s, a, b)
+}
+
+/// A critical pair, sorted by the least common multiple of leading monomials.
+struct Pair {
+    i: usize,
+    j: usize,
+    component: usize,
+    lcm: Monomial,
+}
+
+/// Buchberger's algorithm. If `track` is true, also returns for each basis element $g$ the
+/// cofactors $c$ with $g = \sum\_k c\_k f\_k$, where $f\_k$ are the generators.
+#[allow(clippy::type_complexity)]
+fn buchberger<F: Field, O: MonomialOrder>(
+    gens: &[Vector<F, O>],
+    track: bool,
+) -> (Vec<Vector<F, O>>, Vec<Vector<F, O>>) {
+    assert!(
+        O::GLOBAL,
+        "Buchberger's algorithm needs a global order, see local::standard_basis"
+    );
+    let mut basis: Vec<Vector<F, O>> = vec![];
+    let mut cofactors: Vec<Vector<F, O>> = vec![];
+    let mut pairs: Vec<Pair> = vec![];
+    let is_ideal = gens.iter().all(|g| g.support().all(|i| i == 0));
+
+    let add = |g: Vector<F, O>,
+               c: Vector<F, O>,
+               basis: &mut Vec<Vector<F, O>>,
+               cofactors: &mut Vec<Vector<F, O>>,
+               pairs: &mut Vec<Pair>| {
+        let (g, inv) = make_monic(&g);
+        let (i, m, _) = lead_term(&g).unwrap();
+        for (k, h) in basis.iter().enumerate() {
+            let (j, n, _) = lead_term(h).unwrap();
+            if i == j {
+                pairs.push(Pair {
+                    i: k,
+                    j: basis.len(),
+                    component: i,
+                    lcm: m.lcm(n),
+                });
+            }
+        }
+        if track {
+            cofactors.push(c.scale(&MPolynomial::constant(inv)));
+        }
+        basis.push(g);
+    };
+
+    for (k, f) in gens.iter().enumerate() {
+        if !f.is_zero() {
+            add(
+                f.clone(),
+                Free::basis(k),
+                &mut basis,
+                &mut cofactors,
+                &mut pairs,
+            );
+        }
+    }
+
+    while !pairs.is_empty() {
+        // normal selection strategy: smallest lcm first
+        let next = (0..pairs.len())
+            .min_by(|&a, &b| {
+                cmp_module::<O>(
+                    (pairs[a].component, &pairs[a].lcm),
+                    (pairs[b].component, &pairs[b].lcm),
+                )
+            })
+            .unwrap();
+        let pair = pairs.swap_remove(next);
+        let m = lead_term(&basis[pair.i]).unwrap().1.clone();
+        let n = lead_term(&basis[pair.j]).unwrap().1.clone();
+
+        // Buchberger's first criterion: coprime leading monomials reduce to zero. This uses
+        // fg - gf = 0, so it only works for ideals, not submodules of R^n for n > 1
+        if is_ideal && m.is_coprime(&n) {
+            continue;
+        }
+        // the chain criterion: skip if some other leading term divides the lcm, and the pairs with
+        // it have already been handled
+        let chain = basis.iter().enumerate().any(|(k, g)| {
+            let (c, l, _) = lead_term(g).unwrap();
+            k != pair.i
+                && k != pair.j
+                && c == pair.component
+                && l.divides(&pair.lcm)
+                && !pairs.iter().any(|p| {
+                    (p.i == k.min(pair.i) && p.j == k.max(pair.i))
+                        || (p.i == k.min(pair.j) && p.j == k.max(pair.j))
+                })
+        });
+        if chain {
+            continue;
+        }
+
+        let (s, (a, c), (b, d)) = s_vector(&basis[pair.i], &basis[pair.j]);
+        let (quotients, r) = reduce_with_quotients(&s, &basis);
+        if !r.is_zero() {
+            let cofactor = if track {
+                let mut cofactor = cofactors[pair.i].scale(&MPolynomial::term(a, c))
+                    - cofactors[pair.j].scale(&MPolynomial::term(b, d));
+                for (q, cf) in quotients.iter().zip(cofactors.iter()) {
+                    if !q.is_zero() {
+                        cofactor = cofactor - cf.scale(q);
+                    }
+                }
+                cofactor
+            } else {
+                Free::zero()
+            };
+            add(r, cofactor, &mut basis, &mut cofactors, &mut pairs);
+        }
+    }
+    (basis, cofactors)
+}
+
+/// Removes redundant elements and fully reduces the rest, giving the unique reduced Grobner basis.
+fn interreduce<F: Field, O: MonomialOrder>(basis: Vec<Vector<F, O>>) -> Vec<Vector<F, O>> {
+    let leads: Vec<(usize, Monomial)> = basis
+        .iter()
+        .map(|g| {
+            let (i, m, _) = lead_term(g).unwrap();
+            (i, m.clone())
+        })
+        .collect();
+    // keep g unless another leading term divides its leading term (for equal leading terms, keep the first)
+    let minimal: Vec<Vector<F, O>> = basis
+        .iter()
+        .enumerate()
+        .filter(|(k, _)| {
+            !leads.iter().enumerate().any(|(l, (j, n))| {
+                l != *k
+                    && *j == leads[*k].0
+                    && n.divides(&leads[*k].1)
+                    && (*n != leads[*k].1 || l < *k)
+            })
+        })
+        .map(|(_, g)| g.clone())
+        .collect();
+    let mut reduced: Vec<Vector<F, O>> = (0..minimal.len())
+        .map(|k| {
+            let others: Vec<Vector<F, O>> = minimal
+                .iter()
+                .enumerate()
+                .filter(|(l, _)| *l != k)
+                .map(|(_, g)| g.clone())
+                .collect();
+            // the leading term cannot be reduced, so reduce everything else
+            let (i, m, c) = lead_term(&minimal[k]).unwrap();
+            let (i, m, c) = (i, m.clone(), c.clone());
+            let mut tail = minimal[k].clone();
+            add_term(&mut tail, i, &m, &(-c.clone()));
+            let mut g = module_normal_form(&tail, &others);
+            add_term(&mut g, i, &m, &c);
+            make_monic(&g).0
+        })
+        .collect();
+    reduced.sort_by(|g, h| {
+        let (i, m, _) = lead_term(g).unwrap();
+        let (j, n, _) = lead_term(h).unwrap();
+        cmp_module::<O>((j, n), (i, m))
+    });
+    reduced
+}
+
+/// The reduced Grobner basis of the submodule generated by `gens`, sorted by leading term,
+/// largest first.
+pub fn module_groebner_basis<F: Field, O: MonomialOrder>(
+    gens: &[Vector<F, O>],
+) -> Vec<Vector<F, O>> {
+    interreduce(buchberger(gens, false).0)
+}
+
+/// The reduced Grobner basis of the ideal generated by `gens`, sorted by leading term, largest first.
+pub fn groebner_basis<F: Field, O: MonomialOrder>(
+    gens: &[MPolynomial<F, O>],
+) -> Vec<MPolynomial<F, O>> {
+    let gens: Vec<Vector<F, O>> = gens.iter().map(|f| Free::from(vec![f.clone()])).collect();
+    module_groebner_basis(&gens)
+        .into_iter()
+        .map(|g| g.get(0))
+        .collect()
+}
+
+/// The normal form of `f` with respect to `basis`. If `basis` is a Grobner basis, this is zero
+/// exactly when `f` is in the ideal.
+pub fn normal_form<F: Field, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    basis: &[MPolynomial<F, O>],
+) -> MPolynomial<F, O> {
+    let basis: Vec<Vector<F, O>> = basis.iter().map(|g| Free::from(vec![g.clone()])).collect();
+    module_normal_form(&Free::from(vec![f.clone()]), &basis).get(0)
+}
+
+/// Generators for the module of syzygies of `gens`, that is, the kernel of the map
+/// $R^s \to R^n$ sending $e\_k$ to the $k$th generator.
+///
+/// This is Schreyer's algorithm: for a Grobner basis $g\_1, \ldots, g\_t$, each S-vector reduces to zero,
+/// $$
+/// a\_{ij} g\_i - a\_{ji} g\_j = \sum\_k q\_{ijk} g\_k,
+/// $$
+/// and the relations $a\_{ij} e\_i - a\_{ji} e\_j - \sum\_k q\_{ijk} e\_k$ generate the syzygies of the
+/// $g\_k$. These are pulled back to syzygies of `gens` with the cofactors from Buchberger's
+/// algorithm, and together with the relations expressing each generator in terms of the
+/// Grobner basis, they generate all syzygies. The result is usually far from minimal.
+pub fn syzygies<F: Field, O: MonomialOrder>(gens: &[Vector<F, O>]) -> Vec<Vector<F, O>> {
+    let (basis, cofactors) = buchberger(gens, true);
+    let pull_back = |sigma: &[MPolynomial<F, O>]| {
+        sigma
+            .iter()
+            .zip(cofactors.iter())
+            .filter(|(s, _)| !s.is_zero())
+            .fold(Free::zero(), |acc: Vector<F, O>, (s, c)| acc + c.scale(s))
+    };
+
+    let mut syz = vec![];
+    for i in 0..basis.len() {
+        for j in (i + 1)..basis.len() {
+            if lead_term(&basis[i]).unwrap().0 != lead_term(&basis[j]).unwrap().0 {
+                continue;
+            }
+            let (s, (a, c), (b, d)) = s_vector(&basis[i], &basis[j]);
+            let (quotients, _) = reduce_with_quotients(&s, &basis);
+            let mut sigma: Vec<MPolynomial<F, O>> = quotients.into_iter().map(|q| -q).collect();
+            sigma[i] = sigma[i].clone() + MPolynomial::term(a, c);
+            sigma[j] = sigma[j].clone() - MPolynomial::term(b, d);
+            let s = pull_back(&sigma);
+            if !s.is_zero() {
+                syz.push(s);
+            }
+        }
+    }
+    for (k, f) in gens.iter().enumerate() {
+        let (quotients, _) = reduce_with_quotients(f, &basis);
+        let s = Free::basis(k) - pull_back(&quotients);
+        if !s.is_zero() {
+            syz.push(s);
+        }
+    }
+    syz
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mpoly::{GRevLex, Lex};
+    use crate::test_helpers::poly;
+    use crate::Rational;
+    use num_traits::identities::One;
+
+    type P = MPolynomial<Rational, GRevLex>;
+
+    fn q(n: i64) -> Rational {
+        Rational::from(n)
+    }
+
+    #[test]
+    fn test_groebner_lex() {
+        type L = MPolynomial<Rational, Lex>;
+        // x^2 - y, x^3 - x: reduced lex basis is {x^2 - y, xy - x, y^2 - y}
+        let f = L::from(vec![(vec![2], q(1)), (vec![0, 1], q(-1))]);
+        let g = L::from(vec![(vec![3], q(1)), (vec![1], q(-1))]);
+        let gb = groebner_basis(&[f, g]);
+        assert_eq!(
+            gb,
+            vec![
+                L::from(vec![(vec![2], q(1)), (vec![0, 1], q(-1))]),
+                L::from(vec![(vec![1, 1], q(1)), (vec![1], q(-1))]),
+                L::from(vec![(vec![0, 2], q(1)), (vec![0, 1], q(-1))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normal_form() {
+        // the twisted cubic
+        let gens: Vec<P> = vec![
+            poly(vec![(vec![1, 0, 1], 1), (vec![0, 2], -1)]),
+            poly(vec![(vec![1, 0, 0, 1], 1), (vec![0, 1, 1], -1)]),
+            poly(vec![(vec![0, 1, 0, 1], 1), (vec![0, 0, 2], -1)]),
+        ];
+        let gb = groebner_basis(&gens);
+        assert_eq!(gb.len(), 3);
+        // x w^2 - z^3 = w(xw - yz) + z(yw - z^2)
+        let f = poly(vec![(vec![1, 0, 0, 2], 1), (vec![0, 0, 3], -1)]);
+        assert!(normal_form(&f, &gb).is_zero());
+        assert!(!normal_form(&poly(vec![(vec![1], 1)]), &gb).is_zero());
+        // the unit ideal
+        assert_eq!(
+            groebner_basis(&[
+                poly(vec![(vec![1], 1)]),
+                poly(vec![(vec![1], 1), (vec![], 1)])
+            ]),
+            vec![P::one()]
+        );
+    }
+
+    #[test]
+    fn test_syzygies() {
+        let x = P::var(0);
+        let y = P::var(1);
+        let z = P::var(2);
+        let gens: Vec<Free<P>> = vec![x, y, z]
+            .into_iter()
+            .map(|p| Free::from(vec![p]))
+            .collect();
+        let syz = syzygies(&gens);
+        // every syzygy is a syzygy
+        for s in syz.iter() {
+            let image = (0..3).fold(P::zero(), |acc, k| acc + s.get(k) * gens[k].get(0));
+            assert!(image.is_zero());
+        }
+        // and the Koszul relations are in their span
+        let syz_gb = module_groebner_basis(&syz);
+        let koszul = Free::from(vec![P::var(1), -P::var(0)]);
+        assert!(module_normal_form(&koszul, &syz_gb).is_zero());
+        assert!(!module_normal_form(&Free::from(vec![P::var(1)]), &syz_gb).is_zero());
+    }
+
+    #[test]
+    fn test_module_groebner_basis() {
+        // the submodule of R^2 generated by (x, y) and (y, x)
+        let gens = vec![
+            Free::from(vec![P::var(0), P::var(1)]),
+            Free::from(vec![P::var(1), P::var(0)]),
+        ];
+        let gb = module_groebner_basis(&gens);
+        // (x + y)(1, 1) is in it, (x, 0) is not
+        let sum = P::var(0) + P::var(1);
+        assert!(module_normal_form(&Free::from(vec![sum.clone(), sum]), &gb).is_zero());
+        assert!(!module_normal_form(&Free::from(vec![P::var(0)]), &gb).is_zero());
+    }
+
+    #[test]
+    fn test_module_coprime_leads() {
+        // leading terms -x e_1 and y^2 e_1 are coprime, but the S-vector does not reduce to zero
+        let gens = vec![
+            Free::from(vec![P::var(1), -P::var(0)]),
+            Free::from(vec![P::zero(), poly(vec![(vec![0, 2], 1)]), -P::var(0)]),
+        ];
+        let gb = module_groebner_basis(&gens);
+        let v = Free::from(vec![
+            poly(vec![(vec![0, 3], 1)]),
+            P::zero(),
+            poly(vec![(vec![2], -1)]),
+        ]);
+        assert!(module_normal_form(&v, &gb).is_zero());
+    }
+}
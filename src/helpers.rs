@@ -1,7 +1,6 @@
 //! Helper functions for the other modules. TODO: Place these things elsewhere
 
 use crate::module::RingType;
-use core::ops::Rem;
 use std::iter::once;
 
 /// treating an (abelian) group as a Z-module with this multiplication --
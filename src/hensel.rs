@@ -0,0 +1,444 @@
+//! Hensel lifting
+//!
+//! Hensel's lemma turns solutions modulo $p$ into solutions modulo $p^k$, and so into solutions in $\mathbb{Z}\_p$:
+//! - a simple root $r$ of $f$ mod $p$ (one with $f'(r) \not\equiv 0$) lifts uniquely, and Newton's iteration
+//!   $r \mapsto r - f(r)/f'(r)$ doubles the number of correct digits every step.
+//! - a factorization $f \equiv gh \pmod p$ into coprime factors with $h$ monic lifts uniquely, with quadratic steps
+//!   that lift the Bezout coefficients $sg + th = 1$ along with the factors (von zur Gathen and Gerhard, Algorithm
+//!   15.10).
+//! - a factorization into $r$ pairwise coprime factors is lifted as a tree: split the factors in half, lift the two
+//!   products, and recurse into both halves. That's $O(\log r)$ levels instead of $r$ two-factor lifts.
+//!
+//! The inputs are the integer polynomial and the solution mod $p$ as `Zmod<P>`, and the lifts come out as `ZmodDyn`
+//! modulo $p^k$. For `PAdic` polynomials, `padic_roots` and `padic_factors` find the solutions mod $p$ too.
+//!
+//! TODO: roots that aren't simple mod $p$ can still lift (when $v(f(r)) > 2 v(f'(r))$), but Newton doesn't see them.
+//! # Example:
+//! ```
+//! use bored_algebra::hensel::lift_root;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::zmod::Zmod;
+//! use bored_algebra::Integer;
+//!
+//! // x^2 + 1 = 0 mod 5^10, starting from x = 2 mod 5
+//! let f = Polynomial::from(vec![Integer::from(1), Integer::new(), Integer::from(1)]);
+//! let r = lift_root(&f, Zmod::<5>::new(2), 10).unwrap();
+//! let m: Integer = Integer::u_pow_u(5, 10).into();
+//! assert_eq!(r.value(), &Integer::from(6_139_557));
+//! let y: Integer = Integer::from(r.value() * r.value()) + 1u32;
+//! assert!(y.is_divisible(&m));
+//! ```
+use crate::error::ArithmeticError;
+use crate::factor::{
+    add_mod, derivative_mod, div_rem_mod, ext_gcd_mod, factor_mod_p, gcd_mod, mul_mod, reduce,
+    scale_mod, sub_mod,
+};
+use crate::limits::inverse::PAdic;
+use crate::poly::Polynomial;
+use crate::zmod::{Zmod, ZmodDyn};
+use crate::{Integer, Rational};
+use rug::ops::{Pow, RemRounding};
+
+/// Lifts a simple root $r$ of $f$ mod $P$ to the unique root mod $P^k$ that reduces to it, by Newton's iteration.
+/// Returns `NotUnit` if $f'(r)$ isn't a unit mod $P$, and panics if $r$ isn't a root.
+pub fn lift_root<const P: u64>(
+    f: &Polynomial<Integer>,
+    r: Zmod<P>,
+    k: u32,
+) -> Result<ZmodDyn, ArithmeticError> {
+    let p = Integer::from(P);
+    let f = f.coeffs();
+    let r = Integer::from(r.value());
+    assert!(eval_mod(&f, &r, &p).is_zero(), "not a root mod p");
+    if Integer::from(eval_mod(&derivative_mod(&f, &p), &r, &p).gcd_ref(&p)) != 1 {
+        return Err(ArithmeticError::NotUnit);
+    }
+    Ok(ZmodDyn::new(dense_lift_root(&f, &r, &p, k), &p.pow(k)))
+}
+
+/// Lifts $f \equiv gh \pmod P$, for $g$ and $h$ coprime mod $P$ and $h$ monic, to $f \equiv GH \pmod{P^k}$ with $H$
+/// monic and $G \equiv g$, $H \equiv h \pmod P$. Panics if the factorization or the factors are wrong.
+/// # Example:
+/// ```
+/// use bored_algebra::hensel::lift_factorization;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::zmod::Zmod;
+/// use bored_algebra::Integer;
+///
+/// type F = Zmod<7>;
+/// // x^2 - 2 = (x - 3)(x + 3) mod 7
+/// let f = Polynomial::from(vec![Integer::from(-2), Integer::new(), Integer::from(1)]);
+/// let g = Polynomial::from(vec![F::new(4), F::new(1)]);
+/// let h = Polynomial::from(vec![F::new(3), F::new(1)]);
+/// let (g, h) = lift_factorization(&f, &g, &h, 4);
+/// let product = g * h;
+/// assert_eq!(product.coeffs()[0].value(), &Integer::from(2399)); // -2 mod 7^4
+/// assert!(product.coeffs()[1].value().is_zero());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn lift_factorization<const P: u64>(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Zmod<P>>,
+    h: &Polynomial<Zmod<P>>,
+    k: u32,
+) -> (Polynomial<ZmodDyn>, Polynomial<ZmodDyn>) {
+    let () = Zmod::<P>::ASSERT_PRIME;
+    let p = Integer::from(P);
+    let (f, g, h) = (f.coeffs(), to_dense(g), to_dense(h));
+    assert!(h.last().is_some_and(|c| *c == 1), "h is not monic");
+    assert!(reduce(&f, &p) == mul_mod(&g, &h, &p), "f is not g h mod p");
+    assert!(
+        gcd_mod(&g, &h, &p).len() == 1,
+        "g and h are not coprime mod p"
+    );
+    let (g, h) = dense_lift_pair(&f, g, h, &p, k);
+    let m = p.pow(k);
+    (from_dense(g, &m), from_dense(h, &m))
+}
+
+/// Lifts $f \equiv \mathrm{lc}(f) g\_1 \cdots g\_r \pmod P$, for monic and pairwise coprime $g\_i$ and a unit
+/// $\mathrm{lc}(f)$, to monic factors modulo $P^k$, in the same order. Panics if the factorization is wrong.
+pub fn lift_factors<const P: u64>(
+    f: &Polynomial<Integer>,
+    factors: &[Polynomial<Zmod<P>>],
+    k: u32,
+) -> Vec<Polynomial<ZmodDyn>> {
+    let () = Zmod::<P>::ASSERT_PRIME;
+    let p = Integer::from(P);
+    let f = f.coeffs();
+    let factors: Vec<Vec<Integer>> = factors.iter().map(to_dense).collect();
+    let lc = f.last().map(|c| c.clone().rem_euc(&p)).unwrap_or_default();
+    assert!(!lc.is_zero(), "the leading coefficient is not a unit mod p");
+    let product = factors.iter().fold(vec![lc], |acc, g| mul_mod(&acc, g, &p));
+    assert!(
+        reduce(&f, &p) == product,
+        "f is not the product of the factors mod p"
+    );
+    for (i, g) in factors.iter().enumerate() {
+        assert!(
+            g.last().is_some_and(|c| *c == 1),
+            "the factors are not monic"
+        );
+        for h in &factors[..i] {
+            assert!(
+                gcd_mod(g, h, &p).len() == 1,
+                "the factors are not coprime mod p"
+            );
+        }
+    }
+    if factors.is_empty() {
+        return vec![];
+    }
+    let m = p.clone().pow(k);
+    dense_lift_factors(&f, &factors, &p, k)
+        .into_iter()
+        .map(|g| from_dense(g, &m))
+        .collect()
+}
+
+/// The roots of $f$ in $\mathbb{Z}\_p$ that are simple mod $p$, to the absolute precision the coefficients allow.
+/// The roots mod $p$ come from `factor_mod_p` and are lifted by Newton's iteration. Panics if $f$ is zero.
+/// # Example:
+/// ```
+/// use bored_algebra::hensel::padic_roots;
+/// use bored_algebra::limits::inverse::PAdic;
+/// use bored_algebra::poly::Polynomial;
+/// use bored_algebra::Integer;
+///
+/// type Q7 = PAdic<7>;
+/// // x^3 - 6 has the roots 3, 5 and 6 mod 7, which are all simple
+/// let f = Polynomial::from(vec![Q7::from(Integer::from(-6)), Q7::from(Integer::new()), Q7::from(Integer::new()), Q7::from(Integer::from(1))]);
+/// let roots = padic_roots(&f);
+/// assert_eq!(roots.len(), 3);
+/// assert!(roots.iter().all(|r| r.pow(3) == Q7::from(Integer::from(6))));
+/// ```
+pub fn padic_roots<const P: u64, const N: u32>(f: &Polynomial<PAdic<P, N>>) -> Vec<PAdic<P, N>> {
+    let (f, k) = integral(f);
+    let p = Integer::from(P);
+    let df = derivative_mod(&f, &p);
+    factor_mod_p(&Polynomial::from(f.clone()), &p)
+        .into_iter()
+        .filter(|(g, _)| g.deg() == 1)
+        .map(|(g, _)| Integer::from(-&g.coeffs()[0]).rem_euc(&p))
+        .filter(|r| !eval_mod(&df, r, &p).is_zero())
+        .map(|r| {
+            PAdic::with_absolute_precision(
+                &Rational::from(dense_lift_root(&f, &r, &p, k)),
+                k as i64,
+            )
+        })
+        .collect()
+}
+
+/// Factors $f$ over $\mathbb{Q}\_p$ when it's squarefree mod $p$ (after scaling it into $\mathbb{Z}\_p[x]$) with a
+/// unit leading coefficient: the factors mod $p$ lift to the monic irreducible factors over $\mathbb{Z}\_p$, to the
+/// absolute precision the coefficients allow. Panics if $f$ isn't like that.
+///
+/// TODO: everything else needs the Montes algorithm (or Round 4), which see more than $f$ mod $p$.
+pub fn padic_factors<const P: u64, const N: u32>(
+    f: &Polynomial<PAdic<P, N>>,
+) -> Vec<Polynomial<PAdic<P, N>>> {
+    let (f, k) = integral(f);
+    let p = Integer::from(P);
+    assert!(
+        !f.last().unwrap().is_divisible(&p),
+        "the leading coefficient is not a unit"
+    );
+    assert!(
+        gcd_mod(&reduce(&f, &p), &derivative_mod(&f, &p), &p).len() == 1,
+        "f is not squarefree mod p"
+    );
+    let factors: Vec<Vec<Integer>> = factor_mod_p(&Polynomial::from(f.clone()), &p)
+        .into_iter()
+        .map(|(g, _)| g.coeffs())
+        .collect();
+    dense_lift_factors(&f, &factors, &p, k)
+        .into_iter()
+        .map(|g| {
+            Polynomial::from(
+                g.iter()
+                    .map(|c| PAdic::with_absolute_precision(&Rational::from(c), k as i64))
+                    .collect::<Vec<PAdic<P, N>>>(),
+            )
+        })
+        .collect()
+}
+
+/// $p^s f$ as an integer polynomial with a coefficient prime to $p$, and the absolute precision it's known to.
+fn integral<const P: u64, const N: u32>(f: &Polynomial<PAdic<P, N>>) -> (Vec<Integer>, u32) {
+    let f = f.coeffs();
+    let p = Integer::from(P);
+    let s = -f
+        .iter()
+        .filter_map(|c| c.valuation())
+        .min()
+        .expect("the polynomial is zero");
+    let k = f
+        .iter()
+        .filter_map(|c| c.absolute_precision())
+        .map(|a| a + s)
+        .min()
+        .unwrap_or(N as i64);
+    assert!(k > 0, "not known mod p");
+    let coeffs = f
+        .iter()
+        .map(|c| match c.valuation() {
+            Some(v) => p.clone().pow((v + s) as u32) * c.unit(),
+            None => Integer::new(),
+        })
+        .collect();
+    (coeffs, k as u32)
+}
+
+fn to_dense<const P: u64>(f: &Polynomial<Zmod<P>>) -> Vec<Integer> {
+    reduce(
+        &f.coeffs()
+            .iter()
+            .map(|c| Integer::from(c.value()))
+            .collect::<Vec<Integer>>(),
+        &Integer::from(P),
+    )
+}
+
+fn from_dense(f: Vec<Integer>, m: &Integer) -> Polynomial<ZmodDyn> {
+    Polynomial::from(
+        f.into_iter()
+            .map(|c| ZmodDyn::new(c, m))
+            .collect::<Vec<ZmodDyn>>(),
+    )
+}
+
+fn eval_mod(f: &[Integer], x: &Integer, m: &Integer) -> Integer {
+    f.iter()
+        .rev()
+        .fold(Integer::new(), |acc, c| (acc * x + c).rem_euc(m))
+}
+
+/*
+ * The lifts on coefficient vectors over Z/m, as in `factor`.
+ */
+
+/// Newton's iteration from a root mod $p$ with $f'(r)$ a unit to the root mod $p^k$.
+pub(crate) fn dense_lift_root(f: &[Integer], r: &Integer, p: &Integer, k: u32) -> Integer {
+    let target = p.clone().pow(k);
+    let df = derivative_mod(f, &target);
+    let mut r = r.clone().rem_euc(p);
+    let mut m = p.clone();
+    while m < target {
+        m = Integer::from(&m * &m).min(target.clone());
+        let d = eval_mod(&df, &r, &m).invert(&m).unwrap();
+        let step = eval_mod(f, &r, &m) * d;
+        r = (r - step).rem_euc(&m);
+    }
+    r.rem_euc(&target)
+}
+
+/// One factorization $f \equiv gh \pmod p$, with $h$ monic and $g, h$ coprime, lifted to $p^k$ with quadratic steps.
+pub(crate) fn dense_lift_pair(
+    f: &[Integer],
+    mut g: Vec<Integer>,
+    mut h: Vec<Integer>,
+    p: &Integer,
+    k: u32,
+) -> (Vec<Integer>, Vec<Integer>) {
+    let target = p.clone().pow(k);
+    let (_, mut s, mut t) = ext_gcd_mod(&g, &h, p);
+    let mut m = p.clone();
+    while m < target {
+        let m2 = Integer::from(&m * &m);
+        // von zur Gathen and Gerhard, Algorithm 15.10
+        let e = sub_mod(&reduce(f, &m2), &mul_mod(&g, &h, &m2), &m2);
+        let (q, r) = div_rem_mod(&mul_mod(&s, &e, &m2), &h, &m2);
+        let g2 = add_mod(
+            &add_mod(&g, &mul_mod(&t, &e, &m2), &m2),
+            &mul_mod(&q, &g, &m2),
+            &m2,
+        );
+        let h2 = add_mod(&h, &r, &m2);
+        let b = sub_mod(
+            &add_mod(&mul_mod(&s, &g2, &m2), &mul_mod(&t, &h2, &m2), &m2),
+            &[Integer::from(1)],
+            &m2,
+        );
+        let (c, d) = div_rem_mod(&mul_mod(&s, &b, &m2), &h2, &m2);
+        s = sub_mod(&s, &d, &m2);
+        t = sub_mod(
+            &sub_mod(&t, &mul_mod(&t, &b, &m2), &m2),
+            &mul_mod(&c, &g2, &m2),
+            &m2,
+        );
+        g = g2;
+        h = h2;
+        m = m2;
+    }
+    (reduce(&g, &target), reduce(&h, &target))
+}
+
+/// Lifts a factorization $f \equiv \mathrm{lc}(f) g\_1 \cdots g\_r \pmod p$ into pairwise coprime monic
+/// factors to a factorization modulo $p^k$, splitting the factors in half and lifting each half with
+/// `dense_lift_pair`.
+pub(crate) fn dense_lift_factors(
+    f: &[Integer],
+    factors: &[Vec<Integer>],
+    p: &Integer,
+    k: u32,
+) -> Vec<Vec<Integer>> {
+    let target = p.clone().pow(k);
+    let lc = f.last().unwrap().clone();
+    if factors.len() == 1 {
+        let inv = lc.invert_ref(&target).map(Integer::from).unwrap();
+        return vec![scale_mod(f, &inv, &target)];
+    }
+    let (left, right) = factors.split_at(factors.len() / 2);
+    let g = left
+        .iter()
+        .fold(vec![lc.clone()], |acc, h| mul_mod(&acc, h, p));
+    let h = right
+        .iter()
+        .fold(vec![Integer::from(1)], |acc, h| mul_mod(&acc, h, p));
+    let (g, h) = dense_lift_pair(f, g, h, p, k);
+    let mut lifted = dense_lift_factors(&g, left, p, k);
+    lifted.extend(dense_lift_factors(&h, right, p, k));
+    lifted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zpoly;
+    use num_traits::identities::Zero;
+
+    fn fpoly<const P: u64>(v: Vec<u64>) -> Polynomial<Zmod<P>> {
+        Polynomial::from(v.into_iter().map(Zmod::new).collect::<Vec<Zmod<P>>>())
+    }
+
+    #[test]
+    fn test_lift_root() {
+        // the 7-adic cube roots of 6, from 3, 5 and 6 mod 7
+        let f = zpoly(vec![-6, 0, 0, 1]);
+        let m = Integer::from(7).pow(30);
+        for r in [3, 5, 6] {
+            let x = lift_root(&f, Zmod::<7>::new(r), 30).unwrap();
+            assert_eq!(x.modulus(), Some(&m));
+            assert_eq!(x.value().clone().rem_euc(&Integer::from(7)), r);
+            assert!(eval_mod(&f.coeffs(), x.value(), &m).is_zero());
+        }
+        // 0 is a double root of x^2 + 7x mod 7
+        assert!(matches!(
+            lift_root(&zpoly(vec![0, 7, 1]), Zmod::<7>::new(0), 5),
+            Err(ArithmeticError::NotUnit)
+        ));
+        // composite moduli are fine for roots: x^2 = 7 mod 9^3, from 4^2 = 7 mod 9
+        let x = lift_root(&zpoly(vec![-7, 0, 1]), Zmod::<9>::new(4), 3).unwrap();
+        assert_eq!(
+            Integer::from(x.value() * x.value()).rem_euc(&Integer::from(729)),
+            7
+        );
+    }
+
+    #[test]
+    fn test_lift_factors() {
+        // 3x^3 + x + 7 = (3x^2 + 9x + 2)(x - 3) mod 13
+        let f = zpoly(vec![7, 1, 0, 3]);
+        let (g, h) = lift_factorization(&f, &fpoly::<13>(vec![2, 9, 3]), &fpoly(vec![10, 1]), 6);
+        let m = Integer::from(13).pow(6);
+        let f_m = from_dense(reduce(&f.coeffs(), &m), &m);
+        assert_eq!(g.clone() * h.clone(), f_m);
+        assert!(h.lead_coeff().value() == &1);
+        // x^8 - 1 splits into linear factors mod 17
+        let f = zpoly(vec![-1, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let factors: Vec<Polynomial<Zmod<17>>> = [1, 2, 4, 8, 9, 13, 15, 16]
+            .iter()
+            .map(|&r| fpoly(vec![17 - r, 1]))
+            .collect();
+        let lifted = lift_factors(&f, &factors, 10);
+        assert_eq!(lifted.len(), 8);
+        let m = Integer::from(17).pow(10);
+        let product = lifted
+            .iter()
+            .fold(from_dense(vec![Integer::from(1)], &m), |acc, g| {
+                acc * g.clone()
+            });
+        assert_eq!(product, from_dense(reduce(&f.coeffs(), &m), &m));
+        for (g, r) in lifted.iter().zip([1, 2, 4, 8, 9, 13, 15, 16]) {
+            assert_eq!(g.deg(), 1);
+            assert_eq!(
+                Integer::from(g.coeffs()[0].value() + r).rem_euc(&Integer::from(17)),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_padic() {
+        type Q5 = PAdic<5>;
+        let q = |a: i64| Q5::from(Integer::from(a));
+        // x^2 + 1 over Q_5 has the two roots of -1
+        let roots = padic_roots(&Polynomial::from(vec![q(1), q(0), q(1)]));
+        assert_eq!(roots.len(), 2);
+        assert!(roots.iter().all(|r| r.clone() * r.clone() == q(-1)));
+        assert_eq!(roots[0].absolute_precision(), Some(20));
+        // the same with coefficients that aren't integral: (x^2 + 1)/25
+        let roots = padic_roots(&Polynomial::from(vec![
+            Q5::from(Rational::from((1, 25))),
+            q(0),
+            Q5::from(Rational::from((1, 25))),
+        ]));
+        assert_eq!(roots.len(), 2);
+        // x^2 - 5 has no roots, x(x - 1)^2 only the simple one
+        assert!(padic_roots(&Polynomial::from(vec![q(-5), q(0), q(1)])).is_empty());
+        let roots = padic_roots(&Polynomial::from(vec![q(0), q(1), q(-2), q(1)]));
+        assert_eq!(roots.len(), 1);
+        assert!(roots[0].is_zero());
+
+        // x^4 + 1 = (x^2 + x + 2)(x^2 + 2x + 2) mod 3, so it's a product of two quadratics over Q_3
+        type Q3 = PAdic<3>;
+        let one = Q3::from(Integer::from(1));
+        let zero = Q3::from(Integer::new());
+        let f = Polynomial::from(vec![one.clone(), zero.clone(), zero.clone(), zero, one]);
+        let factors = padic_factors(&f);
+        assert_eq!(factors.len(), 2);
+        assert!(factors.iter().all(|g| g.deg() == 2));
+        assert_eq!(factors[0].clone() * factors[1].clone(), f);
+    }
+}
@@ -0,0 +1,423 @@
+//! Hilbert series, Hilbert polynomials, dimension and degree
+//!
+//! For a homogeneous ideal $I \subseteq R = F[x\_0, \ldots, x\_{n-1}]$, the Hilbert series of $R/I$ is
+//! $$
+//! HS\_{R/I}(t) = \sum\_{s \geq 0} \dim\_F (R/I)\_s t^s = \frac{N(t)}{(1-t)^n} = \frac{h(t)}{(1-t)^d}
+//! $$
+//! where $N, h \in \mathbb{Z}[t]$, $h(1) \neq 0$. Then $d$ is the Krull dimension of $R/I$, $h(1)$ is its degree,
+//! and for large $s$ the coefficient of $t^s$ agrees with a polynomial in $s$ of degree $d - 1$, the
+//! Hilbert polynomial.
+//!
+//! Everything is computed from the ideal of leading monomials, which has the same Hilbert series as $I$.
+//! For a monomial ideal $M$ and a monomial $p$, there is an exact sequence
+//! $$
+//! 0 \to R/(M : p)(-\deg p) \to R/M \to R/(M + (p)) \to 0
+//! $$
+//! so $N\_M(t) = N\_{M + (p)}(t) + t^{\deg p} N\_{M : p}(t)$. Choosing $p$ to be a power of a variable that
+//! appears in several generators splits the problem until the generators are pairwise coprime, where
+//! $N\_M(t) = \prod\_i (1 - t^{\deg m\_i})$.
+//!
+//! For ideals that are not homogeneous, the dimension comes from maximal independent sets of
+//! variables: sets $U$ of variables such that no leading monomial of $I$ is a monomial in $U$ alone. The
+//! largest such sets have size $\dim R/I$.
+use crate::error::ArithmeticError;
+use crate::ideal::Ideal;
+use crate::mpoly::{GRevLex, Monomial, MonomialOrder};
+use crate::poly::Polynomial;
+use crate::structure::Field;
+use crate::{Integer, Rational};
+
+use num_traits::identities::Zero;
+
+/// Removes duplicates and generators divisible by other generators.
+fn minimalize(gens: &[Monomial]) -> Vec<Monomial> {
+    let mut minimal: Vec<Monomial> = vec![];
+    for m in gens {
+        if !minimal.iter().any(|n| n.divides(m)) {
+            minimal.retain(|n| !m.divides(n));
+            minimal.push(m.clone());
+        }
+    }
+    minimal
+}
+
+/// Multiplies a coefficient vector by $1 - t^e$.
+fn times_one_minus(p: &[Integer], e: usize) -> Vec<Integer> {
+    let mut q = vec![Integer::new(); p.len() + e];
+    for (k, a) in p.iter().enumerate() {
+        q[k] += a;
+        q[k + e] -= a;
+    }
+    q
+}
+
+fn add_shifted(p: &mut Vec<Integer>, q: &[Integer], e: usize) {
+    if p.len() < q.len() + e {
+        p.resize(q.len() + e, Integer::new());
+    }
+    for (k, a) in q.iter().enumerate() {
+        p[k + e] += a;
+    }
+}
+
+/// The numerator $N(t)$ of the Hilbert series of $R/M$, for the monomial ideal $M$ generated by `gens`,
+/// as a vector of coefficients.
+fn numerator(gens: &[Monomial]) -> Vec<Integer> {
+    let gens = minimalize(gens);
+    if gens.iter().any(|m| m.is_one()) {
+        return vec![];
+    }
+    let nvars = gens.iter().map(|m| m.nvars()).max().unwrap_or(0);
+    // the variable appearing in the most generators
+    let (x, count) = (0..nvars)
+        .map(|i| (i, gens.iter().filter(|m| m.exp(i) > 0).count()))
+        .max_by_key(|(_, c)| *c)
+        .unwrap_or((0, 0));
+    if count < 2 {
+        // pairwise coprime
+        return gens.iter().fold(vec![Integer::from(1)], |acc, m| {
+            times_one_minus(&acc, m.deg() as usize)
+        });
+    }
+
+    let e = gens
+        .iter()
+        .map(|m| m.exp(x))
+        .filter(|a| *a > 0)
+        .min()
+        .unwrap();
+    let mut p = vec![0; x + 1];
+    p[x] = e;
+    let p = Monomial::from(p);
+
+    // M + (p) is generated by p and the generators without x, and these are coprime
+    let without_x: Vec<Monomial> = gens.iter().filter(|m| m.exp(x) == 0).cloned().collect();
+    let mut n = times_one_minus(&numerator(&without_x), e as usize);
+    let quotient: Vec<Monomial> = gens
+        .iter()
+        .map(|m| m.checked_div(&m.gcd(&p)).unwrap())
+        .collect();
+    add_shifted(&mut n, &numerator(&quotient), e as usize);
+    n
+}
+
+/// The Hilbert series of $R/M$, where $M$ is the monomial ideal generated by `gens` in $n$ = `nvars`
+/// variables.
+/// # Example:
+/// ```
+/// use bored_algebra::hilbert::hilbert_series;
+/// use bored_algebra::mpoly::Monomial;
+///
+/// // k[x, y, z]/(xy, xz, yz), three coordinate lines through the origin
+/// let gens = vec![Monomial::from(vec![1, 1]), Monomial::from(vec![1, 0, 1]), Monomial::from(vec![0, 1, 1])];
+/// let hs = hilbert_series(&gens, 3);
+/// assert_eq!(hs.dim(), Some(1));
+/// assert_eq!(hs.degree(), 3);
+/// // 1 + 3t + 3t^2 + 3t^3 + ...
+/// assert_eq!(hs.hilbert_function(5), 3);
+/// ```
+pub fn hilbert_series(gens: &[Monomial], nvars: usize) -> HilbertSeries {
+    let coeffs = numerator(gens);
+    HilbertSeries {
+        numerator: Polynomial::from(coeffs),
+        nvars,
+    }
+}
+
+/// The Hilbert series $N(t)/(1-t)^n$ of a graded quotient of a polynomial ring in $n$ variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HilbertSeries {
+    numerator: Polynomial<Integer>,
+    nvars: usize,
+}
+
+impl HilbertSeries {
+    /// The numerator $N(t)$, over the denominator $(1-t)^n$.
+    pub fn numerator(&self) -> &Polynomial<Integer> {
+        &self.numerator
+    }
+
+    /// The number of variables $n$.
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    /// Returns true if the series is zero, that is, the quotient ring is zero.
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    /// Returns $h(t)$ and $d$ with $HS(t) = h(t)/(1-t)^d$ and $h(1) \neq 0$, by dividing out factors of $1 - t$.
+    /// For the zero series, returns $(0, 0)$.
+    pub fn reduced(&self) -> (Polynomial<Integer>, usize) {
+        if self.is_zero() {
+            return (Polynomial::zero(), 0);
+        }
+        let mut h = self
+            .numerator
+            .coeffs_take(self.numerator.deg() as usize + 1);
+        let mut d = self.nvars;
+        // N(1) = 0 if and only if 1 - t divides N
+        while h.iter().fold(Integer::new(), |acc, a| acc + a) == 0 {
+            // synthetic division by 1 - t: if N = (1 - t) q, then q_k = N_0 + ... + N_k
+            let mut acc = Integer::new();
+            let q: Vec<Integer> = h[..h.len() - 1]
+                .iter()
+                .map(|a| {
+                    acc += a;
+                    acc.clone()
+                })
+                .collect();
+            h = q;
+            d -= 1;
+        }
+        (Polynomial::from(h), d)
+    }
+
+    /// The Krull dimension $d$, or `None` for the zero ring.
+    pub fn dim(&self) -> Option<usize> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.reduced().1),
+        }
+    }
+
+    /// The degree (or multiplicity) $h(1)$.
+    pub fn degree(&self) -> Integer {
+        self.reduced()
+            .0
+            .coeffs()
+            .into_iter()
+            .fold(Integer::new(), |acc, a| acc + a)
+    }
+
+    /// The value of the Hilbert function at $s$, that is, the coefficient of $t^s$ in the series.
+    pub fn hilbert_function(&self, s: usize) -> Integer {
+        let n = self.nvars as u32;
+        self.numerator
+            .coeffs_take(s + 1)
+            .iter()
+            .enumerate()
+            .fold(Integer::new(), |acc, (k, a)| {
+                // the coefficient of t^j in 1/(1-t)^n is binomial(j + n - 1, n - 1)
+                let b = match n {
+                    0 => Integer::from((s == k) as u32),
+                    _ => Integer::from(s - k + n as usize - 1).binomial(n - 1),
+                };
+                acc + a.clone() * b
+            })
+    }
+
+    /// The Hilbert polynomial $P(s)$, which agrees with the Hilbert function for large $s$:
+    /// $$
+    /// P(s) = \sum\_k h\_k \binom{s - k + d - 1}{d - 1}.
+    /// $$
+    pub fn hilbert_polynomial(&self) -> Polynomial<Rational> {
+        let (h, d) = self.reduced();
+        if d == 0 {
+            return Polynomial::zero();
+        }
+        h.coeffs_take(h.deg() as usize + 1)
+            .into_iter()
+            .enumerate()
+            .fold(Polynomial::zero(), |acc, (k, a)| {
+                // binomial(s - k + d - 1, d - 1) = (s - k + 1)(s - k + 2)...(s - k + d - 1)/(d - 1)!
+                let binomial = (1..d).fold(Polynomial::from(vec![Rational::from(a)]), |p, i| {
+                    let linear = Polynomial::from(vec![
+                        Rational::from((i as i64 - k as i64, i as i64)),
+                        Rational::from((1, i as i64)),
+                    ]);
+                    p * linear
+                });
+                acc + binomial
+            })
+    }
+}
+
+impl<F: Field, O: MonomialOrder> Ideal<F, O> {
+    /// The Hilbert series of $R/I$. Returns `ArithmeticError::NotHomogeneous` if $I$ is not homogeneous.
+    pub fn hilbert_series(&self) -> Result<HilbertSeries, ArithmeticError> {
+        match self.is_homogeneous() {
+            true => Ok(hilbert_series(&self.leading_monomials(), self.nvars())),
+            false => Err(ArithmeticError::NotHomogeneous),
+        }
+    }
+
+    /// The Hilbert polynomial of $R/I$, for homogeneous $I$.
+    pub fn hilbert_polynomial(&self) -> Result<Polynomial<Rational>, ArithmeticError> {
+        Ok(self.hilbert_series()?.hilbert_polynomial())
+    }
+
+    /// The degree of $R/I$. For homogeneous $I$ this is the degree of the projective variety, and
+    /// otherwise it is computed from the leading monomials for a degree-compatible order, which gives the
+    /// degree of the affine variety (the number of points, counted with multiplicity, when $I$ is zero-dimensional).
+    pub fn degree(&self) -> Integer {
+        match self.is_homogeneous() {
+            true => hilbert_series(&self.leading_monomials(), self.nvars()).degree(),
+            false => hilbert_series(&self.reorder::<GRevLex>().leading_monomials(), self.nvars())
+                .degree(),
+        }
+    }
+
+    /// The maximal (with respect to inclusion) independent sets of variables modulo $I$: sets $U$ of
+    /// variables such that $I \cap F[U] = 0$, found as the sets where no leading monomial of $I$
+    /// only involves variables in $U$. Variables are given by their indices.
+    pub fn maximal_independent_sets(&self) -> Vec<Vec<usize>> {
+        let leads = self.leading_monomials();
+        let independent = |set: &[usize]| {
+            !leads
+                .iter()
+                .any(|m| (0..m.nvars()).all(|i| m.exp(i) == 0 || set.contains(&i)))
+        };
+        let mut sets = vec![];
+        if !independent(&[]) {
+            return sets;
+        }
+        // depth-first search over sets listed in increasing order
+        let mut stack: Vec<Vec<usize>> = vec![vec![]];
+        while let Some(set) = stack.pop() {
+            let start = set.last().map(|i| i + 1).unwrap_or(0);
+            for j in start..self.nvars() {
+                let mut bigger = set.clone();
+                bigger.push(j);
+                if independent(&bigger) {
+                    stack.push(bigger);
+                }
+            }
+            let maximal = (0..self.nvars()).all(|j| {
+                set.contains(&j) || {
+                    let mut bigger = set.clone();
+                    bigger.push(j);
+                    !independent(&bigger)
+                }
+            });
+            if maximal {
+                sets.push(set);
+            }
+        }
+        sets.sort();
+        sets
+    }
+
+    /// The Krull dimension of $R/I$, the size of the largest independent set of variables, or `None` if
+    /// $I$ is the unit ideal.
+    pub fn dim(&self) -> Option<usize> {
+        self.maximal_independent_sets()
+            .iter()
+            .map(|set| set.len())
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mpoly::{Lex, MPolynomial};
+    use crate::test_helpers::{poly, zpoly};
+
+    type P = MPolynomial<Rational>;
+
+    fn twisted_cubic() -> Ideal<Rational> {
+        Ideal::new(
+            4,
+            vec![
+                poly(vec![(vec![1, 0, 1], 1), (vec![0, 2], -1)]),
+                poly(vec![(vec![1, 0, 0, 1], 1), (vec![0, 1, 1], -1)]),
+                poly(vec![(vec![0, 1, 0, 1], 1), (vec![0, 0, 2], -1)]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_numerator() {
+        // the polynomial ring itself
+        assert_eq!(hilbert_series(&[], 3).numerator(), &zpoly(vec![1]));
+        // (x^2, y^3): (1 - t^2)(1 - t^3)
+        let gens = vec![Monomial::from(vec![2]), Monomial::from(vec![0, 3])];
+        assert_eq!(
+            hilbert_series(&gens, 2).numerator(),
+            &zpoly(vec![1, 0, -1, -1, 0, 1])
+        );
+        // (x^2, xy): 1 - 2t^2 + t^3
+        let gens = vec![Monomial::from(vec![2]), Monomial::from(vec![1, 1])];
+        let hs = hilbert_series(&gens, 2);
+        assert_eq!(hs.numerator(), &zpoly(vec![1, 0, -2, 1]));
+        // = (1 + t - t^2)/(1 - t)
+        assert_eq!(hs.reduced(), (zpoly(vec![1, 1, -1]), 1));
+        // the unit ideal
+        assert!(hilbert_series(&[Monomial::one()], 2).is_zero());
+        assert_eq!(hilbert_series(&[Monomial::one()], 2).dim(), None);
+    }
+
+    #[test]
+    fn test_twisted_cubic() {
+        let i = twisted_cubic();
+        let hs = i.hilbert_series().unwrap();
+        // 1 + 2t over (1 - t)^2, a curve of degree 3
+        assert_eq!(hs.reduced(), (zpoly(vec![1, 2]), 2));
+        assert_eq!(hs.dim(), Some(2));
+        assert_eq!(i.degree(), 3);
+        assert_eq!(i.dim(), Some(2));
+        // HP(s) = 3s + 1
+        assert_eq!(
+            i.hilbert_polynomial().unwrap(),
+            Polynomial::from(vec![Rational::from(1), Rational::from(3)])
+        );
+        for s in 0..6 {
+            assert_eq!(hs.hilbert_function(s), 3 * s as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_hypersurface() {
+        // a quartic surface in P^3: HP(s) = 2s^2 + 2
+        let f: P = poly(vec![
+            (vec![4], 1),
+            (vec![0, 4], 1),
+            (vec![0, 0, 4], 1),
+            (vec![0, 0, 0, 4], 1),
+        ]);
+        let i = Ideal::new(4, vec![f]);
+        assert_eq!(i.degree(), 4);
+        assert_eq!(i.hilbert_series().unwrap().dim(), Some(3));
+        assert_eq!(
+            i.hilbert_polynomial().unwrap(),
+            Polynomial::from(vec![
+                Rational::from(2),
+                Rational::from(0),
+                Rational::from(2)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_affine() {
+        // the parabola y = x^2 meets the line y = 1 in two points
+        let i = Ideal::new(
+            2,
+            vec![
+                poly::<P>(vec![(vec![0, 1], 1), (vec![2], -1)]),
+                poly(vec![(vec![0, 1], 1), (vec![], -1)]),
+            ],
+        );
+        assert!(matches!(
+            i.hilbert_series(),
+            Err(ArithmeticError::NotHomogeneous)
+        ));
+        assert_eq!(i.dim(), Some(0));
+        assert_eq!(i.degree(), 2);
+        assert_eq!(i.reorder::<Lex>().degree(), 2);
+
+        // the union of the plane x = 0 and the line y = z = 0 in A^3
+        let j = Ideal::new(3, vec![P::var(0) * P::var(1), P::var(0) * P::var(2)]);
+        assert_eq!(j.maximal_independent_sets(), vec![vec![0], vec![1, 2]]);
+        assert_eq!(j.dim(), Some(2));
+
+        // the unit ideal has no independent sets
+        let k = Ideal::new(
+            2,
+            vec![P::var(0), P::var(0) - P::constant(Rational::from(1))],
+        );
+        assert_eq!(k.dim(), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! Ideals of polynomial rings
+//!
+//! An `Ideal<F, O>` is an ideal of $F[x\_0, \ldots, x\_{n-1}]$, stored by a list of generators together with
+//! its reduced Grobner basis with respect to `O`, which is computed when the ideal is made.
+use crate::groebner::{groebner_basis, normal_form};
+use crate::mpoly::{ElimFirst, GRevLex, MPolynomial, Monomial, MonomialOrder};
+use crate::structure::Field;
+
+use num_traits::identities::{One, Zero};
+
+/// An ideal of the polynomial ring $F[x\_0, \ldots, x\_{n-1}]$.
+/// # Example:
+/// ```
+/// use bored_algebra::ideal::Ideal;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Rational;
+///
+/// type P = MPolynomial<Rational>;
+/// let (x, y) = (P::var(0), P::var(1));
+/// let i = Ideal::new(2, vec![x.clone() * x.clone(), x.clone() * y.clone()]);
+/// assert!(i.contains(&(x.clone() * x.clone() * y.clone() + x.clone() * y.clone())));
+/// assert!(!i.contains(&x));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ideal<F, O = GRevLex> {
+    nvars: usize,
+    gens: Vec<MPolynomial<F, O>>,
+    basis: Vec<MPolynomial<F, O>>,
+}
+
+impl<F: Field, O: MonomialOrder> Ideal<F, O> {
+    /// The ideal generated by `gens` in the ring with variables $x\_0, \ldots, x\_{n-1}$, where $n$ is `nvars`.
+    /// Panics if a generator uses a variable past $x\_{n-1}$.
+    pub fn new(nvars: usize, gens: Vec<MPolynomial<F, O>>) -> Self {
+        assert!(
+            gens.iter().all(|f| f.nvars() <= nvars),
+            "generators use more than {} variables",
+            nvars
+        );
+        let basis = groebner_basis(&gens);
+        Self { nvars, gens, basis }
+    }
+
+    /// The ideal generated by the variables, $(x\_0, \ldots, x\_{n-1})$.
+    pub fn maximal(nvars: usize) -> Self {
+        Self::new(nvars, (0..nvars).map(MPolynomial::var).collect())
+    }
+
+    /// The number of variables of the ambient polynomial ring.
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    /// The generators the ideal was made with.
+    pub fn gens(&self) -> &[MPolynomial<F, O>] {
+        &self.gens
+    }
+
+    /// The reduced Grobner basis with respect to `O`.
+    pub fn groebner_basis(&self) -> &[MPolynomial<F, O>] {
+        &self.basis
+    }
+
+    /// The normal form of `f` modulo the ideal.
+    pub fn reduce(&self, f: &MPolynomial<F, O>) -> MPolynomial<F, O> {
+        normal_form(f, &self.basis)
+    }
+
+    pub fn contains(&self, f: &MPolynomial<F, O>) -> bool {
+        self.reduce(f).is_zero()
+    }
+
+    /// Returns true if every generator of `other` is in `self`.
+    pub fn contains_ideal(&self, other: &Self) -> bool {
+        other.gens.iter().all(|f| self.contains(f))
+    }
+
+    /// Returns true for the zero ideal.
+    pub fn is_zero(&self) -> bool {
+        self.basis.is_empty()
+    }
+
+    /// Returns true if the ideal is the whole ring.
+    pub fn is_unit(&self) -> bool {
+        self.basis.len() == 1 && self.basis[0].is_one()
+    }
+
+    /// Returns true if the ideal can be generated by homogeneous polynomials. The reduced Grobner basis
+    /// of a homogeneous ideal is homogeneous, whatever the order, so this only needs to check that.
+    pub fn is_homogeneous(&self) -> bool {
+        self.basis.iter().all(|g| g.is_homogeneous())
+    }
+
+    /// The minimal generators of the ideal of leading monomials.
+    pub fn leading_monomials(&self) -> Vec<Monomial> {
+        // the leading terms of a reduced Grobner basis already minimally generate the initial ideal
+        self.basis
+            .iter()
+            .filter_map(|g| g.lead_monomial().cloned())
+            .collect()
+    }
+
+    /// The same ideal, with a Grobner basis for a different monomial order.
+    pub fn reorder<P: MonomialOrder>(&self) -> Ideal<F, P> {
+        Ideal::new(self.nvars, self.basis.iter().map(|g| g.reorder()).collect())
+    }
+
+    /// The sum $I + J$.
+    pub fn sum(&self, other: &Self) -> Self {
+        assert_eq!(self.nvars, other.nvars, "ideals are in different rings");
+        Self::new(
+            self.nvars,
+            [self.basis.clone(), other.basis.clone()].concat(),
+        )
+    }
+
+    /// The product $IJ$.
+    pub fn product(&self, other: &Self) -> Self {
+        assert_eq!(self.nvars, other.nvars, "ideals are in different rings");
+        let gens = self
+            .basis
+            .iter()
+            .flat_map(|f| other.basis.iter().map(move |g| f * g))
+            .collect();
+        Self::new(self.nvars, gens)
+    }
+
+    /// The intersection $I \cap J$, computed as $(tI + (1 - t)J) \cap R$ for a new variable $t$.
+    pub fn intersect(&self, other: &Self) -> Self {
+        assert_eq!(self.nvars, other.nvars, "ideals are in different rings");
+        let t = MPolynomial::<F, ElimFirst>::var(0);
+        let shift = |f: &MPolynomial<F, O>| f.reorder::<ElimFirst>().map_vars(|i| i + 1);
+        let gens = self
+            .basis
+            .iter()
+            .map(|f| &t * &shift(f))
+            .chain(
+                other
+                    .basis
+                    .iter()
+                    .map(|g| &(MPolynomial::one() - t.clone()) * &shift(g)),
+            )
+            .collect();
+        Self::eliminate_first(self.nvars, gens)
+    }
+
+    /// The ideal quotient $I : f = \{g : gf \in I\}$, which is $(I \cap (f))/f$.
+    pub fn quotient(&self, f: &MPolynomial<F, O>) -> Self {
+        if f.is_zero() {
+            return Self::new(self.nvars, vec![MPolynomial::one()]);
+        }
+        let gens = self
+            .intersect(&Self::new(self.nvars, vec![f.clone()]))
+            .basis
+            .iter()
+            .map(|g| g.div_rem(f).0)
+            .collect();
+        Self::new(self.nvars, gens)
+    }
+
+    /// The ideal quotient $I : J = \{g : gJ \subseteq I\}$, the intersection of $I : f$ over generators $f$ of $J$.
+    pub fn quotient_ideal(&self, other: &Self) -> Self {
+        other
+            .basis
+            .iter()
+            .fold(Self::new(self.nvars, vec![MPolynomial::one()]), |acc, f| {
+                acc.intersect(&self.quotient(f))
+            })
+    }
+
+    /// The saturation $I : f^\infty = \bigcup\_k I : f^k$, computed as $(I + (1 - tf)) \cap R$ for a new
+    /// variable $t$.
+    pub fn saturation(&self, f: &MPolynomial<F, O>) -> Self {
+        let shift = |f: &MPolynomial<F, O>| f.reorder::<ElimFirst>().map_vars(|i| i + 1);
+        let mut gens: Vec<MPolynomial<F, ElimFirst>> = self.basis.iter().map(shift).collect();
+        gens.push(MPolynomial::one() - &MPolynomial::var(0) * &shift(f));
+        Self::eliminate_first(self.nvars, gens)
+    }
+
+    /// The elimination ideal $I \cap F[x\_j : j \notin$ `vars`$]$, as an ideal of the same ring. The
+    /// variables are eliminated one at a time.
+    pub fn eliminate(&self, vars: &[usize]) -> Self {
+        vars.iter().fold(self.clone(), |acc, &v| {
+            let gens = acc
+                .basis
+                .iter()
+                .map(|f| {
+                    f.reorder::<ElimFirst>()
+                        .map_vars(|i| if i == v { 0 } else { i + 1 })
+                })
+                .collect();
+            Self::eliminate_first(acc.nvars, gens)
+        })
+    }
+
+    /// Given generators in $F[x\_0, \ldots, x\_n]$ where $x\_0$ is a new variable and $x\_{i+1}$ stands for
+    /// $x\_i$, returns the intersection with $F[x\_1, \ldots, x\_n]$ with the variables shifted back.
+    fn eliminate_first(nvars: usize, gens: Vec<MPolynomial<F, ElimFirst>>) -> Self {
+        let gens = groebner_basis(&gens)
+            .into_iter()
+            .filter(|g| g.deg_in(0) == 0)
+            .map(|g| g.map_vars(|i| i - 1).reorder())
+            .collect();
+        Self::new(nvars, gens)
+    }
+}
+
+/// Ideals are equal if their reduced Grobner bases are.
+impl<F: Field, O: MonomialOrder> PartialEq for Ideal<F, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nvars == other.nvars && self.basis == other.basis
+    }
+}
+
+impl<F: Field, O: MonomialOrder> Eq for Ideal<F, O> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mpoly::Lex;
+    use crate::test_helpers::poly;
+    use crate::Rational;
+
+    type P = MPolynomial<Rational>;
+
+    #[test]
+    fn test_eq() {
+        // (x + y, y) = (x, y)
+        let a = Ideal::new(2, vec![P::var(0) + P::var(1), P::var(1)]);
+        let b = Ideal::maximal(2);
+        assert_eq!(a, b);
+        assert!(a.contains_ideal(&b) && b.contains_ideal(&a));
+        assert!(!a.is_unit());
+        assert!(Ideal::new(2, vec![P::var(0), P::var(0) + P::one()]).is_unit());
+        assert!(Ideal::<Rational>::new(3, vec![]).is_zero());
+    }
+
+    #[test]
+    fn test_homogeneous() {
+        // (x^2 + y, y) is homogeneous, since it is (x^2, y)
+        let a = Ideal::new(
+            2,
+            vec![poly(vec![(vec![2], 1), (vec![0, 1], 1)]), P::var(1)],
+        );
+        assert!(a.is_homogeneous());
+        let b = Ideal::new(2, vec![poly(vec![(vec![2], 1), (vec![0, 1], 1)])]);
+        assert!(!b.is_homogeneous());
+        assert!(b.reorder::<Lex>().reorder::<GRevLex>() == b);
+    }
+
+    #[test]
+    fn test_operations() {
+        let (x, y) = (P::var(0), P::var(1));
+        let a = Ideal::new(2, vec![x.clone()]);
+        let b = Ideal::new(2, vec![y.clone()]);
+        // (x) ∩ (y) = (x) (y) = (xy)
+        let xy = Ideal::new(2, vec![x.clone() * y.clone()]);
+        assert_eq!(a.intersect(&b), xy);
+        assert_eq!(a.product(&b), xy);
+        assert_eq!(a.sum(&b), Ideal::maximal(2));
+        // (x^2, xy) : x = (x, y), and its saturation by x is everything
+        let c = Ideal::new(2, vec![x.clone() * x.clone(), x.clone() * y.clone()]);
+        assert_eq!(c.quotient(&x), Ideal::maximal(2));
+        assert!(c.saturation(&x).is_unit());
+        assert_eq!(c.saturation(&y), a);
+        assert_eq!(c.quotient_ideal(&Ideal::maximal(2)), a);
+        // eliminating t from (x - t^2, y - t^3) gives the cusp y^2 - x^3
+        let t = P::var(2);
+        let d = Ideal::new(
+            3,
+            vec![
+                x.clone() - t.clone() * t.clone(),
+                y.clone() - t.clone() * t.clone() * t.clone(),
+            ],
+        );
+        assert_eq!(
+            d.eliminate(&[2]),
+            Ideal::new(
+                3,
+                vec![y.clone() * y.clone() - x.clone() * x.clone() * x.clone()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_leading_monomials() {
+        let a = Ideal::new(
+            2,
+            vec![poly(vec![(vec![2], 1), (vec![0, 1], 1)]), P::var(1)],
+        );
+        assert_eq!(
+            a.leading_monomials(),
+            vec![Monomial::from(vec![2]), Monomial::from(vec![0, 1])]
+        );
+    }
+}
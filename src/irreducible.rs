@@ -0,0 +1,302 @@
+//! Irreducible and primitive polynomials over $\mathbb{F}\_p$
+//!
+//! A monic irreducible $f$ of degree $n$ gives the field $\mathrm{GF}(p^n) = \mathbb{F}\_p[x]/(f)$, and $f$ is
+//! primitive if the class of $x$ also generates the multiplicative group. There are two ways to pick one here:
+//! the first one in lexicographic order, or a random one. Both only take a few tries on average, since about
+//! $1/n$ of the monic polynomials of degree $n$ are irreducible.
+//!
+//! The lexicographic order compares the coefficients from $x^{n-1}$ down to $x^0$, so for example $x^4 + x + 1$ is
+//! the first irreducible of degree 4 over $\mathbb{F}\_2$.
+//!
+//! Conway polynomials are a standard choice of primitive polynomials, made so that the fields they give are
+//! compatible with each other: if $d \mid n$ then $\alpha\_n^{(p^n - 1)/(p^d - 1)}$ is a root of $C\_{p,d}$. They're
+//! expensive to compute, so `conway` only looks them up in a table for small $p^n$ (from Frank Lübeck's tables).
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::irreducible::{conway, first_irreducible, first_primitive, is_primitive};
+//! use bored_algebra::galois::is_irreducible;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::zmod::Zmod;
+//!
+//! type F2 = Zmod<2>;
+//! let f = first_irreducible::<2>(4);
+//! assert_eq!(f, Polynomial::from(vec![1, 1, 0, 0, 1].into_iter().map(F2::new).collect::<Vec<_>>()));
+//! assert!(is_primitive(&f));
+//!
+//! // x^2 + 1 is irreducible mod 3, but x has order 4 in GF(9)
+//! let g = first_irreducible::<3>(2);
+//! assert!(is_irreducible(&g) && !is_primitive(&g));
+//! assert!(is_primitive(&first_primitive::<3>(2)));
+//! assert_eq!(conway::<2>(4), Some(f));
+//! ```
+use crate::galois::{is_irreducible, GaloisField};
+use crate::ntheory::arith::{divisors, euler_phi, mobius};
+use crate::poly::Polynomial;
+use crate::zmod::Zmod;
+use crate::Integer;
+
+use num_traits::identities::One;
+use rug::ops::Pow;
+use rug::rand::RandState;
+
+/// Conway polynomials $C\_{p,n}$, as the coefficients $c\_0, \ldots, c\_{n-1}$ (the leading 1 is left out).
+const CONWAY: &[(u64, &[u64])] = &[
+    (2, &[1]),
+    (2, &[1, 1]),
+    (2, &[1, 1, 0]),
+    (2, &[1, 1, 0, 0]),
+    (2, &[1, 0, 1, 0, 0]),
+    (2, &[1, 1, 0, 1, 1, 0]),
+    (2, &[1, 1, 0, 0, 0, 0, 0]),
+    (2, &[1, 0, 1, 1, 1, 0, 0, 0]),
+    (2, &[1, 0, 0, 0, 1, 0, 0, 0, 0]),
+    (2, &[1, 1, 1, 1, 0, 1, 1, 0, 0, 0]),
+    (3, &[1]),
+    (3, &[2, 2]),
+    (3, &[1, 2, 0]),
+    (3, &[2, 0, 0, 2]),
+    (3, &[1, 2, 0, 0, 0]),
+    (3, &[2, 2, 1, 0, 2, 0]),
+    (3, &[1, 0, 2, 0, 0, 0, 0]),
+    (3, &[2, 2, 2, 0, 1, 2, 0, 0]),
+    (5, &[3]),
+    (5, &[2, 4]),
+    (5, &[3, 3, 0]),
+    (5, &[2, 4, 4, 0]),
+    (5, &[3, 4, 0, 0, 0]),
+    (5, &[2, 0, 1, 4, 1, 0]),
+    (7, &[4]),
+    (7, &[3, 6]),
+    (7, &[4, 0, 6]),
+    (7, &[3, 4, 5, 0]),
+    (7, &[4, 1, 0, 0, 0]),
+    (7, &[3, 6, 4, 5, 1, 0]),
+    (11, &[9]),
+    (11, &[2, 7]),
+    (11, &[9, 2, 0]),
+    (11, &[2, 10, 8, 0]),
+    (13, &[11]),
+    (13, &[2, 12]),
+    (13, &[11, 2, 0]),
+    (13, &[2, 12, 3, 0]),
+];
+
+/// The monic polynomial of degree $n$ with coefficients $c\_0, \ldots, c\_{n-1}$.
+fn monic<const P: u64>(coeffs: impl IntoIterator<Item = u64>) -> Polynomial<Zmod<P>> {
+    let mut coeffs: Vec<Zmod<P>> = coeffs.into_iter().map(Zmod::new).collect();
+    coeffs.push(Zmod::one());
+    Polynomial::from(coeffs)
+}
+
+/// The `index`-th monic polynomial of degree $n$ in lexicographic order: $c\_k$ is the $k$-th base $p$ digit.
+fn nth_monic<const P: u64>(n: u64, index: u64) -> Polynomial<Zmod<P>> {
+    let mut i = index;
+    monic((0..n).map(|_| {
+        let digit = i % P;
+        i /= P;
+        digit
+    }))
+}
+
+fn random_monic<const P: u64>(n: u64, rng: &mut RandState) -> Polynomial<Zmod<P>> {
+    let p = Integer::from(P);
+    monic((0..n).map(|_| Integer::from(p.random_below_ref(rng)).to_u64().unwrap()))
+}
+
+/// Returns true if $f$ is irreducible and $x$ generates the multiplicative group of $\mathbb{F}\_p[x]/(f)$. This
+/// needs the factorization of $p^n - 1$.
+pub fn is_primitive<const P: u64>(f: &Polynomial<Zmod<P>>) -> bool {
+    f.deg() > 0
+        && f.lead_coeff().is_one()
+        && is_irreducible(f)
+        && GaloisField::generator(f).is_primitive()
+}
+
+/// The first monic irreducible polynomial of degree $n > 0$ over $\mathbb{F}\_p$.
+pub fn first_irreducible<const P: u64>(n: u64) -> Polynomial<Zmod<P>> {
+    assert!(n > 0, "there are no irreducible polynomials of degree 0");
+    (0..).map(|i| nth_monic(n, i)).find(is_irreducible).unwrap()
+}
+
+/// The first primitive polynomial of degree $n > 0$ over $\mathbb{F}\_p$.
+pub fn first_primitive<const P: u64>(n: u64) -> Polynomial<Zmod<P>> {
+    assert!(n > 0, "there are no primitive polynomials of degree 0");
+    (0..).map(|i| nth_monic(n, i)).find(is_primitive).unwrap()
+}
+
+/// A uniformly random monic irreducible polynomial of degree $n > 0$ over $\mathbb{F}\_p$.
+pub fn random_irreducible<const P: u64>(n: u64, rng: &mut RandState) -> Polynomial<Zmod<P>> {
+    assert!(n > 0, "there are no irreducible polynomials of degree 0");
+    loop {
+        let f = random_monic(n, rng);
+        if is_irreducible(&f) {
+            return f;
+        }
+    }
+}
+
+/// A uniformly random primitive polynomial of degree $n > 0$ over $\mathbb{F}\_p$.
+pub fn random_primitive<const P: u64>(n: u64, rng: &mut RandState) -> Polynomial<Zmod<P>> {
+    assert!(n > 0, "there are no primitive polynomials of degree 0");
+    loop {
+        let f = random_monic(n, rng);
+        if is_primitive(&f) {
+            return f;
+        }
+    }
+}
+
+/// The number of monic irreducible polynomials of degree $n > 0$ over $\mathbb{F}\_q$, by the necklace formula
+/// $\frac{1}{n} \sum\_{d \mid n} \mu(d) q^{n/d}$.
+pub fn count_irreducible(q: &Integer, n: u64) -> Integer {
+    assert!(n > 0, "there are no irreducible polynomials of degree 0");
+    let sum = divisors(&Integer::from(n))
+        .into_iter()
+        .fold(Integer::new(), |acc, d| {
+            let e = (n / d.to_u64().unwrap()) as u32;
+            acc + mobius(&d) * q.clone().pow(e)
+        });
+    sum / n
+}
+
+/// The number of primitive polynomials of degree $n > 0$ over $\mathbb{F}\_q$, $\varphi(q^n - 1)/n$: each of the
+/// $\varphi(q^n - 1)$ generators of $\mathrm{GF}(q^n)^\times$ has a minimal polynomial of degree $n$ with $n$ roots.
+pub fn count_primitive(q: &Integer, n: u64) -> Integer {
+    assert!(n > 0, "there are no primitive polynomials of degree 0");
+    euler_phi(&(q.clone().pow(n as u32) - 1)) / n
+}
+
+/// The Conway polynomial $C\_{p,n}$, if it's in the table. The table has $p = 2$ up to $n = 10$, $p = 3$ up to 8,
+/// $p = 5, 7$ up to 6 and $p = 11, 13$ up to 4.
+pub fn conway<const P: u64>(n: u64) -> Option<Polynomial<Zmod<P>>> {
+    CONWAY
+        .iter()
+        .filter(|(p, _)| *p == P)
+        .find(|(_, coeffs)| coeffs.len() as u64 == n)
+        .map(|(_, coeffs)| monic(coeffs.iter().copied()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_traits::identities::Zero;
+
+    fn poly<const P: u64>(coeffs: Vec<u64>) -> Polynomial<Zmod<P>> {
+        Polynomial::from(coeffs.into_iter().map(Zmod::new).collect::<Vec<Zmod<P>>>())
+    }
+
+    /// $f(a)$, by Horner's rule.
+    fn eval<const P: u64>(f: &Polynomial<Zmod<P>>, a: &GaloisField<P>) -> GaloisField<P> {
+        f.coeffs_take(f.deg() as usize + 1)
+            .into_iter()
+            .rev()
+            .fold(GaloisField::zero(), |acc, c| {
+                acc * a.clone() + GaloisField::constant(c)
+            })
+    }
+
+    /// Checks that $C\_{p,n}$ is primitive and compatible with $C\_{p,d}$ for the proper divisors $d$ of $n$.
+    fn conway_compatible<const P: u64>(f: &Polynomial<Zmod<P>>) -> bool {
+        let n = f.deg();
+        let alpha = GaloisField::generator(f);
+        let q = Integer::from(P).pow(n as u32) - Integer::from(1);
+        is_primitive(f)
+            && (1..n).filter(|d| n.is_multiple_of(*d)).all(|d| {
+                let e = q.clone() / (Integer::from(P).pow(d as u32) - 1);
+                eval(&conway::<P>(d).unwrap(), &alpha.pow(&e)).is_zero()
+            })
+    }
+
+    /// Searches for $C\_{p,n}$ directly: it's the first compatible polynomial when
+    /// $x^n + \sum (-1)^{n-k} a\_k x^k$ is ordered by $(a\_{n-1}, \ldots, a\_0)$.
+    fn conway_search<const P: u64>(n: u64) -> Polynomial<Zmod<P>> {
+        (0..)
+            .map(|i| {
+                let a = nth_monic::<P>(n, i).coeffs_take(n as usize);
+                monic((0..n as usize).map(|k| match (n as usize - k) % 2 {
+                    0 => a[k].value(),
+                    _ => (-a[k]).value(),
+                }))
+            })
+            .find(conway_compatible)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(first_irreducible::<2>(4), poly(vec![1, 1, 0, 0, 1]));
+        assert_eq!(first_irreducible::<3>(2), poly(vec![1, 0, 1]));
+        assert_eq!(first_primitive::<3>(2), poly(vec![2, 1, 1]));
+        // x^8 + x^4 + x^3 + x + 1 (the AES polynomial) is irreducible but not primitive
+        let aes = poly::<2>(vec![1, 1, 0, 1, 1, 0, 0, 0, 1]);
+        assert!(is_irreducible(&aes) && !is_primitive(&aes));
+        assert_eq!(
+            first_primitive::<2>(8),
+            poly(vec![1, 0, 1, 1, 1, 0, 0, 0, 1])
+        );
+        let mut rng = RandState::new();
+        for n in 1..6 {
+            let f = random_irreducible::<5>(n, &mut rng);
+            assert!(f.deg() == n && is_irreducible(&f));
+            let f = random_primitive::<5>(n, &mut rng);
+            assert!(f.deg() == n && is_primitive(&f));
+        }
+        assert!(!is_primitive(&poly::<7>(vec![1, 0, 1])));
+        assert!(!is_primitive(&poly::<7>(vec![0, 1])));
+    }
+
+    #[test]
+    fn test_count() {
+        // by brute force, over F_2 and F_3
+        for n in 1..=8 {
+            let irreducible = (0..1 << n)
+                .filter(|&i| is_irreducible(&nth_monic::<2>(n, i)))
+                .count();
+            assert_eq!(count_irreducible(&Integer::from(2), n), irreducible);
+            let primitive = (0..1 << n)
+                .filter(|&i| is_primitive(&nth_monic::<2>(n, i)))
+                .count();
+            assert_eq!(count_primitive(&Integer::from(2), n), primitive);
+        }
+        for n in 1..=4 {
+            let irreducible = (0..3_u64.pow(n as u32))
+                .filter(|&i| is_irreducible(&nth_monic::<3>(n, i)))
+                .count();
+            assert_eq!(count_irreducible(&Integer::from(3), n), irreducible);
+        }
+        // over F_4
+        assert_eq!(count_irreducible(&Integer::from(4), 2), 6);
+    }
+
+    #[test]
+    fn test_conway() {
+        for n in 1..=10 {
+            assert!(conway_compatible(&conway::<2>(n).unwrap()));
+        }
+        for n in 1..=8 {
+            assert!(conway_compatible(&conway::<3>(n).unwrap()));
+        }
+        for n in 1..=6 {
+            assert!(conway_compatible(&conway::<5>(n).unwrap()));
+            assert!(conway_compatible(&conway::<7>(n).unwrap()));
+        }
+        for n in 1..=4 {
+            assert!(conway_compatible(&conway::<11>(n).unwrap()));
+            assert!(conway_compatible(&conway::<13>(n).unwrap()));
+        }
+        assert_eq!(conway::<2>(11), None);
+        assert_eq!(conway::<17>(1), None);
+        // and they're the first compatible ones, for the small fields
+        for n in 1..=6 {
+            assert_eq!(conway::<2>(n).unwrap(), conway_search::<2>(n));
+        }
+        for n in 1..=4 {
+            assert_eq!(conway::<3>(n).unwrap(), conway_search::<3>(n));
+        }
+        for n in 1..=3 {
+            assert_eq!(conway::<5>(n).unwrap(), conway_search::<5>(n));
+            assert_eq!(conway::<7>(n).unwrap(), conway_search::<7>(n));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Linear feedback shift registers
+//!
+//! An LFSR over $\mathbb{F}\_p$ with characteristic polynomial $f = x^n + f\_{n-1} x^{n-1} + \cdots + f\_0$ produces the
+//! linear recurring sequence
+//! $$
+//! s\_{k+n} = -(f\_{n-1} s\_{k+n-1} + \cdots + f\_0 s\_k)
+//! $$
+//! from the initial state $(s\_0, \ldots, s\_{n-1})$. If $f$ is primitive, every nonzero state gives a sequence of
+//! the maximal period $p^n - 1$ (an m-sequence), see `irreducible::first_primitive`.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::lfsr::Lfsr;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::zmod::Zmod;
+//!
+//! type F2 = Zmod<2>;
+//! let (zero, one) = (F2::new(0), F2::new(1));
+//! // s_{k+4} = s_{k+1} + s_k
+//! let f = Polynomial::from(vec![one, one, zero, zero, one]);
+//! let lfsr = Lfsr::new(&f, vec![one, zero, zero, zero]);
+//! assert_eq!(lfsr.period(), 15);
+//! let bits: Vec<u64> = lfsr.take(15).map(|s| s.value()).collect();
+//! assert_eq!(bits, vec![1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1]);
+//! ```
+use crate::galois::{is_irreducible, GaloisField};
+use crate::poly::Polynomial;
+use crate::zmod::Zmod;
+use crate::Integer;
+
+use num_traits::identities::{One, Zero};
+
+/// A linear feedback shift register over $\mathbb{F}\_p$. It's an `Iterator` over the sequence $s\_0, s\_1, \ldots$
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lfsr<const P: u64> {
+    feedback: Vec<Zmod<P>>, // -f_0, ..., -f_{n-1}
+    state: Vec<Zmod<P>>,    // s_k, ..., s_{k+n-1}
+}
+
+impl<const P: u64> Lfsr<P> {
+    /// The register with characteristic polynomial $f$ and initial state $(s\_0, \ldots, s\_{n-1})$. Panics unless
+    /// $f$ is monic of degree $n > 0$.
+    pub fn new(characteristic: &Polynomial<Zmod<P>>, state: Vec<Zmod<P>>) -> Self {
+        let n = characteristic.deg() as usize;
+        assert!(
+            n > 0 && characteristic.lead_coeff().is_one(),
+            "the characteristic polynomial needs to be monic of positive degree"
+        );
+        assert_eq!(state.len(), n, "the state needs one element per stage");
+        let feedback = characteristic
+            .coeffs_take(n)
+            .into_iter()
+            .map(|c| -c)
+            .collect();
+        Self { feedback, state }
+    }
+
+    /// The number of stages $n$.
+    pub fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Always false, since an LFSR has at least one stage.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_empty()
+    }
+
+    pub fn characteristic(&self) -> Polynomial<Zmod<P>> {
+        let mut coeffs: Vec<Zmod<P>> = self.feedback.iter().map(|c| -*c).collect();
+        coeffs.push(Zmod::one());
+        Polynomial::from(coeffs)
+    }
+
+    /// The next $n$ terms of the sequence.
+    pub fn state(&self) -> &[Zmod<P>] {
+        &self.state
+    }
+
+    /// Moves the register one step along.
+    pub fn step(&mut self) {
+        let next = self
+            .feedback
+            .iter()
+            .zip(self.state.iter())
+            .fold(Zmod::zero(), |acc, (c, s)| acc + *c * *s);
+        self.state.remove(0);
+        self.state.push(next);
+    }
+
+    /// The period of the sequence: the smallest $k > 0$ with $s\_{i+k} = s\_i$ for all large enough $i$. (The
+    /// sequence is only periodic from the start if $f\_0 \neq 0$.)
+    ///
+    /// For irreducible $f$ with $f\_0 \neq 0$ and a nonzero state, this is the order of $x$ mod $f$. Otherwise it
+    /// steps the register with Brent's cycle finding, which takes time proportional to the period.
+    pub fn period(&self) -> Integer {
+        let f = self.characteristic();
+        if self.state.iter().all(|s| s.is_zero()) {
+            return Integer::from(1);
+        }
+        if !self.feedback[0].is_zero() && is_irreducible(&f) {
+            return GaloisField::generator(&f).multiplicative_order();
+        }
+        let (mut power, mut period) = (1_u64, 1_u64);
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        hare.step();
+        while tortoise.state != hare.state {
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare.step();
+            period += 1;
+        }
+        Integer::from(period)
+    }
+}
+
+impl<const P: u64> Iterator for Lfsr<P> {
+    type Item = Zmod<P>;
+
+    fn next(&mut self) -> Option<Zmod<P>> {
+        let output = self.state[0];
+        self.step();
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::irreducible::first_primitive;
+
+    fn poly<const P: u64>(coeffs: Vec<u64>) -> Polynomial<Zmod<P>> {
+        Polynomial::from(coeffs.into_iter().map(Zmod::new).collect::<Vec<Zmod<P>>>())
+    }
+
+    fn state<const P: u64>(s: Vec<u64>) -> Vec<Zmod<P>> {
+        s.into_iter().map(Zmod::new).collect()
+    }
+
+    /// The period by brute force, for sequences that are periodic from the start.
+    fn brute_period<const P: u64>(lfsr: &Lfsr<P>) -> Integer {
+        let mut other = lfsr.clone();
+        let mut k = 0;
+        loop {
+            other.step();
+            k += 1;
+            if other.state() == lfsr.state() {
+                return Integer::from(k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sequence() {
+        // Fibonacci numbers mod 7, with f = x^2 - x - 1
+        let f = poly::<7>(vec![6, 6, 1]);
+        let fib: Vec<u64> = Lfsr::new(&f, state(vec![0, 1]))
+            .take(10)
+            .map(|s| s.value())
+            .collect();
+        assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 1, 6, 0, 6]);
+        let lfsr = Lfsr::new(&f, state(vec![0, 1]));
+        // the Pisano period of 7 is 16
+        assert_eq!(lfsr.period(), 16);
+        assert_eq!(lfsr.characteristic(), f);
+        assert_eq!(lfsr.len(), 2);
+    }
+
+    #[test]
+    fn test_period() {
+        // primitive polynomials give m-sequences
+        let f = first_primitive::<3>(4);
+        let lfsr = Lfsr::new(&f, state(vec![1, 0, 2, 0]));
+        assert_eq!(lfsr.period(), 80);
+        assert_eq!(brute_period(&lfsr), 80);
+        // x^4 + x^3 + x^2 + x + 1 divides x^5 - 1
+        let f = poly::<2>(vec![1, 1, 1, 1, 1]);
+        assert_eq!(Lfsr::new(&f, state(vec![1, 0, 0, 0])).period(), 5);
+        // (x^2 + x + 1)(x^3 + x + 1) isn't irreducible, the periods divide lcm(3, 7)
+        let f = poly::<2>(vec![1, 0, 0, 0, 1, 1]);
+        for s in [
+            vec![0, 1, 1, 0, 1],
+            vec![1, 1, 1, 0, 1],
+            vec![1, 0, 0, 0, 0],
+        ] {
+            let lfsr = Lfsr::new(&f, state(s));
+            assert_eq!(lfsr.period(), brute_period(&lfsr));
+        }
+        assert_eq!(Lfsr::new(&f, state(vec![1, 0, 0, 0, 0])).period(), 21);
+        // with f_0 = 0 the sequence is only eventually periodic: s_{k+2} = s_{k+1}
+        let f = poly::<5>(vec![0, 4, 1]);
+        let lfsr = Lfsr::new(&f, state(vec![3, 2]));
+        assert_eq!(lfsr.period(), 1);
+        assert_eq!(Lfsr::new(&f, state(vec![0, 0])).period(), 1);
+    }
+}
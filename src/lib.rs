@@ -1,10 +1,39 @@
-pub use rug::{Integer, Assign};
+pub use rug::{Integer, Rational, Assign};
+pub mod algebraic;
+pub mod crt;
+pub mod cyclotomic;
+pub mod dedekind;
 pub mod error;
+pub mod factor;
+pub mod frac;
+pub mod free;
+pub mod galois;
+pub mod gcd;
+pub mod groebner;
+pub mod hensel;
 pub mod helpers;
+pub mod hilbert;
+pub mod ideal;
+pub mod irreducible;
+pub mod lfsr;
 pub mod limits;
 pub mod local;
+pub mod matrix;
+pub mod mfactor;
 pub mod module;
+pub mod mpoly;
+pub mod newton;
+pub mod ntheory;
+pub mod number_field;
 pub mod poly;
+pub mod primary;
 pub mod products;
+pub mod quadratic;
+pub mod quadratic_form;
 pub mod quotient;
+pub mod resolution;
+pub mod structure;
 pub mod sub;
+pub mod zmod;
+#[cfg(test)]
+mod test_helpers;
@@ -1 +1,628 @@
 //! Inverse limits
+//!
+//! The $p$-adic integers are the inverse limit $\mathbb{Z}\_p = \varprojlim \mathbb{Z}/p^n$ of the rings
+//! $\mathbb{Z}/p^n$ along the reductions $\mathbb{Z}/p^{n+1} \to \mathbb{Z}/p^n$, and $\mathbb{Q}\_p$ is its field of
+//! fractions. An element can only be known to finitely many digits, so `PAdic<P, N>` is $p^v u$ for a unit $u$
+//! known modulo $p^r$, written $p^v u + O(p^{v+r})$: $r$ is the relative precision and $v + r$ the absolute
+//! precision. This is the capped relative model: exact inputs (integers, rationals) get $r = N$, and each result
+//! is as precise as its inputs allow, up to $N$. Subtracting close numbers loses precision, multiplying doesn't.
+//!
+//! A zero is $O(p^k)$ for its absolute precision $k$, except for `zero()` itself, which is exact. Elements are
+//! equal if they have the same digits to the same precision, and all zeros are equal. To check that two elements
+//! agree as far as both are known, test whether their difference `is_zero`.
+//!
+//! $\log$ and $\exp$ are the power series, on the discs where they converge: $\log(1 + z)$ for $v(z) \geq 1$, and
+//! $\exp(z)$ for $v(z) > 1/(p - 1)$.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::limits::inverse::PAdic;
+//! use bored_algebra::{Integer, Rational};
+//!
+//! type Q5 = PAdic<5>;
+//! let a = Q5::from(Integer::from(-1));
+//! // sqrt(-1) exists in Q_5, since -1 is a square mod 5
+//! let i = a.sqrt().unwrap();
+//! assert_eq!(i.clone() * i, a);
+//! let third = Q5::from(Rational::from((1, 3)));
+//! assert_eq!(third.clone() * Q5::from(Integer::from(3)), Q5::from(Integer::from(1)));
+//! let x = Q5::from(Rational::from((2, 25)));
+//! assert_eq!(x.valuation(), Some(-2));
+//! assert_eq!(x.to_string(), "2/25 + O(5^18)");
+//! // 1/(1 - 5) = 1 + 5 + 5^2 + ...
+//! assert_eq!(Q5::from(Integer::from(-4)).inverse().unwrap().residue(3).value(), &31);
+//! ```
+use crate::error::ArithmeticError;
+use crate::module::ModType;
+use crate::ntheory::residue::sqrt_mod_prime;
+use crate::structure::{CommRing, EuclideanDomain, Field, GcdDomain, IntegralDomain, Monoid};
+use crate::zmod::{is_prime_word, ZmodDyn};
+use crate::{Integer, Rational};
+use rug::ops::{Pow, RemRounding};
+
+use core::fmt::{self, Display};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+
+/// An element $p^v u + O(p^{v+r})$ of $\mathbb{Q}\_p$ with relative precision $r \leq N$. See the module docs.
+#[derive(Clone, Debug)]
+pub struct PAdic<const P: u64, const N: u32 = 20> {
+    unit: Integer,  // in [0, p^precision), coprime to p, or 0 for zero
+    valuation: i64, // the absolute precision for zero, i64::MAX for exact zero
+    precision: u32, // 0 for zero
+}
+
+impl<const P: u64, const N: u32> PAdic<P, N> {
+    const ASSERT_PRIME: () = assert!(is_prime_word(P), "p-adic numbers need a prime p");
+    const EXACT: i64 = i64::MAX;
+
+    fn p() -> Integer {
+        Integer::from(P)
+    }
+
+    fn modulus(k: u32) -> Integer {
+        Self::p().pow(k)
+    }
+
+    /// $O(p^k)$.
+    fn zero_to(absolute: i64) -> Self {
+        Self {
+            unit: Integer::new(),
+            valuation: absolute,
+            precision: 0,
+        }
+    }
+
+    /// $p^v a$, for $a$ known modulo $p^{k - v}$, so to absolute precision $k$.
+    fn normalized(a: Integer, v: i64, absolute: i64) -> Self {
+        let () = Self::ASSERT_PRIME;
+        if absolute <= v {
+            return Self::zero_to(absolute);
+        }
+        let relative = (absolute - v).min(u32::MAX as i64) as u32;
+        let a = a.rem_euc(Self::modulus(relative.min(N + 64)));
+        if a.is_zero() {
+            return Self::zero_to(absolute);
+        }
+        let (unit, k) = a.remove_factor(&Self::p());
+        if k >= relative {
+            return Self::zero_to(absolute);
+        }
+        let precision = (relative - k).min(N);
+        Self {
+            unit: unit.rem_euc(Self::modulus(precision)),
+            valuation: v + k as i64,
+            precision,
+        }
+    }
+
+    /// $a + O(p^{v(a) + r})$: the rational $a$ with relative precision $r$, which is capped at $N$.
+    pub fn with_precision(a: &Rational, precision: u32) -> Self {
+        if a.is_zero() {
+            return Self::zero();
+        }
+        let (num, v_num) = a.numer().clone().remove_factor(&Self::p());
+        let (den, v_den) = a.denom().clone().remove_factor(&Self::p());
+        let precision = precision.min(N);
+        let modulus = Self::modulus(precision);
+        let unit = num * den.invert(&modulus).unwrap();
+        Self::normalized(
+            unit,
+            v_num as i64 - v_den as i64,
+            v_num as i64 - v_den as i64 + precision as i64,
+        )
+    }
+
+    /// The valuation $v$, or `None` for zero.
+    pub fn valuation(&self) -> Option<i64> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.valuation),
+        }
+    }
+
+    /// The unit part $u$, in $[0, p^r)$.
+    pub fn unit(&self) -> &Integer {
+        &self.unit
+    }
+
+    /// The relative precision $r$, which is 0 for zero.
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// The absolute precision $v + r$, or `None` for the exact zero.
+    pub fn absolute_precision(&self) -> Option<i64> {
+        match self.valuation == Self::EXACT {
+            true => None,
+            false => Some(self.valuation + self.precision as i64),
+        }
+    }
+
+    /// The rational $p^v u$.
+    pub fn lift(&self) -> Rational {
+        match self.valuation {
+            _ if self.is_zero() => Rational::new(),
+            v if v >= 0 => Rational::from(Self::modulus(v as u32) * &self.unit),
+            v => Rational::from((self.unit.clone(), Self::modulus(-v as u32))),
+        }
+    }
+
+    /// The image in $\mathbb{Z}/p^k$, which is the projection out of the inverse limit. Panics if `self` isn't in
+    /// $\mathbb{Z}\_p$ or isn't known to absolute precision $k$.
+    pub fn residue(&self, k: u32) -> ZmodDyn {
+        let modulus = Self::modulus(k);
+        if self.is_zero() || self.valuation >= k as i64 {
+            assert!(
+                self.absolute_precision().is_none_or(|a| a >= k as i64),
+                "not known mod p^{}",
+                k
+            );
+            return ZmodDyn::new(Integer::new(), &modulus);
+        }
+        assert!(self.valuation >= 0, "not a p-adic integer");
+        assert!(
+            self.valuation + self.precision as i64 >= k as i64,
+            "not known mod p^{}",
+            k
+        );
+        ZmodDyn::new(self.lift().numer().clone(), &modulus)
+    }
+
+    /// $1/x$, or `DivideByZero` for zero.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        match self.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => Ok(Self {
+                unit: self
+                    .unit
+                    .clone()
+                    .invert(&Self::modulus(self.precision))
+                    .unwrap(),
+                valuation: -self.valuation,
+                precision: self.precision,
+            }),
+        }
+    }
+
+    /// $x^k$, for any integer $k$. Panics for negative $k$ if $x$ is zero.
+    pub fn pow(&self, k: i64) -> Self {
+        let base = match k < 0 {
+            true => self.inverse().expect("negative power of zero"),
+            false => self.clone(),
+        };
+        let mut result = Self::one();
+        for i in (0..64 - k.unsigned_abs().leading_zeros()).rev() {
+            result = result.clone() * result;
+            if (k.unsigned_abs() >> i) & 1 == 1 {
+                result = result * base.clone();
+            }
+        }
+        result
+    }
+
+    /// A square root, if there is one. For odd $p$, $p^v u$ is a square when $v$ is even and $u$ is a square mod
+    /// $p$, and the root is lifted by Newton's method. For $p = 2$ the unit has to be 1 mod 8, and a digit of
+    /// precision is lost.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(match self.valuation {
+                Self::EXACT => Self::zero(),
+                k => Self::zero_to(k.div_euclid(2)),
+            });
+        }
+        if self.valuation % 2 != 0 {
+            return None;
+        }
+        let r = self.precision;
+        let modulus = Self::modulus(r);
+        let root = if P == 2 {
+            let needed = Integer::from(1) << r.min(3);
+            if Integer::from(&self.unit - 1).rem_euc(needed) != 0 {
+                return None;
+            }
+            // x^2 = u mod 2^k, for odd x and k >= 3, lifts to x or x + 2^(k-1) mod 2^(k+1)
+            let mut x = Integer::from(1);
+            for k in 3..r {
+                if (Integer::from(x.square_ref()) - &self.unit).rem_euc(Integer::from(1) << (k + 1))
+                    != 0
+                {
+                    x += Integer::from(1) << (k - 1);
+                }
+            }
+            x
+        } else {
+            // Newton: x -> x - (x^2 - u)/2x doubles the number of correct digits
+            let mut x = sqrt_mod_prime(&self.unit, &Self::p())?;
+            let mut k = 1;
+            while k < r {
+                k = (2 * k).min(r);
+                let m = Self::modulus(k);
+                let step = (Integer::from(x.square_ref()) - &self.unit)
+                    * Integer::from(&x * 2).invert(&m).unwrap();
+                x = (x - step).rem_euc(&m);
+            }
+            x
+        };
+        let precision = match P {
+            2 => r.saturating_sub(1).max(1),
+            _ => r,
+        };
+        Some(Self {
+            unit: root.rem_euc(&modulus).rem_euc(Self::modulus(precision)),
+            valuation: self.valuation / 2,
+            precision,
+        })
+    }
+
+    /// The Teichmüller representative of the residue of $x$ mod $p$: the $(p-1)$th root of unity (or 0) congruent
+    /// to it, which is $\lim x^{p^k}$. Panics if $x \notin \mathbb{Z}\_p$.
+    pub fn teichmuller(&self) -> Self {
+        assert!(
+            self.is_zero() || self.valuation >= 0,
+            "not a p-adic integer"
+        );
+        if self.is_zero() || self.valuation > 0 {
+            return Self::zero();
+        }
+        let modulus = Self::modulus(self.precision);
+        let mut x = self.unit.clone();
+        for _ in 0..self.precision {
+            x = x.pow_mod(&Self::p(), &modulus).unwrap();
+        }
+        Self {
+            unit: x,
+            valuation: 0,
+            precision: self.precision,
+        }
+    }
+
+    /// $\log(x) = \sum\_{k \geq 1} (-1)^{k+1} z^k / k$ for $x = 1 + z$, which converges for $v(z) \geq 1$. Other
+    /// $x$ give `OutOfBounds`.
+    pub fn log(&self) -> Result<Self, ArithmeticError> {
+        let z = self.clone() - Self::one();
+        let w = match z.valuation() {
+            None => return Ok(Self::zero_to(z.valuation)),
+            Some(w) if w >= 1 => w,
+            _ => return Err(ArithmeticError::OutOfBounds),
+        };
+        let absolute = z.absolute_precision().unwrap();
+        let z_lift = z.lift();
+        let (mut sum, mut power) = (Rational::new(), Rational::from(1));
+        let mut k: i64 = 1;
+        // v(z^k/k) >= kw - log_p(k)
+        while k * w - (k as f64).log(P as f64).floor() as i64 <= absolute {
+            power *= &z_lift;
+            let term = Rational::from(&power / k);
+            match k % 2 {
+                1 => sum += term,
+                _ => sum -= term,
+            }
+            k += 1;
+        }
+        Ok(Self::with_absolute_precision(&sum, absolute))
+    }
+
+    /// $\exp(z) = \sum\_{k \geq 0} z^k / k!$, which converges for $v(z) > 1/(p - 1)$. Other $z$ give
+    /// `OutOfBounds`.
+    pub fn exp(&self) -> Result<Self, ArithmeticError> {
+        let bound = match P {
+            2 => 2,
+            _ => 1,
+        };
+        let w = match self.valuation() {
+            None if self.valuation == Self::EXACT => return Ok(Self::one()),
+            None if self.valuation >= bound => return Ok(Self::one() + self.clone()),
+            Some(w) if w >= bound => w,
+            _ => return Err(ArithmeticError::OutOfBounds),
+        };
+        let absolute = self.absolute_precision().unwrap();
+        let z = self.lift();
+        let (mut sum, mut term) = (Rational::from(1), Rational::from(1));
+        let mut k: i64 = 1;
+        // v(z^k/k!) >= kw - (k - 1)/(p - 1)
+        while k * w - (k - 1) / (P as i64 - 1) <= absolute {
+            term *= &z;
+            term /= k;
+            sum += &term;
+            k += 1;
+        }
+        Ok(Self::with_absolute_precision(&sum, absolute))
+    }
+
+    /// $a + O(p^k)$: the rational $a$ to absolute precision $k$, or to relative precision $N$ if that's less.
+    pub fn with_absolute_precision(a: &Rational, absolute: i64) -> Self {
+        if a.is_zero() {
+            return Self::zero_to(absolute);
+        }
+        let x = Self::with_precision(a, N);
+        match x.absolute_precision() {
+            Some(k) if k > absolute => Self::normalized(x.unit, x.valuation, absolute),
+            _ => x,
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> From<Integer> for PAdic<P, N> {
+    fn from(a: Integer) -> Self {
+        Self::with_precision(&Rational::from(a), N)
+    }
+}
+
+impl<const P: u64, const N: u32> From<Rational> for PAdic<P, N> {
+    fn from(a: Rational) -> Self {
+        Self::with_precision(&a, N)
+    }
+}
+
+/// Equal digits to the same precision. Every zero is equal to every other, so that `==` agrees with `is_zero`.
+impl<const P: u64, const N: u32> PartialEq for PAdic<P, N> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => true,
+            (false, false) => {
+                self.valuation == other.valuation
+                    && self.precision == other.precision
+                    && self.unit == other.unit
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> Eq for PAdic<P, N> {}
+
+/// The exact zero.
+impl<const P: u64, const N: u32> Zero for PAdic<P, N> {
+    fn zero() -> Self {
+        Self::zero_to(Self::EXACT)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.precision == 0
+    }
+}
+
+impl<const P: u64, const N: u32> One for PAdic<P, N> {
+    fn one() -> Self {
+        Self::from(Integer::from(1))
+    }
+}
+
+impl<const P: u64, const N: u32> Add for PAdic<P, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match (self.valuation == Self::EXACT, rhs.valuation == Self::EXACT) {
+            (true, _) => return rhs,
+            (_, true) => return self,
+            _ => {}
+        }
+        let absolute = self
+            .absolute_precision()
+            .min(rhs.absolute_precision())
+            .unwrap();
+        let v = self.valuation.min(rhs.valuation);
+        // p^(v_x - v) u_x + p^(v_y - v) u_y, dropping terms below the precision
+        let shifted = |x: &Self| match x.valuation - v {
+            k if k >= absolute - v => Integer::new(),
+            k => Self::modulus(k as u32) * &x.unit,
+        };
+        Self::normalized(shifted(&self) + shifted(&rhs), v, absolute)
+    }
+}
+
+impl<const P: u64, const N: u32> Neg for PAdic<P, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            unit: (-self.unit).rem_euc(Self::modulus(self.precision)),
+            ..self
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> Sub for PAdic<P, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<const P: u64, const N: u32> Mul for PAdic<P, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.valuation == Self::EXACT || rhs.valuation == Self::EXACT {
+            return Self::zero();
+        }
+        // O(p^a) p^v u = O(p^(a + v)), so zeros work out too
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero_to(self.valuation + rhs.valuation);
+        }
+        let precision = self.precision.min(rhs.precision);
+        Self {
+            unit: (self.unit * rhs.unit).rem_euc(Self::modulus(precision)),
+            valuation: self.valuation + rhs.valuation,
+            precision,
+        }
+    }
+}
+
+/// Division, which panics for zero divisors.
+impl<const P: u64, const N: u32> Div for PAdic<P, N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        let inverse = rhs.inverse().expect("divide by zero");
+        match self.is_zero() {
+            true if self.valuation == Self::EXACT => Self::zero(),
+            true => Self::zero_to(self.valuation + inverse.valuation),
+            false => self * inverse,
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> ModType<PAdic<P, N>> for PAdic<P, N> {
+    fn mod_mul(r: Self, m: Self) -> Self {
+        r * m
+    }
+}
+
+impl<const P: u64, const N: u32> ModType<Integer> for PAdic<P, N> {
+    fn mod_mul(r: Integer, m: Self) -> Self {
+        Self::from(r) * m
+    }
+}
+
+impl<const P: u64, const N: u32> Monoid for PAdic<P, N> {}
+impl<const P: u64, const N: u32> CommRing for PAdic<P, N> {}
+
+/// The normal numbers are 0 and 1, like for `Rational`.
+impl<const P: u64, const N: u32> IntegralDomain for PAdic<P, N> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.clone() / other.clone())
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => self.clone(),
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> GcdDomain for PAdic<P, N> {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl<const P: u64, const N: u32> EuclideanDomain for PAdic<P, N> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "divide by zero");
+        (self.clone() / other.clone(), Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+impl<const P: u64, const N: u32> Field for PAdic<P, N> {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        self.inverse()
+    }
+}
+
+impl<const P: u64, const N: u32> Display for PAdic<P, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.absolute_precision() {
+            None => write!(f, "0"),
+            Some(k) => write!(f, "{} + O({}^{})", self.lift(), P, k),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::poly::Polynomial;
+
+    type Q5 = PAdic<5>;
+    type Q2 = PAdic<2, 30>;
+
+    fn q5(a: i64) -> Q5 {
+        Q5::from(Integer::from(a))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = q5(50);
+        assert_eq!(a.valuation(), Some(2));
+        assert_eq!(a.precision(), 20);
+        assert_eq!(a.clone() + q5(-50), Q5::zero());
+        assert_eq!((a.clone() - q5(50)).absolute_precision(), Some(22));
+        assert_eq!(a.clone() * q5(3), q5(150));
+        assert_eq!(a.clone() / q5(25), q5(2));
+        assert_eq!(q5(1) / q5(3) * q5(3), q5(1));
+        // cancellation loses precision: (1 + 5^10) - 1 only knows 10 more digits
+        let close = q5(1) + Q5::from(Integer::from(5).pow(10));
+        let diff = close - q5(1);
+        assert_eq!(diff.valuation(), Some(10));
+        assert_eq!(diff.absolute_precision(), Some(20));
+        assert_eq!(diff.precision(), 10);
+        // the inverse limit: -1 = 4 + 4*5 + 4*5^2 + ...
+        assert_eq!(q5(-1).residue(2).value(), &24);
+        assert_eq!(q5(2).pow(-3) * q5(8), q5(1));
+        assert_eq!(Q5::from(Rational::from((1, 5))).valuation(), Some(-1));
+        assert!(q5(0).is_zero() && q5(0).absolute_precision().is_none());
+        assert!(Q5::zero().inverse().is_err());
+        let rough = Q5::with_precision(&Rational::from(7), 3);
+        // equality is exact, agreement up to the known digits is a zero difference
+        assert_ne!(rough, q5(7 + 125));
+        assert!((rough.clone() - q5(7 + 125)).is_zero());
+        assert!(!(rough.clone() - q5(8)).is_zero());
+        assert_eq!(rough.to_string(), "7 + O(5^3)");
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for a in [-1, 6, 11, 25 * 14, -4] {
+            let r = q5(a).sqrt().unwrap();
+            assert_eq!(r.clone() * r, q5(a));
+        }
+        assert!(q5(2).sqrt().is_none());
+        assert!(q5(5).sqrt().is_none());
+        let r = Q2::from(Integer::from(17)).sqrt().unwrap();
+        assert!((r.clone() * r.clone() - Q2::from(Integer::from(17))).is_zero());
+        assert_eq!(r.precision(), 29);
+        assert!(Q2::from(Integer::from(5)).sqrt().is_none());
+        assert!(Q2::from(Integer::from(-7 * 4)).sqrt().is_some());
+    }
+
+    #[test]
+    fn test_teichmuller() {
+        for a in 1..5 {
+            let t = q5(a).teichmuller();
+            assert_eq!(t.pow(4), q5(1));
+            assert_eq!(t.residue(1).value(), &a);
+        }
+        // the 4th roots of unity are +-1, +-i
+        assert_eq!(q5(2).teichmuller().pow(2), q5(-1));
+        assert!(q5(10).teichmuller().is_zero());
+    }
+
+    #[test]
+    fn test_log_exp() {
+        let (a, b) = (q5(6), q5(-24));
+        let (la, lb) = (a.log().unwrap(), b.log().unwrap());
+        assert_eq!((a.clone() * b.clone()).log().unwrap(), la.clone() + lb);
+        assert_eq!(la.valuation(), Some(1));
+        assert!((la.exp().unwrap() - a).is_zero());
+        assert!((q5(25).exp().unwrap().log().unwrap() - q5(25)).is_zero());
+        assert!(q5(2).log().is_err());
+        assert!(Q5::from(Rational::from((1, 5))).exp().is_err());
+        let x = Q2::from(Integer::from(4));
+        assert!((x.exp().unwrap().log().unwrap() - x).is_zero());
+        assert!(Q2::from(Integer::from(2)).exp().is_err());
+    }
+
+    #[test]
+    fn test_polynomials() {
+        // x^2 - 6 has the roots +-sqrt(6) in Q_5
+        let f = Polynomial::from(vec![q5(-6), q5(0), q5(1)]);
+        let r = q5(6).sqrt().unwrap();
+        assert!(f.eval(r.clone()).is_zero());
+        let g = Polynomial::from(vec![-r.clone(), q5(1)]);
+        let (quotient, remainder) = f.div_rem(&g);
+        assert!(remainder.is_zero());
+        assert_eq!(quotient, Polynomial::from(vec![r, q5(1)]));
+    }
+}
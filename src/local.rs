@@ -1,6 +1,35 @@
 //! Localization
-
+//!
+//! For now, this only does the localization of $F[x\_0, \ldots, x\_{n-1}]$ at the origin, i.e. at the maximal
+//! ideal $\mathfrak{m} = (x\_0, \ldots, x\_{n-1})$, using standard bases for local monomial orders. A local order
+//! has $m < 1$ for every monomial $m \neq 1$, so the leading term is the lowest degree one, and everything with
+//! a nonzero constant term has the same leading monomial as a unit. Buchberger's algorithm doesn't terminate
+//! for these orders, but Mora's normal form, which is allowed to multiply by units, does.
+//!
+//! The reference is Greuel and Pfister, *A Singular Introduction to Commutative Algebra*, chapter 1.6-1.7.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::local::{milnor_number, LocalIdeal};
+//! use bored_algebra::mpoly::MPolynomial;
+//! use bored_algebra::Rational;
+//!
+//! type P = MPolynomial<Rational, bored_algebra::local::NegDegRevLex>;
+//! let (x, y) = (P::var(0), P::var(1));
+//! // x - x^2 = x(1 - x), and 1 - x is a unit near the origin
+//! let i = LocalIdeal::new(1, vec![x.clone() - x.clone() * x.clone()]);
+//! assert!(i.contains(&x));
+//! // the cusp y^2 = x^3 has Milnor number 2
+//! let f = y.clone() * y.clone() - x.clone() * x.clone() * x.clone();
+//! assert_eq!(milnor_number(2, &f), Some(2));
+//! ```
+use crate::hilbert::hilbert_series;
 use crate::module::{ModType, RingType};
+use crate::mpoly::{GRevLex, Lex, MPolynomial, Monomial, MonomialOrder};
+use crate::structure::Field;
+use core::cmp::Ordering;
+
+use num_traits::identities::Zero;
 
 // imagining localization as a function-like proc macro, something like
 //
@@ -14,3 +43,364 @@ use crate::module::{ModType, RingType};
 pub trait MulSet<R: RingType, M: ModType<R>> {
     //TODO:
 }
+
+/// Negative degree reverse lexicographic order (`ds` in Singular): lower total degree is larger, and ties are
+/// broken with `GRevLex`. This is degree compatible, so it's the one to use for multiplicities and tangent cones.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NegDegRevLex;
+
+/// Negative lexicographic order (`ls` in Singular): $x^a > x^b$ if $a\_i < b\_i$ at the first place they differ.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NegLex;
+
+impl MonomialOrder for NegDegRevLex {
+    const GLOBAL: bool = false;
+
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        b.deg().cmp(&a.deg()).then_with(|| GRevLex::cmp(a, b))
+    }
+}
+
+impl MonomialOrder for NegLex {
+    const GLOBAL: bool = false;
+
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        Lex::cmp(b, a)
+    }
+}
+
+/// The ecart of $f$, $\deg f - \deg \operatorname{LM}(f)$. It measures how far $f$ is from homogeneous.
+fn ecart<F: Field, O: MonomialOrder>(f: &MPolynomial<F, O>) -> u64 {
+    f.total_deg() - f.lead_monomial().map_or(0, |m| m.deg())
+}
+
+/// $h - (\operatorname{LT}(h) / \operatorname{LT}(g)) g$, assuming the leading monomial of $g$ divides that of $h$.
+fn reduce_lead<F: Field, O: MonomialOrder>(
+    h: &MPolynomial<F, O>,
+    g: &MPolynomial<F, O>,
+) -> MPolynomial<F, O> {
+    let (m, c) = h.lead_term().unwrap();
+    let (n, d) = g.lead_term().unwrap();
+    let t = m.checked_div(n).unwrap();
+    h.clone() - g.mul_term(&t, &(c.clone() / d.clone()))
+}
+
+/// Mora's weak normal form of `f` with respect to `basis`. The result $h$ satisfies $uf = \sum\_i a\_i g\_i + h$
+/// for some unit $u$ of the localization, and no element of `basis` has a leading monomial dividing that of $h$.
+/// If `basis` is a standard basis, $h$ is zero exactly when `f` is in the ideal it generates in the localization.
+///
+/// Unlike ordinary reduction, only the leading term gets reduced, since reducing the tail might not terminate.
+pub fn mora_normal_form<F: Field, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    basis: &[MPolynomial<F, O>],
+) -> MPolynomial<F, O> {
+    let mut t: Vec<MPolynomial<F, O>> = basis.iter().filter(|g| !g.is_zero()).cloned().collect();
+    let mut h = f.clone();
+    while let Some(m) = h.lead_monomial() {
+        let g = match t
+            .iter()
+            .filter(|g| g.lead_monomial().unwrap().divides(m))
+            .min_by_key(|g| ecart(*g))
+        {
+            Some(g) => g.clone(),
+            None => break,
+        };
+        // reducing by something with a bigger ecart can loop forever, so remember h and allow reducing by it
+        // later: that's where the unit u comes from
+        if ecart(&g) > ecart(&h) {
+            t.push(h.clone());
+        }
+        h = reduce_lead(&h, &g);
+    }
+    h
+}
+
+/// A standard basis for the ideal generated by `gens` in the localization at the origin, using Mora's normal
+/// form in place of ordinary reduction in Buchberger's algorithm. The result is minimal (no leading monomial
+/// divides another) and monic, but the tails aren't reduced, so it isn't unique.
+pub fn standard_basis<F: Field, O: MonomialOrder>(
+    gens: &[MPolynomial<F, O>],
+) -> Vec<MPolynomial<F, O>> {
+    let mut basis: Vec<MPolynomial<F, O>> = vec![];
+    let mut pairs: Vec<(usize, usize, Monomial)> = vec![];
+    let add = |g: MPolynomial<F, O>,
+               basis: &mut Vec<MPolynomial<F, O>>,
+               pairs: &mut Vec<(usize, usize, Monomial)>| {
+        let g = g.monic();
+        let m = g.lead_monomial().unwrap();
+        for (k, h) in basis.iter().enumerate() {
+            pairs.push((k, basis.len(), m.lcm(h.lead_monomial().unwrap())));
+        }
+        basis.push(g);
+    };
+    for f in gens.iter().filter(|f| !f.is_zero()) {
+        add(f.clone(), &mut basis, &mut pairs);
+    }
+
+    while !pairs.is_empty() {
+        // smallest degree first, since a local order would pick the largest
+        let next = (0..pairs.len()).min_by_key(|&k| pairs[k].2.deg()).unwrap();
+        let (i, j, lcm) = pairs.swap_remove(next);
+        // no product criterion here: it relies on the tails being fully reduced. The chain criterion is fine.
+        let chain = basis.iter().enumerate().any(|(k, g)| {
+            k != i
+                && k != j
+                && g.lead_monomial().unwrap().divides(&lcm)
+                && !pairs.iter().any(|p| {
+                    (p.0 == k.min(i) && p.1 == k.max(i)) || (p.0 == k.min(j) && p.1 == k.max(j))
+                })
+        });
+        if chain {
+            continue;
+        }
+        let (f, g) = (&basis[i], &basis[j]);
+        let a = lcm.checked_div(f.lead_monomial().unwrap()).unwrap();
+        let b = lcm.checked_div(g.lead_monomial().unwrap()).unwrap();
+        // both are monic, so this is the S-polynomial
+        let s = f.mul_term(&a, &F::one()) - g.mul_term(&b, &F::one());
+        let h = mora_normal_form(&s, &basis);
+        if !h.is_zero() {
+            add(h, &mut basis, &mut pairs);
+        }
+    }
+
+    // drop elements whose leading monomial is divisible by an earlier or strictly smaller one
+    let leads: Vec<Monomial> = basis
+        .iter()
+        .map(|g| g.lead_monomial().unwrap().clone())
+        .collect();
+    let mut minimal: Vec<MPolynomial<F, O>> = basis
+        .into_iter()
+        .enumerate()
+        .filter(|(k, _)| {
+            !leads
+                .iter()
+                .enumerate()
+                .any(|(l, m)| l != *k && m.divides(&leads[*k]) && (m != &leads[*k] || l < *k))
+        })
+        .map(|(_, g)| g)
+        .collect();
+    minimal.sort_by(|f, g| O::cmp(g.lead_monomial().unwrap(), f.lead_monomial().unwrap()));
+    minimal
+}
+
+/// An ideal of the localization of $F[x\_0, \ldots, x\_{n-1}]$ at the origin, stored by generators together with
+/// a standard basis for the local order `O`.
+#[derive(Clone, Debug)]
+pub struct LocalIdeal<F, O = NegDegRevLex> {
+    nvars: usize,
+    gens: Vec<MPolynomial<F, O>>,
+    basis: Vec<MPolynomial<F, O>>,
+}
+
+impl<F: Field, O: MonomialOrder> LocalIdeal<F, O> {
+    /// The ideal generated by `gens` in the localization at the origin of the ring with variables
+    /// $x\_0, \ldots, x\_{n-1}$, where $n$ is `nvars`. Panics if `O` is a global order, or if a generator uses a
+    /// variable past $x\_{n-1}$.
+    pub fn new(nvars: usize, gens: Vec<MPolynomial<F, O>>) -> Self {
+        assert!(
+            !O::GLOBAL,
+            "the localization at the origin needs a local order"
+        );
+        assert!(
+            gens.iter().all(|f| f.nvars() <= nvars),
+            "generators use more than {} variables",
+            nvars
+        );
+        let basis = standard_basis(&gens);
+        Self { nvars, gens, basis }
+    }
+
+    /// The number of variables of the ambient polynomial ring.
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    /// The generators the ideal was made with.
+    pub fn gens(&self) -> &[MPolynomial<F, O>] {
+        &self.gens
+    }
+
+    /// The standard basis with respect to `O`.
+    pub fn standard_basis(&self) -> &[MPolynomial<F, O>] {
+        &self.basis
+    }
+
+    /// Mora's normal form of `f` modulo the ideal.
+    pub fn reduce(&self, f: &MPolynomial<F, O>) -> MPolynomial<F, O> {
+        mora_normal_form(f, &self.basis)
+    }
+
+    pub fn contains(&self, f: &MPolynomial<F, O>) -> bool {
+        self.reduce(f).is_zero()
+    }
+
+    /// Returns true if the ideal is the whole local ring, i.e. it has an element that doesn't vanish at the origin.
+    pub fn is_unit(&self) -> bool {
+        self.leading_monomials().iter().any(|m| m.is_one())
+    }
+
+    /// The minimal generators of the ideal of leading monomials.
+    pub fn leading_monomials(&self) -> Vec<Monomial> {
+        self.basis
+            .iter()
+            .filter_map(|g| g.lead_monomial().cloned())
+            .collect()
+    }
+
+    /// The same ideal, with a standard basis for a different local order.
+    pub fn reorder<P: MonomialOrder>(&self) -> LocalIdeal<F, P> {
+        LocalIdeal::new(self.nvars, self.basis.iter().map(|g| g.reorder()).collect())
+    }
+
+    /// The dimension of the local ring $F[x]\_\mathfrak{m} / I$ as a vector space over $F$, or `None` if it's
+    /// infinite. This is the number of monomials outside the ideal of leading monomials, for any local order.
+    pub fn colength(&self) -> Option<usize> {
+        let series = hilbert_series(&self.leading_monomials(), self.nvars);
+        match series.dim() {
+            None => Some(0),
+            Some(0) => Some(series.degree().to_usize().unwrap()),
+            Some(_) => None,
+        }
+    }
+
+    /// The Krull dimension of $F[x]\_\mathfrak{m} / I$, or `None` for the unit ideal.
+    pub fn dim(&self) -> Option<usize> {
+        hilbert_series(&self.tangent_cone_monomials(), self.nvars).dim()
+    }
+
+    /// The Hilbert-Samuel multiplicity of $F[x]\_\mathfrak{m} / I$, which is the degree of its tangent cone. For a
+    /// hypersurface this is the order of vanishing at the origin. The unit ideal has multiplicity 0.
+    pub fn multiplicity(&self) -> usize {
+        hilbert_series(&self.tangent_cone_monomials(), self.nvars)
+            .degree()
+            .to_usize()
+            .unwrap()
+    }
+
+    /// The leading monomials for a degree compatible local order. These generate the initial ideal of the ideal
+    /// of the tangent cone, so they have the same Hilbert function as the associated graded ring.
+    fn tangent_cone_monomials(&self) -> Vec<Monomial> {
+        // TODO: skip this when O is already NegDegRevLex
+        self.reorder::<NegDegRevLex>().leading_monomials()
+    }
+}
+
+/// The partial derivatives of `f` with respect to $x\_0, \ldots, x\_{n-1}$.
+fn jacobian<F: Field, O: MonomialOrder>(
+    nvars: usize,
+    f: &MPolynomial<F, O>,
+) -> Vec<MPolynomial<F, O>> {
+    (0..nvars).map(|i| f.derivative(i)).collect()
+}
+
+/// The Milnor number of the hypersurface $f = 0$ at the origin, $\dim\_F F[x]\_\mathfrak{m} / (\partial f / \partial
+/// x\_0, \ldots, \partial f / \partial x\_{n-1})$, or `None` if the singularity isn't isolated. This is 0 at smooth
+/// points. It assumes characteristic 0.
+pub fn milnor_number<F: Field, O: MonomialOrder>(
+    nvars: usize,
+    f: &MPolynomial<F, O>,
+) -> Option<usize> {
+    LocalIdeal::new(nvars, jacobian(nvars, f)).colength()
+}
+
+/// The Tjurina number of the hypersurface $f = 0$ at the origin, $\dim\_F F[x]\_\mathfrak{m} / (f, \partial f /
+/// \partial x\_0, \ldots, \partial f / \partial x\_{n-1})$, or `None` if the singularity isn't isolated. It's at most
+/// the Milnor number, with equality for quasihomogeneous $f$.
+pub fn tjurina_number<F: Field, O: MonomialOrder>(
+    nvars: usize,
+    f: &MPolynomial<F, O>,
+) -> Option<usize> {
+    let mut gens = jacobian(nvars, f);
+    gens.push(f.clone());
+    LocalIdeal::new(nvars, gens).colength()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ideal::Ideal;
+    use crate::test_helpers::poly;
+    use crate::Rational;
+
+    type P = MPolynomial<Rational, NegDegRevLex>;
+
+    #[test]
+    fn test_order() {
+        let (one, x, xy) = (
+            Monomial::one(),
+            Monomial::var(0),
+            Monomial::from(vec![1, 1]),
+        );
+        assert_eq!(NegDegRevLex::cmp(&one, &x), Ordering::Greater);
+        assert_eq!(NegDegRevLex::cmp(&x, &xy), Ordering::Greater);
+        assert_eq!(NegLex::cmp(&Monomial::var(1), &x), Ordering::Greater);
+        // the lead term is the lowest degree one
+        let f: P = poly(vec![(vec![2], 1), (vec![1], 1)]);
+        assert_eq!(f.lead_monomial(), Some(&x));
+    }
+
+    #[test]
+    fn test_membership() {
+        // x is in (x - x^2) locally, but not globally
+        let f: P = poly(vec![(vec![1], 1), (vec![2], -1)]);
+        let x = P::var(0);
+        assert!(LocalIdeal::new(1, vec![f.clone()]).contains(&x));
+        assert!(!Ideal::new(1, vec![f.reorder::<GRevLex>()]).contains(&x.reorder()));
+        // x^2 - x and y: the point (1, 0) is far away, so this is just the maximal ideal
+        let i = LocalIdeal::new(2, vec![poly(vec![(vec![2], 1), (vec![1], -1)]), P::var(1)]);
+        assert_eq!(i.colength(), Some(1));
+        assert!(LocalIdeal::new(1, vec![poly::<P>(vec![(vec![], 1), (vec![1], 1)])]).is_unit());
+        // (y - x^2, y) has colength 2, and the same with the other local order
+        let gens = vec![poly(vec![(vec![0, 1], 1), (vec![2], -1)]), P::var(1)];
+        assert_eq!(LocalIdeal::new(2, gens.clone()).colength(), Some(2));
+        assert_eq!(
+            LocalIdeal::new(2, gens).reorder::<NegLex>().colength(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_milnor() {
+        // A_k: x^(k+1) + y^2 has Milnor number k
+        for k in 1..6 {
+            let f: P = poly(vec![(vec![k + 1], 1), (vec![0, 2], 1)]);
+            assert_eq!(milnor_number(2, &f), Some(k as usize));
+            assert_eq!(tjurina_number(2, &f), Some(k as usize));
+        }
+        // E6
+        let f: P = poly(vec![(vec![3], 1), (vec![0, 4], 1)]);
+        assert_eq!(milnor_number(2, &f), Some(6));
+        // not quasihomogeneous, so the Tjurina number is smaller
+        let f: P = poly(vec![(vec![5], 1), (vec![0, 5], 1), (vec![2, 2], 1)]);
+        assert_eq!(milnor_number(2, &f), Some(11));
+        assert_eq!(tjurina_number(2, &f), Some(10));
+        // smooth points and non-isolated singularities
+        assert_eq!(
+            milnor_number(2, &poly::<P>(vec![(vec![1], 1), (vec![2, 1], 1)])),
+            Some(0)
+        );
+        assert_eq!(milnor_number(2, &poly::<P>(vec![(vec![2], 1)])), None);
+    }
+
+    #[test]
+    fn test_multiplicity() {
+        // the cusp has multiplicity 2, the node too, and a smooth curve 1
+        let cusp: P = poly(vec![(vec![0, 2], 1), (vec![3], -1)]);
+        let node: P = poly(vec![(vec![0, 2], 1), (vec![2], -1), (vec![3], -1)]);
+        let smooth: P = poly(vec![(vec![0, 1], 1), (vec![2], -1)]);
+        assert_eq!(LocalIdeal::new(2, vec![cusp.clone()]).multiplicity(), 2);
+        assert_eq!(LocalIdeal::new(2, vec![node]).multiplicity(), 2);
+        assert_eq!(LocalIdeal::new(2, vec![smooth]).multiplicity(), 1);
+        assert_eq!(LocalIdeal::new(2, vec![cusp]).dim(), Some(1));
+        // (x^2, y^3) is zero dimensional with multiplicity 6
+        let i = LocalIdeal::new(
+            2,
+            vec![poly::<P>(vec![(vec![2], 1)]), poly(vec![(vec![0, 3], 1)])],
+        );
+        assert_eq!(
+            (i.dim(), i.multiplicity(), i.colength()),
+            (Some(0), 6, Some(6))
+        );
+    }
+}
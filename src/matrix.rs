@@ -0,0 +1,529 @@
+//! Matrices
+//!
+//! A `Matrix<R>` with $m$ rows and $n$ columns is the same thing as a homomorphism $R^n \to R^m$ of free
+//! modules, acting on column vectors. `Matrix::into_homo` makes the `Homo` out of it.
+//!
+//! Over a field there are determinants and characteristic polynomials, and over $\mathbb{Z}$ the Hermite normal
+//! form, which is a canonical basis for the lattice spanned by the rows. Over any integral domain there's still
+//! the determinant, by fraction-free elimination.
+use crate::free::Free;
+use crate::module::{Homo, RingType};
+use crate::poly::Polynomial;
+use crate::structure::{Field, IntegralDomain};
+use crate::Integer;
+use core::fmt::{self, Display};
+use core::ops::Mul;
+use num_traits::identities::One;
+
+/// An $m \times n$ matrix with entries in $R$, stored row by row.
+/// # Example:
+/// ```
+/// use bored_algebra::free::Free;
+/// use bored_algebra::matrix::Matrix;
+///
+/// let a = Matrix::from(vec![vec![1_i64, 2], vec![3, 4]]);
+/// assert_eq!(a.apply(&Free::from(vec![1, 1])), Free::from(vec![3, 7]));
+/// let f = a.into_homo();
+/// assert_eq!(f.apply(Free::from(vec![0, 1])), Free::from(vec![2, 4]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<R> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<Vec<R>>,
+}
+
+impl<R: RingType> Matrix<R> {
+    /// The $m \times n$ zero matrix.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            entries: vec![vec![R::zero(); cols]; rows],
+        }
+    }
+
+    /// The $n \times n$ identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut a = Self::zero(n, n);
+        for i in 0..n {
+            a.entries[i][i] = R::one();
+        }
+        a
+    }
+
+    /// Makes an $m \times n$ matrix whose columns are `cols`. Coordinates of the columns past
+    /// `rows` are ignored.
+    pub fn from_columns(rows: usize, cols: &[Free<R>]) -> Self {
+        Self {
+            rows,
+            cols: cols.len(),
+            entries: (0..rows)
+                .map(|i| cols.iter().map(|c| c.get(i)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The entry in row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> &R {
+        &self.entries[i][j]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, r: R) {
+        self.entries[i][j] = r;
+    }
+
+    /// The `i`th row.
+    pub fn row(&self, i: usize) -> &[R] {
+        &self.entries[i]
+    }
+
+    /// The `j`th column, as an element of $R^m$.
+    pub fn column(&self, j: usize) -> Free<R> {
+        Free::from(
+            self.entries
+                .iter()
+                .map(|row| row[j].clone())
+                .collect::<Vec<R>>(),
+        )
+    }
+
+    pub fn columns(&self) -> Vec<Free<R>> {
+        (0..self.cols).map(|j| self.column(j)).collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.entries.iter().flatten().all(|r| r.is_zero())
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            entries: (0..self.cols)
+                .map(|j| (0..self.rows).map(|i| self.entries[i][j].clone()).collect())
+                .collect(),
+        }
+    }
+
+    /// Multiplies the column vector `v` on the left by `self`.
+    pub fn apply(&self, v: &Free<R>) -> Free<R> {
+        Free::from(
+            self.entries
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .fold(R::zero(), |acc, (j, a)| acc + a.clone() * v.get(j))
+                })
+                .collect::<Vec<R>>(),
+        )
+    }
+
+    /// The homomorphism $R^n \to R^m$ given by multiplying column vectors by `self`.
+    pub fn into_homo(self) -> Homo<R, Free<R>, Free<R>>
+    where
+        R: 'static,
+    {
+        Homo::new(Box::new(move |v: Free<R>| self.apply(&v)))
+    }
+}
+
+impl<R: IntegralDomain> Matrix<R> {
+    /// The determinant by Bareiss's fraction-free elimination, for when dividing isn't an option (polynomial
+    /// entries, say). After step $k$ the entries are $(k+1) \times (k+1)$ minors, so every division is exact.
+    /// Panics if the matrix isn't square.
+    pub fn determinant_fraction_free(&self) -> R {
+        assert_eq!(self.rows, self.cols, "determinant of a non-square matrix");
+        let mut a = self.entries.clone();
+        let (mut sign, mut prev) = (R::one(), R::one());
+        for c in 0..self.cols {
+            let Some(pivot) = (c..self.rows).find(|&i| !a[i][c].is_zero()) else {
+                return R::zero();
+            };
+            if pivot != c {
+                a.swap(pivot, c);
+                sign = -sign;
+            }
+            let (top, bottom) = a.split_at_mut(c + 1);
+            let pivot_row = &top[c];
+            for row in bottom.iter_mut() {
+                let u = row[c].clone();
+                for (x, y) in row.iter_mut().zip(pivot_row.iter()).skip(c + 1) {
+                    *x = (x.clone() * pivot_row[c].clone() - u.clone() * y.clone())
+                        .div_exact(&prev)
+                        .unwrap();
+                }
+            }
+            prev = pivot_row[c].clone();
+        }
+        sign * prev
+    }
+}
+
+impl<F: Field> Matrix<F> {
+    /// The determinant, by Gaussian elimination. Panics if the matrix isn't square.
+    pub fn determinant(&self) -> F {
+        assert_eq!(self.rows, self.cols, "determinant of a non-square matrix");
+        let mut a = self.entries.clone();
+        let mut det = F::one();
+        for c in 0..self.cols {
+            let Some(pivot) = (c..self.rows).find(|&i| !a[i][c].is_zero()) else {
+                return F::zero();
+            };
+            if pivot != c {
+                a.swap(pivot, c);
+                det = -det;
+            }
+            det = det * a[c][c].clone();
+            let pivot_row = a[c].clone();
+            for row in a.iter_mut().skip(c + 1) {
+                let u = row[c].clone() / pivot_row[c].clone();
+                for (x, y) in row.iter_mut().zip(pivot_row.iter()).skip(c) {
+                    *x = x.clone() - u.clone() * y.clone();
+                }
+            }
+        }
+        det
+    }
+
+    /// The inverse, by Gauss-Jordan elimination, or `None` if the matrix is singular. Panics if the matrix isn't
+    /// square. Only over a `Field`, so not over `i64`, which has a `Div` but isn't one:
+    /// ```compile_fail
+    /// use bored_algebra::matrix::Matrix;
+    ///
+    /// let _ = Matrix::<i64>::identity(2).inverse();
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        assert_eq!(self.rows, self.cols, "inverse of a non-square matrix");
+        let n = self.rows;
+        let mut a = self.entries.clone();
+        let mut inv = Self::identity(n).entries;
+        for c in 0..n {
+            let pivot = (c..n).find(|&i| !a[i][c].is_zero())?;
+            a.swap(pivot, c);
+            inv.swap(pivot, c);
+            let scale = F::one() / a[c][c].clone();
+            a[c].iter_mut().for_each(|x| *x = x.clone() * scale.clone());
+            inv[c]
+                .iter_mut()
+                .for_each(|x| *x = x.clone() * scale.clone());
+            let (pivot_row, pivot_inv) = (a[c].clone(), inv[c].clone());
+            for (i, (row, inv_row)) in a.iter_mut().zip(inv.iter_mut()).enumerate() {
+                let u = row[c].clone();
+                if i == c || u.is_zero() {
+                    continue;
+                }
+                for (x, y) in row.iter_mut().zip(pivot_row.iter()) {
+                    *x = x.clone() - u.clone() * y.clone();
+                }
+                for (x, y) in inv_row.iter_mut().zip(pivot_inv.iter()) {
+                    *x = x.clone() - u.clone() * y.clone();
+                }
+            }
+        }
+        Some(Self::from(inv))
+    }
+
+    /// The characteristic polynomial $\det(x I - A)$. Panics if the matrix isn't square.
+    ///
+    /// $A$ is first made upper Hessenberg (zero below the subdiagonal) by similarity transformations, and then
+    /// the characteristic polynomials $p\_m$ of the leading $m \times m$ blocks satisfy
+    /// $$
+    /// p\_m = (x - h\_{mm}) p\_{m-1} - \sum\_{i=1}^{m-1} h\_{im} \Big(\prod\_{j=i+1}^m h\_{j,j-1}\Big) p\_{i-1}.
+    /// $$
+    pub fn characteristic_polynomial(&self) -> Polynomial<F> {
+        assert_eq!(
+            self.rows, self.cols,
+            "characteristic polynomial of a non-square matrix"
+        );
+        let n = self.rows;
+        let mut h = self.entries.clone();
+        for c in 0..n.saturating_sub(2) {
+            let Some(pivot) = (c + 1..n).find(|&i| !h[i][c].is_zero()) else {
+                continue;
+            };
+            if pivot != c + 1 {
+                h.swap(pivot, c + 1);
+                for row in h.iter_mut() {
+                    row.swap(pivot, c + 1);
+                }
+            }
+            for i in c + 2..n {
+                let u = h[i][c].clone() / h[c + 1][c].clone();
+                if u.is_zero() {
+                    continue;
+                }
+                let pivot_row = h[c + 1].clone();
+                for (x, y) in h[i].iter_mut().zip(pivot_row.iter()) {
+                    *x = x.clone() - u.clone() * y.clone();
+                }
+                for row in h.iter_mut() {
+                    row[c + 1] = row[c + 1].clone() + u.clone() * row[i].clone();
+                }
+            }
+        }
+        let constant = |a: F| Polynomial::from(vec![a]);
+        let mut p = vec![Polynomial::one()];
+        for m in 1..=n {
+            let x_minus = Polynomial::from(vec![-h[m - 1][m - 1].clone(), F::one()]);
+            let mut next = x_minus * p[m - 1].clone();
+            let mut t = F::one();
+            for i in (1..m).rev() {
+                t = t * h[i][i - 1].clone();
+                next = next - constant(h[i - 1][m - 1].clone() * t.clone()) * p[i - 1].clone();
+            }
+            p.push(next);
+        }
+        p.pop().unwrap()
+    }
+}
+
+impl Matrix<Integer> {
+    /// The Hermite normal form of the row lattice: a basis of the $\mathbb{Z}$-span of the rows that is in row
+    /// echelon form, with positive pivots and the entries above each pivot reduced to $[0, \text{pivot})$. Zero
+    /// rows are dropped, so the result has as many rows as the rank.
+    ///
+    /// TODO: this works with the full entries, which can blow up. Working modulo the determinant avoids that for
+    /// full rank lattices.
+    pub fn hermite_normal_form(&self) -> Self {
+        let mut a = self.entries.clone();
+        let mut r = 0;
+        for c in 0..self.cols {
+            if r == a.len() {
+                break;
+            }
+            for i in r + 1..a.len() {
+                if a[i][c] == 0 {
+                    continue;
+                }
+                // replace rows r and i by a unimodular combination with a gcd in row r and 0 in row i
+                let (g, s, t) = a[r][c]
+                    .clone()
+                    .extended_gcd(a[i][c].clone(), Integer::new());
+                let (u, v) = (Integer::from(&a[r][c] / &g), Integer::from(&a[i][c] / &g));
+                let (top, bottom) = a.split_at_mut(i);
+                for (x, y) in top[r].iter_mut().zip(bottom[0].iter_mut()).skip(c) {
+                    let (x0, y0) = (x.clone(), y.clone());
+                    *x = Integer::from(&s * &x0) + Integer::from(&t * &y0);
+                    *y = Integer::from(&u * &y0) - Integer::from(&v * &x0);
+                }
+            }
+            if a[r][c] == 0 {
+                continue;
+            }
+            if a[r][c] < 0 {
+                a[r].iter_mut().for_each(|x| *x = Integer::from(-&*x));
+            }
+            for i in 0..r {
+                let q = <(Integer, Integer)>::from(a[i][c].div_rem_floor_ref(&a[r][c])).0;
+                if q != 0 {
+                    let pivot_row = a[r].clone();
+                    for (x, y) in a[i].iter_mut().zip(pivot_row.iter()).skip(c) {
+                        *x -= Integer::from(&q * y);
+                    }
+                }
+            }
+            r += 1;
+        }
+        a.truncate(r);
+        Self {
+            rows: r,
+            cols: self.cols,
+            entries: a,
+        }
+    }
+}
+
+impl Matrix<Integer> {
+    /// The elementary divisors $d\_1 \mid d\_2 \mid \cdots \mid d\_r$, the nonzero diagonal entries of the Smith
+    /// normal form, where $r$ is the rank. The cokernel $\mathbb{Z}^n / (\text{row lattice})$ is
+    /// $\bigoplus\_i \mathbb{Z}/d\_i \oplus \mathbb{Z}^{n - r}$.
+    ///
+    /// This alternates Hermite normal forms of the rows and the columns until the matrix is diagonal, and then
+    /// fixes up the divisibility with gcds and lcms.
+    pub fn elementary_divisors(&self) -> Vec<Integer> {
+        let mut a = self.hermite_normal_form();
+        loop {
+            let diagonal = (0..a.rows).all(|i| (0..a.cols).all(|j| i == j || a.entries[i][j] == 0));
+            if diagonal {
+                break;
+            }
+            a = a.transpose().hermite_normal_form();
+        }
+        let mut d: Vec<Integer> = (0..a.rows).map(|i| a.entries[i][i].clone()).collect();
+        for i in 0..d.len() {
+            for j in i + 1..d.len() {
+                let g = Integer::from(d[i].gcd_ref(&d[j]));
+                let l = Integer::from(d[i].lcm_ref(&d[j]));
+                (d[i], d[j]) = (g, l);
+            }
+        }
+        d
+    }
+}
+
+/// Makes a matrix from a list of rows, which should all have the same length.
+impl<R: RingType> From<Vec<Vec<R>>> for Matrix<R> {
+    fn from(entries: Vec<Vec<R>>) -> Self {
+        let rows = entries.len();
+        let cols = entries.first().map(|row| row.len()).unwrap_or(0);
+        assert!(
+            entries.iter().all(|row| row.len() == cols),
+            "rows of a matrix must have the same length"
+        );
+        Self {
+            rows,
+            cols,
+            entries,
+        }
+    }
+}
+
+/// Matrix multiplication, $(AB)\_{ik} = \sum\_j A\_{ij} B\_{jk}$.
+impl<R: RingType> Mul for Matrix<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        assert_eq!(self.cols, rhs.rows, "matrix dimensions do not match");
+        let cols = rhs.columns();
+        Self::from_columns(
+            self.rows,
+            &cols.iter().map(|c| self.apply(c)).collect::<Vec<Free<R>>>(),
+        )
+    }
+}
+
+/// Prints one row per line, with entries separated by commas.
+impl<R: RingType + Display> Display for Matrix<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.entries.iter() {
+            writeln!(
+                f,
+                "[{}]",
+                row.iter()
+                    .map(|r| r.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::from(vec![vec![1_i64, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from(vec![vec![1_i64], vec![0], vec![-1]]);
+        assert_eq!(a.clone() * b, Matrix::from(vec![vec![-2], vec![-2]]));
+        assert_eq!(Matrix::identity(2) * a.clone(), a.clone());
+        assert_eq!(a.transpose().transpose(), a.clone());
+        assert_eq!(a.transpose().row(2), &[3, 6]);
+    }
+
+    #[test]
+    fn test_columns() {
+        let cols = vec![Free::from(vec![1_i64, 2]), Free::from(vec![0, 0, 7])];
+        let a = Matrix::from_columns(2, &cols);
+        assert_eq!(a, Matrix::from(vec![vec![1, 0], vec![2, 0]]));
+        assert_eq!(a.column(0), cols[0]);
+        assert!(Matrix::<i64>::zero(3, 2).is_zero());
+    }
+
+    #[test]
+    fn test_determinant() {
+        use crate::Rational;
+        let q = |rows: Vec<Vec<i64>>| {
+            Matrix::from(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(Rational::from).collect())
+                    .collect::<Vec<Vec<Rational>>>(),
+            )
+        };
+        let a = q(vec![vec![0, 2, 1], vec![1, 1, 0], vec![3, 0, 4]]);
+        assert_eq!(a.determinant(), -11);
+        assert_eq!(q(vec![vec![1, 2], vec![2, 4]]).determinant(), 0);
+        let z = Matrix::from(vec![
+            vec![Integer::from(0), Integer::from(2), Integer::from(1)],
+            vec![Integer::from(1), Integer::from(1), Integer::from(0)],
+            vec![Integer::from(3), Integer::from(0), Integer::from(4)],
+        ]);
+        assert_eq!(z.determinant_fraction_free(), -11);
+        // x^3 - 5x^2 - x + 11, and the charpoly of a companion matrix is its polynomial
+        let p =
+            |c: Vec<i64>| Polynomial::from(c.into_iter().map(Rational::from).collect::<Vec<_>>());
+        assert_eq!(a.characteristic_polynomial(), p(vec![11, -1, -5, 1]));
+        let companion = q(vec![
+            vec![0, 0, 0, -7],
+            vec![1, 0, 0, 3],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 2],
+        ]);
+        assert_eq!(
+            companion.characteristic_polynomial(),
+            p(vec![7, -3, 0, -2, 1])
+        );
+        assert_eq!(
+            q(vec![vec![2, 0], vec![0, 2]]).characteristic_polynomial(),
+            p(vec![4, -4, 1])
+        );
+    }
+
+    #[test]
+    fn test_hermite() {
+        let z = |rows: Vec<Vec<i64>>| {
+            Matrix::from(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(Integer::from).collect())
+                    .collect::<Vec<Vec<Integer>>>(),
+            )
+        };
+        let a = z(vec![vec![2, 3, 6], vec![4, 1, 2], vec![6, 4, 8]]);
+        // the third row is the sum of the first two
+        assert_eq!(
+            a.hermite_normal_form(),
+            z(vec![vec![2, 3, 6], vec![0, 5, 10]])
+        );
+        let b = z(vec![vec![4, 6], vec![6, 4], vec![0, 10]]);
+        assert_eq!(b.hermite_normal_form(), z(vec![vec![2, 8], vec![0, 10]]));
+        assert_eq!(
+            z(vec![vec![-3, 1]]).hermite_normal_form(),
+            z(vec![vec![3, -1]])
+        );
+        // Z^2 / <(4, 6), (6, 4), (0, 10)> = Z/2 + Z/10
+        assert_eq!(
+            b.elementary_divisors(),
+            vec![Integer::from(2), Integer::from(10)]
+        );
+        assert_eq!(
+            a.elementary_divisors(),
+            vec![Integer::from(1), Integer::from(10)]
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        use crate::Rational;
+        let a = Matrix::from(vec![
+            vec![Rational::from(2), Rational::from(1)],
+            vec![Rational::from(5), Rational::from(3)],
+        ]);
+        let b = a.inverse().unwrap();
+        assert_eq!(a * b.clone(), Matrix::identity(2));
+        assert_eq!(b.get(0, 1), &-1);
+        let singular = Matrix::from(vec![vec![Rational::from(1); 2]; 2]);
+        assert!(singular.inverse().is_none());
+    }
+}
@@ -0,0 +1,631 @@
+//! Factoring multivariate polynomials
+//!
+//! Multivariate polynomials are reduced to univariate ones by evaluating all but one variable, and the
+//! univariate factors (from `factor`) are lifted back with a multivariate version of Hensel's lemma.
+//!
+//! This works over $\mathbb{Q}$ and $\mathbb{F}\_p$. In characteristic $p$ a polynomial can have a zero derivative
+//! without being constant, which the squarefree decomposition deals with by taking $p$th roots, and over a small
+//! field there might be no evaluation points that keep things squarefree, in which case Kronecker's substitution
+//! reduces to one variable instead.
+use crate::factor::{next_subset, FactorField};
+use crate::helpers::mul_z_module;
+use crate::module::pow;
+use crate::mpoly::{MPolynomial, Monomial, MonomialOrder};
+use crate::poly::Polynomial;
+use crate::{Integer, Rational};
+use rug::ops::Pow;
+
+use num_traits::identities::{One, Zero};
+
+/// Factors a multivariate polynomial into monic irreducibles (with respect to `O`) with their
+/// multiplicities, sorted by total degree. Panics if `f` is zero.
+///
+/// With respect to a main variable $x$ (one of smallest degree), the content is factored recursively and the
+/// primitive part is split into squarefree parts by Musser's algorithm. A squarefree part $f$ of degree $n$ with
+/// leading coefficient $c$ is made monic as $c^{n-1} f(x/c, z)$ and evaluated at a few points $z = a$ where it
+/// stays squarefree. The univariate factors for the point with the fewest of them are Hensel lifted to factors
+/// modulo $(z - a)^{D+1}$, for $D$ the total degree, and every true factor is a product of some of these. The
+/// contents and squarefree parts need gcds, which come from the `GcdField`.
+///
+/// The main variable needs a nonzero derivative, and if there isn't one, $f$ is a $p$th power in characteristic
+/// $p$. If there are no good evaluation points the factors come from Kronecker's substitution.
+/// # Example:
+/// ```
+/// use bored_algebra::mfactor::factor_multivariate;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Rational;
+///
+/// type P = MPolynomial<Rational>;
+/// let (x, y) = (P::var(0), P::var(1));
+/// // x^2 - y^2 = (x - y)(x + y)
+/// let f = x.clone() * x.clone() - y.clone() * y.clone();
+/// let factors = factor_multivariate(&f);
+/// assert_eq!(factors.len(), 2);
+/// assert!(factors.contains(&(x.clone() + y.clone(), 1)));
+/// assert!(factors.contains(&(x - y, 1)));
+/// ```
+pub fn factor_multivariate<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+) -> Vec<(MPolynomial<F, O>, usize)> {
+    assert!(!f.is_zero(), "cannot factor zero");
+    let mut factors = vec![];
+    factor_into(f, 1, &mut factors);
+    factors.sort_by_key(|(g, _)| g.total_deg());
+    factors
+}
+
+/// Factors a multivariate polynomial over $\mathbb{Z}$ as a constant times primitive irreducible factors with
+/// positive leading coefficients (with respect to `O`), with their multiplicities, sorted by total degree. The
+/// factors are the ones over $\mathbb{Q}$ with their denominators cleared, by Gauss's lemma. Panics if `f` is zero.
+/// # Example:
+/// ```
+/// use bored_algebra::mfactor::factor_multivariate_over_z;
+/// use bored_algebra::mpoly::MPolynomial;
+/// use bored_algebra::Integer;
+///
+/// // 6x^2 - 6y^2 = 6 (x - y)(x + y)
+/// let f: MPolynomial<Integer> = MPolynomial::from(vec![(vec![2], Integer::from(6)), (vec![0, 2], Integer::from(-6))]);
+/// let (c, factors) = factor_multivariate_over_z(&f);
+/// assert_eq!((c, factors.len()), (Integer::from(6), 2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn factor_multivariate_over_z<O: MonomialOrder>(
+    f: &MPolynomial<Integer, O>,
+) -> (Integer, Vec<(MPolynomial<Integer, O>, usize)>) {
+    let factors: Vec<(MPolynomial<Integer, O>, usize)> =
+        factor_multivariate(&f.map_coeffs(|c| Rational::from(c.clone())))
+            .into_iter()
+            .map(|(g, e)| {
+                let g = g.scale(&Rational::from(
+                    g.terms()
+                        .iter()
+                        .fold(Integer::from(1), |acc, (_, c)| acc.lcm(c.denom())),
+                ));
+                let content = g
+                    .terms()
+                    .iter()
+                    .fold(Integer::new(), |acc, (_, c)| acc.gcd(c.numer()));
+                (g.map_coeffs(|c| Integer::from(c.numer() / &content)), e)
+            })
+            .collect();
+    let lead = factors.iter().fold(Integer::from(1), |acc, (g, e)| {
+        acc * g.lead_coeff().unwrap().clone().pow(*e as u32)
+    });
+    (Integer::from(f.lead_coeff().unwrap() / &lead), factors)
+}
+
+fn push_factor<F: FactorField, O: MonomialOrder>(
+    factors: &mut Vec<(MPolynomial<F, O>, usize)>,
+    g: MPolynomial<F, O>,
+    e: usize,
+) {
+    let g = g.monic();
+    match factors.iter_mut().find(|(h, _)| *h == g) {
+        Some((_, k)) => *k += e,
+        None => factors.push((g, e)),
+    }
+}
+
+/// Adds the factors of `f` to `factors`, with their multiplicities multiplied by `e`.
+fn factor_into<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    e: usize,
+    factors: &mut Vec<(MPolynomial<F, O>, usize)>,
+) {
+    if f.is_constant() {
+        return;
+    }
+    let n = f.nvars();
+    let content = Monomial::from(
+        (0..n)
+            .map(|i| f.terms().iter().map(|(m, _)| m.exp(i)).min().unwrap())
+            .collect::<Vec<u32>>(),
+    );
+    for i in 0..n {
+        if content.exp(i) > 0 {
+            push_factor(factors, MPolynomial::var(i), e * content.exp(i) as usize);
+        }
+    }
+    let f = MPolynomial::from_terms(
+        f.terms()
+            .iter()
+            .map(|(m, c)| (m.checked_div(&content).unwrap(), c.clone()))
+            .collect(),
+    );
+    if f.is_constant() {
+        return;
+    }
+    let Some(x) = (0..n)
+        .filter(|&i| !f.derivative(i).is_zero())
+        .min_by_key(|&i| f.deg_in(i))
+    else {
+        // all the exponents are multiples of p, and over F_p the coefficients are their own pth roots
+        let p = F::CHARACTERISTIC as u32;
+        let root = MPolynomial::from_terms(
+            f.terms()
+                .iter()
+                .map(|(m, c)| {
+                    let exps = m.exps().iter().map(|k| k / p).collect::<Vec<u32>>();
+                    (Monomial::from(exps), c.clone())
+                })
+                .collect(),
+        );
+        factor_into(&root, e * p as usize, factors);
+        return;
+    };
+    let content = coefficients_in(&f, x)
+        .iter()
+        .fold(MPolynomial::zero(), |acc, c| F::gcd_multivariate(&acc, c));
+    let f = match content.is_constant() {
+        true => f,
+        false => {
+            factor_into(&content, e, factors);
+            f.div_rem(&content).0
+        }
+    };
+    let (parts, rest) = squarefree_in(&f, x);
+    for (g, i) in parts {
+        for h in split_squarefree(&g, x) {
+            push_factor(factors, h, e * i);
+        }
+    }
+    factor_into(&rest, e, factors);
+}
+
+/// The coefficients of `f` as a polynomial in $x\_i$.
+fn coefficients_in<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    i: usize,
+) -> Vec<MPolynomial<F, O>> {
+    (0..=f.deg_in(i))
+        .map(|k| {
+            MPolynomial::from_terms(
+                f.terms()
+                    .iter()
+                    .filter(|(m, _)| m.exp(i) == k)
+                    .map(|(m, c)| {
+                        let exps = (0..m.nvars()).map(|j| if j == i { 0 } else { m.exp(j) });
+                        (Monomial::from(exps.collect::<Vec<u32>>()), c.clone())
+                    })
+                    .collect(),
+            )
+        })
+        .filter(|c| !c.is_zero())
+        .collect()
+}
+
+/// Musser's squarefree decomposition of a polynomial that is primitive with respect to $x\_i$, with a nonzero
+/// derivative in $x\_i$: returns the $(g\_k, k)$, squarefree and pairwise coprime, and the rest $c$ with
+/// $f = c \prod\_k g\_k^k$. In characteristic zero $c$ is constant. In characteristic $p$ it's the product of the
+/// factors with multiplicities divisible by $p$ or a vanishing derivative in $x\_i$, so $\partial c / \partial x\_i = 0$.
+#[allow(clippy::type_complexity)]
+fn squarefree_in<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    i: usize,
+) -> (Vec<(MPolynomial<F, O>, usize)>, MPolynomial<F, O>) {
+    let mut result = vec![];
+    let mut c = F::gcd_multivariate(f, &f.derivative(i));
+    let mut w = f.div_rem(&c).0;
+    let mut k = 1;
+    while !w.is_constant() {
+        let y = F::gcd_multivariate(&w, &c);
+        let z = w.div_rem(&y).0;
+        if !z.is_constant() {
+            result.push((z, k));
+        }
+        c = c.div_rem(&y).0;
+        w = y;
+        k += 1;
+    }
+    (result, c)
+}
+
+fn to_univariate<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    x: usize,
+) -> Polynomial<F> {
+    let mut coeffs = vec![F::zero(); f.deg_in(x) as usize + 1];
+    for (m, c) in f.terms() {
+        coeffs[m.exp(x) as usize] = c.clone();
+    }
+    Polynomial::from(coeffs)
+}
+
+fn from_univariate<F: FactorField, O: MonomialOrder>(
+    u: &Polynomial<F>,
+    x: usize,
+) -> MPolynomial<F, O> {
+    MPolynomial::from_terms(
+        u.coeffs_take(u.deg() as usize + 1)
+            .into_iter()
+            .enumerate()
+            .map(|(k, c)| {
+                let mut exps = vec![0; x + 1];
+                exps[x] = k as u32;
+                (Monomial::from(exps), c)
+            })
+            .collect(),
+    )
+}
+
+/// The terms of `f` of degree at most `j` in the variables other than $x\_0$.
+fn truncate<F: FactorField, O: MonomialOrder>(f: &MPolynomial<F, O>, j: u64) -> MPolynomial<F, O> {
+    MPolynomial::from_terms(
+        f.terms()
+            .iter()
+            .filter(|(m, _)| m.deg() - m.exp(0) as u64 <= j)
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Factors a squarefree polynomial, primitive with respect to $x$.
+fn split_squarefree<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    x: usize,
+) -> Vec<MPolynomial<F, O>> {
+    let zs: Vec<usize> = (0..f.nvars())
+        .filter(|&i| i != x && f.deg_in(i) > 0)
+        .collect();
+    if zs.is_empty() {
+        return F::factor_poly(&to_univariate(f, x))
+            .into_iter()
+            .map(|(u, _)| from_univariate(&u, x))
+            .collect();
+    }
+    let n = f.deg_in(x);
+    if n == 1 {
+        return vec![f.clone()];
+    }
+    // move x to x_0 and the other variables to x_1, ..., x_k
+    let g = f.map_vars(|i| match i == x {
+        true => 0,
+        false => zs.iter().position(|&j| j == i).unwrap() + 1,
+    });
+    let coeffs: Vec<MPolynomial<F, O>> = (0..=n)
+        .map(|k| {
+            MPolynomial::from_terms(
+                g.terms()
+                    .iter()
+                    .filter(|(m, _)| m.exp(0) == k)
+                    .map(|(m, c)| {
+                        (
+                            Monomial::from([&[0], m.exps().get(1..).unwrap_or(&[])].concat()),
+                            c.clone(),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    let lead = coeffs[n as usize].clone();
+    // c^(n-1) g(x/c) is monic in x
+    let monic = coeffs
+        .iter()
+        .enumerate()
+        .fold(MPolynomial::zero(), |acc, (k, a)| {
+            let term = MPolynomial::term(Monomial::from(vec![k as u32]), F::one());
+            match k == n as usize {
+                true => acc + term,
+                false => acc + &(a * &pow(lead.clone(), n as usize - 1 - k)) * &term,
+            }
+        });
+    let k = zs.len();
+    // a few good evaluation points, keeping the one with the fewest univariate factors, since the
+    // recombination is exponential in that. Small points first, since they keep the shifted polynomial sparse
+    let mut best: Option<(Vec<F>, _, Vec<_>)> = None;
+    let mut good = 0;
+    for attempt in 0..100_i64 {
+        let point: Vec<F> = (0..k)
+            .map(|j| {
+                mul_z_module(
+                    (attempt * (j as i64 + 3) + j as i64 * attempt * attempt) % (23 + attempt),
+                    F::one(),
+                )
+            })
+            .collect();
+        let shift: Vec<MPolynomial<F, O>> = (0..=k)
+            .map(|i| match i {
+                0 => MPolynomial::var(0),
+                i => MPolynomial::var(i) + MPolynomial::constant(point[i - 1].clone()),
+            })
+            .collect();
+        let shifted = monic.compose(&shift);
+        let u = to_univariate(&truncate(&shifted, 0), 0);
+        if u.gcd(&u.clone().derivative()).deg() > 0 {
+            continue;
+        }
+        let us: Vec<Polynomial<F>> = F::factor_poly(&u).into_iter().map(|(v, _)| v).collect();
+        if us.len() == 1 {
+            return vec![f.clone()];
+        }
+        if best.as_ref().is_none_or(|(_, _, b)| us.len() < b.len()) {
+            best = Some((point, shifted, us));
+        }
+        good += 1;
+        if good == 3 {
+            break;
+        }
+    }
+    // over a small field every point might be bad
+    let Some((point, shifted, us)) = best else {
+        return kronecker_split(f, x);
+    };
+    let mut remaining = hensel_lift_multivariate(&shifted, &us, shifted.total_deg());
+
+    // try products of subsets, smallest first
+    let mut rest = shifted;
+    let mut found = vec![];
+    let mut size = 1;
+    'outer: while 2 * size <= remaining.len() {
+        let mut subset: Vec<usize> = (0..size).collect();
+        loop {
+            let candidate = subset.iter().fold(MPolynomial::one(), |acc, &i| {
+                truncate(&(&acc * &remaining[i]), rest.total_deg())
+            });
+            let (q, r) = rest.div_rem(&candidate);
+            if r.is_zero() {
+                found.push(candidate);
+                rest = q;
+                remaining = remaining
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !subset.contains(i))
+                    .map(|(_, h)| h)
+                    .collect();
+                continue 'outer;
+            }
+            if !next_subset(&mut subset, remaining.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    found.push(rest);
+
+    // undo the shift and the monic transformation, and take primitive parts
+    let unshift: Vec<MPolynomial<F, O>> = (0..=k)
+        .map(|i| match i {
+            0 => &MPolynomial::var(0) * &lead,
+            i => MPolynomial::var(i) - MPolynomial::constant(point[i - 1].clone()),
+        })
+        .collect();
+    found
+        .into_iter()
+        .map(|h| {
+            let h = h.compose(&unshift);
+            let content = coefficients_in(&h, 0)
+                .iter()
+                .fold(MPolynomial::zero(), |acc, c| F::gcd_multivariate(&acc, c));
+            h.div_rem(&content).0.map_vars(|i| match i {
+                0 => x,
+                i => zs[i - 1],
+            })
+        })
+        .collect()
+}
+
+/// Factors like `split_squarefree`, by Kronecker's substitution: with $x$ first and the other variables
+/// $z\_1, \ldots, z\_k$ after it, $z\_j \mapsto x^{D^j}$ for $D$ bigger than every degree in `f` is a ring homomorphism
+/// that can be undone on polynomials of degree less than $D$ in each variable. Every factor of `f` maps to a product
+/// of some of the univariate factors of the image, with multiplicity, so this is exponential in their number, and
+/// the image has degree up to $D^{k+1}$. It doesn't need any evaluation points though.
+fn kronecker_split<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    x: usize,
+) -> Vec<MPolynomial<F, O>> {
+    let n = f.nvars();
+    let d = (0..n).map(|i| f.deg_in(i)).max().unwrap() as usize + 1;
+    let order: Vec<usize> = std::iter::once(x)
+        .chain((0..n).filter(|&i| i != x && f.deg_in(i) > 0))
+        .collect();
+    let substitute = |g: &MPolynomial<F, O>| {
+        let mut coeffs = vec![F::zero(); d.pow(order.len() as u32)];
+        for (m, c) in g.terms() {
+            let k = order
+                .iter()
+                .rev()
+                .fold(0, |acc, &i| acc * d + m.exp(i) as usize);
+            coeffs[k] = c.clone();
+        }
+        Polynomial::from(coeffs)
+    };
+    // None if some digit would be a degree past the last variable
+    let undo = |u: &Polynomial<F>| -> Option<MPolynomial<F, O>> {
+        let mut terms = vec![];
+        for (mut k, c) in u.coeffs().into_iter().enumerate() {
+            if c.is_zero() {
+                continue;
+            }
+            let mut exps = vec![0; n];
+            for &i in order.iter() {
+                exps[i] = (k % d) as u32;
+                k /= d;
+            }
+            if k > 0 {
+                return None;
+            }
+            terms.push((Monomial::from(exps), c));
+        }
+        Some(MPolynomial::from_terms(terms))
+    };
+    let mut remaining: Vec<Polynomial<F>> = F::factor_poly(&substitute(f))
+        .into_iter()
+        .flat_map(|(u, e)| std::iter::repeat_n(u, e))
+        .collect();
+
+    // try products of subsets, smallest first
+    let mut rest = f.clone();
+    let mut found = vec![];
+    let mut size = 1;
+    'outer: while 2 * size <= remaining.len() {
+        let mut subset: Vec<usize> = (0..size).collect();
+        loop {
+            let product = subset
+                .iter()
+                .fold(Polynomial::one(), |acc, &i| acc * remaining[i].clone());
+            if let Some(candidate) = undo(&product) {
+                let (q, r) = rest.div_rem(&candidate);
+                if r.is_zero() {
+                    found.push(candidate);
+                    rest = q;
+                    remaining = remaining
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, _)| !subset.contains(i))
+                        .map(|(_, u)| u)
+                        .collect();
+                    continue 'outer;
+                }
+            }
+            if !next_subset(&mut subset, remaining.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    found.push(rest);
+    found
+}
+
+/// Lifts a factorization $f(x\_0, 0) = u\_1 \cdots u\_r$ into monic pairwise coprime factors, for $f$ monic in
+/// $x\_0$, to $f \equiv G\_1 \cdots G\_r$ modulo $(x\_1, \ldots, x\_k)^{D+1}$, one degree at a time. The error in degree
+/// $j$ is $\sum\_i \delta\_i \prod\_{l \neq i} u\_l$ with $\delta\_i = s\_i e \bmod u\_i$, where $\sum\_i s\_i \prod\_{l \neq i} u\_l = 1$.
+fn hensel_lift_multivariate<F: FactorField, O: MonomialOrder>(
+    f: &MPolynomial<F, O>,
+    us: &[Polynomial<F>],
+    degree: u64,
+) -> Vec<MPolynomial<F, O>> {
+    let total = us.iter().fold(Polynomial::one(), |acc, u| acc * u.clone());
+    let s: Vec<Polynomial<F>> = us
+        .iter()
+        .map(|u| {
+            let cofactor = total.clone() / u.clone();
+            cofactor.ext_gcd(u).1 % u.clone()
+        })
+        .collect();
+    let mut gs: Vec<MPolynomial<F, O>> = us.iter().map(|u| from_univariate(u, 0)).collect();
+    for j in 1..=degree {
+        let product = gs
+            .iter()
+            .fold(MPolynomial::one(), |acc, g| truncate(&(&acc * g), j));
+        let error = truncate(f, j) - product;
+        // group the error by monomials in x_1, ..., x_k
+        let mut parts: Vec<(Monomial, Vec<(Monomial, F)>)> = vec![];
+        for (m, c) in error.terms() {
+            let z = Monomial::from([&[0], m.exps().get(1..).unwrap_or(&[])].concat());
+            let t = (Monomial::from(vec![m.exp(0)]), c.clone());
+            match parts.iter_mut().find(|(y, _)| *y == z) {
+                Some((_, ts)) => ts.push(t),
+                None => parts.push((z, vec![t])),
+            }
+        }
+        for (z, ts) in parts {
+            let e = to_univariate(&MPolynomial::<F, O>::from_terms(ts), 0);
+            for (i, u) in us.iter().enumerate() {
+                let delta = (s[i].clone() * e.clone()) % u.clone();
+                gs[i] = gs[i].clone() + from_univariate::<F, O>(&delta, 0).mul_term(&z, &F::one());
+            }
+        }
+    }
+    gs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zmpoly;
+    use crate::zmod::Zmod;
+
+    #[test]
+    fn test_multivariate() {
+        type P = MPolynomial<Rational>;
+        let (x, y, z) = (P::var(0), P::var(1), P::var(2));
+        // x^2 y (x + y + 1)^2 (x y - z)
+        let a = x.clone() + y.clone() + P::one();
+        let b = x.clone() * y.clone() - z.clone();
+        let f = x.clone() * x.clone() * y.clone() * a.clone() * a.clone() * b.clone();
+        let factors = factor_multivariate(&f);
+        assert_eq!(factors.len(), 4);
+        assert!(factors.contains(&(x.clone(), 2)));
+        assert!(factors.contains(&(y.clone(), 1)));
+        assert!(factors.contains(&(a, 2)));
+        assert!(factors.contains(&(b.monic(), 1)));
+        // x^2 + y^2 is irreducible over Q
+        let f = x.clone() * x.clone() + y.clone() * y.clone();
+        assert_eq!(factor_multivariate(&f), vec![(f, 1)]);
+        assert!(factor_multivariate(&P::constant(Rational::from(3))).is_empty());
+    }
+
+    #[test]
+    fn test_multivariate_mod_p() {
+        type P = MPolynomial<Zmod<3>>;
+        let c = |k: i64| P::constant(Zmod::from(k));
+        let (x, y, z) = (P::var(0), P::var(1), P::var(2));
+        // (x^3 + y)^2 (x y + z)^3 (x + y^3 z^3): the first has a zero derivative in x, the second is a cube, and
+        // the last one is x + (y z)^3 with a zero derivative in y and z
+        let a = x.clone() * x.clone() * x.clone() + y.clone();
+        let b = x.clone() * y.clone() + z.clone();
+        let d = x.clone() + pow(y.clone() * z.clone(), 3);
+        let f = pow(a.clone(), 2) * pow(b.clone(), 3) * d.clone();
+        let factors = factor_multivariate(&f);
+        assert_eq!(factors.len(), 3);
+        assert!(factors.contains(&(a, 2)));
+        assert!(factors.contains(&(b, 3)));
+        assert!(factors.contains(&(d, 1)));
+        // x^3 - y^3 = (x - y)^3, and x^3 - x y^2 = x (x - y)(x + y)
+        let f = pow(x.clone(), 3) - pow(y.clone(), 3);
+        assert_eq!(factor_multivariate(&f), vec![(x.clone() - y.clone(), 3)]);
+        let f = pow(x.clone(), 3) - x.clone() * y.clone() * y.clone();
+        assert_eq!(factor_multivariate(&f).len(), 3);
+        // x^2 + y^2 + 1 is irreducible, and so is x^2 - 2 y^2 + 1
+        for f in [
+            x.clone() * x.clone() + y.clone() * y.clone() + c(1),
+            x.clone() * x.clone() - c(2) * y.clone() * y.clone() + c(1),
+        ] {
+            assert_eq!(factor_multivariate(&f), vec![(f.monic(), 1)]);
+        }
+    }
+
+    #[test]
+    fn test_small_field() {
+        type P = MPolynomial<Zmod<2>>;
+        let (x, y) = (P::var(0), P::var(1));
+        let one = P::one();
+        // (x + y)(x + y^2) mod 2: the factors agree at y = 0 and at y = 1, so there are no good points
+        let a = x.clone() + y.clone();
+        let b = x.clone() + y.clone() * y.clone();
+        let f = a.clone() * b.clone();
+        let factors = factor_multivariate(&f);
+        assert_eq!(factors.len(), 2);
+        assert!(factors.contains(&(a, 1)));
+        assert!(factors.contains(&(b, 1)));
+        // the substitution directly, with something irreducible
+        let g = x.clone() * x.clone() * y.clone() + x.clone() + y.clone() * y.clone() + one;
+        assert_eq!(kronecker_split(&g, 0), vec![g.clone()]);
+        assert_eq!(kronecker_split(&(g.clone() * f.clone()), 0).len(), 3);
+    }
+
+    #[test]
+    fn test_multivariate_over_z() {
+        // -4 (2x y - 3)^2 (x^2 + z^3 + y) (x - y)
+        let a = zmpoly(vec![(vec![1, 1], 2), (vec![], -3)]);
+        let b = zmpoly(vec![(vec![2], 1), (vec![0, 0, 3], 1), (vec![0, 1], 1)]);
+        let d = zmpoly(vec![(vec![1], 1), (vec![0, 1], -1)]);
+        let f = (&(&a * &a) * &(&b * &d)).scale(&Integer::from(-4));
+        let (c, factors) = factor_multivariate_over_z(&f);
+        assert_eq!(c, -4);
+        assert_eq!(factors, vec![(d, 1), (a, 2), (b, 1)]);
+        // three irreducible factors that all vanish at the origin, so the evaluation point matters
+        let g = zmpoly(vec![(vec![2], 1), (vec![0, 1], 3), (vec![1, 0, 1], -1)]);
+        let h = zmpoly(vec![
+            (vec![0, 2], 1),
+            (vec![1, 0, 1], 1),
+            (vec![0, 0, 1], 2),
+        ]);
+        let k = zmpoly(vec![(vec![1, 1, 1], 1), (vec![3], 1), (vec![0, 0, 2], -5)]);
+        let (c, factors) = factor_multivariate_over_z(&(&g * &(&h * &k)));
+        assert_eq!((c, factors.len()), (Integer::from(1), 3));
+        assert!([g, h, k].iter().all(|p| factors.contains(&(p.clone(), 1))));
+    }
+}
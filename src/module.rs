@@ -23,10 +23,9 @@
 use crate::Integer;
 
 use core::fmt::Debug;
-use core::ops::{Add, Mul, Neg, Sub};
+use core::ops::{Add, Div, Mul, Neg, Sub};
 pub use num_traits::identities::{one, zero, One, Zero};
 use std::marker::PhantomData;
-use std::rc::Rc;
 use trait_set::trait_set;
 
 trait_set! {
@@ -34,6 +33,11 @@ trait_set! {
     /// in stable rust.
     pub trait RingType = AbGroupType + Mul<Output = Self> + One;
 
+    /// For fields. This only asks for a division operator, so it is up to the user that `Div` is the
+    /// inverse of `Mul` (integer division on `i64` satisfies the trait but is not a field!). Code that needs an
+    /// actual field asks for `structure::Field` instead.
+    pub trait FieldType = RingType + Div<Output = Self>;
+
     /// For abelian groups. As with `RingType`, this trait is defined as an alias in the `trait_set!` macro.
     pub trait AbGroupType =
         Eq
@@ -67,61 +71,67 @@ impl<R: RingType, A: ModType<R>, B: ModType<R>> Homo<R, A, B> {
     pub fn new(function: Box<dyn Fn(A) -> B>) -> Self {
         Self {
             ring: PhantomData,
-            function: function,
+            function,
         }
     }
 
-    /*
-        /// Compose morphisms `self`$:A \to B$ and `other`$:B \to C$.
-        pub fn compose<C: ModType<R>>(self, other: Homo<R, B,C>) -> Homo<R, A, C> {
-    Self {
-                function:
-        Box::new(compose_fns(other.as_fn(), self.as_fn())),
-            }
-        }
-        */
+    /// Applies the morphism to `a`, without consuming it.
+    pub fn apply(&self, a: A) -> B {
+        (self.function)(a)
+    }
+
+    /// Compose morphisms `self`$:A \to B$ and `other`$:B \to C$, giving `other`$\circ$`self`$:A \to C$.
+    pub fn compose<C: ModType<R> + 'static>(self, other: Homo<R, B, C>) -> Homo<R, A, C>
+    where
+        A: 'static,
+        B: 'static,
+    {
+        Homo::new(compose_fns(other.as_fn(), self.as_fn()))
+    }
 }
 
-/// Returns `a` to the power of `i`, defined as multiplying `a` by itself `i` times. Returns an error for negative `i`. 
-pub fn pow<R: RingType>(a: R, i: usize) -> R { //TODO change usize to Integer, but not sure how to iterate over them (or if it is wise). Or maybe i will need to implement my own bigint lol
-    if i < 0 {
-        println!("error!"); //TODO handle this
-        a
-    } else if i == 0 {
-        R::one()
-    } else {
-        (0..i).into_iter().fold(R::one(), |acc, x| acc * a.clone()) //TODO make less horribly inefficient 
+/// Returns `a` to the power of `i`, using repeated squaring.
+pub fn pow<R: RingType>(a: R, i: usize) -> R {
+    //TODO change usize to Integer
+    let mut base = a;
+    let mut exp = i;
+    let mut acc = R::one();
+    while exp > 0 {
+        if exp % 2 == 1 {
+            acc = acc * base.clone();
+        }
+        exp /= 2;
+        if exp > 0 {
+            base = base.clone() * base;
+        }
     }
+    acc
 }
 
-/// Add morphisms `self`, `other$:A \to B$ by elements
-impl<R: RingType, A: ModType<R>, B: ModType<R>> Add for Homo<R, A, B> {
+/// Add morphisms `self`, `other`$:A \to B$ by elements
+impl<R: RingType, A: ModType<R> + 'static, B: ModType<R> + 'static> Add for Homo<R, A, B> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
-        Self {
-            ring: PhantomData,
-            function: todo!(),
-        }
+        let f = self.as_fn();
+        let g = rhs.as_fn();
+        Self::new(Box::new(move |a: A| f(a.clone()) + g(a)))
     }
 }
 
-fn compose_fns<A, B, C, G, F>(
+fn compose_fns<A: 'static, B: 'static, C: 'static>(
     g: Box<dyn Fn(B) -> C>,
     f: Box<dyn Fn(A) -> B>,
 ) -> Box<dyn Fn(A) -> C> {
-    todo!();
+    Box::new(move |a| g(f(a)))
 }
-/*
+
 impl ModType<Integer> for Integer {
     fn mod_mul(r: Integer, m: Integer) -> Integer {
         r * m
     }
 }
 
-*/
-
-
 #[cfg(test)]
 mod test {
 
@@ -131,13 +141,27 @@ mod test {
 
     #[test]
     fn test_add() {
-        todo!();
-        // let HOMO_1: ZHomo = ZHomo::new(Box::new(|x| 3 * x));
-        // let HOMO_2: ZHomo = ZHomo::new(Box::new(|x| 2 * x));
-        // let expected = ZHomo::new(Box::new(|x| 3 * x + 2 * x));
-        // assert_eq!(
-        // (expected.as_fn())(Integer::one()),
-        // ((HOMO_1 + HOMO_2).as_fn())(Integer::one())
-        // );
+        let homo_1: ZHomo = ZHomo::new(Box::new(|x| 3 * x));
+        let homo_2: ZHomo = ZHomo::new(Box::new(|x| 2 * x));
+        let expected = ZHomo::new(Box::new(|x: Integer| 3 * x.clone() + 2 * x));
+        assert_eq!(
+            (expected.as_fn())(Integer::one()),
+            ((homo_1 + homo_2).as_fn())(Integer::one())
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let homo_1: ZHomo = ZHomo::new(Box::new(|x| 3 * x));
+        let homo_2: ZHomo = ZHomo::new(Box::new(|x| x + 1));
+        // (3x) + 1, not 3(x + 1)
+        assert_eq!(homo_1.compose(homo_2).apply(Integer::from(2)), 7);
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(pow(Integer::from(3), 5), 243);
+        assert_eq!(pow(Integer::from(7), 0), 1);
+        assert_eq!(pow(-2_i64, 3), -8);
     }
 }
@@ -0,0 +1,745 @@
+//! Multivariate polynomials
+//!
+//! `MPolynomial<R, O>` is a polynomial in the variables $x\_0, x\_1, x\_2, \ldots$ with coefficients in $R$.
+//! Unlike `Polynomial<R>`, which is dense, these are stored sparsely as a list of terms, sorted from
+//! largest to smallest by the monomial order `O`. The monomial order is part of the type, since Grobner
+//! basis computations (and so most of what you would want to do with these) depend on it.
+//!
+//! There is no fixed number of variables: a monomial is a vector of exponents where missing
+//! entries are taken to be zero, so $\mathbb{Z}[x\_0, x\_1] \subset \mathbb{Z}[x\_0, x\_1, x\_2]$ and so on.
+//! This is needed so that `MPolynomial<R>` can implement `Zero` and `One`, which take no arguments.
+use crate::helpers::mul_z_module;
+use crate::module::{ModType, RingType};
+use crate::structure::Field;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+use std::iter::once;
+
+use num_traits::identities::{One, Zero};
+
+/// A monomial $x\_0^{a\_0} x\_1^{a\_1} \cdots x\_n^{a\_n}$, stored as its vector of exponents $(a\_0, \ldots, a\_n)$.
+/// Trailing zeros are removed, so two monomials are equal if and only if their vectors are.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Monomial {
+    exps: Vec<u32>,
+}
+
+impl Monomial {
+    /// The monomial $1$.
+    pub fn one() -> Self {
+        Self::default()
+    }
+
+    /// The variable $x\_i$.
+    pub fn var(i: usize) -> Self {
+        let mut exps = vec![0; i + 1];
+        exps[i] = 1;
+        Self { exps }
+    }
+
+    /// The exponent vector, without trailing zeros.
+    pub fn exps(&self) -> &[u32] {
+        &self.exps
+    }
+
+    /// The exponent of $x\_i$.
+    pub fn exp(&self, i: usize) -> u32 {
+        self.exps.get(i).copied().unwrap_or(0)
+    }
+
+    /// One more than the index of the last variable appearing in the monomial.
+    pub fn nvars(&self) -> usize {
+        self.exps.len()
+    }
+
+    /// The total degree $a\_0 + \cdots + a\_n$.
+    pub fn deg(&self) -> u64 {
+        self.exps.iter().map(|&a| a as u64).sum()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.exps.is_empty()
+    }
+
+    /// Returns true if `self` divides `other`.
+    pub fn divides(&self, other: &Self) -> bool {
+        self.exps.len() <= other.exps.len()
+            && self.exps.iter().zip(other.exps.iter()).all(|(a, b)| a <= b)
+    }
+
+    /// Returns `self / other`, if `other` divides `self`.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.divides(self) {
+            Some(Self::from(
+                self.exps
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| a - other.exp(i))
+                    .collect::<Vec<u32>>(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The least common multiple, taking the maximum of exponents.
+    pub fn lcm(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a.max(b))
+    }
+
+    /// The greatest common divisor, taking the minimum of exponents.
+    pub fn gcd(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a.min(b))
+    }
+
+    /// Returns true if no variable appears in both monomials.
+    pub fn is_coprime(&self, other: &Self) -> bool {
+        self.gcd(other).is_one()
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u32, u32) -> u32) -> Self {
+        let n = self.exps.len().max(other.exps.len());
+        Self::from(
+            (0..n)
+                .map(|i| f(self.exp(i), other.exp(i)))
+                .collect::<Vec<u32>>(),
+        )
+    }
+}
+
+/// The product of two monomials, adding exponents.
+impl Mul for &Monomial {
+    type Output = Monomial;
+
+    fn mul(self, rhs: Self) -> Monomial {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl From<Vec<u32>> for Monomial {
+    fn from(mut exps: Vec<u32>) -> Self {
+        while exps.last() == Some(&0) {
+            exps.pop();
+        }
+        Self { exps }
+    }
+}
+
+/// A total order on monomials such that $m < n$ implies $km < kn$. Global orders also have $1 \leq m$ for all
+/// $m$, and are the ones Buchberger's algorithm works with. Local orders (with $m < 1$ for $m \neq 1$, see
+/// `local`) are for computing in the localization at the origin.
+pub trait MonomialOrder: Copy + Clone + Debug + Default + PartialEq + Eq + 'static {
+    /// True if $1 \leq m$ for all monomials $m$.
+    const GLOBAL: bool = true;
+
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering;
+}
+
+/// Lexicographic order, $x\_0 > x\_1 > \cdots$. Good for elimination, bad for everything else.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Lex;
+
+/// Graded lexicographic order: compare total degree first, then break ties with `Lex`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrLex;
+
+/// Graded reverse lexicographic order: compare total degree first, then the monomial with the
+/// smaller exponent in the last variable where they differ is larger. This is usually the fastest
+/// order for Grobner bases, and is the default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GRevLex;
+
+/// An elimination order for $x\_0$: compare the exponent of $x\_0$ first, then break ties with `GRevLex`
+/// on the other variables. A Grobner basis for this order contains one for $I \cap R[x\_1, x\_2, \ldots]$.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElimFirst;
+
+impl MonomialOrder for Lex {
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        let n = a.nvars().max(b.nvars());
+        (0..n)
+            .map(|i| a.exp(i).cmp(&b.exp(i)))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl MonomialOrder for GrLex {
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        a.deg().cmp(&b.deg()).then_with(|| Lex::cmp(a, b))
+    }
+}
+
+impl MonomialOrder for GRevLex {
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        let n = a.nvars().max(b.nvars());
+        a.deg().cmp(&b.deg()).then_with(|| {
+            (0..n)
+                .rev()
+                .map(|i| b.exp(i).cmp(&a.exp(i)))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+impl MonomialOrder for ElimFirst {
+    fn cmp(a: &Monomial, b: &Monomial) -> Ordering {
+        let rest = |m: &Monomial| {
+            Monomial::from(
+                once(0)
+                    .chain(m.exps().iter().skip(1).copied())
+                    .collect::<Vec<u32>>(),
+            )
+        };
+        a.exp(0)
+            .cmp(&b.exp(0))
+            .then_with(|| GRevLex::cmp(&rest(a), &rest(b)))
+    }
+}
+
+/// A polynomial in several variables with coefficients in $R$, and terms ordered by `O`.
+/// # Example:
+/// ```
+/// use bored_algebra::mpoly::{MPolynomial, Lex};
+///
+/// // x0^2 + 2 x0 x1 - 3
+/// let p: MPolynomial<i64, Lex> = MPolynomial::from(vec![
+///     (vec![0, 0], -3),
+///     (vec![2], 1),
+///     (vec![1, 1], 2),
+/// ]);
+/// assert_eq!(p.total_deg(), 2);
+/// assert_eq!(p.lead_coeff(), Some(&1));
+/// assert_eq!(p.eval(&[1, 1]), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MPolynomial<R, O = GRevLex> {
+    terms: Vec<(Monomial, R)>, // sorted, largest first, no zero coefficients
+    order: PhantomData<O>,
+}
+
+impl<R: RingType, O: MonomialOrder> MPolynomial<R, O> {
+    /// Makes a polynomial out of terms that are already sorted and nonzero.
+    fn from_sorted(terms: Vec<(Monomial, R)>) -> Self {
+        Self {
+            terms,
+            order: PhantomData,
+        }
+    }
+
+    /// Makes a polynomial from a list of terms in any order, combining like terms.
+    pub fn from_terms(mut terms: Vec<(Monomial, R)>) -> Self {
+        terms.sort_by(|(a, _), (b, _)| O::cmp(b, a));
+        let mut combined: Vec<(Monomial, R)> = Vec::with_capacity(terms.len());
+        for (m, c) in terms {
+            match combined.last_mut() {
+                Some((last, acc)) if *last == m => *acc = acc.clone() + c,
+                _ => combined.push((m, c)),
+            }
+        }
+        combined.retain(|(_, c)| !c.is_zero());
+        Self::from_sorted(combined)
+    }
+
+    /// The constant polynomial $c$.
+    pub fn constant(c: R) -> Self {
+        Self::from_terms(vec![(Monomial::one(), c)])
+    }
+
+    /// The polynomial $x\_i$.
+    pub fn var(i: usize) -> Self {
+        Self::from_sorted(vec![(Monomial::var(i), R::one())])
+    }
+
+    /// The polynomial $c m$.
+    pub fn term(m: Monomial, c: R) -> Self {
+        Self::from_terms(vec![(m, c)])
+    }
+
+    /// The terms, largest first with respect to `O`.
+    pub fn terms(&self) -> &[(Monomial, R)] {
+        &self.terms
+    }
+
+    /// The number of nonzero terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The leading term (monomial and coefficient), or `None` for the zero polynomial.
+    pub fn lead_term(&self) -> Option<(&Monomial, &R)> {
+        self.terms.first().map(|(m, c)| (m, c))
+    }
+
+    pub fn lead_monomial(&self) -> Option<&Monomial> {
+        self.terms.first().map(|(m, _)| m)
+    }
+
+    pub fn lead_coeff(&self) -> Option<&R> {
+        self.terms.first().map(|(_, c)| c)
+    }
+
+    /// The coefficient of the monomial `m`.
+    pub fn coeff(&self, m: &Monomial) -> R {
+        self.terms
+            .iter()
+            .find(|(n, _)| n == m)
+            .map(|(_, c)| c.clone())
+            .unwrap_or_else(R::zero)
+    }
+
+    /// One more than the largest index of a variable appearing in `self`.
+    pub fn nvars(&self) -> usize {
+        self.terms.iter().map(|(m, _)| m.nvars()).max().unwrap_or(0)
+    }
+
+    /// The total degree. The zero polynomial has degree 0.
+    pub fn total_deg(&self) -> u64 {
+        self.terms.iter().map(|(m, _)| m.deg()).max().unwrap_or(0)
+    }
+
+    /// The degree in the variable $x\_i$.
+    pub fn deg_in(&self, i: usize) -> u32 {
+        self.terms.iter().map(|(m, _)| m.exp(i)).max().unwrap_or(0)
+    }
+
+    /// Returns true if every term has the same total degree. Zero is homogeneous.
+    pub fn is_homogeneous(&self) -> bool {
+        match self.terms.first() {
+            None => true,
+            Some((m, _)) => self.terms.iter().all(|(n, _)| n.deg() == m.deg()),
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.terms.iter().all(|(m, _)| m.is_one())
+    }
+
+    /// Multiplies by the term $c m$.
+    pub fn mul_term(&self, m: &Monomial, c: &R) -> Self {
+        if c.is_zero() {
+            return Self::zero();
+        }
+        // monomial orders are compatible with multiplication, so the result is still sorted,
+        // but the coefficients might multiply to zero if R has zero divisors.
+        Self::from_sorted(
+            self.terms
+                .iter()
+                .map(|(n, d)| (n * m, d.clone() * c.clone()))
+                .filter(|(_, d)| !d.is_zero())
+                .collect(),
+        )
+    }
+
+    /// Multiplies every coefficient by `c`.
+    pub fn scale(&self, c: &R) -> Self {
+        self.mul_term(&Monomial::one(), c)
+    }
+
+    /// Evaluates `self` at the point `point`, where `point[i]` is substituted for $x\_i$. Variables
+    /// past the end of `point` are set to zero.
+    pub fn eval(&self, point: &[R]) -> R {
+        self.terms.iter().fold(R::zero(), |acc, (m, c)| {
+            let value = (0..m.nvars()).fold(c.clone(), |v, i| {
+                let x = point.get(i).cloned().unwrap_or_else(R::zero);
+                v * crate::module::pow(x, m.exp(i) as usize)
+            });
+            acc + value
+        })
+    }
+
+    /// The same polynomial with its terms sorted by a different monomial order.
+    pub fn reorder<P: MonomialOrder>(&self) -> MPolynomial<R, P> {
+        MPolynomial::from_terms(self.terms.clone())
+    }
+
+    /// Applies `f` to every coefficient.
+    pub fn map_coeffs<S: RingType>(&self, f: impl Fn(&R) -> S) -> MPolynomial<S, O> {
+        MPolynomial::from_terms(self.terms.iter().map(|(m, c)| (m.clone(), f(c))).collect())
+    }
+
+    /// Renames the variables, sending $x\_i$ to $x\_{f(i)}$. `f` is only called on variables that
+    /// appear, and should be injective on them.
+    pub fn map_vars(&self, f: impl Fn(usize) -> usize) -> Self {
+        Self::from_terms(
+            self.terms
+                .iter()
+                .map(|(m, c)| {
+                    let vars: Vec<usize> = (0..m.nvars()).filter(|&i| m.exp(i) > 0).collect();
+                    let mut exps = vec![0; vars.iter().map(|&i| f(i) + 1).max().unwrap_or(0)];
+                    for i in vars {
+                        exps[f(i)] += m.exp(i);
+                    }
+                    (Monomial::from(exps), c.clone())
+                })
+                .collect(),
+        )
+    }
+
+    /// The partial derivative with respect to $x\_i$.
+    pub fn derivative(&self, i: usize) -> Self {
+        Self::from_terms(
+            self.terms
+                .iter()
+                .filter(|(m, _)| m.exp(i) > 0)
+                .map(|(m, c)| {
+                    let mut exps = m.exps().to_vec();
+                    exps[i] -= 1;
+                    (
+                        Monomial::from(exps),
+                        mul_z_module(m.exp(i) as i64, c.clone()),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Substitutes `images[i]` for $x\_i$. Variables past the end of `images` are left alone.
+    pub fn compose(&self, images: &[Self]) -> Self {
+        self.terms.iter().fold(Self::zero(), |acc, (m, c)| {
+            let value = (0..m.nvars()).fold(Self::constant(c.clone()), |v, i| {
+                let x = images.get(i).cloned().unwrap_or_else(|| Self::var(i));
+                v * crate::module::pow(x, m.exp(i) as usize)
+            });
+            acc + value
+        })
+    }
+
+    /// Adds `other`, merging the two sorted term lists.
+    fn add_ref(&self, other: &Self) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() + other.terms.len());
+        let mut a = self.terms.iter().peekable();
+        let mut b = other.terms.iter().peekable();
+        loop {
+            match (a.peek(), b.peek()) {
+                (Some((ma, ca)), Some((mb, cb))) => match O::cmp(ma, mb) {
+                    Ordering::Greater => {
+                        terms.push((ma.clone(), ca.clone()));
+                        a.next();
+                    }
+                    Ordering::Less => {
+                        terms.push((mb.clone(), cb.clone()));
+                        b.next();
+                    }
+                    Ordering::Equal => {
+                        let c = ca.clone() + cb.clone();
+                        if !c.is_zero() {
+                            terms.push((ma.clone(), c));
+                        }
+                        a.next();
+                        b.next();
+                    }
+                },
+                (Some(t), None) | (None, Some(t)) => {
+                    terms.push((*t).clone());
+                    a.next();
+                    b.next();
+                }
+                (None, None) => break,
+            }
+        }
+        Self::from_sorted(terms)
+    }
+
+    fn mul_ref(&self, other: &Self) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() * other.terms.len());
+        for (m, c) in self.terms.iter() {
+            for (n, d) in other.terms.iter() {
+                terms.push((m * n, c.clone() * d.clone()));
+            }
+        }
+        Self::from_terms(terms)
+    }
+}
+
+impl<F: Field, O: MonomialOrder> MPolynomial<F, O> {
+    /// `self` divided by its leading coefficient. Zero stays zero.
+    pub fn monic(&self) -> Self {
+        match self.lead_coeff() {
+            Some(c) => self.scale(&(F::one() / c.clone())),
+            None => self.clone(),
+        }
+    }
+
+    /// Division with remainder by a single polynomial: returns $(q, r)$ with `self` $= qg + r$, where
+    /// no term of $r$ is divisible by the leading monomial of $g$. In particular $g$ divides `self`
+    /// exactly when $r = 0$. Panics if $g$ is zero.
+    pub fn div_rem(&self, g: &Self) -> (Self, Self) {
+        let (n, d) = g.lead_term().expect("divide by zero");
+        let (n, d) = (n.clone(), d.clone());
+        let (mut q, mut r, mut p) = (vec![], vec![], self.clone());
+        while let Some((m, c)) = p.lead_term().map(|(m, c)| (m.clone(), c.clone())) {
+            match m.checked_div(&n) {
+                Some(t) => {
+                    let coeff = c / d.clone();
+                    p = p - g.mul_term(&t, &coeff);
+                    q.push((t, coeff));
+                }
+                None => {
+                    p.terms.remove(0);
+                    r.push((m, c));
+                }
+            }
+        }
+        (Self::from_sorted(q), Self::from_sorted(r))
+    }
+}
+
+/// Makes a polynomial from a list of (exponent vector, coefficient) pairs, in any order.
+impl<R: RingType, O: MonomialOrder> From<Vec<(Vec<u32>, R)>> for MPolynomial<R, O> {
+    fn from(terms: Vec<(Vec<u32>, R)>) -> Self {
+        Self::from_terms(
+            terms
+                .into_iter()
+                .map(|(exps, c)| (Monomial::from(exps), c))
+                .collect(),
+        )
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Default for MPolynomial<R, O> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Since terms are kept sorted and without zero coefficients, equality is just equality of terms.
+impl<R: RingType, O: MonomialOrder> PartialEq for MPolynomial<R, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.terms == other.terms
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Eq for MPolynomial<R, O> {}
+
+impl<R: RingType, O: MonomialOrder> Zero for MPolynomial<R, O> {
+    fn zero() -> Self {
+        Self::from_sorted(vec![])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> One for MPolynomial<R, O> {
+    fn one() -> Self {
+        Self::constant(R::one())
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Add for MPolynomial<R, O> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.add_ref(&rhs)
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Neg for MPolynomial<R, O> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_sorted(self.terms.into_iter().map(|(m, c)| (m, -c)).collect())
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Sub for MPolynomial<R, O> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.add_ref(&(-rhs))
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Mul for MPolynomial<R, O> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.mul_ref(&rhs)
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Mul for &MPolynomial<R, O> {
+    type Output = MPolynomial<R, O>;
+
+    fn mul(self, rhs: Self) -> MPolynomial<R, O> {
+        self.mul_ref(rhs)
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> Add for &MPolynomial<R, O> {
+    type Output = MPolynomial<R, O>;
+
+    fn add(self, rhs: Self) -> MPolynomial<R, O> {
+        self.add_ref(rhs)
+    }
+}
+
+impl<R: RingType, O: MonomialOrder> ModType<MPolynomial<R, O>> for MPolynomial<R, O> {
+    fn mod_mul(r: Self, m: Self) -> Self {
+        r * m
+    }
+}
+
+/// Prints like `3*x0^2*x1 + -1*x2 + 5`.
+impl<R: RingType + Display, O: MonomialOrder> Display for MPolynomial<R, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (k, (m, c)) in self.terms.iter().enumerate() {
+            if k > 0 {
+                write!(f, " + ")?;
+            }
+            let vars = m
+                .exps()
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| **a > 0)
+                .map(|(i, a)| match a {
+                    1 => format!("x{}", i),
+                    _ => format!("x{}^{}", i, a),
+                })
+                .collect::<Vec<String>>();
+            match (vars.is_empty(), c.is_one()) {
+                (true, _) => write!(f, "{}", c)?,
+                (false, true) => write!(f, "{}", vars.join("*"))?,
+                (false, false) => write!(f, "{}*{}", c, vars.join("*"))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mono(v: Vec<u32>) -> Monomial {
+        Monomial::from(v)
+    }
+
+    #[test]
+    fn test_monomial() {
+        let a = mono(vec![1, 2, 0, 0]);
+        let b = mono(vec![0, 1, 3]);
+        assert_eq!(a.exps(), &[1, 2]);
+        assert_eq!(&a * &b, mono(vec![1, 3, 3]));
+        assert_eq!(a.lcm(&b), mono(vec![1, 2, 3]));
+        assert_eq!(a.gcd(&b), mono(vec![0, 1]));
+        assert!(mono(vec![0, 1]).divides(&a));
+        assert!(!a.divides(&b));
+        assert_eq!(a.checked_div(&mono(vec![1, 1])), Some(mono(vec![0, 1])));
+        assert_eq!(a.checked_div(&b), None);
+        assert!(mono(vec![1]).is_coprime(&mono(vec![0, 4])));
+    }
+
+    #[test]
+    fn test_orders() {
+        // x0 x1^2 vs x0^2 x2
+        let a = mono(vec![1, 2, 0]);
+        let b = mono(vec![2, 0, 1]);
+        assert_eq!(Lex::cmp(&a, &b), Ordering::Less);
+        assert_eq!(GrLex::cmp(&a, &b), Ordering::Less);
+        assert_eq!(GRevLex::cmp(&a, &b), Ordering::Greater);
+        // degree wins for the graded orders
+        let c = mono(vec![3]);
+        let d = mono(vec![0, 2, 2]);
+        assert_eq!(Lex::cmp(&c, &d), Ordering::Greater);
+        assert_eq!(GrLex::cmp(&c, &d), Ordering::Less);
+        assert_eq!(GRevLex::cmp(&c, &d), Ordering::Less);
+        assert_eq!(GRevLex::cmp(&Monomial::one(), &c), Ordering::Less);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        type P = MPolynomial<i64>;
+        let x = P::var(0);
+        let y = P::var(1);
+        // (x + y)(x - y) = x^2 - y^2
+        assert_eq!(
+            (x.clone() + y.clone()) * (x.clone() - y.clone()),
+            P::from(vec![(vec![2], 1), (vec![0, 2], -1)])
+        );
+        assert_eq!(x.clone() - x.clone(), P::zero());
+        assert_eq!(x.clone() * P::one(), x.clone());
+        assert_eq!(x.clone() * P::zero(), P::zero());
+        let p = P::from(vec![(vec![1, 1], 2), (vec![0, 0, 1], 1), (vec![], -1)]);
+        assert_eq!(p.len(), 3);
+        assert_eq!(p.total_deg(), 2);
+        assert_eq!(p.nvars(), 3);
+        assert_eq!(p.coeff(&mono(vec![1, 1])), 2);
+        assert!(!p.is_homogeneous());
+        assert!((x.clone() * y.clone() + x.clone() * x).is_homogeneous());
+        assert_eq!(p.eval(&[2, 3, 5]), 16);
+    }
+
+    #[test]
+    fn test_lead_term() {
+        // x0 x1^2 + x0^2 x2
+        let p: MPolynomial<i64, Lex> = MPolynomial::from(vec![(vec![1, 2], 1), (vec![2, 0, 1], 5)]);
+        assert_eq!(p.lead_term(), Some((&mono(vec![2, 0, 1]), &5)));
+        let q: MPolynomial<i64, GRevLex> = p.reorder();
+        assert_eq!(q.lead_coeff(), Some(&1));
+        assert_eq!(q.reorder::<Lex>(), p);
+    }
+
+    #[test]
+    fn test_elim_first() {
+        // x0 beats any power of the other variables
+        let a = mono(vec![1]);
+        let b = mono(vec![0, 5, 5]);
+        assert_eq!(ElimFirst::cmp(&a, &b), Ordering::Greater);
+        assert_eq!(
+            ElimFirst::cmp(&mono(vec![1, 2]), &mono(vec![1, 0, 1])),
+            Ordering::Greater
+        );
+        assert_eq!(
+            ElimFirst::cmp(&mono(vec![1, 0, 3]), &mono(vec![1, 0, 3])),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_substitution() {
+        type P = MPolynomial<crate::Rational>;
+        let (x, y) = (P::var(0), P::var(1));
+        let p = x.clone() * x.clone() * y.clone() + y.clone();
+        // swap x and y
+        assert_eq!(
+            p.map_vars(|i| 1 - i),
+            y.clone() * y.clone() * x.clone() + x.clone()
+        );
+        // x -> x + y
+        assert_eq!(
+            p.compose(&[x.clone() + y.clone()]),
+            (x.clone() + y.clone()) * (x.clone() + y.clone()) * y.clone() + y.clone()
+        );
+        assert_eq!(
+            p.derivative(0),
+            (x.clone() * y.clone()).scale(&crate::Rational::from(2))
+        );
+        assert_eq!(p.derivative(2), P::zero());
+        let (q, r) = p.div_rem(&(x.clone() * y.clone()));
+        assert_eq!((q, r), (x.clone(), y.clone()));
+        let (q, r) = (p.clone() * (x.clone() - y.clone())).div_rem(&(x.clone() - y.clone()));
+        assert_eq!((q, r), (p.clone(), P::zero()));
+        assert_eq!(x.scale(&crate::Rational::from(3)).monic(), x);
+    }
+
+    #[test]
+    fn test_display() {
+        let p: MPolynomial<i64> =
+            MPolynomial::from(vec![(vec![2, 1], 3), (vec![], 5), (vec![0, 0, 1], 1)]);
+        assert_eq!(format!("{}", p), "3*x0^2*x1 + x2 + 5");
+        assert_eq!(format!("{}", MPolynomial::<i64>::zero()), "0");
+    }
+}
@@ -0,0 +1,200 @@
+//! Newton polygons
+//!
+//! The Newton polygon of $f = a\_0 + a\_1 x + \cdots + a\_n x^n$ at a prime $p$ is the lower convex hull of the points
+//! $(i, v\_p(a\_i))$ for the nonzero coefficients. If it has a segment of slope $s$ and horizontal length $l$, then
+//! exactly $l$ roots of $f$ (in an algebraic closure of $\mathbb{Q}\_p$, with multiplicity) have valuation $-s$.
+//!
+//! Newton polygons multiply by Minkowski sum, so the polygon of a factor is made of pieces of the segments of $f$.
+//! The roots of an irreducible factor over $\mathbb{Q}\_p$ are conjugate, so they all have the same valuation, and
+//! the denominator $e$ of its slope in lowest terms divides its degree. That bounds the degrees of the factors over
+//! $\mathbb{Q}\_p$, and so over $\mathbb{Q}$, since those are products of the $p$-adic ones. The simplest case is
+//! Eisenstein's criterion, a single segment from $(0, 1)$ to $(n, 0)$, and in general a single segment whose slope
+//! has denominator $n$ is enough (Dumas' criterion).
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::{Integer, Rational};
+//!
+//! // x^3 + 2x + 4 at p = 2: the points are (0, 2), (1, 1), (3, 0)
+//! let f = Polynomial::from(vec![Integer::from(4), Integer::from(2), Integer::new(), Integer::from(1)]);
+//! let polygon = f.newton_polygon(&Integer::from(2));
+//! assert_eq!(polygon.vertices(), &[(0, 2), (1, 1), (3, 0)]);
+//! // one root of valuation 1 and two of valuation 1/2
+//! assert_eq!(polygon.root_valuations(), vec![(Rational::from(1), 1), (Rational::from((1, 2)), 2)]);
+//! // so a factor over Q_2 has degree 1, 2 or 3, and x^3 + 2x + 4 might not be irreducible
+//! assert_eq!(polygon.factor_degrees(), vec![1, 2, 3]);
+//! assert!(!polygon.is_irreducible());
+//!
+//! // x^4 + 4x^2 + 8 is irreducible by Dumas: the slope -3/4 has denominator 4
+//! let g = Polynomial::from(vec![Integer::from(8), Integer::new(), Integer::from(4), Integer::new(), Integer::from(1)]);
+//! assert!(g.newton_polygon(&Integer::from(2)).is_irreducible());
+//! ```
+use crate::{Integer, Rational};
+
+/// Rings with a $p$-adic valuation, for primes $p$.
+pub trait Valuation {
+    /// $v\_p$ of `self`, or `None` for zero, which has valuation $\infty$.
+    fn valuation(&self, p: &Integer) -> Option<i64>;
+}
+
+impl Valuation for Integer {
+    fn valuation(&self, p: &Integer) -> Option<i64> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.clone().remove_factor(p).1 as i64),
+        }
+    }
+}
+
+impl Valuation for Rational {
+    fn valuation(&self, p: &Integer) -> Option<i64> {
+        let v = self.numer().valuation(p)?;
+        Some(v - self.denom().valuation(p).unwrap())
+    }
+}
+
+/// The lower convex hull of some points $(i, v)$, as its vertices from left to right. See the module docs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewtonPolygon {
+    vertices: Vec<(u64, i64)>,
+}
+
+impl NewtonPolygon {
+    /// The lower convex hull of `points`, which need distinct $x$-coordinates. Panics if there are none.
+    pub fn new(points: &[(u64, i64)]) -> Self {
+        assert!(!points.is_empty(), "no points");
+        let mut points = points.to_vec();
+        points.sort();
+        // Andrew's monotone chain, keeping left turns only
+        let mut vertices: Vec<(u64, i64)> = vec![];
+        for (x, y) in points {
+            while let [.., (x1, y1), (x2, y2)] = vertices[..] {
+                let cross = (x2 as i128 - x1 as i128) * (y as i128 - y1 as i128)
+                    - (y2 as i128 - y1 as i128) * (x as i128 - x1 as i128);
+                if cross > 0 {
+                    break;
+                }
+                vertices.pop();
+            }
+            vertices.push((x, y));
+        }
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[(u64, i64)] {
+        &self.vertices
+    }
+
+    /// The segments as (slope, horizontal length), from left to right, so with increasing slopes.
+    pub fn segments(&self) -> Vec<(Rational, u64)> {
+        self.vertices
+            .windows(2)
+            .map(|w| {
+                let ((x1, y1), (x2, y2)) = (w[0], w[1]);
+                (Rational::from((y2 - y1, (x2 - x1) as i64)), x2 - x1)
+            })
+            .collect()
+    }
+
+    pub fn slopes(&self) -> Vec<Rational> {
+        self.segments().into_iter().map(|(s, _)| s).collect()
+    }
+
+    /// The valuations $-s$ of the nonzero roots with how many roots have them, from the largest valuation down. Roots
+    /// at zero (for a polygon starting at $i > 0$) aren't included.
+    pub fn root_valuations(&self) -> Vec<(Rational, u64)> {
+        self.segments().into_iter().map(|(s, l)| (-s, l)).collect()
+    }
+
+    /// The degrees from 1 up to the degree of $f$ that a factor of $f$ over $\mathbb{Q}\_p$ could have. A factor
+    /// takes a multiple of $e$ from each segment of length $l$ and slope with denominator $e$, and any power of $x$
+    /// dividing $f$.
+    pub fn factor_degrees(&self) -> Vec<u64> {
+        let start = self.vertices[0].0;
+        let n = self.vertices.last().unwrap().0 as usize;
+        let mut possible = vec![false; n + 1];
+        for d in possible.iter_mut().take(start as usize + 1) {
+            *d = true;
+        }
+        for (s, l) in self.segments() {
+            let e = s.denom().to_usize().unwrap();
+            let previous = possible.clone();
+            for (d, _) in previous.iter().enumerate().filter(|(_, &p)| p) {
+                for k in (e..=l as usize).step_by(e) {
+                    possible[d + k] = true;
+                }
+            }
+        }
+        (1..=n as u64).filter(|&d| possible[d as usize]).collect()
+    }
+
+    /// Whether the polygon shows that $f$ is irreducible over $\mathbb{Q}\_p$, and so over $\mathbb{Q}$ if it has
+    /// rational coefficients: no factor degree but $\deg f$ is possible. Not being able to show it doesn't mean $f$
+    /// is reducible.
+    pub fn is_irreducible(&self) -> bool {
+        self.factor_degrees() == [self.vertices.last().unwrap().0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::poly::Polynomial;
+    use crate::test_helpers::zpoly;
+
+    #[test]
+    fn test_valuation() {
+        let p = Integer::from(3);
+        assert_eq!(Integer::from(-162).valuation(&p), Some(4));
+        assert_eq!(Integer::from(5).valuation(&p), Some(0));
+        assert_eq!(Integer::new().valuation(&p), None);
+        assert_eq!(Rational::from((4, 45)).valuation(&p), Some(-2));
+        assert_eq!(Rational::new().valuation(&p), None);
+    }
+
+    #[test]
+    fn test_newton_polygon() {
+        let two = Integer::from(2);
+        // collinear points are dropped: x^2 + 2x + 4 has one segment of slope -1
+        let polygon = zpoly(vec![4, 2, 1]).newton_polygon(&two);
+        assert_eq!(polygon.vertices(), &[(0, 2), (2, 0)]);
+        assert_eq!(polygon.segments(), vec![(Rational::from(-1), 2)]);
+        // so the factors have degree 1 or 2, and x^2 + 2x + 4 is irreducible, but that's not visible at 2
+        assert_eq!(polygon.factor_degrees(), vec![1, 2]);
+        // Eisenstein at 3: x^5 + 3x + 6
+        let f = zpoly(vec![6, 3, 0, 0, 0, 1]);
+        assert!(f.newton_polygon(&Integer::from(3)).is_irreducible());
+        assert!(!f.newton_polygon(&two).is_irreducible());
+        // x^2 (x - 2): a double root at 0 and one of valuation 1
+        let polygon = zpoly(vec![0, 0, -2, 1]).newton_polygon(&two);
+        assert_eq!(polygon.vertices(), &[(2, 1), (3, 0)]);
+        assert_eq!(polygon.root_valuations(), vec![(Rational::from(1), 1)]);
+        assert_eq!(polygon.factor_degrees(), vec![1, 2, 3]);
+        // segments of lengths 2 and 4 with denominators 2 and 4: factors have degree 2, 4 or 6
+        let polygon = Polynomial::from(vec![
+            Rational::from(4),
+            Rational::new(),
+            Rational::from(2),
+            Rational::new(),
+            Rational::new(),
+            Rational::new(),
+            Rational::from(1),
+        ])
+        .newton_polygon(&two);
+        assert_eq!(
+            polygon.slopes(),
+            vec![Rational::from((-1, 2)), Rational::from((-1, 4))]
+        );
+        assert_eq!(polygon.factor_degrees(), vec![2, 4, 6]);
+        // rational coefficients: (x^2 - 1/2)/4
+        let polygon = Polynomial::from(vec![
+            Rational::from((-1, 8)),
+            Rational::new(),
+            Rational::from((1, 4)),
+        ])
+        .newton_polygon(&two);
+        assert_eq!(polygon.vertices(), &[(0, -3), (2, -2)]);
+        assert!(polygon.is_irreducible());
+    }
+}
@@ -0,0 +1,365 @@
+//! Arithmetic functions
+//!
+//! A function $f$ on the positive integers is multiplicative if $f(mn) = f(m) f(n)$ for coprime $m, n$, so it's
+//! determined by its values on prime powers. That covers the usual ones:
+//! * Euler's $\varphi(p^e) = p^{e-1}(p - 1)$, the number of units mod $n$,
+//! * Möbius' $\mu(p) = -1$ and $\mu(p^e) = 0$ for $e > 1$,
+//! * $\sigma\_k(n) = \sum\_{d \mid n} d^k$, and $\tau = \sigma\_0$, the number of divisors.
+//!
+//! A single value needs $n$ factored. For all $n \leq N$ at once, a sieve for the smallest prime factors gives every
+//! factorization for $O(N \log N)$ work in total, which is what the `_up_to` functions do.
+//!
+//! Arithmetic functions form a ring under pointwise addition and Dirichlet convolution
+//! $(f * g)(n) = \sum\_{d \mid n} f(d) g(n/d)$, which is the multiplication of the Dirichlet series
+//! $\sum\_n f(n) n^{-s}$. The unit is $\varepsilon$ (1 at 1, 0 elsewhere), and $f$ is invertible exactly when $f(1)$
+//! is. The classic identities are $\mu * 1 = \varepsilon$ (Möbius inversion), $\varphi * 1 = \mathrm{id}$ and
+//! $\sigma\_k = \mathrm{id}\_k * 1$. `DirichletSeries` is the truncation to $n \leq N$, which is closed under all of
+//! that.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::arith::{euler_phi, euler_phi_up_to, mobius, mobius_up_to, DirichletSeries};
+//! use bored_algebra::Integer;
+//!
+//! assert_eq!(euler_phi(&Integer::from(36)), 12);
+//! assert_eq!(mobius(&Integer::from(30)), -1);
+//! // phi * 1 = id
+//! let phi = DirichletSeries::from_fn(100, |n| Integer::from(euler_phi_up_to(100)[n]));
+//! let one = DirichletSeries::from_fn(100, |_| Integer::from(1));
+//! assert_eq!(phi * one.clone(), DirichletSeries::from_fn(100, Integer::from));
+//! // and the inverse of 1 is mu
+//! let mu = mobius_up_to(100);
+//! assert_eq!(one.inverse().unwrap(), DirichletSeries::from_fn(100, |n| Integer::from(mu[n])));
+//! ```
+use crate::error::ArithmeticError;
+use crate::module::RingType;
+use crate::ntheory::factor::factor_integer;
+use crate::structure::IntegralDomain;
+use crate::Integer;
+use rug::ops::Pow;
+
+use core::ops::{Add, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+
+/// The multiplicative function with the values `f(p, e)` on prime powers, at $n > 0$.
+fn multiplicative(n: &Integer, f: impl Fn(&Integer, u32) -> Integer) -> Integer {
+    assert!(*n > 0, "arithmetic functions need n > 0");
+    factor_integer(n)
+        .iter()
+        .fold(Integer::from(1), |acc, (p, e)| acc * f(p, *e as u32))
+}
+
+/// Euler's $\varphi(n)$, the number of $0 \leq a < n$ coprime to $n$. Panics unless $n > 0$.
+pub fn euler_phi(n: &Integer) -> Integer {
+    multiplicative(n, |p, e| p.clone().pow(e - 1) * Integer::from(p - 1))
+}
+
+/// The Möbius function $\mu(n)$: $(-1)^k$ for squarefree $n$ with $k$ prime factors, and 0 otherwise. Panics unless
+/// $n > 0$.
+pub fn mobius(n: &Integer) -> i32 {
+    multiplicative(n, |_, e| match e {
+        1 => Integer::from(-1),
+        _ => Integer::new(),
+    })
+    .to_i32()
+    .unwrap()
+}
+
+/// $\sigma\_k(n)$, the sum of the $k$th powers of the divisors of $n$. Panics unless $n > 0$.
+pub fn divisor_sigma(n: &Integer, k: u32) -> Integer {
+    multiplicative(n, |p, e| {
+        let q = p.clone().pow(k);
+        (0..=e).fold(Integer::new(), |acc, i| acc + q.clone().pow(i))
+    })
+}
+
+/// $\tau(n) = \sigma\_0(n)$, the number of divisors of $n$. Panics unless $n > 0$.
+pub fn divisor_count(n: &Integer) -> Integer {
+    multiplicative(n, |_, e| Integer::from(e + 1))
+}
+
+/// The positive divisors of $n > 0$ in increasing order.
+pub fn divisors(n: &Integer) -> Vec<Integer> {
+    assert!(*n > 0, "arithmetic functions need n > 0");
+    let mut divisors = vec![Integer::from(1)];
+    for (p, e) in factor_integer(n) {
+        let mut powers = vec![];
+        for d in &divisors {
+            let mut q = d.clone();
+            for _ in 0..e {
+                q *= &p;
+                powers.push(q.clone());
+            }
+        }
+        divisors.extend(powers);
+    }
+    divisors.sort();
+    divisors
+}
+
+/// The smallest prime factor of each $n \leq$ `bound`, with 0 for $n = 0, 1$. A linear sieve: each composite is
+/// crossed off once, by its smallest prime.
+pub fn smallest_prime_factors(bound: usize) -> Vec<usize> {
+    let mut spf = vec![0; bound + 1];
+    let mut primes = vec![];
+    for i in 2..=bound {
+        if spf[i] == 0 {
+            spf[i] = i;
+            primes.push(i);
+        }
+        for &p in &primes {
+            if p > spf[i] || i * p > bound {
+                break;
+            }
+            spf[i * p] = p;
+        }
+    }
+    spf
+}
+
+/// The values $f(0), \ldots, f(N)$ of the multiplicative function with values `f(p, e)` on prime powers, with
+/// $f(0) = 0$, from the factorizations by `smallest_prime_factors`.
+pub fn multiplicative_up_to<T: Clone + Zero + One + Mul<Output = T>>(
+    bound: usize,
+    f: impl Fn(u64, u32) -> T,
+) -> Vec<T> {
+    let spf = smallest_prime_factors(bound);
+    let mut values = vec![T::zero(); bound + 1];
+    if bound >= 1 {
+        values[1] = T::one();
+    }
+    for n in 2..=bound {
+        let p = spf[n];
+        let (mut m, mut e) = (n / p, 1);
+        while m % p == 0 {
+            m /= p;
+            e += 1;
+        }
+        values[n] = values[m].clone() * f(p as u64, e);
+    }
+    values
+}
+
+/// $\varphi(n)$ for $n \leq$ `bound`, with $\varphi(0) = 0$.
+pub fn euler_phi_up_to(bound: usize) -> Vec<u64> {
+    multiplicative_up_to(bound, |p, e| p.pow(e - 1) * (p - 1))
+}
+
+/// $\mu(n)$ for $n \leq$ `bound`, with $\mu(0) = 0$.
+pub fn mobius_up_to(bound: usize) -> Vec<i64> {
+    multiplicative_up_to(bound, |_, e| match e {
+        1 => -1,
+        _ => 0,
+    })
+}
+
+/// $\tau(n)$ for $n \leq$ `bound`, with $\tau(0) = 0$.
+pub fn divisor_count_up_to(bound: usize) -> Vec<u64> {
+    multiplicative_up_to(bound, |_, e| e as u64 + 1)
+}
+
+/// $\sigma\_k(n)$ for $n \leq$ `bound`, with $\sigma\_k(0) = 0$.
+pub fn divisor_sigma_up_to(bound: usize, k: u32) -> Vec<Integer> {
+    multiplicative_up_to(bound, |p, e| {
+        let q = Integer::from(p).pow(k);
+        (0..=e).fold(Integer::new(), |acc, i| acc + q.clone().pow(i))
+    })
+}
+
+/// An arithmetic function $f$ with values in $R$, known at $1, \ldots, N$, or the Dirichlet series
+/// $\sum\_{n \leq N} f(n) n^{-s}$. Sums and products of series with different $N$ are truncated to the smaller one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletSeries<R: RingType> {
+    coeffs: Vec<R>, // coeffs[n - 1] = f(n)
+}
+
+impl<R: RingType> DirichletSeries<R> {
+    /// The function with the values $f(1), \ldots, f(N)$.
+    pub fn new(coeffs: Vec<R>) -> Self {
+        Self { coeffs }
+    }
+
+    /// The function $f$ at $1, \ldots, N$.
+    pub fn from_fn(bound: usize, f: impl Fn(usize) -> R) -> Self {
+        Self::new((1..=bound).map(f).collect())
+    }
+
+    /// The unit $\varepsilon$ for Dirichlet convolution.
+    pub fn identity(bound: usize) -> Self {
+        Self::from_fn(bound, |n| match n {
+            1 => R::one(),
+            _ => R::zero(),
+        })
+    }
+
+    /// $N$, the largest $n$ where the values are known.
+    pub fn bound(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// $f(n)$. Panics unless $1 \leq n \leq N$.
+    pub fn coeff(&self, n: usize) -> R {
+        assert!(n >= 1 && n <= self.bound(), "f({}) isn't known", n);
+        self.coeffs[n - 1].clone()
+    }
+
+    /// $f(1), \ldots, f(N)$.
+    pub fn coeffs(&self) -> &[R] {
+        &self.coeffs
+    }
+
+    fn pointwise(self, other: Self, op: impl Fn(R, R) -> R) -> Self {
+        Self::new(
+            self.coeffs
+                .into_iter()
+                .zip(other.coeffs)
+                .map(|(a, b)| op(a, b))
+                .collect(),
+        )
+    }
+}
+
+impl<R: RingType + IntegralDomain> DirichletSeries<R> {
+    /// The inverse for Dirichlet convolution, from $g(1) = f(1)^{-1}$ and
+    /// $g(n) = -f(1)^{-1} \sum\_{d \mid n, d < n} f(n/d) g(d)$. Fails with `NotUnit` unless $f(1)$ is a unit.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        let n = self.bound();
+        if n == 0 {
+            return Ok(self.clone());
+        }
+        let inv = R::one()
+            .div_exact(&self.coeffs[0])
+            .ok_or(ArithmeticError::NotUnit)?;
+        // sums[m - 1] collects the sum over d | m, d < m, filled in as the g(d) are found
+        let mut sums = vec![R::zero(); n];
+        let mut g = Vec::with_capacity(n);
+        for d in 1..=n {
+            let gd = match d {
+                1 => inv.clone(),
+                _ => -(inv.clone() * sums[d - 1].clone()),
+            };
+            for m in (2 * d..=n).step_by(d) {
+                sums[m - 1] = sums[m - 1].clone() + self.coeffs[m / d - 1].clone() * gd.clone();
+            }
+            g.push(gd);
+        }
+        Ok(Self::new(g))
+    }
+}
+
+/// Pointwise.
+impl<R: RingType> Add for DirichletSeries<R> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.pointwise(other, |a, b| a + b)
+    }
+}
+
+impl<R: RingType> Neg for DirichletSeries<R> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(self.coeffs.into_iter().map(|a| -a).collect())
+    }
+}
+
+impl<R: RingType> Sub for DirichletSeries<R> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.pointwise(other, |a, b| a - b)
+    }
+}
+
+/// Dirichlet convolution.
+impl<R: RingType> Mul for DirichletSeries<R> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let n = self.bound().min(other.bound());
+        let mut coeffs = vec![R::zero(); n];
+        for d in 1..=n {
+            if self.coeffs[d - 1] == R::zero() {
+                continue;
+            }
+            for e in 1..=n / d {
+                coeffs[d * e - 1] = coeffs[d * e - 1].clone()
+                    + self.coeffs[d - 1].clone() * other.coeffs[e - 1].clone();
+            }
+        }
+        Self::new(coeffs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Rational;
+
+    #[test]
+    fn test_values() {
+        let n = |a: u64| Integer::from(a);
+        assert_eq!(euler_phi(&n(1)), 1);
+        assert_eq!(euler_phi(&n(1 << 40)), 1_u64 << 39);
+        assert_eq!(mobius(&n(1)), 1);
+        assert_eq!(mobius(&n(12)), 0);
+        assert_eq!(mobius(&n(2 * 3 * 5 * 7)), 1);
+        assert_eq!(divisor_count(&n(36)), 9);
+        assert_eq!(divisor_sigma(&n(28), 1), 56); // perfect
+        assert_eq!(divisor_sigma(&n(6), 2), 50);
+        assert_eq!(divisors(&n(12)), [1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(&n(1)), [1]);
+
+        // the tables agree with the factorizations
+        let bound = 500;
+        let (phi, mu) = (euler_phi_up_to(bound), mobius_up_to(bound));
+        let (tau, sigma) = (divisor_count_up_to(bound), divisor_sigma_up_to(bound, 3));
+        for k in 1..=bound as u64 {
+            let i = k as usize;
+            assert_eq!(euler_phi(&n(k)), phi[i]);
+            assert_eq!(mobius(&n(k)) as i64, mu[i]);
+            assert_eq!(divisor_count(&n(k)), tau[i]);
+            assert_eq!(divisor_sigma(&n(k), 3), sigma[i]);
+            assert_eq!(divisors(&n(k)).len() as u64, tau[i]);
+        }
+        assert_eq!(
+            smallest_prime_factors(10),
+            [0, 0, 2, 3, 2, 5, 2, 7, 2, 3, 2]
+        );
+        assert_eq!(euler_phi_up_to(0), [0]);
+    }
+
+    #[test]
+    fn test_dirichlet() {
+        let bound = 200;
+        let one = DirichletSeries::from_fn(bound, |_| Integer::from(1));
+        let id = DirichletSeries::from_fn(bound, Integer::from);
+        let mu = mobius_up_to(bound);
+        let mu = DirichletSeries::from_fn(bound, |n| Integer::from(mu[n]));
+        // Mobius inversion, and sigma_1 = id * 1, tau = 1 * 1
+        assert_eq!(mu.clone() * one.clone(), DirichletSeries::identity(bound));
+        let sigma = divisor_sigma_up_to(bound, 1);
+        assert_eq!(
+            id.clone() * one.clone(),
+            DirichletSeries::from_fn(bound, |n| sigma[n].clone())
+        );
+        let tau = divisor_count_up_to(bound);
+        assert_eq!(
+            one.clone() * one.clone(),
+            DirichletSeries::from_fn(bound, |n| Integer::from(tau[n]))
+        );
+        assert_eq!(mu.inverse().unwrap(), one);
+        assert_eq!(
+            (one.clone() + mu.clone() - one.clone()).coeffs(),
+            mu.coeffs()
+        );
+        // truncated to the shorter one
+        assert_eq!((id.clone() * DirichletSeries::identity(10)).bound(), 10);
+        // 2 isn't a unit in Z, but it is in Q
+        let two = DirichletSeries::from_fn(bound, |_| Integer::from(2));
+        assert!(matches!(two.inverse(), Err(ArithmeticError::NotUnit)));
+        let f = DirichletSeries::from_fn(bound, |n| Rational::from((n as i64 + 1, 3)));
+        assert_eq!(f.inverse().unwrap() * f, DirichletSeries::identity(bound));
+    }
+}
@@ -0,0 +1,389 @@
+//! Continued fractions
+//!
+//! Every real $x$ is $[a\_0; a\_1, a\_2, \ldots] = a\_0 + 1/(a\_1 + 1/(a\_2 + \cdots))$ with $a\_0 = \lfloor x \rfloor$
+//! and $a\_i \geq 1$ after that, found by taking $x \mapsto 1/(x - \lfloor x \rfloor)$ repeatedly. The expansion
+//! ends exactly when $x$ is rational (it's the Euclidean algorithm on the numerator and denominator), and is
+//! eventually periodic exactly when $x$ is a quadratic irrational (Lagrange). The convergents
+//! $p\_k/q\_k = [a\_0; a\_1, \ldots, a\_k]$ come from $p\_k = a\_k p\_{k-1} + p\_{k-2}$ and the same for $q\_k$.
+//!
+//! The convergents are the best approximations: no fraction with a denominator $\leq q\_k$ is closer to $x$. Between
+//! them are the semiconvergents $(p\_{k-1} + j p\_k)/(q\_{k-1} + j q\_k)$, and the best approximation with a bounded
+//! denominator is always one of those. A `Float` is an exact dyadic rational, so `best_approximation` works on that.
+//!
+//! Rational reconstruction goes the other way: given $a \bmod m$, it finds $r/s$ with $r \equiv as \pmod m$ and
+//! $|r| \leq N$, $0 < s \leq D$. When $2ND < m$ there's at most one, and it's one of the remainders of the extended
+//! Euclidean algorithm on $m$ and $a$. With $N = D = \sqrt{m/2}$ that recovers any rational whose numerator and
+//! denominator are small enough from its image mod $m$, which is the last step of modular algorithms over
+//! $\mathbb{Q}$.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::continued_fraction::{
+//!     best_approximation, continued_fraction, rational_reconstruction, sqrt_continued_fraction,
+//! };
+//! use bored_algebra::{Integer, Rational};
+//! use rug::float::Constant;
+//! use rug::Float;
+//!
+//! let ints = |v: Vec<i64>| v.into_iter().map(Integer::from).collect::<Vec<_>>();
+//! assert_eq!(continued_fraction(&Rational::from((415, 93))), ints(vec![4, 2, 6, 7]));
+//!
+//! // sqrt(7) = [2; 1, 1, 1, 4, 1, 1, 1, 4, ...]
+//! let cf = sqrt_continued_fraction(&Integer::from(7));
+//! assert_eq!((cf.preperiod(), cf.period()), (&ints(vec![2])[..], &ints(vec![1, 1, 1, 4])[..]));
+//! // and the convergent before the end of the first period solves Pell's equation: 8^2 - 7 * 3^2 = 1
+//! assert_eq!(cf.convergents(4)[3], Rational::from((8, 3)));
+//!
+//! let pi = Float::with_val(53, Constant::Pi);
+//! assert_eq!(best_approximation(&pi, &Integer::from(1000)), Rational::from((355, 113)));
+//!
+//! // -2/3 mod 10007
+//! let a = Integer::from(-2) * Integer::from(3).invert(&Integer::from(10007)).unwrap();
+//! assert_eq!(rational_reconstruction(&a, &Integer::from(10007)), Some(Rational::from((-2, 3))));
+//! ```
+use crate::{Integer, Rational};
+use rug::ops::RemRounding;
+use rug::Float;
+
+use std::collections::HashMap;
+
+/// The continued fraction $[a\_0; a\_1, \ldots, a\_n]$ of $x$, with $a\_n > 1$ unless $n = 0$.
+pub fn continued_fraction(x: &Rational) -> Vec<Integer> {
+    let (mut p, mut q) = (x.numer().clone(), x.denom().clone());
+    let mut terms = vec![];
+    while q != 0 {
+        let (a, r) = p.div_rem_floor(q.clone());
+        terms.push(a);
+        (p, q) = (q, r);
+    }
+    terms
+}
+
+/// $[a\_0; a\_1, \ldots, a\_n]$. Panics if there are no terms, or a zero denominator comes up.
+pub fn from_continued_fraction(terms: &[Integer]) -> Rational {
+    convergents(terms).pop().expect("no terms")
+}
+
+/// The convergents $p\_k/q\_k = [a\_0; a\_1, \ldots, a\_k]$ for $k = 0, \ldots, n$.
+pub fn convergents(terms: &[Integer]) -> Vec<Rational> {
+    let (mut p, mut p_prev) = (Integer::from(1), Integer::new());
+    let (mut q, mut q_prev) = (Integer::new(), Integer::from(1));
+    terms
+        .iter()
+        .map(|a| {
+            (p, p_prev) = (Integer::from(a * &p) + &p_prev, p.clone());
+            (q, q_prev) = (Integer::from(a * &q) + &q_prev, q.clone());
+            Rational::from((p.clone(), q.clone()))
+        })
+        .collect()
+}
+
+/// An eventually periodic continued fraction $[a\_0; \ldots, a\_{k-1}, \overline{b\_0, \ldots, b\_{l-1}}]$, which is
+/// the expansion of a quadratic irrational.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeriodicContinuedFraction {
+    preperiod: Vec<Integer>,
+    period: Vec<Integer>,
+}
+
+impl PeriodicContinuedFraction {
+    /// $a\_0, \ldots, a\_{k-1}$, the terms before the period.
+    pub fn preperiod(&self) -> &[Integer] {
+        &self.preperiod
+    }
+
+    /// $b\_0, \ldots, b\_{l-1}$, the terms that repeat.
+    pub fn period(&self) -> &[Integer] {
+        &self.period
+    }
+
+    /// All the terms, which never end.
+    pub fn terms(&self) -> impl Iterator<Item = Integer> + '_ {
+        self.preperiod
+            .iter()
+            .chain(self.period.iter().cycle())
+            .cloned()
+    }
+
+    /// The first $n$ convergents.
+    pub fn convergents(&self, n: usize) -> Vec<Rational> {
+        convergents(&self.terms().take(n).collect::<Vec<_>>())
+    }
+}
+
+/// The continued fraction of $(P + \sqrt{D})/Q$ for $Q \neq 0$ and a $D > 0$ that isn't a square.
+///
+/// The complete quotients stay in the form $(P\_i + \sqrt{D})/Q\_i$ with $Q\_i \mid D - P\_i^2$, after scaling by
+/// $|Q|$ at the start if needed, by $a\_i = \lfloor (P\_i + \sqrt{D})/Q\_i \rfloor$, $P\_{i+1} = a\_i Q\_i - P\_i$ and
+/// $Q\_{i+1} = (D - P\_{i+1}^2)/Q\_i$. There are finitely many such pairs $(P\_i, Q\_i)$ once they're reduced, so the
+/// first one that repeats starts the period.
+pub fn quadratic_continued_fraction(
+    p: &Integer,
+    q: &Integer,
+    d: &Integer,
+) -> PeriodicContinuedFraction {
+    assert!(*q != 0, "the denominator can't be zero");
+    assert!(*d > 0 && !d.is_perfect_square(), "{} is a square", d);
+    let (mut p, mut q, mut d) = (p.clone(), q.clone(), d.clone());
+    if !Integer::from(&d - p.square_ref()).is_divisible(&q) {
+        let scale = q.clone().abs();
+        d *= Integer::from(scale.square_ref());
+        p *= &scale;
+        q *= scale;
+    }
+    let s = d.clone().sqrt();
+    let mut seen = HashMap::new();
+    let mut terms = vec![];
+    loop {
+        if let Some(&start) = seen.get(&(p.clone(), q.clone())) {
+            let period = terms.split_off(start);
+            return PeriodicContinuedFraction {
+                preperiod: terms,
+                period,
+            };
+        }
+        seen.insert((p.clone(), q.clone()), terms.len());
+        // no multiple of Q is strictly between P + floor(sqrt(D)) and P + sqrt(D), or their negatives
+        let a = match q > 0 {
+            true => Integer::from(&p + &s).div_rem_floor(q.clone()).0,
+            false => {
+                let num: Integer = Integer::from(&p + &s) + 1;
+                num.div_rem_floor(q.clone()).0
+            }
+        };
+        p = Integer::from(&a * &q) - p;
+        q = (Integer::from(&d - p.square_ref())) / q;
+        terms.push(a);
+    }
+}
+
+/// The continued fraction $[a\_0; \overline{a\_1, \ldots, a\_l}]$ of $\sqrt{D}$, where the period ends with
+/// $2 a\_0$. Panics if $D$ is a square.
+pub fn sqrt_continued_fraction(d: &Integer) -> PeriodicContinuedFraction {
+    quadratic_continued_fraction(&Integer::new(), &Integer::from(1), d)
+}
+
+/// The $r/s$ with $r \equiv as \pmod m$, $|r| \leq N$ and $0 < s \leq D$, for $2ND < m$, if there is one. It's the
+/// first remainder $r\_i \leq N$ in the extended Euclidean algorithm on $m$ and $a$, as $r\_i/t\_i$ if
+/// $|t\_i| \leq D$ and it's in lowest terms.
+pub fn rational_reconstruction_with_bounds(
+    a: &Integer,
+    m: &Integer,
+    n: &Integer,
+    d: &Integer,
+) -> Option<Rational> {
+    assert!(*m > 0, "the modulus needs to be positive");
+    let (mut r0, mut r1) = (m.clone(), a.clone().rem_euc(m));
+    let (mut t0, mut t1) = (Integer::new(), Integer::from(1));
+    while r1 > *n {
+        let (quotient, r) = r0.div_rem(r1.clone());
+        (r0, r1) = (r1, r);
+        let t = t0 - Integer::from(&quotient * &t1);
+        (t0, t1) = (t1, t);
+    }
+    if t1 == 0 || Integer::from(t1.abs_ref()) > *d || Integer::from(r1.gcd_ref(&t1)) != 1 {
+        return None;
+    }
+    Some(Rational::from((r1, t1)))
+}
+
+/// Rational reconstruction with $N = D = \lfloor \sqrt{(m - 1)/2} \rfloor$, the largest equal bounds that make the
+/// answer unique.
+pub fn rational_reconstruction(a: &Integer, m: &Integer) -> Option<Rational> {
+    let bound: Integer = Integer::from(m - 1) / 2;
+    let bound = bound.sqrt();
+    rational_reconstruction_with_bounds(a, m, &bound, &bound)
+}
+
+/// The closest fraction to $x$ with denominator at most `bound`, preferring the smaller denominator in a tie. It's
+/// either the last convergent with $q\_k \leq$ `bound` or the largest semiconvergent after it.
+pub fn limit_denominator(x: &Rational, bound: &Integer) -> Rational {
+    assert!(*bound >= 1, "the denominator bound needs to be positive");
+    if x.denom() <= bound {
+        return x.clone();
+    }
+    let (mut p, mut p_prev) = (Integer::from(1), Integer::new());
+    let (mut q, mut q_prev) = (Integer::new(), Integer::from(1));
+    for a in continued_fraction(x) {
+        let q_next = Integer::from(&a * &q) + &q_prev;
+        if q_next > *bound {
+            break;
+        }
+        (p, p_prev) = (Integer::from(&a * &p) + &p_prev, p);
+        (q, q_prev) = (q_next, q);
+    }
+    let j = Integer::from(bound - &q_prev) / &q;
+    let semi = Rational::from((
+        Integer::from(&j * &p) + p_prev,
+        Integer::from(&j * &q) + q_prev,
+    ));
+    let convergent = Rational::from((p, q));
+    match Rational::from(&convergent - x).abs() <= Rational::from(&semi - x).abs() {
+        true => convergent,
+        false => semi,
+    }
+}
+
+/// The closest fraction to the finite `Float` $x$ with denominator at most `bound`.
+pub fn best_approximation(x: &Float, bound: &Integer) -> Rational {
+    let x = x.to_rational().expect("not a finite number");
+    limit_denominator(&x, bound)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::ints;
+    use rug::float::Constant;
+
+    #[test]
+    fn test_rational() {
+        let x = Rational::from((415, 93));
+        assert_eq!(continued_fraction(&x), ints(vec![4, 2, 6, 7]));
+        assert_eq!(
+            convergents(&ints(vec![4, 2, 6, 7])),
+            vec![
+                Rational::from(4),
+                Rational::from((9, 2)),
+                Rational::from((58, 13)),
+                x.clone()
+            ]
+        );
+        assert_eq!(from_continued_fraction(&continued_fraction(&x)), x);
+        // floor first, so negative numbers only have a negative a_0
+        assert_eq!(
+            continued_fraction(&Rational::from((-7, 3))),
+            ints(vec![-3, 1, 2])
+        );
+        assert_eq!(continued_fraction(&Rational::from(5)), ints(vec![5]));
+        assert_eq!(continued_fraction(&Rational::new()), ints(vec![0]));
+        // a non-canonical expansion still evaluates
+        assert_eq!(
+            from_continued_fraction(&ints(vec![1, 2, 1])),
+            Rational::from((4, 3))
+        );
+        for (a, b) in [(1, 7), (-100, 37), (123456, 789), (1, 1)] {
+            let x = Rational::from((a, b));
+            assert_eq!(from_continued_fraction(&continued_fraction(&x)), x);
+        }
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let check = |cf: PeriodicContinuedFraction, pre: Vec<i64>, period: Vec<i64>| {
+            assert_eq!(cf.preperiod(), ints(pre));
+            assert_eq!(cf.period(), ints(period));
+        };
+        check(sqrt_continued_fraction(&Integer::from(2)), vec![1], vec![2]);
+        check(
+            sqrt_continued_fraction(&Integer::from(7)),
+            vec![2],
+            vec![1, 1, 1, 4],
+        );
+        check(
+            sqrt_continued_fraction(&Integer::from(61)),
+            vec![7],
+            vec![1, 4, 3, 1, 2, 2, 1, 3, 4, 1, 14],
+        );
+        let (one, two, five) = (Integer::from(1), Integer::from(2), Integer::from(5));
+        // the golden ratio phi, and -phi = (1 + sqrt(5))/(-2), which needs scaling to (-2 + sqrt(20))/(-4)
+        check(
+            quadratic_continued_fraction(&one, &two, &five),
+            vec![],
+            vec![1],
+        );
+        check(
+            quadratic_continued_fraction(&one, &-two.clone(), &five),
+            vec![-2, 2],
+            vec![1],
+        );
+        // (2 + sqrt(3))/5 = 0.746..., scaled to (10 + sqrt(75))/25
+        check(
+            quadratic_continued_fraction(&two, &five, &Integer::from(3)),
+            vec![0, 1, 2],
+            vec![1, 16, 1, 1],
+        );
+
+        // the convergents of sqrt(d) at the ends of the periods solve p^2 - d q^2 = +-1
+        for d in [2, 3, 13, 61, 94] {
+            let d = Integer::from(d);
+            let cf = sqrt_continued_fraction(&d);
+            let l = cf.period().len();
+            let c = &cf.convergents(2 * l)[l - 1];
+            let norm =
+                Integer::from(c.numer().square_ref()) - d * Integer::from(c.denom().square_ref());
+            assert_eq!(norm.abs(), 1);
+        }
+    }
+
+    /// The $r/s$ with the smallest $s$, by trying them all.
+    fn brute_force(a: &Integer, m: &Integer, n: i64, d: i64) -> Option<Rational> {
+        (1..=d).find_map(|s| {
+            let r = Integer::from(a * s).rem_euc(m);
+            let r = if r > Integer::from(m / 2) { r - m } else { r };
+            (r.clone().abs() <= n && Integer::from(r.gcd_ref(&Integer::from(s))) == 1)
+                .then(|| Rational::from((r, Integer::from(s))))
+        })
+    }
+
+    #[test]
+    fn test_reconstruction() {
+        let m = Integer::from(1_000_003);
+        for (r, s) in [(2, 3), (-5, 7), (0, 1), (700, 1), (-13, 701), (1, 706)] {
+            let a = Integer::from(r) * Integer::from(s).invert(&m).unwrap();
+            assert_eq!(
+                rational_reconstruction(&a, &m),
+                Some(Rational::from((r, s)))
+            );
+        }
+        // when the numerator and denominator are too big, there's usually nothing small
+        for (r, s) in [(1001, 1003), (3, 1_000_000), (99_999, 4), (-123_456, 777)] {
+            let a = Integer::from(r) * Integer::from(s).invert(&m).unwrap();
+            assert_eq!(
+                rational_reconstruction(&a, &m),
+                brute_force(&a, &m, 706, 706)
+            );
+        }
+        // with unequal bounds, numerators up to 100000 and denominators up to 4
+        let a = Integer::from(99_999) * Integer::from(4).invert(&m).unwrap();
+        let (n, d) = (Integer::from(100_000), Integer::from(4));
+        assert_eq!(
+            rational_reconstruction_with_bounds(&a, &m, &n, &d),
+            Some(Rational::from((99_999, 4)))
+        );
+        assert_eq!(
+            brute_force(&a, &m, 100_000, 4),
+            Some(Rational::from((99_999, 4)))
+        );
+    }
+
+    #[test]
+    fn test_best_approximation() {
+        let pi = Float::with_val(53, Constant::Pi);
+        let approx = |b: i64| best_approximation(&pi, &Integer::from(b));
+        assert_eq!(approx(1), 3);
+        assert_eq!(approx(7), Rational::from((22, 7)));
+        assert_eq!(approx(100), Rational::from((311, 99)));
+        assert_eq!(approx(113), Rational::from((355, 113)));
+        // the first fraction closer to pi than 355/113 is a semiconvergent
+        assert_eq!(approx(16603), Rational::from((355, 113)));
+        assert_eq!(approx(16604), Rational::from((52163, 16604)));
+        // against brute force
+        let x = Rational::from((314159, 100000));
+        for b in 1..200 {
+            let best = (1..=b)
+                .map(|q| {
+                    let p = Rational::from(&x * q).round().numer().clone();
+                    Rational::from((p, Integer::from(q)))
+                })
+                .min_by(|u, v| {
+                    let (du, dv) = (Rational::from(u - &x).abs(), Rational::from(v - &x).abs());
+                    du.cmp(&dv).then(u.denom().cmp(v.denom()))
+                })
+                .unwrap();
+            assert_eq!(limit_denominator(&x, &Integer::from(b)), best);
+        }
+        let half = Float::with_val(10, 0.5);
+        assert_eq!(best_approximation(&half, &Integer::from(1)), 0);
+    }
+}
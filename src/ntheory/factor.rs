@@ -0,0 +1,467 @@
+//! Integer factorization
+//!
+//! `factor_integer` runs through the usual methods, from cheapest to most expensive:
+//!
+//! * trial division by the primes below $2^{16}$,
+//! * Pollard's $\rho$, which finds a prime factor $p$ in about $\sqrt{p}$ steps,
+//! * Pollard's $p - 1$, which finds $p$ if $p - 1$ is smooth,
+//! * Lenstra's elliptic curve method (ECM), which finds $p$ if some curve mod $p$ has a smooth order, and so
+//!   depends on the size of $p$ rather than of $n$,
+//! * the self-initialising quadratic sieve (SIQS) for numbers of more than 30 digits, which depends only on the
+//!   size of $n$. It's the fastest way to split a product of two large primes.
+//!
+//! Each factor found gets tested with `is_prime` (Baillie-PSW), so the result is only "probably" prime; get a
+//! `Certificate` for the factors if that matters.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::factor::factor_integer;
+//! use bored_algebra::Integer;
+//! use rug::ops::Pow;
+//!
+//! let n = Integer::from(2).pow(64) + 1;
+//! let factors = factor_integer(&n);
+//! assert_eq!(factors, vec![(Integer::from(274177), 1), (Integer::from(67280421310721_u64), 1)]);
+//! ```
+use crate::ntheory::prime::{is_prime, primes_up_to};
+use crate::Integer;
+use rug::ops::Pow;
+
+pub use crate::ntheory::siqs::siqs;
+
+/// The bound for trial division in `factor_integer`.
+const TRIAL_BOUND: u64 = 1 << 16;
+
+/// Divides out the primes up to `bound` from $n \neq 0$, giving the prime factors found with their multiplicities,
+/// and the cofactor. The sign of $n$ is dropped.
+pub fn trial_division(n: &Integer, bound: u64) -> (Vec<(Integer, usize)>, Integer) {
+    let mut n = n.clone().abs();
+    let mut factors = vec![];
+    for p in primes_up_to(bound) {
+        if Integer::from(p) * p > n {
+            break;
+        }
+        let e = n.remove_factor_mut(&Integer::from(p));
+        if e > 0 {
+            factors.push((Integer::from(p), e as usize));
+        }
+    }
+    if n > 1 && n <= Integer::from(bound) * bound {
+        factors.push((n, 1));
+        n = Integer::from(1);
+    }
+    (factors, n)
+}
+
+/// If $n = r^k$ with $k > 1$, the smallest such $r$ and the largest $k$.
+pub fn perfect_power(n: &Integer) -> Option<(Integer, u32)> {
+    if *n < 4 {
+        return None;
+    }
+    let mut power = None;
+    let (mut base, mut exponent) = (n.clone(), 1);
+    // taking prime roots for as long as there are any
+    'outer: loop {
+        for k in primes_up_to(base.significant_bits() as u64) {
+            let (root, rem) = base.clone().root_rem(Integer::new(), k as u32);
+            if rem == 0 {
+                base = root;
+                exponent *= k as u32;
+                power = Some((base.clone(), exponent));
+                continue 'outer;
+            }
+        }
+        return power;
+    }
+}
+
+/// Pollard's $\rho$ method with Brent's cycle finding, iterating $x \mapsto x^2 + c$ for a few $c$. Gives a
+/// nontrivial factor of $n$, or `None` if there isn't one within about `iterations` steps.
+pub fn pollard_rho(n: &Integer, iterations: u64) -> Option<Integer> {
+    if n.is_even() {
+        return (*n > 2).then(|| Integer::from(2));
+    }
+    let batch = 128;
+    let mut steps = 0;
+    for c in 1_u32.. {
+        let f = |x: &Integer| (x.clone().square() + c) % n;
+        let (mut y, mut r, mut q, mut g) =
+            (Integer::from(2), 1_u64, Integer::from(1), Integer::from(1));
+        let (mut x, mut ys) = (y.clone(), y.clone());
+        while g == 1 {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y.clone();
+                for _ in 0..batch.min(r - k) {
+                    y = f(&y);
+                    q = q * Integer::from(&x - &y) % n;
+                }
+                g = q.clone().gcd(n);
+                k += batch;
+            }
+            steps += r;
+            r *= 2;
+            if steps > iterations {
+                return None;
+            }
+        }
+        if g == *n {
+            // the batch overshot, so go through it one step at a time
+            loop {
+                ys = f(&ys);
+                g = Integer::from(&x - &ys).gcd(n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != *n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+/// Pollard's $p - 1$ method: finds a prime factor $p$ of $n$ if $p - 1$ is a product of prime powers up to $B\_1$,
+/// except for one prime up to $B\_2 = 50 B\_1$. Gives `None` if it doesn't find a nontrivial factor.
+pub fn pollard_pm1(n: &Integer, b1: u64) -> Option<Integer> {
+    let nontrivial = |g: Integer| (g > 1 && g != *n).then_some(g);
+    let b2 = 50 * b1;
+    let primes = primes_up_to(b2);
+    let stage_one = primes.partition_point(|&p| p <= b1);
+    // stage 1, with a gcd every 100 primes and a slower rerun if everything came out at once
+    let mut a = Integer::from(2);
+    for chunk in primes[..stage_one].chunks(100) {
+        let previous = a.clone();
+        for &p in chunk {
+            a = a.pow_mod(&Integer::from(prime_power(p, b1)), n).unwrap();
+        }
+        let g = Integer::from(&a - 1).gcd(n);
+        if g == *n {
+            a = previous;
+            for &p in chunk {
+                a = a.pow_mod(&Integer::from(prime_power(p, b1)), n).unwrap();
+                let g = Integer::from(&a - 1).gcd(n);
+                if g > 1 {
+                    return nontrivial(g);
+                }
+            }
+        }
+        if g > 1 {
+            return nontrivial(g);
+        }
+    }
+    // stage 2: a^q for the primes q up to B_2, from the powers of a to the gaps between them
+    let mut gaps: Vec<Integer> = vec![];
+    let mut x = a
+        .clone()
+        .pow_mod(&Integer::from(primes[stage_one - 1]), n)
+        .unwrap();
+    let mut product = Integer::from(1);
+    for i in stage_one..primes.len() {
+        let gap = (primes[i] - primes[i - 1]) as usize / 2;
+        while gaps.len() < gap {
+            let k = 2 * (gaps.len() as u64 + 1);
+            gaps.push(a.clone().pow_mod(&Integer::from(k), n).unwrap());
+        }
+        x = x * &gaps[gap - 1] % n;
+        product = product * Integer::from(&x - 1) % n;
+    }
+    nontrivial(product.gcd(n))
+}
+
+/// The largest power of $p$ that's at most `bound`.
+fn prime_power(p: u64, bound: u64) -> u64 {
+    let mut q = p;
+    while q <= bound / p {
+        q *= p;
+    }
+    q
+}
+
+/// A point $(X : Z)$ on a Montgomery curve $By^2 = x^3 + Ax^2 + x$.
+type Point = (Integer, Integer);
+
+/// A Montgomery curve mod $n$, given by $(A + 2)/4$ as a fraction so that nothing needs inverting.
+struct Curve<'a> {
+    a24: (Integer, Integer),
+    n: &'a Integer,
+}
+
+impl Curve<'_> {
+    fn double(&self, (x, z): &Point) -> Point {
+        let (num, den) = &self.a24;
+        let t1 = Integer::from(x + z).square() % self.n;
+        let t2 = Integer::from(x - z).square() % self.n;
+        let t3 = Integer::from(&t1 - &t2);
+        let x2 = t1 * &t2 % self.n * den % self.n;
+        let z2 = (t2 * den + Integer::from(num * &t3)) % self.n * t3 % self.n;
+        (x2, z2)
+    }
+
+    /// $P + Q$, from $P$, $Q$ and $P - Q$.
+    fn add(&self, (x1, z1): &Point, (x2, z2): &Point, (xd, zd): &Point) -> Point {
+        let u = Integer::from(x1 - z1) * Integer::from(x2 + z2);
+        let v = Integer::from(x1 + z1) * Integer::from(x2 - z2);
+        let x = Integer::from(&u + &v).square() % self.n * zd % self.n;
+        let z = (u - v).square() % self.n * xd % self.n;
+        (x, z)
+    }
+
+    /// $kP$, by the Montgomery ladder.
+    fn mul(&self, p: &Point, k: u64) -> Point {
+        let (mut r0, mut r1) = (p.clone(), self.double(p));
+        for i in (0..63 - k.leading_zeros()).rev() {
+            if (k >> i) & 1 == 1 {
+                r0 = self.add(&r1, &r0, p);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r0, &r1, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+/// One ECM curve, from Suyama's parametrization with parameter $\sigma$.
+fn ecm_curve(
+    n: &Integer,
+    sigma: u64,
+    b1: u64,
+    primes: &[u64],
+    is_prime: &[bool],
+) -> Option<Integer> {
+    let nontrivial = |g: Integer| (g > 1 && g != *n).then_some(g);
+    let u: Integer = (Integer::from(sigma).square() - 5) % n;
+    let v = Integer::from(4 * sigma) % n;
+    let u3 = u.clone().pow_mod(&Integer::from(3), n).unwrap();
+    let num = Integer::from(&v - &u)
+        .pow_mod(&Integer::from(3), n)
+        .unwrap()
+        * (Integer::from(3) * &u + &v)
+        % n;
+    let den = Integer::from(16) * &u3 % n * &v % n;
+    let g = den.clone().gcd(n);
+    if g > 1 {
+        return nontrivial(g);
+    }
+    let curve = Curve { a24: (num, den), n };
+    let mut p = (u3, v.pow_mod(&Integer::from(3), n).unwrap());
+    // stage 1
+    let stage_one = primes.partition_point(|&q| q <= b1);
+    for &q in &primes[..stage_one] {
+        p = curve.mul(&p, prime_power(q, b1));
+    }
+    let g = p.1.clone().gcd(n);
+    if g > 1 {
+        return nontrivial(g);
+    }
+    // stage 2: a prime q = mD ± j is caught by X_{mD} Z_j - X_j Z_{mD}, since [mD]P = ±[j]P mod p
+    const D: u64 = 210;
+    let b2 = *primes.last().unwrap();
+    let two = curve.double(&p);
+    let mut baby = vec![p.clone(), curve.mul(&p, 3)];
+    for _ in (5..D / 2).step_by(2) {
+        let next = curve.add(&baby[baby.len() - 1], &two, &baby[baby.len() - 2]);
+        baby.push(next);
+    }
+    let step = curve.mul(&p, D);
+    let m0 = (b1 / D).max(2);
+    let (mut previous, mut giant) = (curve.mul(&p, (m0 - 1) * D), curve.mul(&p, m0 * D));
+    let mut product = Integer::from(1);
+    let mut m = m0;
+    while m * D <= b2 + D / 2 {
+        for (i, (xj, zj)) in baby.iter().enumerate() {
+            let j = 2 * i as u64 + 1;
+            let hit = |q: u64| q > b1 && q <= b2 && is_prime[q as usize];
+            if hit(m * D + j) || hit(m * D - j) {
+                let term = Integer::from(&giant.0 * zj) - Integer::from(xj * &giant.1);
+                product = product * term % n;
+            }
+        }
+        let next = curve.add(&giant, &step, &previous);
+        previous = std::mem::replace(&mut giant, next);
+        m += 1;
+    }
+    nontrivial(product.gcd(n))
+}
+
+/// Lenstra's elliptic curve method, trying up to `curves` curves with stage 1 bound $B\_1 \geq 210$ and stage 2
+/// bound $B\_2 = 50 B\_1$. Gives `None` if it doesn't find a nontrivial factor.
+///
+/// The usual choices are $B\_1 = 2000$ for factors of about 15 digits, 11000 for 20 and 50000 for 25, with a few
+/// dozen to a few hundred curves.
+pub fn ecm(n: &Integer, b1: u64, curves: usize) -> Option<Integer> {
+    assert!(b1 >= 210, "B_1 is too small");
+    let b2 = 50 * b1;
+    let primes = primes_up_to(b2);
+    let mut is_prime = vec![false; b2 as usize + 1];
+    for &q in &primes {
+        is_prime[q as usize] = true;
+    }
+    (6..6 + curves as u64).find_map(|sigma| ecm_curve(n, sigma, b1, &primes, &is_prime))
+}
+
+/// A nontrivial factor of $n$, which is composite, odd, not a perfect power and has no prime factors below
+/// `TRIAL_BOUND`.
+fn find_factor(n: &Integer) -> Integer {
+    if let Some(d) = pollard_rho(n, 1 << 16) {
+        return d;
+    }
+    if let Some(d) = pollard_pm1(n, 20_000) {
+        return d;
+    }
+    if let Some(d) = ecm(n, 2_000, 25) {
+        return d;
+    }
+    if n.significant_bits() > 100 {
+        if let Some(d) = siqs(n) {
+            return d;
+        }
+    }
+    let mut b1 = 11_000;
+    loop {
+        if let Some(d) = ecm(n, b1, 100) {
+            return d;
+        }
+        b1 *= 4;
+    }
+}
+
+/// Splits `cofactor` into primes and adds them to `factors`, stopping early once the prime factors found so far multiply
+/// to more than `bound`.
+fn split(
+    mut factors: Vec<(Integer, usize)>,
+    cofactor: Integer,
+    bound: Option<&Integer>,
+) -> Vec<(Integer, usize)> {
+    let mut parts = vec![(cofactor, 1)];
+    let enough = |factors: &[(Integer, usize)]| match bound {
+        Some(bound) => {
+            let product = factors.iter().fold(Integer::from(1), |acc, (p, e)| {
+                acc * p.clone().pow(*e as u32)
+            });
+            product > *bound
+        }
+        None => false,
+    };
+    while let Some((m, e)) = parts.pop() {
+        if enough(&factors) {
+            break;
+        }
+        if m == 1 {
+            continue;
+        }
+        if is_prime(&m) {
+            factors.push((m, e));
+        } else if let Some((r, k)) = perfect_power(&m) {
+            parts.push((r, e * k as usize));
+        } else {
+            let d = find_factor(&m);
+            let rest = Integer::from(&m / &d);
+            parts.push((d, e));
+            parts.push((rest, e));
+        }
+    }
+    // merge repeated primes
+    factors.sort();
+    let mut merged: Vec<(Integer, usize)> = vec![];
+    for (p, e) in factors {
+        match merged.last_mut() {
+            Some((q, f)) if *q == p => *f += e,
+            _ => merged.push((p, e)),
+        }
+    }
+    merged
+}
+
+/// The prime factorization of $n \neq 0$, as the primes with their multiplicities in increasing order. The sign
+/// of $n$ is dropped, so $\pm 1$ has no factors.
+pub fn factor_integer(n: &Integer) -> Vec<(Integer, usize)> {
+    assert!(*n != 0, "zero has no factorization");
+    let (factors, cofactor) = trial_division(n, TRIAL_BOUND);
+    split(factors, cofactor, None)
+}
+
+/// Some of the prime factors of $n > 0$, with their full multiplicities, whose product is more than `bound`. It
+/// only factors as much as it needs to (which may still be all of $n$).
+pub(crate) fn partial_factor(n: &Integer, bound: &Integer) -> Vec<(Integer, usize)> {
+    let (factors, cofactor) = trial_division(n, TRIAL_BOUND);
+    split(factors, cofactor, Some(bound))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(n: &Integer) {
+        let factors = factor_integer(n);
+        let product = factors.iter().fold(Integer::from(1), |acc, (p, e)| {
+            acc * p.clone().pow(*e as u32)
+        });
+        assert_eq!(product, n.clone().abs());
+        assert!(factors.iter().all(|(p, _)| is_prime(p)));
+        assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_methods() {
+        let p = Integer::from(1_000_000_007);
+        let q = Integer::from(1_000_000_321);
+        let n = Integer::from(&p * &q);
+        let d = pollard_rho(&n, 1 << 20).unwrap();
+        assert!(d == p || d == q);
+        // 1000000321 - 1 = 2^6 * 3 * 5 * 11 * 281 * 337, but 1000000007 - 1 = 2 * 500000003
+        assert_eq!(pollard_pm1(&n, 1000), Some(q.clone()));
+        // 2^64 + 1 = 274177 * 67280421310721
+        let f6 = Integer::from(2).pow(64) + 1;
+        assert_eq!(ecm(&f6, 2000, 50), Some(Integer::from(274177)));
+        assert_eq!(
+            perfect_power(&Integer::from(3).pow(12)),
+            Some((Integer::from(3), 12))
+        );
+        assert_eq!(perfect_power(&Integer::from(72)), None);
+        assert_eq!(
+            perfect_power(&Integer::from(6).pow(6)),
+            Some((Integer::from(6), 6))
+        );
+        let (factors, cofactor) = trial_division(&Integer::from(-360 * 1_000_003), 100);
+        assert_eq!(
+            factors,
+            vec![
+                (Integer::from(2), 3),
+                (Integer::from(3), 2),
+                (Integer::from(5), 1)
+            ]
+        );
+        assert_eq!(cofactor, 1_000_003);
+    }
+
+    #[test]
+    fn test_factor_integer() {
+        assert!(factor_integer(&Integer::from(1)).is_empty());
+        assert_eq!(
+            factor_integer(&Integer::from(-12)),
+            vec![(Integer::from(2), 2), (Integer::from(3), 1)]
+        );
+        for n in 2..2000 {
+            check(&Integer::from(n));
+        }
+        check(&(Integer::from(2).pow(128) + 1));
+        check(&(Integer::from(10).pow(30) - 1));
+        check(&(Integer::from(1_000_000_007).pow(3) * Integer::from(998_244_353).pow(2)));
+        // 2^67 - 1 = 193707721 * 761838257287 (Cole)
+        assert_eq!(
+            factor_integer(&(Integer::from(2).pow(67) - 1)),
+            vec![
+                (Integer::from(193707721), 1),
+                (Integer::from(761838257287_u64), 1)
+            ]
+        );
+    }
+}
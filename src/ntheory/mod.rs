@@ -0,0 +1,13 @@
+//! Elementary number theory on `rug::Integer`
+//!
+//! `prime` has primality tests and certificates, `factor` factors integers, `residue` has Jacobi symbols and square
+//! roots mod $n$, `arith` has $\varphi$, $\mu$, divisor sums and Dirichlet convolution, `continued_fraction` has
+//! continued fractions, best approximations and rational reconstruction, and `units` has orders, primitive roots
+//! and discrete logarithms in $(\mathbb{Z}/n)^\times$.
+pub mod arith;
+pub mod continued_fraction;
+pub mod factor;
+pub mod prime;
+pub mod residue;
+pub(crate) mod siqs;
+pub mod units;
@@ -0,0 +1,387 @@
+//! Primality testing and proving
+//!
+//! `is_prime` is the Baillie-PSW test: trial division, a strong Fermat test to base 2, and a strong Lucas test.
+//! No composite is known to pass it, and there are none below $2^{64}$, but nobody has proven there are none at
+//! all. When that matters, a `Certificate` is a proof of primality that can be checked without trusting any of
+//! the code that found it, since checking only needs modular exponentiation and gcds:
+//!
+//! * a Pratt certificate for $n$ is a witness $a$ of order $n - 1$ mod $n$, together with the prime factors of
+//!   $n - 1$ and certificates for them. It needs $n - 1$ to be factored completely.
+//! * a Pocklington certificate only needs a factored part $F \mid n - 1$ with $F > \sqrt{n}$, and a witness
+//!   $a\_q$ for each prime $q \mid F$ with $a\_q^{n-1} \equiv 1$ and $\gcd(a\_q^{(n-1)/q} - 1, n) = 1$. Then every
+//!   prime factor of $n$ is $1 \bmod F$, so there's only room for one.
+//!
+//! Both bottom out at primes below $2^{32}$, which are checked by trial division.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::prime::{is_prime, Certificate};
+//! use bored_algebra::Integer;
+//!
+//! let n = (Integer::from(1) << 127) - 1;
+//! assert!(is_prime(&n));
+//! assert!(!is_prime(&(n.clone() + 2)));
+//! let certificate = Certificate::pocklington(&n).unwrap();
+//! assert!(certificate.verify());
+//! assert_eq!(certificate.n(), &n);
+//! ```
+use crate::ntheory::factor::{factor_integer, partial_factor};
+use crate::Integer;
+
+use rug::ops::RemRounding;
+
+/// Primes this small get proven by trial division.
+const SMALL: u64 = 1 << 32;
+
+/// The primes up to `bound`, by the sieve of Eratosthenes.
+pub fn primes_up_to(bound: u64) -> Vec<u64> {
+    if bound < 2 {
+        return vec![];
+    }
+    let mut composite = vec![false; bound as usize + 1];
+    let mut primes = vec![];
+    for i in 2..=bound as usize {
+        if !composite[i] {
+            primes.push(i as u64);
+            for j in (i * i..=bound as usize).step_by(i) {
+                composite[j] = true;
+            }
+        }
+    }
+    primes
+}
+
+/// Returns true if $n > 1$ has no divisor $1 < d \leq \sqrt{n}$.
+fn is_prime_by_trial_division(n: u64) -> bool {
+    n > 1 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+/// The strong Fermat (Miller-Rabin) test of odd $n > 2$ to base $a$: with $n - 1 = d 2^s$ and $d$ odd, either
+/// $a^d \equiv 1$ or $a^{d 2^r} \equiv -1$ for some $0 \leq r < s$.
+pub fn is_strong_probable_prime(n: &Integer, a: &Integer) -> bool {
+    let minus_one = Integer::from(n - 1);
+    let s = minus_one.find_one(0).unwrap();
+    let d = Integer::from(&minus_one >> s);
+    let mut x = a.clone().pow_mod(&d, n).unwrap();
+    if x == 1 || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = x.square() % n;
+        if x == minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Halves $a$ mod odd $n$.
+fn half(a: Integer, n: &Integer) -> Integer {
+    match a.is_odd() {
+        true => (a + n) >> 1,
+        false => a >> 1,
+    }
+}
+
+/// The strong Lucas test of odd $n > 2$, with Selfridge's parameters: $D$ is the first of $5, -7, 9, -11, \ldots$
+/// with Jacobi symbol $(D/n) = -1$, $P = 1$ and $Q = (1 - D)/4$. Then with $n + 1 = d 2^s$ and $d$ odd, either
+/// $U\_d \equiv 0$ or $V\_{d 2^r} \equiv 0$ for some $0 \leq r < s$.
+pub fn is_strong_lucas_probable_prime(n: &Integer) -> bool {
+    // squares would never find a D
+    if n.is_perfect_square() {
+        return false;
+    }
+    let mut d = Integer::from(5);
+    loop {
+        match d.jacobi(n) {
+            -1 => break,
+            0 if d.clone().abs() != *n => return false,
+            _ => {}
+        }
+        d = match d.is_positive() {
+            true => -(d + 2_u32),
+            false => -d + 2_u32,
+        };
+    }
+    let q: Integer = Integer::from(1 - &d) / 4;
+    let plus_one = Integer::from(n + 1);
+    let s = plus_one.find_one(0).unwrap();
+    let k = Integer::from(&plus_one >> s);
+    let (mut u, mut v, mut qk) = (Integer::from(1), Integer::from(1), q.clone() % n);
+    for i in (0..k.significant_bits() - 1).rev() {
+        u = u * &v % n;
+        v = (v.square() - Integer::from(2) * &qk) % n;
+        qk = qk.square() % n;
+        if k.get_bit(i) {
+            let (u_next, v_next) = (Integer::from(&u + &v), Integer::from(&d * &u) + &v);
+            u = half(u_next.rem_euc(n), n);
+            v = half(v_next.rem_euc(n), n);
+            qk = qk * &q % n;
+        }
+    }
+    if u.is_divisible(n) || v.is_divisible(n) {
+        return true;
+    }
+    for _ in 1..s {
+        v = (v.square() - Integer::from(2) * &qk) % n;
+        if v.is_divisible(n) {
+            return true;
+        }
+        qk = qk.square() % n;
+    }
+    false
+}
+
+/// The Baillie-PSW probable prime test. It's correct for $n < 2^{64}$, and there are no known counterexamples.
+/// Negative numbers are never prime.
+pub fn is_prime(n: &Integer) -> bool {
+    if *n < 2 {
+        return false;
+    }
+    for p in [2_u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47] {
+        if n.is_divisible_u(p) {
+            return *n == p;
+        }
+    }
+    if *n < 53 * 53 {
+        return true;
+    }
+    is_strong_probable_prime(n, &Integer::from(2)) && is_strong_lucas_probable_prime(n)
+}
+
+/// A proof that an integer is prime, see the module documentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Certificate {
+    /// $n < 2^{32}$, checked by trial division.
+    Small(Integer),
+    /// $a$ has order $n - 1$ mod $n$, and the certificates are for all the prime factors of $n - 1$.
+    Pratt {
+        n: Integer,
+        witness: Integer,
+        factors: Vec<Certificate>,
+    },
+    /// For each certified prime $q$ with its witness $a\_q$, $a\_q^{n-1} \equiv 1$ and
+    /// $\gcd(a\_q^{(n-1)/q} - 1, n) = 1$, and the $q$-parts of $n - 1$ multiply to more than $\sqrt{n}$.
+    Pocklington {
+        n: Integer,
+        factors: Vec<(Certificate, Integer)>,
+    },
+}
+
+impl Certificate {
+    /// The number the certificate is for.
+    pub fn n(&self) -> &Integer {
+        match self {
+            Certificate::Small(n) => n,
+            Certificate::Pratt { n, .. } => n,
+            Certificate::Pocklington { n, .. } => n,
+        }
+    }
+
+    /// A Pratt certificate for $n$, or `None` if $n$ isn't prime. This factors $n - 1$ completely, and then the
+    /// prime factors of those, and so on.
+    pub fn pratt(n: &Integer) -> Option<Self> {
+        if *n < SMALL {
+            return is_prime_by_trial_division(n.to_u64()?).then(|| Certificate::Small(n.clone()));
+        }
+        if !is_prime(n) {
+            return None;
+        }
+        let minus_one = Integer::from(n - 1);
+        let primes: Vec<Integer> = factor_integer(&minus_one)
+            .into_iter()
+            .map(|(q, _)| q)
+            .collect();
+        let witness = (2_u32..).map(Integer::from).find(|a| {
+            primes.iter().all(|q| {
+                a.clone()
+                    .pow_mod(&Integer::from(&minus_one / q), n)
+                    .unwrap()
+                    != 1
+            })
+        })?;
+        let factors = primes.iter().map(Self::pratt).collect::<Option<_>>()?;
+        Some(Certificate::Pratt {
+            n: n.clone(),
+            witness,
+            factors,
+        })
+    }
+
+    /// A Pocklington certificate for $n$, or `None` if $n$ isn't prime. Only enough of $n - 1$ gets factored to
+    /// get past $\sqrt{n}$, which is usually much faster than a Pratt certificate. The factors of $n - 1$ get
+    /// Pocklington certificates too.
+    pub fn pocklington(n: &Integer) -> Option<Self> {
+        if *n < SMALL {
+            return is_prime_by_trial_division(n.to_u64()?).then(|| Certificate::Small(n.clone()));
+        }
+        if !is_prime(n) {
+            return None;
+        }
+        let minus_one = Integer::from(n - 1);
+        let sqrt = n.clone().sqrt();
+        let mut factors = vec![];
+        for (q, _) in partial_factor(&minus_one, &sqrt) {
+            let e = Integer::from(&minus_one / &q);
+            let witness = (2_u32..).map(Integer::from).find(|a| {
+                let b = a.clone().pow_mod(&e, n).unwrap();
+                b.clone().pow_mod(&q, n).unwrap() == 1 && (b - 1_u32).gcd(n) == 1
+            })?;
+            factors.push((Self::pocklington(&q)?, witness));
+        }
+        Some(Certificate::Pocklington {
+            n: n.clone(),
+            factors,
+        })
+    }
+
+    /// Checks the certificate, including all the certificates inside it.
+    pub fn verify(&self) -> bool {
+        match self {
+            Certificate::Small(n) => n.to_u64().is_some_and(is_prime_by_trial_division),
+            Certificate::Pratt {
+                n,
+                witness,
+                factors,
+            } => {
+                if *n < 3 {
+                    return false;
+                }
+                let minus_one = Integer::from(n - 1);
+                // the factors have to be all of n - 1
+                let mut rest = minus_one.clone();
+                for q in factors {
+                    if !rest.is_divisible(q.n()) || *q.n() < 2 {
+                        return false;
+                    }
+                    rest.remove_factor_mut(q.n());
+                }
+                rest == 1
+                    && witness.clone().pow_mod(&minus_one, n).unwrap() == 1
+                    && factors.iter().all(|q| {
+                        let e = Integer::from(&minus_one / q.n());
+                        witness.clone().pow_mod(&e, n).unwrap() != 1 && q.verify()
+                    })
+            }
+            Certificate::Pocklington { n, factors } => {
+                if *n < 3 {
+                    return false;
+                }
+                let minus_one = Integer::from(n - 1);
+                let mut rest = minus_one.clone();
+                for (q, _) in factors {
+                    if !rest.is_divisible(q.n()) || *q.n() < 2 {
+                        return false;
+                    }
+                    rest.remove_factor_mut(q.n());
+                }
+                // F = (n - 1)/rest is the factored part
+                let f = Integer::from(&minus_one / &rest);
+                f.square() > *n
+                    && factors.iter().all(|(q, a)| {
+                        let b = a
+                            .clone()
+                            .pow_mod(&Integer::from(&minus_one / q.n()), n)
+                            .unwrap();
+                        b.clone().pow_mod(q.n(), n).unwrap() == 1
+                            && (b - 1_u32).gcd(n) == 1
+                            && q.verify()
+                    })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rug::ops::Pow;
+
+    #[test]
+    fn test_is_prime() {
+        let primes = primes_up_to(10000);
+        assert_eq!(primes.len(), 1229);
+        for n in 0..10000_u64 {
+            assert_eq!(
+                is_prime(&Integer::from(n)),
+                primes.binary_search(&n).is_ok()
+            );
+        }
+        assert!(!is_prime(&Integer::from(-7)));
+        // strong pseudoprimes to base 2, and Carmichael numbers
+        for n in [
+            2047_u64,
+            3277,
+            4033,
+            4681,
+            8321,
+            561,
+            1105,
+            1729,
+            3215031751,
+            3825123056546413051,
+        ] {
+            assert!(!is_prime(&Integer::from(n)));
+        }
+        assert!(is_strong_probable_prime(
+            &Integer::from(2047),
+            &Integer::from(2)
+        ));
+        // strong Lucas pseudoprimes
+        for n in [5459_u64, 5777, 10877, 16109, 18971] {
+            assert!(is_strong_lucas_probable_prime(&Integer::from(n)));
+            assert!(!is_prime(&Integer::from(n)));
+        }
+        let mut n = Integer::from(10).pow(30);
+        for _ in 0..50 {
+            n += 1;
+            assert_eq!(
+                is_prime(&n),
+                n.is_probably_prime(30) != rug::integer::IsPrime::No
+            );
+        }
+        assert!(is_prime(&Integer::from(2_u64.pow(61) - 1)));
+        assert!(!is_prime(
+            &(Integer::from(2_u64.pow(61) - 1) * Integer::from(2_u64.pow(31) - 1))
+        ));
+    }
+
+    #[test]
+    fn test_certificates() {
+        for n in [
+            Integer::from(97),
+            Integer::from(1_000_000_007),
+            Integer::from(2_u64.pow(61) - 1),
+        ] {
+            let pratt = Certificate::pratt(&n).unwrap();
+            assert!(pratt.verify());
+            let pocklington = Certificate::pocklington(&n).unwrap();
+            assert!(pocklington.verify());
+        }
+        let n = Integer::from(10).pow(40) + 121; // the first prime after 10^40
+        assert!(is_prime(&n));
+        assert!(Certificate::pocklington(&n).unwrap().verify());
+        assert!(Certificate::pratt(&n).unwrap().verify());
+        assert_eq!(Certificate::pratt(&Integer::from(2_u64.pow(32) + 1)), None);
+        assert_eq!(
+            Certificate::pocklington(&(Integer::from(10).pow(40) + 1)),
+            None
+        );
+        // wrong witnesses, missing factors and composites don't verify
+        let bad = Certificate::Pratt {
+            n: Integer::from(2_u64.pow(61) - 1),
+            witness: Integer::from(1),
+            factors: vec![],
+        };
+        assert!(!bad.verify());
+        let pocklington = Certificate::pocklington(&Integer::from(2_u64.pow(61) - 1)).unwrap();
+        if let Certificate::Pocklington { factors, .. } = pocklington {
+            let liar = Certificate::Pocklington {
+                n: Integer::from(2_u64.pow(61) + 1),
+                factors,
+            };
+            assert!(!liar.verify());
+        }
+        assert!(!Certificate::Small(Integer::from(91)).verify());
+    }
+}
@@ -0,0 +1,328 @@
+//! Quadratic residues and modular square roots
+//!
+//! The Jacobi symbol $\left(\frac{a}{n}\right)$ for odd $n > 0$ is the product of the Legendre symbols for the primes
+//! in $n$, so for a prime it says whether $a$ is a square. GMP computes it without factoring $n$, by quadratic
+//! reciprocity and $\left(\frac{2}{n}\right) = (-1)^{(n^2 - 1)/8}$, like a gcd. The Kronecker symbol extends it to
+//! all $n$.
+//!
+//! Square roots mod an odd prime $p$ come from Tonelli-Shanks, which takes $O(s^2)$ multiplications for
+//! $p - 1 = 2^s q$, or Cipolla, which works in $\mathbb{F}\_{p^2} = \mathbb{F}\_p(\sqrt{t^2 - a})$ and doesn't care
+//! about $s$. Roots of units lift to $p^k$ by Hensel's lemma, and mod $2^k$ by fixing one bit at a time. The roots
+//! mod $n$ are the CRT combinations of the roots mod the prime powers in $n$, so that needs $n$ factored.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::residue::{jacobi, sqrt_mod_factored, sqrt_mod_prime};
+//! use bored_algebra::Integer;
+//!
+//! let p = Integer::from(1_000_000_007);
+//! assert_eq!(jacobi(&Integer::from(5), &p), -1);
+//! assert_eq!(sqrt_mod_prime(&Integer::from(5), &p), None);
+//! let r = sqrt_mod_prime(&Integer::from(3), &p).unwrap();
+//! assert_eq!(Integer::from(&r * &r) % &p, 3);
+//! // x^2 = 4 mod 15 has the four roots 2, 7, 8, 13
+//! let n = [(Integer::from(3), 1), (Integer::from(5), 1)];
+//! assert_eq!(sqrt_mod_factored(&Integer::from(4), &n), vec![2, 7, 8, 13]);
+//! ```
+use crate::hensel::dense_lift_root;
+use crate::Integer;
+use rug::ops::{Pow, RemRounding};
+
+/// The Jacobi symbol $\left(\frac{a}{n}\right)$, which is 0 if $\gcd(a, n) > 1$. Panics unless $n$ is odd and
+/// positive.
+pub fn jacobi(a: &Integer, n: &Integer) -> i32 {
+    assert!(
+        *n > 0 && n.is_odd(),
+        "the Jacobi symbol needs an odd positive n"
+    );
+    a.jacobi(n)
+}
+
+/// The Kronecker symbol $\left(\frac{a}{n}\right)$, for any $n$: the Jacobi symbol times
+/// $\left(\frac{a}{2}\right)$ for each factor 2 of $n$ (0 for even $a$, otherwise 1 for $a \equiv \pm 1 \bmod 8$ and
+/// $-1$ for $a \equiv \pm 3$), and $\left(\frac{a}{-1}\right) = \mathrm{sign}(a)$.
+pub fn kronecker(a: &Integer, n: &Integer) -> i32 {
+    a.kronecker(n)
+}
+
+/// A square root of $a$ mod the prime $p$ in $[0, p)$, or `None` if $a$ isn't a square. Uses Tonelli-Shanks.
+pub fn sqrt_mod_prime(a: &Integer, p: &Integer) -> Option<Integer> {
+    tonelli_shanks(a, p)
+}
+
+/// A square root of $a$ mod the prime $p$ by Tonelli-Shanks: with $p - 1 = 2^s q$, $a^{(q+1)/2}$ is a root of $a$
+/// times a $2^s$th root of unity, and that gets fixed up by powers of $z^q$ for a non-residue $z$.
+pub fn tonelli_shanks(a: &Integer, p: &Integer) -> Option<Integer> {
+    let a = a.clone().rem_euc(p);
+    if let Some(r) = trivial_sqrt(&a, p) {
+        return r;
+    }
+    let p1 = Integer::from(p - 1);
+    let s = p1.find_one(0).unwrap();
+    let q = Integer::from(&p1 >> s);
+    let mut z = Integer::from(2);
+    while jacobi(&z, p) != -1 {
+        z += 1;
+    }
+    let pow = |b: &Integer, e: &Integer| Integer::from(b.pow_mod_ref(e, p).unwrap());
+    let mut m = s;
+    let mut c = pow(&z, &q);
+    let mut t = pow(&a, &q);
+    let mut r = pow(&a, &(Integer::from(&q + 1) >> 1));
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while t2 != 1 {
+            t2 = t2.square() % p;
+            i += 1;
+        }
+        let b = pow(&c, &(Integer::from(1) << (m - i - 1)));
+        m = i;
+        c = Integer::from(&b * &b) % p;
+        t = t * &c % p;
+        r = r * b % p;
+    }
+    Some(r)
+}
+
+/// A square root of $a$ mod the prime $p$ by Cipolla: for $t$ with $t^2 - a$ a non-residue, $(t + \omega)^{(p+1)/2}$
+/// is a root in $\mathbb{F}\_p$, where $\omega^2 = t^2 - a$.
+pub fn cipolla(a: &Integer, p: &Integer) -> Option<Integer> {
+    let a = a.clone().rem_euc(p);
+    if let Some(r) = trivial_sqrt(&a, p) {
+        return r;
+    }
+    let mut t = Integer::from(1);
+    let w = loop {
+        let w = (Integer::from(&t * &t) - &a).rem_euc(p);
+        if jacobi(&w, p) == -1 {
+            break w;
+        }
+        t += 1;
+    };
+    // (x + y omega)(u + v omega) = (xu + yvw) + (xv + yu) omega
+    let mul = |(x, y): &(Integer, Integer), (u, v): &(Integer, Integer)| {
+        (
+            (Integer::from(x * u) + Integer::from(y * v) * &w) % p,
+            (Integer::from(x * v) + Integer::from(y * u)) % p,
+        )
+    };
+    let e: Integer = Integer::from(p + 1) >> 1;
+    let base = (t, Integer::from(1));
+    let mut result = (Integer::from(1), Integer::new());
+    for i in (0..e.significant_bits()).rev() {
+        result = mul(&result, &result);
+        if e.get_bit(i) {
+            result = mul(&result, &base);
+        }
+    }
+    Some(result.0)
+}
+
+/// The cases that don't need a non-residue: $a = 0$, $p = 2$, and non-residues.
+fn trivial_sqrt(a: &Integer, p: &Integer) -> Option<Option<Integer>> {
+    if *a == 0 || *p == 2 {
+        return Some(Some(a.clone()));
+    }
+    match jacobi(a, p) {
+        -1 => Some(None),
+        _ => None,
+    }
+}
+
+/// All the square roots of $a$ mod $p^k$ for a prime $p$ and $k \geq 1$, in increasing order. If $a = p^{2j} b$ for a
+/// unit $b$ then the roots are $p^j y$ for the roots $y$ of $b$ mod $p^{k - 2j}$, and each of those is only determined
+/// mod $p^{k-j}$, so there can be a lot of them: $x^2 \equiv 0 \bmod p^k$ has $p^{\lfloor k/2 \rfloor}$ roots.
+pub fn sqrt_mod_prime_power(a: &Integer, p: &Integer, k: u32) -> Vec<Integer> {
+    let modulus = p.clone().pow(k);
+    let a = a.clone().rem_euc(&modulus);
+    let (b, v) = match a == 0 {
+        true => (Integer::from(1), k + k % 2),
+        false => a.remove_factor(p),
+    };
+    if v % 2 == 1 {
+        return vec![];
+    }
+    let (j, rest) = (v / 2, k.saturating_sub(v));
+    let mut roots = vec![];
+    let spread = p.clone().pow(rest);
+    for y in unit_sqrt_mod_prime_power(&b, p, rest) {
+        // x = p^j (y + t p^rest) for t < p^(k - j - rest)
+        let mut x = p.clone().pow(j) * y;
+        let step = &spread * p.clone().pow(j);
+        while x < modulus {
+            roots.push(x.clone());
+            x += &step;
+        }
+    }
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// The square roots of a unit $b$ mod $p^k$.
+fn unit_sqrt_mod_prime_power(b: &Integer, p: &Integer, k: u32) -> Vec<Integer> {
+    let modulus = p.clone().pow(k);
+    if k == 0 {
+        return vec![Integer::new()];
+    }
+    if *p != 2 {
+        let Some(r) = sqrt_mod_prime(b, p) else {
+            return vec![];
+        };
+        let f = [Integer::from(-b), Integer::new(), Integer::from(1)];
+        let r = dense_lift_root(&f, &r, p, k);
+        return vec![Integer::from(&modulus - &r), r];
+    }
+    match k {
+        1 => vec![Integer::from(1)],
+        2 => match b.mod_u(4) {
+            1 => vec![Integer::from(1), Integer::from(3)],
+            _ => vec![],
+        },
+        _ if b.mod_u(8) != 1 => vec![],
+        _ => {
+            // a root mod 2^(i+1), which is one mod 2^i after squaring, fixed by adding 2^(i-1)
+            let mut r = Integer::from(1);
+            for i in 3..k {
+                let error = (Integer::from(&r * &r) - b) >> i;
+                if error.is_odd() {
+                    r += Integer::from(1) << (i - 1);
+                }
+            }
+            let half = Integer::from(&modulus >> 1);
+            [
+                r.clone(),
+                Integer::from(&modulus - &r),
+                Integer::from(&r + &half),
+                Integer::from(&modulus - &r) + &half,
+            ]
+            .into_iter()
+            .map(|x| x.rem_euc(&modulus))
+            .collect()
+        }
+    }
+}
+
+/// All the square roots of $a$ mod $n = \prod p^k$ in increasing order, given the factorization of $n$ as the primes
+/// with their exponents (like `factor_integer` gives it): the CRT combinations of the roots mod each $p^k$.
+pub fn sqrt_mod_factored(a: &Integer, factors: &[(Integer, usize)]) -> Vec<Integer> {
+    let mut roots = vec![Integer::new()];
+    let mut m = Integer::from(1);
+    for (p, k) in factors {
+        let pk = p.clone().pow(*k as u32);
+        let local = sqrt_mod_prime_power(a, p, *k as u32);
+        // x = r mod m and s mod p^k
+        let inv = m.clone().invert(&pk).unwrap();
+        let mut combined = vec![];
+        for r in &roots {
+            for s in &local {
+                let t = (Integer::from(s - r) * &inv).rem_euc(&pk);
+                combined.push(r + t * &m);
+            }
+        }
+        roots = combined;
+        m *= pk;
+    }
+    roots.sort();
+    roots
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ntheory::factor::factor_integer;
+    use crate::test_helpers::ints;
+
+    #[test]
+    fn test_symbols() {
+        for n in (-60_i64..60).map(Integer::from) {
+            for a in (-30_i64..30).map(Integer::from) {
+                assert_eq!(kronecker(&a, &n), a.kronecker(&n), "({}/{})", a, n);
+                if n > 0 && n.is_odd() {
+                    assert_eq!(jacobi(&a, &n), a.jacobi(&n));
+                }
+            }
+        }
+        let p = (Integer::from(1) << 127) - 1;
+        assert_eq!(jacobi(&Integer::from(3), &p), Integer::from(3).jacobi(&p));
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime() {
+        // 7340033 = 7 * 2^20 + 1 makes Tonelli-Shanks work hard
+        for p in [2_u64, 3, 5, 13, 7340033, 1_000_000_007].map(Integer::from) {
+            let mut squares = 0;
+            for a in (0..200_u64).map(Integer::from) {
+                let (r, s) = (tonelli_shanks(&a, &p), cipolla(&a, &p));
+                assert_eq!(r.is_some(), s.is_some());
+                if let (Some(r), Some(s)) = (r, s) {
+                    assert_eq!(Integer::from(&r * &r) % &p, Integer::from(&a % &p));
+                    assert!(r == s || Integer::from(&r + &s) == p);
+                    squares += 1;
+                }
+            }
+            assert!(p < 200 || squares < 150);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime_power() {
+        let three = Integer::from(3);
+        // 7 = 4^2 mod 9, and 9 mod 27 has roots 3, 6, 12, 15, 21, 24
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::from(7), &three, 2),
+            ints(vec![4, 5])
+        );
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::from(9), &three, 3),
+            ints(vec![3, 6, 12, 15, 21, 24])
+        );
+        assert!(sqrt_mod_prime_power(&Integer::from(3), &three, 3).is_empty());
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::new(), &three, 3),
+            ints(vec![0, 9, 18])
+        );
+        // powers of 2
+        let two = Integer::from(2);
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::from(1), &two, 1),
+            ints(vec![1])
+        );
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::from(1), &two, 3),
+            ints(vec![1, 3, 5, 7])
+        );
+        assert_eq!(sqrt_mod_prime_power(&Integer::from(17), &two, 10).len(), 4);
+        assert!(sqrt_mod_prime_power(&Integer::from(5), &two, 3).is_empty());
+        assert_eq!(
+            sqrt_mod_prime_power(&Integer::from(4), &two, 3),
+            ints(vec![2, 6])
+        );
+        // compare with brute force
+        for (p, k) in [(2, 6), (3, 4), (5, 3), (7, 2)] {
+            let pk = Integer::from(p).pow(k);
+            for a in 0..pk.to_u64().unwrap() {
+                let expected: Vec<Integer> = (0..pk.to_u64().unwrap())
+                    .filter(|x| (x * x) % pk.to_u64().unwrap() == a)
+                    .map(Integer::from)
+                    .collect();
+                assert_eq!(
+                    sqrt_mod_prime_power(&Integer::from(a), &Integer::from(p), k),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_factored() {
+        // 1 has 4 * 2 * 2 roots mod 8 * 3 * 5
+        let n = Integer::from(120);
+        let roots = sqrt_mod_factored(&Integer::from(1), &factor_integer(&n));
+        assert_eq!(roots.len(), 16);
+        assert!(roots.iter().all(|r| Integer::from(r * r) % &n == 1));
+        assert!(sqrt_mod_factored(&Integer::from(2), &factor_integer(&n)).is_empty());
+        assert_eq!(sqrt_mod_factored(&Integer::from(5), &[]), vec![0]);
+    }
+}
@@ -0,0 +1,456 @@
+//! The self-initialising quadratic sieve
+//!
+//! The quadratic sieve looks for many $y$ with $y^2 - kn$ smooth over a factor base of small primes, and then
+//! finds a product of them that's a square by linear algebra over $\mathbb{F}\_2$. That gives $X^2 \equiv Y^2 \bmod n$,
+//! and $\gcd(X - Y, n)$ is a factor of $n$ about half the time.
+//!
+//! The values come from polynomials $(Ax + B)^2 - kn = A(Ax^2 + 2Bx + C)$ on $-M \leq x \leq M$, where $A$ is a
+//! product of $s$ factor base primes, which keeps the values small. "Self-initialising" means each $A$ is used for
+//! $2^{s-1}$ different $B$, so the expensive part of switching polynomials (inverting $A$ mod every prime) is
+//! shared. Relations with one prime left over that's too big for the factor base (partial relations) are kept,
+//! and two with the same large prime make a full relation.
+//!
+//! TODO: the linear algebra is dense Gaussian elimination, which is the bottleneck past about 60 digits. Block
+//! Lanczos would fix that.
+use crate::ntheory::factor::perfect_power;
+use crate::ntheory::prime::{is_prime, primes_up_to};
+use crate::ntheory::residue::{jacobi, sqrt_mod_prime};
+use crate::Integer;
+
+use rug::rand::RandState;
+use std::collections::{HashMap, HashSet};
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// $a^{-1} \bmod p$ for a prime $p \nmid a$.
+fn inverse_mod(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+/// The Knuth-Schroeppel multiplier: the $k$ that makes the most small primes likely to divide $y^2 - kn$.
+fn multiplier(n: &Integer) -> u64 {
+    let primes = primes_up_to(1000);
+    let score = |k: u64| {
+        let mut score = -0.5 * (k as f64).ln();
+        score += match (n.mod_u(8) as u64 * k) % 8 {
+            1 => 2.0,
+            5 => 1.0,
+            _ => 0.5,
+        } * 2_f64.ln();
+        for &p in &primes[1..] {
+            let kn = (n.mod_u(p as u32) as u64 * k) % p;
+            if k.is_multiple_of(p) {
+                score += (p as f64).ln() / p as f64;
+            } else if jacobi(&Integer::from(kn), &Integer::from(p)) == 1 {
+                score += 2.0 * (p as f64).ln() / (p - 1) as f64;
+            }
+        }
+        score
+    };
+    [
+        1, 3, 5, 7, 11, 13, 15, 17, 19, 21, 23, 29, 31, 33, 35, 37, 39, 41, 43, 47, 51, 53, 55, 57,
+        59, 61,
+    ]
+    .into_iter()
+    .max_by(|&a, &b| score(a).total_cmp(&score(b)))
+    .unwrap()
+}
+
+/// The factor base size and the sieve half-width $M$ for $kn$ of the given size.
+fn parameters(bits: u32) -> (usize, u64) {
+    match bits {
+        0..=70 => (80, 8192),
+        71..=90 => (120, 16384),
+        91..=110 => (200, 32768),
+        111..=130 => (350, 32768),
+        131..=150 => (600, 65536),
+        151..=170 => (900, 65536),
+        171..=190 => (1400, 65536),
+        191..=210 => (2000, 98304),
+        211..=230 => (3000, 131072),
+        _ => (4500, 196608),
+    }
+}
+
+/// A factor base prime $p$, with a square root $t$ of $kn$ mod $p$. The primes that divide $2kn$ aren't sieved.
+struct Prime {
+    p: u64,
+    sqrt: u64,
+    log: u8,
+    sieve: bool,
+}
+
+/// $y^2 \equiv \ell^2 \prod p\_i^{e\_i} \pmod n$, where $p\_0 = -1$ and $p\_i$ is the $i$-th factor base prime
+/// for $i > 0$, and $\ell$ is a product of large primes.
+#[derive(Clone)]
+struct Relation {
+    y: Integer,
+    exponents: Vec<(usize, u32)>,
+    large: Integer,
+}
+
+/// Dependencies among the exponent vectors mod 2, as sets of relations, by Gaussian elimination.
+fn dependencies(relations: &[Relation], columns: usize) -> Vec<Vec<usize>> {
+    let words = |bits: usize| bits.div_ceil(64);
+    let mut rows: Vec<Vec<u64>> = relations
+        .iter()
+        .map(|r| {
+            let mut row = vec![0; words(columns)];
+            for &(i, e) in &r.exponents {
+                row[i / 64] ^= (e as u64 & 1) << (i % 64);
+            }
+            row
+        })
+        .collect();
+    let mut history: Vec<Vec<u64>> = (0..relations.len())
+        .map(|i| {
+            let mut row = vec![0; words(relations.len())];
+            row[i / 64] = 1 << (i % 64);
+            row
+        })
+        .collect();
+    let mut pivot = vec![false; relations.len()];
+    for c in 0..columns {
+        let bit = |row: &Vec<u64>| (row[c / 64] >> (c % 64)) & 1 == 1;
+        let Some(r) = (0..rows.len()).find(|&r| !pivot[r] && bit(&rows[r])) else {
+            continue;
+        };
+        pivot[r] = true;
+        let (pivot_row, pivot_history) = (rows[r].clone(), history[r].clone());
+        for r2 in 0..rows.len() {
+            if r2 != r && bit(&rows[r2]) {
+                rows[r2]
+                    .iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(a, b)| *a ^= b);
+                history[r2]
+                    .iter_mut()
+                    .zip(&pivot_history)
+                    .for_each(|(a, b)| *a ^= b);
+            }
+        }
+    }
+    (0..rows.len())
+        .filter(|&r| !pivot[r] && rows[r].iter().all(|&w| w == 0))
+        .map(|r| {
+            (0..relations.len())
+                .filter(|&i| (history[r][i / 64] >> (i % 64)) & 1 == 1)
+                .collect()
+        })
+        .collect()
+}
+
+/// Tries the dependencies for a nontrivial factor of $n$.
+fn square_roots(n: &Integer, relations: &[Relation], base: &[Prime]) -> Option<Integer> {
+    for dependency in dependencies(relations, base.len() + 1) {
+        let mut x = Integer::from(1);
+        let mut y = Integer::from(1);
+        let mut exponents = vec![0_u32; base.len() + 1];
+        for &i in &dependency {
+            x = x * &relations[i].y % n;
+            y = y * &relations[i].large % n;
+            for &(j, e) in &relations[i].exponents {
+                exponents[j] += e;
+            }
+        }
+        for (j, e) in exponents.into_iter().enumerate().skip(1) {
+            y = y * Integer::from(base[j - 1].p)
+                .pow_mod(&Integer::from(e / 2), n)
+                .unwrap()
+                % n;
+        }
+        let g = (x - y).gcd(n);
+        if g > 1 && g != *n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+/// A nontrivial factor of $n$ by the self-initialising quadratic sieve, or `None` if it gives up (which it does
+/// for primes). It's meant for $n$ with no small prime factors: use `factor_integer` for everything else.
+/// # Example:
+/// ```
+/// use bored_algebra::ntheory::factor::siqs;
+/// use bored_algebra::Integer;
+///
+/// let n = Integer::from(1000000007) * Integer::from(1000000009);
+/// let d = siqs(&n).unwrap();
+/// assert!(d == 1000000007 || d == 1000000009);
+/// ```
+pub fn siqs(n: &Integer) -> Option<Integer> {
+    if *n < 4 || is_prime(n) {
+        return None;
+    }
+    if n.is_even() {
+        return Some(Integer::from(2));
+    }
+    if let Some((r, _)) = perfect_power(n) {
+        return Some(r);
+    }
+    let k = multiplier(n);
+    let kn = Integer::from(n * k);
+    let (size, m) = parameters(kn.significant_bits());
+
+    // the factor base
+    let mut base = vec![Prime {
+        p: 2,
+        sqrt: 1,
+        log: 1,
+        sieve: false,
+    }];
+    let mut bound = 16 * size as u64;
+    while base.len() < size {
+        base.truncate(1);
+        for &p in &primes_up_to(bound)[1..] {
+            if n.is_divisible_u(p as u32) {
+                return Some(Integer::from(p));
+            }
+            let r = kn.mod_u(p as u32) as u64;
+            if let Some(root) = sqrt_mod_prime(&Integer::from(r), &Integer::from(p)) {
+                base.push(Prime {
+                    p,
+                    sqrt: root.to_u64().unwrap(),
+                    log: (p as f64).log2().round() as u8,
+                    sieve: r != 0,
+                });
+            }
+            if base.len() == size {
+                break;
+            }
+        }
+        bound *= 2;
+    }
+    let largest = base.last().unwrap().p;
+    let large_bound = largest * 64;
+    let columns = base.len() + 1;
+    let mut wanted = columns + 32;
+
+    // values at the ends of the interval are about M sqrt(kn/2), and we can lose a large prime and some bits from
+    // the primes we don't sieve
+    let threshold = ((m as f64).log2() + kn.significant_bits() as f64 / 2.0
+        - 0.5
+        - (large_bound as f64).log2()
+        - 4.0)
+        .max(0.0) as u8;
+
+    // candidates for the primes in A: the ones of about the right size, or any sieved primes if there aren't
+    // enough of those
+    let target = (Integer::from(&kn * 2).sqrt() / m).max(Integer::from(1));
+    let target_bits = target.significant_bits() as f64;
+    let s = ((target_bits / 11.0).round() as usize).max(1);
+    let ideal = target_bits / s as f64;
+    let mut candidates: Vec<usize> = (1..base.len())
+        .filter(|&i| base[i].sieve && ((base[i].p as f64).log2() - ideal).abs() < 1.0)
+        .collect();
+    if candidates.len() < s + 3 {
+        candidates = (1..base.len())
+            .filter(|&i| base[i].sieve && base[i].p > 3)
+            .collect();
+    }
+    if candidates.len() < s + 1 {
+        return None;
+    }
+
+    let mut rng = RandState::new();
+    let mut used = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut relations: Vec<Relation> = vec![];
+    let mut partials: HashMap<u64, Relation> = HashMap::new();
+    let mut failures = 0;
+    let mut attempts = 0;
+    let mut sieve = vec![0_u8; 2 * m as usize];
+    while attempts < 1000 {
+        // choose A, close to the target
+        let mut chosen: Vec<usize> = vec![];
+        while chosen.len() + 1 < s {
+            let i = candidates[rng.below(candidates.len() as u32) as usize];
+            if !chosen.contains(&i) {
+                chosen.push(i);
+            }
+        }
+        let partial = chosen
+            .iter()
+            .fold(Integer::from(1), |acc, &i| acc * base[i].p);
+        let want = Integer::from(&target / &partial).to_f64();
+        let last = candidates
+            .iter()
+            .copied()
+            .filter(|i| !chosen.contains(i))
+            .min_by(|&i, &j| {
+                let d = |i: usize| ((base[i].p as f64) / want).ln().abs();
+                d(i).total_cmp(&d(j))
+            })
+            .unwrap();
+        chosen.push(last);
+        chosen.sort();
+        if !used.insert(chosen.clone()) {
+            attempts += 1;
+            continue;
+        }
+        let a = partial * base[last].p;
+
+        // the B_l, with B_l^2 = kn mod q_l and B_l = 0 mod the other q
+        let b_parts: Vec<Integer> = chosen
+            .iter()
+            .map(|&i| {
+                let q = base[i].p;
+                let aq = Integer::from(&a / q);
+                let mut gamma = mul_mod(base[i].sqrt, inverse_mod(aq.mod_u(q as u32) as u64, q), q);
+                if gamma > q / 2 {
+                    gamma = q - gamma;
+                }
+                aq * gamma
+            })
+            .collect();
+        let a_inverse: Vec<u64> = base
+            .iter()
+            .map(
+                |prime| match prime.sieve && !a.is_divisible_u(prime.p as u32) {
+                    true => inverse_mod(a.mod_u(prime.p as u32) as u64, prime.p),
+                    false => 0,
+                },
+            )
+            .collect();
+
+        for signs in 0..1_u64 << (s - 1) {
+            let b = b_parts
+                .iter()
+                .enumerate()
+                .fold(Integer::new(), |acc, (l, bl)| {
+                    match l > 0 && (signs >> (l - 1)) & 1 == 1 {
+                        true => acc - bl,
+                        false => acc + bl,
+                    }
+                });
+            let c = (Integer::from(b.square_ref()) - &kn) / &a;
+
+            // sieve, with the roots of A x^2 + 2 B x + C mod p, which are A^(-1) (+-t - B)
+            sieve.iter_mut().for_each(|v| *v = 0);
+            let mut roots = vec![(u64::MAX, u64::MAX); base.len()];
+            for (i, prime) in base.iter().enumerate() {
+                if a_inverse[i] == 0 {
+                    continue;
+                }
+                let p = prime.p;
+                let bp = b.mod_u(p as u32) as u64;
+                let r1 = mul_mod(a_inverse[i], (prime.sqrt + p - bp) % p, p);
+                let r2 = mul_mod(a_inverse[i], (2 * p - prime.sqrt - bp) % p, p);
+                roots[i] = (r1, r2);
+                for r in [r1, r2] {
+                    let mut j = ((r + m) % p) as usize;
+                    while j < sieve.len() {
+                        sieve[j] = sieve[j].wrapping_add(prime.log);
+                        j += p as usize;
+                    }
+                    if r1 == r2 {
+                        break;
+                    }
+                }
+            }
+
+            for (j, _) in sieve.iter().enumerate().filter(|(_, &v)| v >= threshold) {
+                let x = j as i64 - m as i64;
+                let mut value = (Integer::from(&a * x) + Integer::from(&b * 2)) * x + &c;
+                let y = Integer::from(&a * x) + &b;
+                let mut exponents = vec![];
+                if value < 0 {
+                    exponents.push((0, 1));
+                    value = -value;
+                }
+                if value == 0 {
+                    continue;
+                }
+                for (i, prime) in base.iter().enumerate() {
+                    let divides = match roots[i] {
+                        (u64::MAX, _) => value.is_divisible_u(prime.p as u32),
+                        (r1, r2) => {
+                            let xp = x.rem_euclid(prime.p as i64) as u64;
+                            xp == r1 || xp == r2
+                        }
+                    };
+                    let mut e = chosen.contains(&i) as u32;
+                    if divides {
+                        e += value.remove_factor_mut(&Integer::from(prime.p));
+                    }
+                    if e > 0 {
+                        exponents.push((i + 1, e));
+                    }
+                }
+                let relation = Relation {
+                    y: y.clone(),
+                    exponents,
+                    large: Integer::from(1),
+                };
+                if value == 1 {
+                    if seen.insert(y) {
+                        relations.push(relation);
+                    }
+                } else if value < large_bound {
+                    let large = value.to_u64().unwrap();
+                    match partials.get(&large) {
+                        Some(other) if other.y != relation.y => {
+                            let mut exponents = relation.exponents.clone();
+                            exponents.extend(other.exponents.iter().copied());
+                            relations.push(Relation {
+                                y: Integer::from(&relation.y * &other.y) % n,
+                                exponents,
+                                large: Integer::from(large),
+                            });
+                        }
+                        Some(_) => {}
+                        None => {
+                            partials.insert(large, relation);
+                        }
+                    }
+                }
+            }
+
+            if relations.len() >= wanted {
+                if let Some(d) = square_roots(n, &relations, &base) {
+                    return Some(d);
+                }
+                failures += 1;
+                if failures > 5 {
+                    return None;
+                }
+                wanted += 32;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_siqs() {
+        for (p, q) in [
+            ("1000000007", "1000000009"),
+            ("100000000000000003", "1000000000000000003"),
+            ("1000000000000000000117", "10000000000000000000009"),
+        ] {
+            let p: Integer = p.parse().unwrap();
+            let q: Integer = q.parse().unwrap();
+            let d = siqs(&Integer::from(&p * &q)).unwrap();
+            assert!(d == p || d == q);
+        }
+        assert_eq!(siqs(&Integer::from(1_000_000_007)), None);
+    }
+}
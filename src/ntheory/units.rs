@@ -0,0 +1,319 @@
+//! The unit group of $\mathbb{Z}/n$
+//!
+//! By the Chinese remainder theorem $(\mathbb{Z}/n)^\times$ is the product of the groups for the prime powers in $n$.
+//! $(\mathbb{Z}/p^k)^\times$ is cyclic of order $p^{k-1}(p - 1)$ for odd $p$, and $(\mathbb{Z}/2^k)^\times$ is
+//! $\mathbb{Z}/2 \times \mathbb{Z}/2^{k-2}$ for $k \geq 3$. So the exponent of the group, the Carmichael function
+//! $\lambda(n)$, is the lcm of those, and there's a primitive root (a generator) exactly for $n = 1, 2, 4, p^k, 2p^k$.
+//!
+//! Orders start from $\lambda(n)$ and divide out primes for as long as the power stays 1, so they need $n$ and the
+//! $p - 1$ for $p \mid n$ factored, which `factor_integer` does.
+//!
+//! Discrete logarithms use Pohlig-Hellman: if $g$ has order $\prod q^e$, then $\log\_g h$ mod $q^e$ is found one
+//! base-$q$ digit at a time from logarithms in the subgroup of order $q$, and the results are glued with the CRT.
+//! The logarithms of prime order use baby-step giant-step for small $q$, which needs $\sqrt{q}$ memory, and
+//! Pollard's $\rho$ for large $q$, which needs none. Either way it takes about $\sqrt{q}$ steps, so it's fast exactly
+//! when the largest prime in the order of $g$ is small.
+//!
+//! `Zmod` and `ZmodDyn` have these as methods too.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::ntheory::units::{carmichael_lambda, discrete_log, multiplicative_order, primitive_root};
+//! use bored_algebra::Integer;
+//!
+//! let n = Integer::from(1000);
+//! // (Z/1000)^x = Z/2 x Z/50 x Z/4 has exponent lcm(2, 50, 4) = 100, but no generator
+//! assert_eq!(carmichael_lambda(&n), 100);
+//! assert_eq!(primitive_root(&n), None);
+//! assert_eq!(multiplicative_order(&Integer::from(3), &n), Some(Integer::from(100)));
+//! // 3^123 = 827 mod 1000, and 3 has order 100
+//! assert_eq!(discrete_log(&Integer::from(3), &Integer::from(827), &n), Some(Integer::from(23)));
+//! assert_eq!(primitive_root(&Integer::from(54)), Some(Integer::from(5)));
+//! ```
+use crate::ntheory::factor::factor_integer;
+use crate::Integer;
+use rug::ops::{Pow, RemRounding};
+use rug::rand::RandState;
+
+use std::collections::HashMap;
+
+/// Prime orders up to this use baby-step giant-step, bigger ones Pollard's $\rho$.
+const BSGS_BOUND: u64 = 1 << 24;
+
+/// $\lambda(n)$ with its factorization, for $n > 0$.
+fn lambda_factored(n: &Integer) -> (Integer, Vec<(Integer, usize)>) {
+    let mut factors: Vec<(Integer, usize)> = vec![];
+    let mut merge = |q: Integer, e: usize| match factors.iter_mut().find(|(r, _)| *r == q) {
+        Some((_, f)) => *f = (*f).max(e),
+        None => factors.push((q, e)),
+    };
+    if *n > 1 {
+        for (p, k) in factor_integer(n) {
+            match p == 2 {
+                true if k >= 3 => merge(p, k - 2),
+                true if k == 2 => merge(p, 1),
+                true => {}
+                false => {
+                    for (q, e) in factor_integer(&Integer::from(&p - 1)) {
+                        merge(q, e);
+                    }
+                    if k > 1 {
+                        merge(p, k - 1);
+                    }
+                }
+            }
+        }
+    }
+    factors.sort();
+    let lambda = factors.iter().fold(Integer::from(1), |acc, (q, e)| {
+        acc * q.clone().pow(*e as u32)
+    });
+    (lambda, factors)
+}
+
+/// The Carmichael function $\lambda(n)$, the exponent of $(\mathbb{Z}/n)^\times$. Panics unless $n > 0$.
+pub fn carmichael_lambda(n: &Integer) -> Integer {
+    assert!(*n > 0, "the modulus needs to be positive");
+    lambda_factored(n).0
+}
+
+/// The order of the unit $a$ mod $n$ with its factorization.
+fn order_factored(a: &Integer, n: &Integer) -> Option<(Integer, Vec<(Integer, usize)>)> {
+    if Integer::from(a.gcd_ref(n)) != 1 {
+        return None;
+    }
+    let (mut order, lambda) = lambda_factored(n);
+    let mut factors = vec![];
+    for (q, e) in lambda {
+        let mut f = e;
+        while f > 0 {
+            let smaller = Integer::from(&order / &q);
+            if Integer::from(a.pow_mod_ref(&smaller, n).unwrap()) != Integer::from(1).rem_euc(n) {
+                break;
+            }
+            order = smaller;
+            f -= 1;
+        }
+        if f > 0 {
+            factors.push((q, f));
+        }
+    }
+    Some((order, factors))
+}
+
+/// The order of $a$ in $(\mathbb{Z}/n)^\times$, or `None` if $a$ isn't a unit mod $n$. Panics unless $n > 0$.
+pub fn multiplicative_order(a: &Integer, n: &Integer) -> Option<Integer> {
+    assert!(*n > 0, "the modulus needs to be positive");
+    order_factored(a, n).map(|(order, _)| order)
+}
+
+/// The smallest primitive root mod $n$ in $[0, n)$, or `None` if $(\mathbb{Z}/n)^\times$ isn't cyclic. Panics
+/// unless $n > 0$.
+pub fn primitive_root(n: &Integer) -> Option<Integer> {
+    assert!(*n > 0, "the modulus needs to be positive");
+    if *n <= 2 {
+        return Some(Integer::from(1).rem_euc(n));
+    }
+    // cyclic means n = 4, p^k or 2p^k
+    let odd = Integer::from(n >> n.find_one(0).unwrap());
+    let cyclic = match n.find_one(0).unwrap() {
+        0 | 1 => odd == 1 || factor_integer(&odd).len() == 1,
+        2 => odd == 1,
+        _ => false,
+    };
+    if !cyclic {
+        return None;
+    }
+    let (lambda, factors) = lambda_factored(n);
+    let mut g = Integer::from(2);
+    loop {
+        if Integer::from(g.gcd_ref(n)) == 1
+            && factors.iter().all(|(q, _)| {
+                Integer::from(g.pow_mod_ref(&Integer::from(&lambda / q), n).unwrap()) != 1
+            })
+        {
+            return Some(g);
+        }
+        g += 1;
+    }
+}
+
+/// The smallest $x \geq 0$ with $g^x \equiv h \pmod n$, for units $g$ and $h$, by Pohlig-Hellman. `None` if $h$
+/// isn't a power of $g$, or either isn't a unit. Panics unless $n > 0$.
+pub fn discrete_log(g: &Integer, h: &Integer, n: &Integer) -> Option<Integer> {
+    assert!(*n > 0, "the modulus needs to be positive");
+    let (order, factors) = order_factored(g, n)?;
+    if Integer::from(h.gcd_ref(n)) != 1 {
+        return None;
+    }
+    let (g, h) = (g.clone().rem_euc(n), h.clone().rem_euc(n));
+    let (mut x, mut m) = (Integer::new(), Integer::from(1));
+    for (q, e) in factors {
+        let qe = q.clone().pow(e as u32);
+        let cofactor = Integer::from(&order / &qe);
+        let gi = g.clone().pow_mod(&cofactor, n).unwrap();
+        let hi = h.clone().pow_mod(&cofactor, n).unwrap();
+        // gamma has order q, and the digits are logs to base gamma
+        let gamma = gi.clone().pow_mod(&Integer::from(&qe / &q), n).unwrap();
+        let gi_inv = gi.clone().invert(n).unwrap();
+        let mut xi = Integer::new();
+        let mut qk = Integer::from(1);
+        for k in 0..e {
+            let shifted = hi.clone() * gi_inv.clone().pow_mod(&xi, n).unwrap() % n;
+            let power = q.clone().pow((e - 1 - k) as u32);
+            let d = prime_order_log(&gamma, &shifted.pow_mod(&power, n).unwrap(), n, &q)?;
+            xi += d * &qk;
+            qk *= &q;
+        }
+        // x = x mod m and xi mod q^e
+        let t = Integer::from(&xi - &x) * m.clone().invert(&qe).unwrap();
+        x += t.rem_euc(&qe) * &m;
+        m *= qe;
+    }
+    x = x.rem_euc(&order);
+    (g.pow_mod_ref(&x, n).map(Integer::from).unwrap() == h).then_some(x)
+}
+
+fn prime_order_log(g: &Integer, h: &Integer, n: &Integer, q: &Integer) -> Option<Integer> {
+    match *q <= BSGS_BOUND {
+        true => baby_step_giant_step(g, h, n, q),
+        false => pollard_rho_log(g, h, n, q),
+    }
+}
+
+/// The $x \in [0, m)$ with $g^x \equiv h \pmod n$, for $g$ a unit of order at most $m$, or `None` if there isn't
+/// one. Stores $\lceil \sqrt{m} \rceil$ baby steps $g^j$, and takes giant steps $h g^{-i \lceil \sqrt{m} \rceil}$ until
+/// one lands on them.
+pub fn baby_step_giant_step(g: &Integer, h: &Integer, n: &Integer, m: &Integer) -> Option<Integer> {
+    let s = Integer::from(m - 1).sqrt() + 1u32;
+    let steps = s.to_u64().expect("order too big for baby-step giant-step");
+    let mut baby = HashMap::new();
+    let mut power = Integer::from(1).rem_euc(n);
+    for j in 0..steps {
+        baby.entry(power.clone()).or_insert(j);
+        power = power * g % n;
+    }
+    // power is g^s now
+    let giant = power.invert(n).ok()?;
+    let mut y = h.clone().rem_euc(n);
+    for i in 0..steps {
+        if let Some(j) = baby.get(&y) {
+            let x = Integer::from(&s * i) + j;
+            return (x < *m).then_some(x);
+        }
+        y = y * &giant % n;
+    }
+    None
+}
+
+/// The $x \in [0, q)$ with $g^x \equiv h \pmod n$, for $g$ a unit of prime order $q$, or `None` if there isn't one,
+/// by Pollard's $\rho$: a pseudorandom walk through elements $g^a h^b$ with Floyd's cycle finding, until a collision
+/// $g^a h^b = g^{a'} h^{b'}$ gives $x = (a' - a)/(b - b') \bmod q$. Walks that collide with $b \equiv b'$ are
+/// restarted from a random point.
+pub fn pollard_rho_log(g: &Integer, h: &Integer, n: &Integer, q: &Integer) -> Option<Integer> {
+    let h = h.clone().rem_euc(n);
+    if h == Integer::from(1).rem_euc(n) {
+        return Some(Integer::new());
+    }
+    // one step of the walk, by which third of [0, n) the element is in
+    let step = |(x, a, b): (Integer, Integer, Integer)| match Integer::from(&x * 3u32) / n {
+        i if i == 0 => (x * g % n, (a + 1u32) % q, b),
+        i if i == 1 => (
+            Integer::from(&x * &x) % n,
+            Integer::from(&a * 2u32) % q,
+            Integer::from(&b * 2u32) % q,
+        ),
+        _ => (x * &h % n, a, (b + 1u32) % q),
+    };
+    let mut rng = RandState::new();
+    for _ in 0..20 {
+        let a = Integer::from(q.random_below_ref(&mut rng));
+        let b = Integer::from(q.random_below_ref(&mut rng));
+        let x =
+            Integer::from(g.pow_mod_ref(&a, n).unwrap()) * h.clone().pow_mod(&b, n).unwrap() % n;
+        let mut tortoise = (x, a, b);
+        let mut hare = step(tortoise.clone());
+        while tortoise.0 != hare.0 {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+        }
+        let db = Integer::from(&tortoise.2 - &hare.2).rem_euc(q);
+        if db == 0 {
+            continue;
+        }
+        let x = (Integer::from(&hare.1 - &tortoise.1) * db.invert(q).unwrap()).rem_euc(q);
+        return (Integer::from(g.pow_mod_ref(&x, n).unwrap()) == h).then_some(x);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn int(a: u64) -> Integer {
+        Integer::from(a)
+    }
+
+    #[test]
+    fn test_lambda_and_order() {
+        let lambdas: Vec<Integer> = [1, 2, 4, 8, 15, 1024, 561, 65520]
+            .iter()
+            .map(|&n| carmichael_lambda(&int(n)))
+            .collect();
+        assert_eq!(lambdas, [1, 1, 2, 2, 4, 256, 80, 12]);
+        // 561 is a Carmichael number, so every unit has order dividing 560
+        assert_eq!(multiplicative_order(&int(2), &int(561)), Some(int(40)));
+        assert_eq!(multiplicative_order(&int(3), &int(561)), None);
+        assert_eq!(multiplicative_order(&int(0), &int(1)), Some(int(1)));
+        assert_eq!(
+            multiplicative_order(&int(1000002), &int(1000003)),
+            Some(int(2))
+        );
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        let roots: Vec<Option<Integer>> = [1, 2, 4, 8, 9, 15, 18, 1000003]
+            .iter()
+            .map(|&n| primitive_root(&int(n)))
+            .collect();
+        assert_eq!(
+            roots,
+            [
+                Some(int(0)),
+                Some(int(1)),
+                Some(int(3)),
+                None,
+                Some(int(2)),
+                None,
+                Some(int(5)),
+                Some(int(2))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        // a safe prime p = 2q + 1 with q > 2^30, so the part of order q needs rho
+        let p = int(2147483783);
+        let q = int(1073741891);
+        let x = int(123456789);
+        let h = int(5).pow_mod(&x, &p).unwrap();
+        assert_eq!(discrete_log(&int(5), &h, &p), Some(x.clone()));
+        let (g2, h2) = (int(25), Integer::from(h.pow_mod_ref(&int(2), &p).unwrap()));
+        assert_eq!(pollard_rho_log(&g2, &h2, &p, &q), Some(x.clone()));
+        assert_eq!(
+            baby_step_giant_step(&int(5), &int(1), &p, &int(10)),
+            Some(int(0))
+        );
+        // 5 isn't a square, so it's not a power of 25
+        assert_eq!(discrete_log(&g2, &int(5), &p), None);
+        // a composite modulus, where the order of 7 is smooth
+        let n = int(1024 * 243);
+        let h = int(7).pow_mod(&int(77), &n).unwrap();
+        let order = multiplicative_order(&int(7), &n).unwrap();
+        assert_eq!(discrete_log(&int(7), &h, &n), Some(int(77) % order));
+        assert_eq!(discrete_log(&int(2), &int(4), &int(10)), None);
+    }
+}
@@ -0,0 +1,730 @@
+//! Number fields
+//!
+//! A number field $K = \mathbb{Q}(\theta) = \mathbb{Q}[x]/(f)$ for an irreducible $f \in \mathbb{Q}[x]$ of degree $n$,
+//! and its elements, which are polynomials in $\theta$ of degree less than $n$. As with `GaloisField`, `zero()`
+//! and `one()` (and rationals in general) can be made without a field, and pick one up from whatever they're
+//! combined with.
+//!
+//! Multiplication by $\alpha \in K$ is a $\mathbb{Q}$-linear map $K \to K$, and its matrix in the basis
+//! $1, \theta, \ldots, \theta^{n-1}$ gives the norm (determinant), trace and characteristic polynomial of $\alpha$.
+//! The characteristic polynomial is a power of the minimal polynomial.
+//!
+//! The ring of integers $\mathcal{O}\_K$ is found with the Round 2 algorithm of Zassenhaus and Pohst: start with the
+//! order $\mathbb{Z}[d\theta]$ for a $d$ that makes $d\theta$ integral, and for each prime $p$ with $p^2$ dividing
+//! its discriminant, replace the order $\mathcal{O}$ by the multiplier ring $\\{x \in K : xI\_p \subseteq I\_p\\}$
+//! of its $p$-radical $I\_p = \\{x \in \mathcal{O} : x^{p^j} \in p\mathcal{O}\\}$ (for $p^j \geq n$) until that
+//! doesn't change anything. The lattices are kept in Hermite normal form, in coordinates over the power basis.
+//!
+//! TODO: the discriminant of $\mathbb{Z}[d\theta]$ gets factored completely, which is the slow part for big
+//! coefficients. Only the square part is needed.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::number_field::NumberField;
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::Rational;
+//!
+//! let p = |c: Vec<i64>| Polynomial::from(c.into_iter().map(Rational::from).collect::<Vec<_>>());
+//! // Q(sqrt(5)), where the ring of integers is Z[(1 + sqrt(5))/2]
+//! let k = NumberField::new(&p(vec![-5, 0, 1])).unwrap();
+//! let theta = k.generator();
+//! assert_eq!(theta.clone() * theta.clone(), k.element(&p(vec![5])));
+//! assert_eq!(theta.norm(), -5);
+//! assert_eq!(k.discriminant(), 5);
+//! let golden = k.element(&Polynomial::from(vec![Rational::from((1, 2)), Rational::from((1, 2))]));
+//! assert!(golden.is_integral());
+//! assert_eq!(golden.minimal_polynomial(), p(vec![-1, -1, 1]));
+//! ```
+use crate::error::ArithmeticError;
+use crate::factor::FactorField;
+use crate::matrix::Matrix;
+use crate::module::ModType;
+use crate::ntheory::factor::factor_integer;
+use crate::poly::Polynomial;
+use crate::{Integer, Rational};
+use rug::ops::{Pow, RemRounding};
+
+use core::cmp::Ordering;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+use std::rc::Rc;
+
+/// The field $\mathbb{Q}[x]/(f)$, which is cheap to clone.
+#[derive(Clone, Debug)]
+pub struct NumberField {
+    modulus: Rc<Polynomial<Rational>>,
+}
+
+/// Fields are equal if they have the same (monic) modulus.
+impl PartialEq for NumberField {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.modulus, &other.modulus) || self.modulus == other.modulus
+    }
+}
+
+impl Eq for NumberField {}
+
+impl NumberField {
+    /// The field $\mathbb{Q}[x]/(f)$, or `NotIrreducible` if $f$ isn't irreducible (constants aren't).
+    pub fn new(f: &Polynomial<Rational>) -> Result<Self, ArithmeticError> {
+        if f.deg() == 0 {
+            return Err(ArithmeticError::NotIrreducible);
+        }
+        match Rational::factor_poly(f).as_slice() {
+            [(g, 1)] if g.deg() == f.deg() => Ok(Self {
+                modulus: Rc::new(f.monic()),
+            }),
+            _ => Err(ArithmeticError::NotIrreducible),
+        }
+    }
+
+    /// $\mathbb{Q}[x]/(f)$ for an $f$ that's known to be irreducible, without factoring it.
+    pub(crate) fn new_unchecked(f: &Polynomial<Rational>) -> Self {
+        Self {
+            modulus: Rc::new(f.monic()),
+        }
+    }
+
+    /// The monic defining polynomial $f$.
+    pub fn modulus(&self) -> &Polynomial<Rational> {
+        &self.modulus
+    }
+
+    /// $n = [K : \mathbb{Q}]$.
+    pub fn degree(&self) -> usize {
+        self.modulus.deg() as usize
+    }
+
+    /// $\theta$, the class of $x$.
+    pub fn generator(&self) -> NumberFieldElement {
+        self.element(&Polynomial::from(vec![Rational::new(), Rational::from(1)]))
+    }
+
+    /// The class of the polynomial $g(x)$, which is $g(\theta)$.
+    pub fn element(&self, g: &Polynomial<Rational>) -> NumberFieldElement {
+        NumberFieldElement {
+            value: g.div_rem(&self.modulus).1,
+            field: Some(self.clone()),
+        }
+    }
+
+    /// $\sum\_i c\_i \theta^i$.
+    pub fn from_coords(&self, c: &[Rational]) -> NumberFieldElement {
+        self.element(&Polynomial::from(c.to_vec()))
+    }
+
+    /// The discriminant of $f$, $(-1)^{n(n-1)/2} N(f'(\theta))$. It's the discriminant of the order
+    /// $\mathbb{Z}[\theta]$ when $\theta$ is integral.
+    pub fn polynomial_discriminant(&self) -> Rational {
+        let n = self.degree();
+        let norm = self.element(&(*self.modulus).clone().derivative()).norm();
+        match (n * (n - 1) / 2) % 2 {
+            0 => norm,
+            _ => -norm,
+        }
+    }
+
+    /// The signature $(r\_1, r\_2)$: $f$ has $r\_1$ real roots and $r\_2$ pairs of complex ones. The real roots are
+    /// counted with a Sturm sequence.
+    pub fn signature(&self) -> (usize, usize) {
+        let f = (*self.modulus).clone();
+        let mut sturm = vec![f.clone(), f.derivative()];
+        loop {
+            let r = sturm[sturm.len() - 2].div_rem(&sturm[sturm.len() - 1]).1;
+            if r.is_zero() {
+                break;
+            }
+            sturm.push(-r);
+        }
+        // sign changes at -infinity and +infinity, from the leading terms
+        let changes = |at_infinity: &dyn Fn(&Polynomial<Rational>) -> Ordering| {
+            let signs: Vec<Ordering> = sturm.iter().map(at_infinity).collect();
+            signs.windows(2).filter(|w| w[0] != w[1]).count()
+        };
+        let r1 = changes(&|g| match g.deg() % 2 {
+            0 => g.lead_coeff().cmp0(),
+            _ => g.lead_coeff().cmp0().reverse(),
+        }) - changes(&|g| g.lead_coeff().cmp0());
+        (r1, (self.degree() - r1) / 2)
+    }
+
+    /// A $\mathbb{Z}$-basis of the ring of integers, by Round 2. The basis is in Hermite normal form over the power
+    /// basis, so the $i$th element has degree $i$ in $\theta$, and it starts with 1.
+    pub fn integral_basis(&self) -> Vec<NumberFieldElement> {
+        let n = self.degree();
+        // d theta is integral when d clears the denominators of f
+        let d = self
+            .modulus
+            .coeffs_take(n + 1)
+            .iter()
+            .fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+        let mut order: Vec<Vec<Rational>> = (0..n)
+            .map(|i| {
+                let mut row = vec![Rational::new(); n];
+                row[i] = Rational::from(d.clone().pow(i as u32));
+                row
+            })
+            .collect();
+        let disc = self.lattice_discriminant(&order);
+        for (p, e) in factor_integer(&disc.abs()) {
+            if e < 2 {
+                continue;
+            }
+            while let Some(larger) = self.enlarge_at(&order, &p) {
+                order = larger;
+            }
+        }
+        order.iter().map(|w| self.from_coords(w)).collect()
+    }
+
+    /// The discriminant of $K$, which is the discriminant $\det(\mathrm{Tr}(w\_i w\_j))$ of the ring of integers.
+    pub fn discriminant(&self) -> Integer {
+        let basis = self
+            .integral_basis()
+            .iter()
+            .map(|w| w.coords())
+            .collect::<Vec<_>>();
+        self.lattice_discriminant(&basis)
+    }
+
+    /// $\det(\mathrm{Tr}(w\_i w\_j))$ for a basis of an order, which is an integer.
+    pub(crate) fn lattice_discriminant(&self, basis: &[Vec<Rational>]) -> Integer {
+        let w: Vec<NumberFieldElement> = basis.iter().map(|c| self.from_coords(c)).collect();
+        let traces = w
+            .iter()
+            .map(|a| w.iter().map(|b| (a.clone() * b.clone()).trace()).collect())
+            .collect::<Vec<Vec<Rational>>>();
+        let det = Matrix::from(traces).determinant();
+        assert!(*det.denom() == 1, "the basis doesn't span an order");
+        det.numer().clone()
+    }
+
+    /// The multiplication table of an order with (lower triangular) basis $w$: `table[i][j]` is $w\_i w\_j$ in coordinates over $w$.
+    pub(crate) fn multiplication_table(&self, basis: &[Vec<Rational>]) -> Vec<Vec<Vec<Integer>>> {
+        let w: Vec<NumberFieldElement> = basis.iter().map(|c| self.from_coords(c)).collect();
+        w.iter()
+            .map(|a| {
+                w.iter()
+                    .map(|b| {
+                        let c = solve_triangular(basis, &(a.clone() * b.clone()).coords());
+                        c.into_iter()
+                            .map(|x| {
+                                assert!(*x.denom() == 1, "the basis doesn't span an order");
+                                x.numer().clone()
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// One step of Round 2 at $p$: the multiplier ring of the $p$-radical of the order with basis `order`, or
+    /// `None` if that's the same order, which happens exactly when the order is $p$-maximal.
+    fn enlarge_at(&self, order: &[Vec<Rational>], p: &Integer) -> Option<Vec<Vec<Rational>>> {
+        let n = order.len();
+        let table = self.multiplication_table(order);
+        let unit = |i: usize| {
+            let mut e = vec![Integer::new(); n];
+            e[i] = Integer::from(1);
+            e
+        };
+        let one: Vec<Integer> =
+            solve_triangular(order, &self.from_coords(&[Rational::from(1)]).coords())
+                .into_iter()
+                .map(|x| x.numer().clone())
+                .collect();
+
+        // the radical mod p is the kernel of x -> x^q, which is linear mod p
+        let mut q = p.clone();
+        while q < n {
+            q *= p;
+        }
+        let frobenius: Vec<Vec<Integer>> = (0..n)
+            .map(|i| pow_mod_p(&table, &one, &unit(i), &q, p))
+            .collect();
+        let mut generators = left_kernel_mod_p(&frobenius, p);
+        generators.extend((0..n).map(|i| unit(i).into_iter().map(|x| x * p).collect()));
+        let generators: Vec<Vec<Rational>> = generators
+            .iter()
+            .map(|row| row.iter().map(Rational::from).collect())
+            .collect();
+        let radical = hermite_normal_form(&generators);
+
+        // U = {x in O : x I in pI}, the kernel mod p of x -> (x b_k in I/pI)_k for the basis b_k of I
+        let rows: Vec<Vec<Integer>> = (0..n)
+            .map(|i| {
+                radical
+                    .iter()
+                    .flat_map(|b| {
+                        let product: Vec<Rational> = (0..n)
+                            .map(|k| {
+                                (0..n).fold(Rational::new(), |acc, l| {
+                                    acc + Rational::from(&b[l] * &table[i][l][k])
+                                })
+                            })
+                            .collect();
+                        solve_triangular(&radical, &product)
+                            .into_iter()
+                            .map(|x| x.numer().clone().rem_euc(p))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut generators = left_kernel_mod_p(&rows, p);
+        if generators.is_empty() {
+            return None;
+        }
+        generators.extend((0..n).map(|i| unit(i).into_iter().map(|x| x * p).collect()));
+        let u = Matrix::from(generators).hermite_normal_form();
+
+        // the new order is U/p, back in coordinates over the power basis
+        let larger: Vec<Vec<Rational>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|k| {
+                        (0..n).fold(Rational::new(), |acc, j| {
+                            acc + Rational::from(u.get(i, j) * &order[j][k])
+                        }) / Rational::from(p)
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(hermite_normal_form(&larger))
+    }
+}
+
+/// The coordinates of `v` over the rows of `basis`, which is lower triangular with a nonzero diagonal.
+pub(crate) fn solve_triangular(basis: &[Vec<Rational>], v: &[Rational]) -> Vec<Rational> {
+    let mut v = v.to_vec();
+    let mut x = vec![Rational::new(); basis.len()];
+    for (i, row) in basis.iter().enumerate().rev() {
+        x[i] = Rational::from(&v[i] / &row[i]);
+        for (vj, rj) in v.iter_mut().zip(row.iter()).take(i + 1) {
+            *vj -= Rational::from(&x[i] * rj);
+        }
+    }
+    x
+}
+
+/// The Hermite normal form of a lattice in $\mathbb{Q}^n$, given by generating rows, with the columns taken in
+/// reverse so that it comes out lower triangular.
+pub(crate) fn hermite_normal_form(rows: &[Vec<Rational>]) -> Vec<Vec<Rational>> {
+    let den = rows
+        .iter()
+        .flatten()
+        .fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+    let integral: Vec<Vec<Integer>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .rev()
+                .map(|c| c.numer() * Integer::from(&den / c.denom()))
+                .collect()
+        })
+        .collect();
+    let h = Matrix::from(integral).hermite_normal_form();
+    (0..h.rows())
+        .rev()
+        .map(|i| {
+            h.row(i)
+                .iter()
+                .rev()
+                .map(|c| Rational::from((c, &den)))
+                .collect()
+        })
+        .collect()
+}
+
+/// $xy$ in an order with the given multiplication table, modulo $p$.
+pub(crate) fn mul_mod_p(
+    table: &[Vec<Vec<Integer>>],
+    x: &[Integer],
+    y: &[Integer],
+    p: &Integer,
+) -> Vec<Integer> {
+    let n = x.len();
+    let mut z = vec![Integer::new(); n];
+    for (i, xi) in x.iter().enumerate().filter(|(_, xi)| **xi != 0) {
+        for (j, yj) in y.iter().enumerate().filter(|(_, yj)| **yj != 0) {
+            let c = Integer::from(xi * yj);
+            for (zk, t) in z.iter_mut().zip(table[i][j].iter()) {
+                *zk += Integer::from(&c * t);
+            }
+        }
+    }
+    z.into_iter().map(|zk| zk.rem_euc(p)).collect()
+}
+
+/// $x^e$ modulo $p$, by repeated squaring.
+pub(crate) fn pow_mod_p(
+    table: &[Vec<Vec<Integer>>],
+    one: &[Integer],
+    x: &[Integer],
+    e: &Integer,
+    p: &Integer,
+) -> Vec<Integer> {
+    let mut result = one.to_vec();
+    for k in (0..e.significant_bits()).rev() {
+        result = mul_mod_p(table, &result, &result, p);
+        if e.get_bit(k) {
+            result = mul_mod_p(table, &result, x, p);
+        }
+    }
+    result
+}
+
+/// A basis of $\\{x \in \mathbb{F}\_p^m : xA = 0\\}$ for the $m$ rows of $A$, by row reducing $A$ next to an
+/// identity matrix: the rows of $A$ that reduce to zero carry the combinations that did it.
+pub(crate) fn left_kernel_mod_p(a: &[Vec<Integer>], p: &Integer) -> Vec<Vec<Integer>> {
+    let m = a.len();
+    let cols = a.first().map_or(0, |row| row.len());
+    let mut rows: Vec<(Vec<Integer>, Vec<Integer>)> = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut e = vec![Integer::new(); m];
+            e[i] = Integer::from(1);
+            (row.iter().map(|x| x.clone().rem_euc(p)).collect(), e)
+        })
+        .collect();
+    let mut rank = 0;
+    for c in 0..cols {
+        let Some(pivot) = (rank..m).find(|&i| rows[i].0[c] != 0) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let inv = rows[rank].0[c].clone().invert(p).unwrap();
+        let (pa, pe) = rows[rank].clone();
+        let scale = |v: &[Integer]| {
+            v.iter()
+                .map(|x| Integer::from(x * &inv).rem_euc(p))
+                .collect()
+        };
+        let (pa, pe): (Vec<Integer>, Vec<Integer>) = (scale(&pa), scale(&pe));
+        for (i, (ra, re)) in rows.iter_mut().enumerate() {
+            if i == rank || ra[c] == 0 {
+                continue;
+            }
+            let u = ra[c].clone();
+            for (x, y) in ra
+                .iter_mut()
+                .zip(pa.iter())
+                .chain(re.iter_mut().zip(pe.iter()))
+            {
+                *x = Integer::from(&*x - &u * y).rem_euc(p);
+            }
+        }
+        rows[rank] = (pa, pe);
+        rank += 1;
+    }
+    rows.into_iter().skip(rank).map(|(_, e)| e).collect()
+}
+
+/// An element $g(\theta)$ of a number field. Elements without a field are rationals.
+#[derive(Clone, Debug)]
+pub struct NumberFieldElement {
+    value: Polynomial<Rational>,
+    field: Option<NumberField>,
+}
+
+impl NumberFieldElement {
+    /// The rational number $c$, which works in any field.
+    pub fn constant(c: Rational) -> Self {
+        Self {
+            value: Polynomial::from(vec![c]),
+            field: None,
+        }
+    }
+
+    /// The representative $g$ of degree less than $n$, with `self` $= g(\theta)$.
+    pub fn value(&self) -> &Polynomial<Rational> {
+        &self.value
+    }
+
+    /// The field, which is `None` for rationals made without one.
+    pub fn field(&self) -> Option<&NumberField> {
+        self.field.as_ref()
+    }
+
+    fn degree(&self) -> usize {
+        self.field.as_ref().map_or(1, NumberField::degree)
+    }
+
+    /// The coordinates over $1, \theta, \ldots, \theta^{n-1}$.
+    pub fn coords(&self) -> Vec<Rational> {
+        let mut c = self.value.coeffs_take(self.degree());
+        c.resize(self.degree(), Rational::new());
+        c
+    }
+
+    fn common_field(&self, other: &Self) -> Option<NumberField> {
+        match (&self.field, &other.field) {
+            (None, k) | (k, None) => k.clone(),
+            (Some(k), Some(l)) => {
+                assert!(k == l, "elements of different number fields");
+                Some(k.clone())
+            }
+        }
+    }
+
+    fn with_field(value: Polynomial<Rational>, field: Option<NumberField>) -> Self {
+        match field {
+            Some(k) => k.element(&value),
+            None => Self { value, field: None },
+        }
+    }
+
+    /// The inverse, from the extended Euclidean algorithm: $sg + tf = 1$ makes $s(\theta)$ the inverse of
+    /// $g(\theta)$. Zero gives `DivideByZero`.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        if self.is_zero() {
+            return Err(ArithmeticError::DivideByZero);
+        }
+        match &self.field {
+            Some(k) => Ok(k.element(&self.value.ext_gcd(k.modulus()).1)),
+            None => Ok(Self::constant(self.value.lead_coeff().recip())),
+        }
+    }
+
+    /// The matrix of $x \mapsto \alpha x$ over the power basis, acting on columns of coordinates.
+    pub fn multiplication_matrix(&self) -> Matrix<Rational> {
+        let n = self.degree();
+        let mut power = self.clone();
+        let mut columns = vec![];
+        for _ in 0..n {
+            columns.push(power.coords());
+            if let Some(k) = &self.field {
+                power = power * k.generator();
+            }
+        }
+        Matrix::from(columns).transpose()
+    }
+
+    /// $N\_{K/\mathbb{Q}}(\alpha)$, the determinant of multiplication by $\alpha$.
+    pub fn norm(&self) -> Rational {
+        self.multiplication_matrix().determinant()
+    }
+
+    /// $\mathrm{Tr}\_{K/\mathbb{Q}}(\alpha)$, the trace of multiplication by $\alpha$.
+    pub fn trace(&self) -> Rational {
+        let m = self.multiplication_matrix();
+        (0..m.rows()).fold(Rational::new(), |acc, i| acc + m.get(i, i))
+    }
+
+    /// The characteristic polynomial of multiplication by $\alpha$, which has degree $n$.
+    pub fn characteristic_polynomial(&self) -> Polynomial<Rational> {
+        self.multiplication_matrix().characteristic_polynomial()
+    }
+
+    /// The monic minimal polynomial of $\alpha$ over $\mathbb{Q}$. The characteristic polynomial is a power of
+    /// it, so it's the squarefree part.
+    pub fn minimal_polynomial(&self) -> Polynomial<Rational> {
+        let chi = self.characteristic_polynomial();
+        let g = chi.gcd(&chi.clone().derivative());
+        chi.div_rem(&g).0.monic()
+    }
+
+    /// Whether $\alpha$ is an algebraic integer, i.e. its minimal polynomial is in $\mathbb{Z}[x]$.
+    pub fn is_integral(&self) -> bool {
+        let m = self.minimal_polynomial();
+        m.coeffs_take(m.deg() as usize + 1)
+            .iter()
+            .all(|c| *c.denom() == 1)
+    }
+}
+
+impl PartialEq for NumberFieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.common_field(other);
+        self.value == other.value
+    }
+}
+
+impl Eq for NumberFieldElement {}
+
+impl Zero for NumberFieldElement {
+    fn zero() -> Self {
+        Self::constant(Rational::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl One for NumberFieldElement {
+    fn one() -> Self {
+        Self::constant(Rational::from(1))
+    }
+
+    fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+}
+
+impl Add for NumberFieldElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let field = self.common_field(&rhs);
+        Self::with_field(self.value + rhs.value, field)
+    }
+}
+
+impl Neg for NumberFieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            value: -self.value,
+            field: self.field,
+        }
+    }
+}
+
+impl Sub for NumberFieldElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Mul for NumberFieldElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let field = self.common_field(&rhs);
+        Self::with_field(self.value * rhs.value, field)
+    }
+}
+
+/// Panics when dividing by zero.
+impl Div for NumberFieldElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        let field = self.common_field(&rhs);
+        let rhs = Self::with_field(rhs.value, field);
+        self * rhs.inverse().expect("divide by zero")
+    }
+}
+
+impl ModType<NumberFieldElement> for NumberFieldElement {
+    fn mod_mul(lhs: Self, rhs: Self) -> Self {
+        lhs * rhs
+    }
+}
+
+/// $K$ as a $\mathbb{Q}$-vector space.
+impl ModType<Rational> for NumberFieldElement {
+    fn mod_mul(r: Rational, m: Self) -> Self {
+        Self::constant(r) * m
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn poly(coeffs: Vec<i64>) -> Polynomial<Rational> {
+        Polynomial::from(coeffs.into_iter().map(Rational::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert!(NumberField::new(&poly(vec![-4, 0, 1])).is_err());
+        assert!(NumberField::new(&poly(vec![3])).is_err());
+
+        // Q(cbrt(2))
+        let k = NumberField::new(&poly(vec![-2, 0, 0, 1])).unwrap();
+        let theta = k.generator();
+        let one = NumberFieldElement::one();
+        assert_eq!(
+            theta.clone() * theta.clone() * theta.clone(),
+            k.element(&poly(vec![2]))
+        );
+        let a = one.clone() + theta.clone();
+        assert_eq!(a.clone() * a.inverse().unwrap(), one.clone());
+        assert_eq!(one.clone() / a.clone() * a.clone(), one.clone());
+        assert!(NumberFieldElement::zero().inverse().is_err());
+        assert_eq!(a.norm(), 3);
+        assert_eq!(a.trace(), 3);
+        assert_eq!(theta.trace(), 0);
+        assert_eq!(one.trace(), 1);
+        assert_eq!(k.element(&poly(vec![1])).trace(), 3);
+        assert_eq!(a.minimal_polynomial(), poly(vec![-3, 3, -3, 1]));
+        assert_eq!(
+            (theta.clone() * theta.clone()).minimal_polynomial(),
+            poly(vec![-4, 0, 0, 1])
+        );
+        assert_eq!(k.polynomial_discriminant(), -108);
+        assert!(a.is_integral());
+        assert!(!(theta.clone() / k.element(&poly(vec![2]))).is_integral());
+
+        // in Q(2^(1/4)), theta^2 = sqrt(2) has characteristic polynomial (x^2 - 2)^2
+        let l = NumberField::new(&poly(vec![-2, 0, 0, 0, 1])).unwrap();
+        let root2 = l.generator() * l.generator();
+        assert_eq!(
+            root2.characteristic_polynomial(),
+            poly(vec![4, 0, -4, 0, 1])
+        );
+        assert_eq!(root2.minimal_polynomial(), poly(vec![-2, 0, 1]));
+        assert_eq!(root2.norm(), 4);
+    }
+
+    fn check_basis(f: Vec<i64>, disc: i64) {
+        let k = NumberField::new(&poly(f)).unwrap();
+        let basis = k.integral_basis();
+        assert_eq!(basis.len(), k.degree());
+        assert!(basis[0].is_one());
+        assert!(basis.iter().all(NumberFieldElement::is_integral));
+        assert_eq!(k.discriminant(), disc);
+    }
+
+    #[test]
+    fn test_signature() {
+        let k = |f: Vec<i64>| NumberField::new(&poly(f)).unwrap();
+        assert_eq!(k(vec![1, 0, 1]).signature(), (0, 1));
+        assert_eq!(k(vec![-5, 0, 1]).signature(), (2, 0));
+        assert_eq!(k(vec![-2, 0, 0, 1]).signature(), (1, 1));
+        assert_eq!(k(vec![-8, -2, -1, 1]).signature(), (1, 1));
+        // x^3 - 3x + 1 is totally real
+        assert_eq!(k(vec![1, -3, 0, 1]).signature(), (3, 0));
+        assert_eq!(k(vec![2, 0, 0, 0, 1]).signature(), (0, 2));
+    }
+
+    #[test]
+    fn test_integral_basis() {
+        check_basis(vec![-5, 0, 1], 5);
+        check_basis(vec![3, 0, 1], -3);
+        check_basis(vec![1, 0, 1], -4);
+        check_basis(vec![-2, 0, 0, 1], -108);
+        // cbrt(19): 19 = 1 mod 9, so the index of Z[theta] is 3
+        check_basis(vec![-19, 0, 0, 1], -1083);
+        // Dedekind's field, where O_K isn't Z[alpha] for any alpha
+        check_basis(vec![-8, -2, -1, 1], -503);
+        check_basis(vec![1, 1, 1, 1, 1], 125);
+        check_basis(vec![1, 0, 0, 0, 1], 256);
+        // Q(sqrt(-3)) and Q(i) as a biquadratic field Q(zeta_12)
+        check_basis(vec![1, 0, -1, 0, 1], 144);
+
+        // x^2 - 5/4 has root sqrt(5)/2
+        let k = NumberField::new(&Polynomial::from(vec![
+            Rational::from((-5, 4)),
+            Rational::new(),
+            Rational::from(1),
+        ]))
+        .unwrap();
+        assert_eq!(k.discriminant(), 5);
+        let basis = k.integral_basis();
+        // (1 + sqrt(5))/2 = 1/2 + theta
+        assert_eq!(
+            basis[1].value(),
+            &Polynomial::from(vec![Rational::from((1, 2)), Rational::from(1)])
+        );
+    }
+}
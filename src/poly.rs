@@ -1,6 +1,11 @@
 //! Polynomials
+use crate::algebraic::{ComplexAlgebraic, RealAlgebraic};
 use crate::helpers::mul_z_module; //TODO: move this functionality to ModType implementation
-use crate::module::{ModType, RingType, pow};
+use crate::matrix::Matrix;
+use crate::module::{ModType, RingType};
+use crate::newton::{NewtonPolygon, Valuation};
+use crate::structure::{Field, IntegralDomain};
+use crate::{Integer, Rational};
 use std::fmt::Debug;
 use std::iter::once;
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
@@ -11,13 +16,12 @@ use num_traits::identities::{One, Zero};
 /// # Example:
 /// ```
 /// use bored_algebra::poly::Polynomial;
-/// use std::rc::Rc;
 ///
 /// let vec = vec![0, 1, 2];
 /// // x + 2x^2
 /// let p: Polynomial<i64> = Polynomial::from(vec.clone());
 /// assert_eq!(p.deg(), 2_u64);
-/// assert_eq!(p.coeffs(), Rc::new(vec));
+/// assert_eq!(p.coeffs(), vec);
 /// ```
 #[derive(Debug, Clone)] //TODO: just implement these by hand
 pub struct Polynomial<T: Debug + Clone> {
@@ -33,19 +37,18 @@ impl<R: RingType> Polynomial<R> {
         self.coeffs.clone()
     }
 
+    /// The coefficient of $x^{\deg}$. This is zero only for the zero polynomial.
+    pub fn lead_coeff(&self) -> R {
+        self.coeffs[self.deg as usize].clone()
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
 
     /// Takes two polynomials. If the first is higher or equal degree, return true. Otherwise, return false.
     pub fn compare_deg(&self, other: &Self) -> bool {
-        let self_deg = self.deg();
-        let other_deg = other.deg();
-
-        match self_deg >= other_deg {
-            true => true,
-            false => false,
-        }
+        self.deg() >= other.deg()
     }
 
     /// Returns the first k coefficients of a polynomial.
@@ -53,20 +56,21 @@ impl<R: RingType> Polynomial<R> {
         self.coeffs.clone().into_iter().take(k).collect()
     }
 
-    /// Evaluates `self` at `a`. This is how we consider an abstract polynomial to be a function. 
+    /// Evaluates `self` at `a`. This is how we consider an abstract polynomial to be a function.
+    /// Uses Horner's rule,
+    /// $$
+    /// a\_0 + a\_1 x + \cdots + a\_n x^n = a\_0 + x(a\_1 + x(a\_2 + \cdots + x a\_n)).
+    /// $$
     pub fn eval(&self, a: R) -> R {
-        if a == R::zero() {
-            return (&self).coeffs().into_iter().nth(0).unwrap();
-            // safe to unwrap since poly's cannot have empty coeffs if made by constructor
-        } else {
-            return (&self).coeffs().into_iter().enumerate().skip(1)
-            .fold(a, |acc, (i, coeff)| acc + pow(coeff, i));
-        }
+        self.coeffs_take(self.deg() as usize + 1)
+            .into_iter()
+            .rev()
+            .fold(R::zero(), |acc, coeff| acc * a.clone() + coeff)
     }
 
     /// add, assuming rhs has lower or equal degree to self.
     fn add_pad_second(self, rhs: Self) -> Self {
-        let pad_len = &self.deg() - &rhs.deg();
+        let pad_len = self.deg() - rhs.deg();
         let zero = R::zero();
         let padding = once(&zero).cycle().take(pad_len.try_into().unwrap());
         if pad_len == 0 {
@@ -88,14 +92,6 @@ impl<R: RingType> Polynomial<R> {
         }
     }
 
-    /// Returns the roots of the polynomial. This is a stub function that represents several implementation ideas.
-    /// 1. complex solutions by radicals for polynomials with real or complex (ie. arbitrary precision floats) numbers, for polynomials with deg < 5. For > 5, the solutions will have to be more brute-force or advanced. For the reason for this, see any intro textbook to Galois theory. 
-    /// 2. solutions mod p, in $F_{p^n}$,  
-    /// 3. A funcion to check if a given ring element is a root of a polynomial. May use faster methods than 1.
-    pub fn roots(self) -> Vec<R> {
-        todo!();
-    }
-
     /// The derivative of a polynomial, given by the formula
     /// $$
     /// \frac{d}{dx} \sum\_{i=0}^{n}a\_i x^i = \sum_{i=1}^{n} i a\_i x^{i-1}
@@ -134,7 +130,7 @@ impl<T: RingType> Default for Polynomial<T> {
 
 impl<T: RingType> ModType<Polynomial<T>> for Polynomial<T> {
     fn mod_mul(lhs: Self, rhs: Self) -> Self {
-        todo!();
+        lhs * rhs
     }
 }
 
@@ -150,7 +146,7 @@ where
             // otherwise, the degree is the maximum of the indices of nonzero coefficients
             let degree = match vec.iter().rposition(|coeff| *coeff != T::zero()) {
                 Some(index) => index as u64,
-                None => 0 as u64,
+                None => 0,
             };
 
             Self {
@@ -170,7 +166,7 @@ impl<T: RingType> PartialEq for Polynomial<T> {
 
         if lhs_deg == rhs_deg {
             //iterate over (elem, index) of each, up to lhs_deg + 1,check all nonzero ones are equal
-            return self
+            self
                 .coeffs()
                 .iter()
                 .enumerate()
@@ -179,15 +175,11 @@ impl<T: RingType> PartialEq for Polynomial<T> {
                     .coeffs()
                     .iter()
                     .enumerate()
-                    .take((lhs_deg + 1).try_into().unwrap()));
+                    .take((lhs_deg + 1).try_into().unwrap()))
         } else {
-            return false;
+            false
         }
     }
-
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
-    }
 }
 
 impl<T: RingType> Eq for Polynomial<T> {}
@@ -199,11 +191,7 @@ impl<T: RingType> One for Polynomial<T> {
     }
 
     fn is_one(&self) -> bool {
-        if *self == Self::one() {
-            return true;
-        } else {
-            return false;
-        }
+        *self == Self::one()
     }
 }
 
@@ -214,11 +202,7 @@ impl<T: RingType> Zero for Polynomial<T> {
     }
 
     fn is_zero(&self) -> bool {
-        if *self == Self::zero() {
-            return true;
-        } else {
-            return false;
-        }
+        *self == Self::zero()
     }
 }
 
@@ -271,67 +255,197 @@ impl<T: RingType> Mul for Polynomial<T> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self {
-        let self_deg: usize = (&self).deg().try_into().unwrap();
-        let rhs_deg: usize = (&rhs).deg().try_into().unwrap();
-
-        match ((&self).is_zero(), (&rhs).is_zero()) {
-            (true, _) => Self::zero(),
-            (_, true) => Self::zero(),
-            _ => {
-                match ((&self).is_one(), (&rhs).is_one()) {
-                    (true, _) => rhs,
-                    (_, true) => self,
-                    _ => {
-                        // pad both with zeros to index n + m
-                        let zero = T::zero();
-                        let self_padding = once(&zero).cycle().take(rhs_deg);
-                        let rhs_padding = once(&zero).cycle().take(self_deg);
-
-                        let prod_max_index: usize = (self_deg + rhs_deg).try_into().unwrap();
-
-                        // iterate over coefficients, using formula for kth coefficient
-                        let prod = (0..=prod_max_index)
-                            .map(|k| {
-                                self.clone()
-                                    .coeffs()
-                                    .iter()
-                                    .chain(self_padding.clone())
-                                    .take(k + 1)
-                                    .zip(
-                                        rhs.clone()
-                                            .coeffs_take(k + 1)
-                                            .iter()
-                                            .rev()
-                                            .chain(rhs_padding.clone()),
-                                    )
-                                    .map(|(self_i, rhs_k_minus_i)| {
-                                        (*self_i).clone() * (*rhs_k_minus_i).clone()
-                                    })
-                                    .fold(zero.clone(), |acc, elem| acc + elem)
-                            })
-                            .collect::<Vec<T>>();
-
-                        Self::from(prod)
-                    }
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        let self_coeffs = self.coeffs_take(self.deg() as usize + 1);
+        let rhs_coeffs = rhs.coeffs_take(rhs.deg() as usize + 1);
+
+        // kth coefficient is the sum of a_i b_{k-i}
+        let prod = (0..self_coeffs.len() + rhs_coeffs.len() - 1)
+            .map(|k| {
+                self_coeffs
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i <= k && k - *i < rhs_coeffs.len())
+                    .fold(T::zero(), |acc, (i, a_i)| {
+                        acc + a_i.clone() * rhs_coeffs[k - i].clone()
+                    })
+            })
+            .collect::<Vec<T>>();
+
+        Self::from(prod)
+    }
+}
+
+impl<R: IntegralDomain> Polynomial<R> {
+    /// The resultant $\mathrm{Res}(f, g) = a\_m^n \prod\_{f(\alpha) = 0} g(\alpha)$ of $f = a\_m x^m + \cdots$ and
+    /// $g$ of degree $n$, as the determinant of the Sylvester matrix. It's zero exactly when $f$ and $g$ have a
+    /// common root (or one of them is zero), and it's a polynomial in the coefficients, so it makes sense over
+    /// any integral domain. With polynomial coefficients this eliminates a variable.
+    pub fn resultant(&self, other: &Self) -> R {
+        if self.is_zero() || other.is_zero() {
+            return R::zero();
+        }
+        let (m, n) = (self.deg() as usize, other.deg() as usize);
+        let shifted = |f: &Self, d: usize, i: usize| {
+            let mut row = vec![R::zero(); m + n];
+            for (j, c) in f.coeffs_take(d + 1).into_iter().rev().enumerate() {
+                row[i + j] = c;
+            }
+            row
+        };
+        let rows = (0..n)
+            .map(|i| shifted(self, m, i))
+            .chain((0..m).map(|i| shifted(other, n, i)))
+            .collect::<Vec<Vec<R>>>();
+        Matrix::from(rows).determinant_fraction_free()
+    }
+}
+
+impl Polynomial<Integer> {
+    /// The distinct real roots, in increasing order. Panics for the zero polynomial.
+    pub fn real_roots(&self) -> Vec<RealAlgebraic> {
+        RealAlgebraic::roots_of(self)
+    }
+
+    /// The distinct complex roots, sorted by real part and then imaginary part. Panics for the zero polynomial.
+    pub fn roots(&self) -> Vec<ComplexAlgebraic> {
+        ComplexAlgebraic::roots_of(self)
+    }
+}
+
+impl Polynomial<Rational> {
+    /// The same polynomial times the lcm of the denominators.
+    fn clear_denominators(&self) -> Polynomial<Integer> {
+        let coeffs = self.coeffs_take(self.deg() as usize + 1);
+        let d = coeffs
+            .iter()
+            .fold(Integer::from(1), |acc, c| acc.lcm(c.denom()));
+        let f = coeffs
+            .into_iter()
+            .map(|c| (c * &d).numer().clone())
+            .collect::<Vec<Integer>>();
+        Polynomial::from(f)
+    }
+
+    /// The distinct real roots, in increasing order. Panics for the zero polynomial.
+    pub fn real_roots(&self) -> Vec<RealAlgebraic> {
+        RealAlgebraic::roots_of(&self.clear_denominators())
+    }
+
+    /// The distinct complex roots, sorted by real part and then imaginary part. Panics for the zero polynomial.
+    pub fn roots(&self) -> Vec<ComplexAlgebraic> {
+        ComplexAlgebraic::roots_of(&self.clear_denominators())
+    }
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Long division: returns $(q, r)$ with `self` $= q \cdot$ `rhs` $+ r$ and $\deg r < \deg$ `rhs`
+    /// (or $r = 0$). Panics if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        assert!(!rhs.is_zero(), "divide by zero");
+        let n = rhs.deg() as usize;
+        let lead = rhs.lead_coeff();
+        let divisor = rhs.coeffs_take(n + 1);
+        let mut r = self.coeffs_take(self.deg() as usize + 1);
+        if r.len() <= n {
+            return (Self::zero(), self.clone());
+        }
+        let mut q = vec![F::zero(); r.len() - n];
+        for k in (n..r.len()).rev() {
+            let c = r[k].clone() / lead.clone();
+            if !c.is_zero() {
+                for (j, d) in divisor.iter().enumerate() {
+                    r[k - n + j] = r[k - n + j].clone() - c.clone() * d.clone();
                 }
             }
+            q[k - n] = c;
+        }
+        r.truncate(n);
+        (Self::from(q), Self::from(r))
+    }
+
+    /// `self` divided by its leading coefficient. Zero stays zero.
+    pub fn monic(&self) -> Self {
+        if self.is_zero() {
+            return self.clone();
+        }
+        let inv = F::one() / self.lead_coeff();
+        Self::from(
+            self.coeffs_take(self.deg() as usize + 1)
+                .into_iter()
+                .map(|c| c * inv.clone())
+                .collect::<Vec<F>>(),
+        )
+    }
+
+    /// The monic greatest common divisor, by the Euclidean algorithm. $\gcd(0, 0) = 0$.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// The extended Euclidean algorithm: returns $(g, s, t)$ with $g = s \cdot$ `self` $+ t \cdot$ `other` the
+    /// monic gcd.
+    pub fn ext_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            let s = s0 - q.clone() * s1.clone();
+            let t = t0 - q * t1.clone();
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+            (t0, t1) = (t1, t);
+        }
+        if r0.is_zero() {
+            return (r0, s0, t0);
         }
+        let inv = Self::from(vec![F::one() / r0.lead_coeff()]);
+        (r0 * inv.clone(), s0 * inv.clone(), t0 * inv)
     }
 }
 
-impl<T: RingType> Div for Polynomial<T> {
+impl<R: RingType + Valuation> Polynomial<R> {
+    /// The Newton polygon at the prime $p$, the lower convex hull of the points $(i, v\_p(a\_i))$. See `newton`.
+    /// Panics for the zero polynomial.
+    pub fn newton_polygon(&self, p: &Integer) -> NewtonPolygon {
+        let points: Vec<(u64, i64)> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some((i as u64, c.valuation(p)?)))
+            .collect();
+        assert!(
+            !points.is_empty(),
+            "the zero polynomial has no Newton polygon"
+        );
+        NewtonPolygon::new(&points)
+    }
+}
+
+/// The quotient from `div_rem`.
+impl<F: Field> Div for Polynomial<F> {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self {
-        todo!();
+        self.div_rem(&rhs).0
     }
 }
 
-impl<T: RingType> Rem for Polynomial<T> {
+/// The remainder from `div_rem`.
+impl<F: Field> Rem for Polynomial<F> {
     type Output = Self;
 
     fn rem(self, rhs: Self) -> Self {
-        todo!();
+        self.div_rem(&rhs).1
     }
 }
 
@@ -352,6 +466,7 @@ pub fn int_pow<R: RingType>(r: R, n: usize) -> R {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_helpers::zpoly;
 
     #[test]
     fn test_from() {
@@ -374,7 +489,7 @@ mod test {
         let a = Polynomial::<i64>::from(vec![0, 1, 1]);
         //1 + x over Z, with a bunch of extra zeros
         let b = Polynomial::<i64>::from(vec![1, 1, 0, 0, 0, 0]);
-        assert_eq!(Polynomial::compare_deg(&a, &b), true);
+        assert!(Polynomial::compare_deg(&a, &b));
     }
 
     #[test]
@@ -445,14 +560,32 @@ mod test {
             Polynomial::<i64>::from(vec![0, -1]), //x's
         ]);
 
+        assert_eq!(r.clone() * one, r.clone());
         assert_eq!(r + l, zero);
 
-        // P(x,y) = xy + 3x^2 - 4y^2x - 8
-        let p = Polynomial::<Polynomial<i64>>::from(vec![Polynomial::<i64>::from(vec![-8])]);
+        // the outer variable is y, the inner one is x.
+        // P(x,y) = xy + 3x^2 - 4y^2x - 8 = (-8 + 3x^2) + (x)y + (-4x)y^2
+        let p = Polynomial::<Polynomial<i64>>::from(vec![
+            Polynomial::<i64>::from(vec![-8, 0, 3]),
+            Polynomial::<i64>::from(vec![0, 1]),
+            Polynomial::<i64>::from(vec![0, -4]),
+        ]);
 
-        // Q(x,y) = x^3 - 4y + 1
+        // Q(x,y) = x^3 - 4y + 1 = (1 + x^3) + (-4)y
+        let q = Polynomial::<Polynomial<i64>>::from(vec![
+            Polynomial::<i64>::from(vec![1, 0, 0, 1]),
+            Polynomial::<i64>::from(vec![-4]),
+        ]);
 
-        //indexing confusion ....................................
+        // P + Q = (-7 + 3x^2 + x^3) + (-4 + x)y + (-4x)y^2
+        assert_eq!(
+            p + q,
+            Polynomial::<Polynomial<i64>>::from(vec![
+                Polynomial::<i64>::from(vec![-7, 0, 3, 1]),
+                Polynomial::<i64>::from(vec![-4, 1]),
+                Polynomial::<i64>::from(vec![0, -4]),
+            ])
+        );
     }
 
     #[test]
@@ -498,12 +631,39 @@ mod test {
 
         //assert_eq!(x.clone() * x.clone(), x_squared.clone());
 
-        // 1 + 3x + 2x^3
+        // 1 + 3x + 2x^2
         let a = Polynomial::<i64>::from(vec![1, 3, 2, 0, 0]);
         // x + 3
         let b = Polynomial::<i64>::from(vec![3, 1]);
 
-        assert_eq!(Polynomial::<i64>::from(vec![12, 1, 3, 6, 2]), a * b);
+        // 3 + 10x + 9x^2 + 2x^3
+        assert_eq!(Polynomial::<i64>::from(vec![3, 10, 9, 2]), a * b.clone());
+
+        // 1 + 3x + 2x^3 times x + 3 is 3 + 10x + 3x^2 + 6x^3 + 2x^4
+        let c = Polynomial::<i64>::from(vec![1, 3, 0, 2, 0]);
+        assert_eq!(Polynomial::<i64>::from(vec![3, 10, 3, 6, 2]), c * b);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        use crate::Rational;
+        let p = |v: Vec<i64>| {
+            Polynomial::from(v.into_iter().map(Rational::from).collect::<Vec<Rational>>())
+        };
+        // x^3 - 1 = (x - 1)(x^2 + x + 1), and x^3 + 2 = (x^2 + x + 1)(x - 1) + 3
+        let a = p(vec![-1, 0, 0, 1]);
+        let b = p(vec![1, 1, 1]);
+        assert_eq!(a.clone() / b.clone(), p(vec![-1, 1]));
+        assert!((a.clone() % b.clone()).is_zero());
+        let (q, r) = p(vec![2, 0, 0, 1]).div_rem(&b);
+        assert_eq!((q, r), (p(vec![-1, 1]), p(vec![3])));
+        assert_eq!(p(vec![1, 2]).div_rem(&b), (p(vec![]), p(vec![1, 2])));
+        // gcd(x^3 - 1, x^2 - 1) = x - 1
+        assert_eq!(a.gcd(&p(vec![-2, 0, 2])), p(vec![-1, 1]));
+        let (g, s, t) = a.ext_gcd(&b);
+        assert_eq!(g.clone(), b.clone());
+        assert_eq!(s * a.clone() + t * b.clone(), g);
+        assert_eq!(p(vec![3, 6]).monic(), Polynomial::from(vec![Rational::from((1, 2)), Rational::from(1)]));
     }
 
     #[test]
@@ -512,7 +672,7 @@ mod test {
         let a = Polynomial::<i64>::from(vec![1, 3, 0, 2]);
         let t = 4;
 
-        assert_eq!((&a).eval(4), 141);
+        assert_eq!(a.eval(t), 141);
     }
 
     #[test]
@@ -521,14 +681,14 @@ mod test {
         let a = Polynomial::<i64>::from(vec![1, -1, 1]);
         let t = 1;
         // should have a(t) = 1 - 1 + 1 = 1
-        assert_eq!((&a).eval(1), 1 as i64);
+        assert_eq!(a.eval(t), 1_i64);
     }
 
     #[test]
     fn test_eval_3() {
         // 1 + x^3
         let a = Polynomial::<i64>::from(vec![1, 0, 0, 1]);
-        assert_eq!((&a).eval(2), 1 + int_pow(2, 3));
+        assert_eq!(a.eval(2), 1 + int_pow(2, 3));
     }
 
     #[test]
@@ -538,8 +698,23 @@ mod test {
         // 1 - x + 2x^2 - 69x^3 + 420x^4
         let b = Polynomial::<i64>::from(vec![1, -1, 2, -69, 420]);
         // a(0) = b(0) = 1
-        assert_eq!((&a).eval(0), (&b).eval(0));
-        assert_eq!((&a).eval(0), 1);
+        assert_eq!(a.eval(0), b.eval(0));
+        assert_eq!(a.eval(0), 1);
+    }
+
+    #[test]
+    fn test_resultant() {
+        // Res(x - a, x - b) = a - b
+        assert_eq!(zpoly(vec![-2, 1]).resultant(&zpoly(vec![-5, 1])), -3);
+        // 2^2 ((-1/2)^2 + 1)
+        assert_eq!(zpoly(vec![1, 2]).resultant(&zpoly(vec![1, 0, 1])), 5);
+        assert_eq!(zpoly(vec![-2, 0, 1]).resultant(&zpoly(vec![-3, 0, 1])), 1);
+        assert_eq!(zpoly(vec![-1, 0, 1]).resultant(&zpoly(vec![-1, 1])), 0);
+        assert_eq!(zpoly(vec![3]).resultant(&zpoly(vec![1, 1])), 3);
+        // eliminating y from y^2 - 2 and (x - y)^2 - 3 gives the minimal polynomial of sqrt(2) + sqrt(3)
+        let f = Polynomial::from(vec![zpoly(vec![-2]), zpoly(vec![0]), zpoly(vec![1])]);
+        let g = Polynomial::from(vec![zpoly(vec![-3, 0, 1]), zpoly(vec![0, -2]), zpoly(vec![1])]);
+        assert_eq!(f.resultant(&g), zpoly(vec![1, 0, -10, 0, 1]));
     }
 
     #[test]
@@ -0,0 +1,604 @@
+//! Primary decomposition
+//!
+//! Every ideal $I$ of $F[x\_0, \ldots, x\_{n-1}]$ is an intersection $Q\_1 \cap \cdots \cap Q\_r$ of primary ideals.
+//! If the decomposition is irredundant (no $Q\_i$ can be left out and the primes $P\_i = \sqrt{Q\_i}$ are distinct),
+//! the $P\_i$ are determined by $I$: they are its associated primes. The minimal ones are the minimal primes of
+//! $I$, whose intersection is $\sqrt{I}$. The embedded components (the $Q\_i$ with non-minimal primes) are not
+//! unique.
+//!
+//! This is the algorithm of Gianni, Trager and Zacharias:
+//! 1. Pick a maximal independent set $U$ of variables of largest size, and let $Y$ be the other variables.
+//!    Then $I F(U)[Y]$ is zero-dimensional.
+//! 2. Decompose that: after a linear change of coordinates putting the ideal in general position, the
+//!    factors $p\_j^{e\_j}$ of the eliminant in the last variable of $Y$ give the primary components $I + (p\_j^{e\_j})$.
+//! 3. Contract back to $F[X]$. The contraction of $I$ itself is $I : h^\infty$, where $h$ is the product of the
+//!    leading coefficients (in $F[U]$) of a Grobner basis, and
+//!    $$
+//!    I = (I : h^\infty) \cap (I + (h^s))
+//!    $$
+//!    for large enough $s$, so recurse on $I + (h^s)$, which has smaller dimension or degree.
+//!
+//! Computations over $F(U)$ are done in $F[X]$ with lexicographic Grobner bases where $Y > U$.
+//!
+//! The coefficient field needs to be a `FactorField`, so $\mathbb{Q}$ or $\mathbb{F}\_p$. Over a small finite field there
+//! might be no linear form separating the points of a zero-dimensional ideal, and then the forms get coefficients
+//! in $F(t)$ for a new variable $t$, which is infinite. The radicals of the components over $F(U)$ come from
+//! Seidenberg's lemma when the eliminants are separable, which can fail over $\mathbb{F}\_p(U)$ since it isn't
+//! perfect, and then from $p$th roots: $f^p = f(x\_0^p, \ldots, x\_{n-1}^p)$ over $\mathbb{F}\_p$. A component whose
+//! residue field over $\mathbb{F}\_p(U)$ has no primitive element, like that of $(x^p - u, y^p - v)$, can't be put in
+//! general position, and then the decomposition fails with `NotSeparable`.
+//!
+//! TODO: a faster `radical` that doesn't go through the whole decomposition.
+use crate::error::ArithmeticError;
+use crate::factor::FactorField;
+use crate::helpers::mul_z_module;
+use crate::ideal::Ideal;
+use crate::mfactor::factor_multivariate;
+use crate::module::pow;
+use crate::mpoly::{GRevLex, Lex, MPolynomial, Monomial, MonomialOrder};
+
+use num_traits::identities::{One, Zero};
+
+/// How many linear changes of coordinates to try before giving up on general position.
+const ATTEMPTS: u64 = 20;
+
+/// A primary ideal together with its associated prime.
+#[derive(Clone, Debug)]
+pub struct PrimaryComponent<F, O = GRevLex> {
+    primary: Ideal<F, O>,
+    prime: Ideal<F, O>,
+}
+
+impl<F, O> PrimaryComponent<F, O> {
+    pub fn primary(&self) -> &Ideal<F, O> {
+        &self.primary
+    }
+
+    /// The radical of the primary ideal.
+    pub fn prime(&self) -> &Ideal<F, O> {
+        &self.prime
+    }
+}
+
+impl<F: FactorField, O: MonomialOrder> Ideal<F, O> {
+    /// An irredundant primary decomposition, with components for minimal primes first. The unit ideal has
+    /// no components. Fails with `NotSeparable` over $\mathbb{F}\_p$ if some component has a residue field over
+    /// $\mathbb{F}\_p(U)$ without a primitive element, like $(x^p - u, y^p - v)$, and so do the functions below that
+    /// use it.
+    /// # Example:
+    /// ```
+    /// use bored_algebra::ideal::Ideal;
+    /// use bored_algebra::mpoly::MPolynomial;
+    /// use bored_algebra::Rational;
+    ///
+    /// type P = MPolynomial<Rational>;
+    /// let (x, y) = (P::var(0), P::var(1));
+    /// // (x^2, xy) = (x) ∩ (x^2, y), with an embedded component at the origin
+    /// let i = Ideal::new(2, vec![x.clone() * x.clone(), x.clone() * y.clone()]);
+    /// let components = i.primary_decomposition().unwrap();
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[0].primary(), &Ideal::new(2, vec![x.clone()]));
+    /// assert_eq!(components[1].prime(), &Ideal::maximal(2));
+    /// assert_eq!(i.radical().unwrap(), Ideal::new(2, vec![x]));
+    /// ```
+    pub fn primary_decomposition(&self) -> Result<Vec<PrimaryComponent<F, O>>, ArithmeticError> {
+        // intersections of primary ideals with the same prime are primary
+        let mut components: Vec<(Ideal<F, O>, Ideal<F, O>)> = vec![];
+        for (q, p) in decompose(self)? {
+            match components.iter_mut().find(|(_, p2)| *p2 == p) {
+                Some((q2, _)) => *q2 = q2.intersect(&q),
+                None => components.push((q, p)),
+            }
+        }
+        // drop components containing the intersection of the others, trying the later ones first
+        let mut k = components.len();
+        while k > 0 {
+            k -= 1;
+            let others = components
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != k)
+                .map(|(_, (q, _))| q.clone())
+                .reduce(|a, b| a.intersect(&b));
+            if others.is_some_and(|others| components[k].0.contains_ideal(&others)) {
+                components.remove(k);
+            }
+        }
+        components.sort_by_key(|(_, p)| std::cmp::Reverse(p.dim()));
+        Ok(components
+            .into_iter()
+            .map(|(primary, prime)| PrimaryComponent { primary, prime })
+            .collect())
+    }
+
+    /// The associated primes, the radicals of the components of a primary decomposition.
+    pub fn associated_primes(&self) -> Result<Vec<Ideal<F, O>>, ArithmeticError> {
+        Ok(self
+            .primary_decomposition()?
+            .into_iter()
+            .map(|c| c.prime)
+            .collect())
+    }
+
+    /// The minimal primes, the associated primes not containing any other one.
+    pub fn minimal_primes(&self) -> Result<Vec<Ideal<F, O>>, ArithmeticError> {
+        let primes = self.associated_primes()?;
+        Ok(primes
+            .iter()
+            .filter(|p| !primes.iter().any(|q| q != *p && p.contains_ideal(q)))
+            .cloned()
+            .collect())
+    }
+
+    /// The radical $\sqrt{I} = \\{f : f^k \in I \text{ for some } k\\}$, the intersection of the minimal primes.
+    pub fn radical(&self) -> Result<Self, ArithmeticError> {
+        Ok(self
+            .minimal_primes()?
+            .into_iter()
+            .reduce(|a, b| a.intersect(&b))
+            .unwrap_or_else(|| Self::new(self.nvars(), vec![MPolynomial::one()])))
+    }
+
+    pub fn is_prime(&self) -> Result<bool, ArithmeticError> {
+        let components = self.primary_decomposition()?;
+        Ok(components.len() == 1 && components[0].primary == components[0].prime)
+    }
+
+    pub fn is_primary(&self) -> Result<bool, ArithmeticError> {
+        Ok(self.primary_decomposition()?.len() == 1)
+    }
+
+    pub fn is_radical(&self) -> Result<bool, ArithmeticError> {
+        Ok(self.radical()? == *self)
+    }
+}
+
+/// A primary decomposition, possibly redundant, as (primary, prime) pairs.
+#[allow(clippy::type_complexity)]
+fn decompose<F: FactorField, O: MonomialOrder>(
+    ideal: &Ideal<F, O>,
+) -> Result<Vec<(Ideal<F, O>, Ideal<F, O>)>, ArithmeticError> {
+    if ideal.is_unit() {
+        return Ok(vec![]);
+    }
+    if ideal.is_zero() {
+        return Ok(vec![(ideal.clone(), ideal.clone())]);
+    }
+    let n = ideal.nvars();
+    let u = ideal
+        .maximal_independent_sets()
+        .into_iter()
+        .max_by_key(|set| set.len())
+        .unwrap();
+    // move the dependent variables Y to the front, so that lex order has Y > U
+    let order: Vec<usize> = (0..n)
+        .filter(|i| !u.contains(i))
+        .chain(u.iter().copied())
+        .collect();
+    let m = n - u.len();
+    let to_new = |f: &MPolynomial<F, O>| {
+        f.reorder::<Lex>()
+            .map_vars(|i| order.iter().position(|&j| j == i).unwrap())
+    };
+    let to_old = |i: &Ideal<F, Lex>| {
+        Ideal::new(
+            n,
+            i.groebner_basis()
+                .iter()
+                .map(|f| f.map_vars(|k| order[k]).reorder())
+                .collect(),
+        )
+    };
+    let moved = Ideal::new(n, ideal.groebner_basis().iter().map(to_new).collect());
+    let mut components: Vec<(Ideal<F, O>, Ideal<F, O>)> = zero_dim_over(&moved, m)?
+        .iter()
+        .map(|(q, p)| (to_old(q), to_old(p)))
+        .collect();
+
+    let h = lead_coeff_product(&moved, m)
+        .map_vars(|k| order[k])
+        .reorder::<O>();
+    if h.is_constant() {
+        return Ok(components);
+    }
+    // the first s with I : h^s = I : h^(s + 1)
+    let mut s = 0;
+    let mut quotient = ideal.clone();
+    loop {
+        let next = quotient.quotient(&h);
+        if next == quotient {
+            break;
+        }
+        quotient = next;
+        s += 1;
+    }
+    components.extend(decompose(&ideal.sum(&Ideal::new(n, vec![pow(h, s)])))?);
+    Ok(components)
+}
+
+/// Decomposes $I F(U)[Y]$, where $Y = \\{x\_0, \ldots, x\_{m-1}\\}$ and $U$ is the rest, for zero-dimensional
+/// $I F(U)[Y]$. The components come back contracted to $F[X]$.
+#[allow(clippy::type_complexity)]
+fn zero_dim_over<F: FactorField>(
+    ideal: &Ideal<F, Lex>,
+    m: usize,
+) -> Result<Vec<(Ideal<F, Lex>, Ideal<F, Lex>)>, ArithmeticError> {
+    let last = m - 1;
+    let mut tried: Vec<Vec<F>> = vec![];
+    for attempt in 0..ATTEMPTS {
+        // x_last -> x_last + sum c_i x_i, with c = 0 on the first try
+        let c: Vec<F> = (0..last)
+            .map(|i| mul_z_module((attempt.pow(i as u32 + 1) % 31) as i64, F::one()))
+            .collect();
+        if tried.contains(&c) {
+            continue;
+        }
+        let forms = c
+            .iter()
+            .map(|ci| MPolynomial::constant(ci.clone()))
+            .collect();
+        if let Some(components) = separate(ideal, m, forms) {
+            return Ok(components);
+        }
+        tried.push(c);
+    }
+    // F can be too small to have a separating form, but F(t) for a new variable t isn't: two points that
+    // x_last + t x_0 + t^2 x_1 + ... doesn't separate would make t algebraic over F(U). The components over
+    // F(U, t) are the ones over F(U) extended, so eliminating t gives them back.
+    let n = ideal.nvars();
+    let extended = Ideal::new(n + 1, ideal.groebner_basis().to_vec());
+    let forms = (0..last).map(|i| pow(MPolynomial::var(n), i + 1)).collect();
+    let drop_t = |i: &Ideal<F, Lex>| Ideal::new(n, i.eliminate(&[n]).groebner_basis().to_vec());
+    match separate(&extended, m, forms) {
+        Some(components) => Ok(components
+            .iter()
+            .map(|(q, p)| (drop_t(q), drop_t(p)))
+            .collect()),
+        // then some residue field has no primitive element, which takes an inseparable extension
+        None => Err(ArithmeticError::NotSeparable),
+    }
+}
+
+/// The components of $I F(U)[Y]$ from the eliminant in $x\_{m-1}$ after $x\_{m-1} \mapsto x\_{m-1} + \sum\_i c\_i x\_i$,
+/// with the $c\_i \in F[U]$, or `None` if that doesn't put the primes in shape position.
+#[allow(clippy::type_complexity)]
+fn separate<F: FactorField>(
+    ideal: &Ideal<F, Lex>,
+    m: usize,
+    c: Vec<MPolynomial<F, Lex>>,
+) -> Option<Vec<(Ideal<F, Lex>, Ideal<F, Lex>)>> {
+    let n = ideal.nvars();
+    let last = m - 1;
+    let change = |sign: F| -> Vec<MPolynomial<F, Lex>> {
+        (0..n)
+            .map(|i| match i == last {
+                true => c
+                    .iter()
+                    .enumerate()
+                    .fold(MPolynomial::var(last), |acc, (j, cj)| {
+                        acc + &MPolynomial::var(j) * &cj.scale(&sign)
+                    }),
+                false => MPolynomial::var(i),
+            })
+            .collect()
+    };
+    let (forward, back) = (change(F::one()), change(-F::one()));
+    let moved = Ideal::new(
+        n,
+        ideal
+            .groebner_basis()
+            .iter()
+            .map(|f| f.compose(&forward))
+            .collect(),
+    );
+
+    let mut components = vec![];
+    for (p, e) in factor_multivariate(&eliminant(&moved, last, m)) {
+        if p.deg_in(last) == 0 {
+            continue;
+        }
+        let prime = radical_over(&moved.sum(&Ideal::new(n, vec![p.clone()])), m);
+        if prime.is_unit() {
+            continue;
+        }
+        if !in_shape_position(&prime, m, p.deg_in(last)) {
+            return None;
+        }
+        let primary = contract(&moved.sum(&Ideal::new(n, vec![pow(p, e)])), m);
+        components.push((primary, prime));
+    }
+    let undo = |i: &Ideal<F, Lex>| {
+        Ideal::new(
+            n,
+            i.groebner_basis()
+                .iter()
+                .map(|f| f.compose(&back))
+                .collect(),
+        )
+    };
+    Some(components.iter().map(|(q, p)| (undo(q), undo(p))).collect())
+}
+
+/// The generator of $I F(U)[Y] \cap F(U)[x\_i]$, as a polynomial in $F[U][x\_i]$.
+fn eliminant<F: FactorField>(ideal: &Ideal<F, Lex>, i: usize, m: usize) -> MPolynomial<F, Lex> {
+    let others: Vec<usize> = (0..m).filter(|&j| j != i).collect();
+    // lex order already eliminates everything before the last variable of Y
+    let elim = match i + 1 == m {
+        true => ideal.clone(),
+        false => ideal.eliminate(&others),
+    };
+    elim.groebner_basis()
+        .iter()
+        .filter(|g| others.iter().all(|&j| g.deg_in(j) == 0) && g.deg_in(i) > 0)
+        .min_by_key(|g| g.deg_in(i))
+        .cloned()
+        .expect("ideal is not zero-dimensional over the independent variables")
+}
+
+/// $\sqrt{I F(U)[Y]}$, contracted to $F[X]$. By Seidenberg's lemma, adding the squarefree parts of the eliminants
+/// in each variable of $Y$ gives the radical as long as they're separable. Over $\mathbb{F}\_p(U)$, which isn't
+/// perfect, they might not be, and then `frobenius_radical` finishes the job.
+fn radical_over<F: FactorField>(ideal: &Ideal<F, Lex>, m: usize) -> Ideal<F, Lex> {
+    let mut separable = true;
+    let gens = (0..m)
+        .map(|i| {
+            factor_multivariate(&eliminant(ideal, i, m))
+                .into_iter()
+                .filter(|(p, _)| p.deg_in(i) > 0)
+                .fold(MPolynomial::one(), |acc, (p, _)| {
+                    separable &= !p.derivative(i).is_zero();
+                    acc * p
+                })
+        })
+        .collect();
+    let radical = contract(&ideal.sum(&Ideal::new(ideal.nvars(), gens)), m);
+    match separable {
+        true => radical,
+        false => frobenius_radical(&radical),
+    }
+}
+
+/// The radical of an ideal $J$ of $\mathbb{F}\_p[X]$, the union of the ascending chain of
+/// $J\_k = \\{f : f^{p^k} \in J\\}$. Since $f^p = f(x\_0^p, \ldots, x\_{n-1}^p)$ over $\mathbb{F}\_p$, $J\_{k+1}$ is the
+/// preimage of $J\_k$ under $x\_i \mapsto x\_i^p$, which is $(J\_k + (y\_i - x\_i^p)) \cap \mathbb{F}\_p[Y]$.
+fn frobenius_radical<F: FactorField>(ideal: &Ideal<F, Lex>) -> Ideal<F, Lex> {
+    let n = ideal.nvars();
+    let p = F::CHARACTERISTIC as usize;
+    let mut radical = ideal.clone();
+    loop {
+        // y_i is x_i and x_i is x_(n+i)
+        let gens = radical
+            .groebner_basis()
+            .iter()
+            .map(|f| f.map_vars(|i| n + i))
+            .chain((0..n).map(|i| MPolynomial::var(i) - pow(MPolynomial::var(n + i), p)))
+            .collect();
+        let preimage = Ideal::new(2 * n, gens).eliminate(&(n..2 * n).collect::<Vec<usize>>());
+        let next = Ideal::new(n, preimage.groebner_basis().to_vec());
+        if next == radical {
+            return radical;
+        }
+        radical = next;
+    }
+}
+
+/// The leading coefficient of `f` as a polynomial in $x\_0, \ldots, x\_{m-1}$ with coefficients in the other
+/// variables.
+fn lead_coeff_in<F: FactorField>(f: &MPolynomial<F, Lex>, m: usize) -> MPolynomial<F, Lex> {
+    let y_part = |a: &Monomial| (0..m).map(|i| a.exp(i)).collect::<Vec<u32>>();
+    let lead = y_part(f.lead_monomial().unwrap());
+    MPolynomial::from_terms(
+        f.terms()
+            .iter()
+            .filter(|(a, _)| y_part(a) == lead)
+            .map(|(a, c)| {
+                let exps = (0..a.nvars())
+                    .map(|i| if i < m { 0 } else { a.exp(i) })
+                    .collect::<Vec<u32>>();
+                (Monomial::from(exps), c.clone())
+            })
+            .collect(),
+    )
+}
+
+/// The product of the distinct leading coefficients in $F[U]$ of the Grobner basis.
+fn lead_coeff_product<F: FactorField>(ideal: &Ideal<F, Lex>, m: usize) -> MPolynomial<F, Lex> {
+    let mut coeffs: Vec<MPolynomial<F, Lex>> = vec![];
+    for g in ideal.groebner_basis() {
+        let c = lead_coeff_in(g, m).monic();
+        if !c.is_constant() && !coeffs.contains(&c) {
+            coeffs.push(c);
+        }
+    }
+    coeffs
+        .into_iter()
+        .fold(MPolynomial::one(), |acc, c| acc * c)
+}
+
+/// The contraction $I F(U)[Y] \cap F[X] = I : h^\infty$.
+fn contract<F: FactorField>(ideal: &Ideal<F, Lex>, m: usize) -> Ideal<F, Lex> {
+    let h = lead_coeff_product(ideal, m);
+    match h.is_constant() {
+        true => ideal.clone(),
+        false => ideal.saturation(&h),
+    }
+}
+
+/// Checks that over $F(U)$ the prime has a Grobner basis with leading monomials $x\_0, \ldots, x\_{m-2}$ and
+/// $x\_{m-1}^d$. Then its points are determined by their last coordinate, so the last variable separates them.
+fn in_shape_position<F: FactorField>(prime: &Ideal<F, Lex>, m: usize, d: u32) -> bool {
+    let mut leads: Vec<Monomial> = prime
+        .groebner_basis()
+        .iter()
+        .map(|g| {
+            let a = g.lead_monomial().unwrap();
+            Monomial::from((0..m).map(|i| a.exp(i)).collect::<Vec<u32>>())
+        })
+        .collect();
+    leads.sort_by(|a, b| a.exps().cmp(b.exps()));
+    leads.dedup();
+    let minimal: Vec<&Monomial> = leads
+        .iter()
+        .filter(|a| !leads.iter().any(|b| b != *a && b.divides(a)))
+        .collect();
+    let mut shape: Vec<Monomial> = (0..m - 1).map(Monomial::var).collect();
+    shape.push(Monomial::from(
+        (0..m)
+            .map(|i| if i == m - 1 { d } else { 0 })
+            .collect::<Vec<u32>>(),
+    ));
+    minimal.len() == shape.len() && shape.iter().all(|a| minimal.contains(&a))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::zmod::Zmod;
+    use crate::Rational;
+
+    type P = MPolynomial<Rational>;
+
+    fn x(i: usize) -> P {
+        P::var(i)
+    }
+
+    fn c(k: i64) -> P {
+        P::constant(Rational::from(k))
+    }
+
+    /// Checks the components intersect to `ideal` and the primes are the radicals of the primaries.
+    fn check<F: FactorField>(ideal: &Ideal<F>) -> Vec<PrimaryComponent<F>> {
+        let components = ideal.primary_decomposition().unwrap();
+        let intersection = components
+            .iter()
+            .map(|c| c.primary().clone())
+            .reduce(|a, b| a.intersect(&b))
+            .unwrap();
+        assert_eq!(&intersection, ideal);
+        for component in components.iter() {
+            assert!(component.primary().contains_ideal(ideal));
+            assert!(component.prime().contains_ideal(component.primary()));
+        }
+        components
+    }
+
+    #[test]
+    fn test_embedded() {
+        // (x^2, xy) = (x) ∩ (x^2, y)
+        let i = Ideal::new(2, vec![x(0) * x(0), x(0) * x(1)]);
+        let components = check(&i);
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            i.associated_primes().unwrap(),
+            vec![Ideal::new(2, vec![x(0)]), Ideal::maximal(2)]
+        );
+        assert_eq!(i.minimal_primes().unwrap(), vec![Ideal::new(2, vec![x(0)])]);
+        assert!(!i.is_primary().unwrap());
+        // (x^2, y^2) is primary to (x, y)
+        let j = Ideal::new(2, vec![x(0) * x(0), x(1) * x(1)]);
+        assert!(j.is_primary().unwrap() && !j.is_prime().unwrap());
+        assert_eq!(j.radical().unwrap(), Ideal::maximal(2));
+    }
+
+    #[test]
+    fn test_positive_dimensional() {
+        // three coordinate axes
+        let i = Ideal::new(3, vec![x(0) * x(1), x(0) * x(2), x(1) * x(2)]);
+        let primes = check(&i).into_iter().map(|c| c.prime).collect::<Vec<_>>();
+        assert_eq!(primes.len(), 3);
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            assert!(primes.contains(&Ideal::new(3, vec![x(a), x(b)])));
+        }
+        assert!(i.is_radical().unwrap());
+        // x^2 - y^2 splits, x^2 - 2y^2 does not
+        let j = Ideal::new(2, vec![x(0) * x(0) - x(1) * x(1)]);
+        let primes = j.minimal_primes().unwrap();
+        assert_eq!(primes.len(), 2);
+        assert!(primes.contains(&Ideal::new(2, vec![x(0) - x(1)])));
+        assert!(primes.contains(&Ideal::new(2, vec![x(0) + x(1)])));
+        assert!(Ideal::new(2, vec![x(0) * x(0) - c(2) * x(1) * x(1)])
+            .is_prime()
+            .unwrap());
+    }
+
+    #[test]
+    fn test_twisted_cubic() {
+        // the twisted cubic is prime, and it's the radical of a non-reduced structure on it
+        let i = Ideal::new(
+            4,
+            vec![
+                x(0) * x(2) - x(1) * x(1),
+                x(1) * x(3) - x(2) * x(2),
+                x(0) * x(3) - x(1) * x(2),
+            ],
+        );
+        assert!(i.is_prime().unwrap());
+        let g = i.gens();
+        let j = Ideal::new(4, vec![&g[0] * &g[0], g[1].clone(), g[2].clone()]);
+        assert!(!j.is_radical().unwrap());
+        assert_eq!(j.radical().unwrap(), i);
+    }
+
+    #[test]
+    fn test_general_position() {
+        // the four points (±√2, ±√2) form two Galois orbits, which y alone can't see
+        let i = Ideal::new(2, vec![x(0) * x(0) - c(2), x(1) * x(1) - c(2)]);
+        let components = check(&i);
+        assert_eq!(components.len(), 2);
+        let primes: Vec<Ideal<Rational>> = components.into_iter().map(|c| c.prime).collect();
+        assert!(primes.contains(&Ideal::new(2, vec![x(0) * x(0) - c(2), x(1) - x(0)])));
+        assert!(primes.contains(&Ideal::new(2, vec![x(0) * x(0) - c(2), x(1) + x(0)])));
+        // a double point and a simple one: (x^2 (x - 1), y)
+        let j = Ideal::new(2, vec![x(0) * x(0) * (x(0) - c(1)), x(1)]);
+        assert_eq!(check(&j).len(), 2);
+        assert!(!j.is_radical().unwrap());
+        assert!(Ideal::<Rational>::new(2, vec![c(1)])
+            .primary_decomposition()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mod_p() {
+        type Q = MPolynomial<Zmod<2>>;
+        let (x, y, u) = (Q::var(0), Q::var(1), Q::var(2));
+        // the four points of F_2^2, which no linear form over F_2 separates
+        let i = Ideal::new(2, vec![&x * &x + x.clone(), &y * &y + y.clone()]);
+        let primes = check(&i).into_iter().map(|c| c.prime).collect::<Vec<_>>();
+        assert_eq!(primes.len(), 4);
+        for (a, b) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            let point = Ideal::new(
+                2,
+                vec![
+                    x.clone() + Q::constant(Zmod::new(a)),
+                    y.clone() + Q::constant(Zmod::new(b)),
+                ],
+            );
+            assert!(primes.contains(&point));
+        }
+        // x^2 + u is inseparable over F_2(u) but prime, and (x^2 + u, y^2 + u) is primary to (x + y, x^2 + u)
+        // since (x + y)^2 = x^2 + y^2
+        assert!(Ideal::new(3, vec![&x * &x + u.clone()]).is_prime().unwrap());
+        let j = Ideal::new(3, vec![&x * &x + u.clone(), &y * &y + u.clone()]);
+        assert_eq!(check(&j).len(), 1);
+        assert!(!j.is_prime().unwrap());
+        assert_eq!(
+            j.radical().unwrap(),
+            Ideal::new(3, vec![x.clone() + y, &x * &x + u])
+        );
+    }
+
+    #[test]
+    fn test_inseparable() {
+        type Q = MPolynomial<Zmod<2>>;
+        let (x, y, u, v) = (Q::var(0), Q::var(1), Q::var(2), Q::var(3));
+        // F_2(u, v)[x, y] / (x^2 + u, y^2 + v) = F_2(sqrt(u), sqrt(v)) has degree 4 but a^2 is in F_2(u, v) for
+        // every a in it, so it has no primitive element
+        let i = Ideal::new(4, vec![&x * &x + u, &y * &y + v]);
+        assert!(matches!(
+            i.primary_decomposition(),
+            Err(ArithmeticError::NotSeparable)
+        ));
+        assert!(matches!(i.is_prime(), Err(ArithmeticError::NotSeparable)));
+    }
+}
@@ -1,3 +1,70 @@
+//! Direct products
+//!
+//! `DirectProduct<A, B>` is $A \times B$ with the operations done componentwise. It's an $R$-module when $A$ and $B$
+//! are, and a ring when they're rings (never an integral domain, since $(1, 0)(0, 1) = 0$).
+use crate::module::{AbGroupType, ModType, RingType};
+
+use core::ops::{Add, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+
+/// An element $(a, b) \in A \times B$.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DirectProduct<A, B>(pub A, pub B);
+
+impl<A: AbGroupType, B: AbGroupType> Add for DirectProduct<A, B> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<A: AbGroupType, B: AbGroupType> Neg for DirectProduct<A, B> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl<A: AbGroupType, B: AbGroupType> Sub for DirectProduct<A, B> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<A: AbGroupType, B: AbGroupType> Zero for DirectProduct<A, B> {
+    fn zero() -> Self {
+        Self(A::zero(), B::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<A: RingType, B: RingType> Mul for DirectProduct<A, B> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0, self.1 * rhs.1)
+    }
+}
+
+impl<A: RingType, B: RingType> One for DirectProduct<A, B> {
+    fn one() -> Self {
+        Self(A::one(), B::one())
+    }
+}
+
+impl<R: RingType, A: ModType<R>, B: ModType<R>> ModType<R> for DirectProduct<A, B> {
+    fn mod_mul(r: R, m: Self) -> Self {
+        Self(A::mod_mul(r.clone(), m.0), B::mod_mul(r, m.1))
+    }
+}
+
 // coproducts (disjoint unions)
 
 // maybe make as Fn(n: u64) -> [R; n]
@@ -0,0 +1,575 @@
+//! Quadratic integers
+//!
+//! For a squarefree $D \neq 0, 1$, the ring of integers of $\mathbb{Q}(\sqrt{D})$ is $\mathbb{Z}[\omega]$ with
+//! $$
+//! \omega = \begin{cases} \frac{1 + \sqrt{D}}{2} & D \equiv 1 \pmod 4, \\\\ \sqrt{D} & \text{otherwise,} \end{cases}
+//! $$
+//! and a `QuadraticInteger<D>` is $a + b\omega$ with $a, b \in \mathbb{Z}$. `GaussianInteger` is $\mathbb{Z}[i]$.
+//! Whether $D$ is squarefree is checked at compile time.
+//!
+//! The norm $N(x) = x \bar{x}$ is multiplicative, so the units are the elements of norm $\pm 1$. For $D < 0$ there
+//! are finitely many, and for $D > 0$ they are $\pm \varepsilon^k$ for the fundamental unit $\varepsilon$, which
+//! comes from the continued fraction of $\omega$.
+//!
+//! $\mathbb{Z}[\omega]$ is a `EuclideanDomain` with $|N|$ as the norm for exactly the $D$ listed in
+//! `is_norm_euclidean`, which is also checked at compile time. Other $D$ are still integral domains, even when
+//! they're PIDs ($D = -19$) or Euclidean for some other function ($D = 69$).
+//!
+//! Normal forms: in $\mathbb{Z}[i]$ and $\mathbb{Z}[\frac{1 + \sqrt{-3}}{2}]$ the normal associates are the ones with
+//! $a > 0$ and $b \geq 0$ (an angle in $[0, \pi/2)$ or $[0, \pi/3)$), and otherwise it's only the sign: $a > 0$, or
+//! $a = 0$ and $b > 0$. So gcds in real quadratic fields are only normalized up to powers of $\varepsilon$.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::quadratic::{GaussianInteger, QuadraticInteger};
+//! use bored_algebra::structure::GcdDomain;
+//! use bored_algebra::Integer;
+//!
+//! let z = |a: i64, b: i64| GaussianInteger::new(Integer::from(a), Integer::from(b));
+//! assert_eq!(z(3, 2) * z(3, -2), z(13, 0));
+//! assert_eq!(z(3, 2).norm(), 13);
+//! assert_eq!(GcdDomain::gcd(&z(13, 0), &z(5, 1)), z(2, 3));
+//!
+//! // 1 + sqrt(2) is the fundamental unit of Z[sqrt(2)]
+//! let eps = QuadraticInteger::<2>::fundamental_unit().unwrap();
+//! assert_eq!(eps, QuadraticInteger::new(Integer::from(1), Integer::from(1)));
+//! ```
+use crate::module::ModType;
+use crate::ntheory::continued_fraction::quadratic_continued_fraction;
+use crate::ntheory::factor::factor_integer;
+use crate::structure::{euclid_gcd, CommRing, EuclideanDomain, GcdDomain, IntegralDomain, Monoid};
+use crate::Integer;
+
+use core::fmt;
+use core::fmt::Display;
+use core::ops::{Add, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+
+/// Whether no square $k^2 > 1$ divides $d$.
+const fn is_squarefree(d: i64) -> bool {
+    let n = d.unsigned_abs();
+    let mut k = 2;
+    while k <= n / k {
+        if n.is_multiple_of(k * k) {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+/// Whether $\mathbb{Z}[\omega]$ is Euclidean for $|N|$. For $D < 0$ this list is complete, and for $D > 0$ it's
+/// complete among squarefree $D$ (this is a theorem of several people, finished by Barnes and Swinnerton-Dyer).
+pub const fn is_norm_euclidean(d: i64) -> bool {
+    const EUCLIDEAN: [i64; 21] = [
+        -11, -7, -3, -2, -1, 2, 3, 5, 6, 7, 11, 13, 17, 19, 21, 29, 33, 37, 41, 57, 73,
+    ];
+    let mut i = 0;
+    while i < EUCLIDEAN.len() {
+        if EUCLIDEAN[i] == d {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// $a + b\omega$ in the ring of integers of $\mathbb{Q}(\sqrt{D})$.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuadraticInteger<const D: i64> {
+    a: Integer,
+    b: Integer,
+}
+
+/// $\mathbb{Z}[i]$.
+pub type GaussianInteger = QuadraticInteger<-1>;
+
+impl<const D: i64> QuadraticInteger<D> {
+    const VALID: () = assert!(
+        D != 0 && D != 1 && is_squarefree(D),
+        "D has to be squarefree and not 0 or 1"
+    );
+    /// Evaluating this fails to compile unless $D$ is in `is_norm_euclidean`.
+    pub(crate) const ASSERT_EUCLIDEAN: () = assert!(
+        is_norm_euclidean(D),
+        "Z[w] is not norm-Euclidean for this D"
+    );
+    /// Whether $\omega = (1 + \sqrt{D})/2$.
+    const HALF: bool = D.rem_euclid(4) == 1;
+
+    /// $a + b\omega$.
+    pub fn new(a: Integer, b: Integer) -> Self {
+        let () = Self::VALID;
+        Self { a, b }
+    }
+
+    pub fn omega() -> Self {
+        Self::new(Integer::new(), Integer::from(1))
+    }
+
+    /// $(a, b)$ for $a + b\omega$.
+    pub fn components(&self) -> (&Integer, &Integer) {
+        (&self.a, &self.b)
+    }
+
+    /// $\omega^2 = c + d\omega$, as $(c, d)$.
+    fn omega_squared() -> (Integer, Integer) {
+        match Self::HALF {
+            true => (Integer::from((D - 1) / 4), Integer::from(1)),
+            false => (Integer::from(D), Integer::new()),
+        }
+    }
+
+    /// The discriminant of $\mathbb{Q}(\sqrt{D})$, $D$ or $4D$.
+    pub fn discriminant() -> Integer {
+        match Self::HALF {
+            true => Integer::from(D),
+            false => Integer::from(D) * 4,
+        }
+    }
+
+    /// The Galois conjugate, $\sqrt{D} \mapsto -\sqrt{D}$. This takes $\omega$ to $1 - \omega$ when
+    /// $D \equiv 1 \pmod 4$.
+    pub fn conjugate(&self) -> Self {
+        match Self::HALF {
+            true => Self::new(Integer::from(&self.a + &self.b), Integer::from(-&self.b)),
+            false => Self::new(self.a.clone(), Integer::from(-&self.b)),
+        }
+    }
+
+    /// $N(x) = x\bar{x}$, which is $a^2 - Db^2$ or $a^2 + ab + \frac{1 - D}{4} b^2$. It's negative for some
+    /// elements of real fields, unlike `EuclideanDomain::norm`, which is $|N(x)|$.
+    pub fn norm(&self) -> Integer {
+        let (c, _) = Self::omega_squared();
+        let ab = match Self::HALF {
+            true => Integer::from(&self.a * &self.b),
+            false => Integer::new(),
+        };
+        Integer::from(self.a.square_ref()) + ab - c * Integer::from(self.b.square_ref())
+    }
+
+    /// $x + \bar{x}$.
+    pub fn trace(&self) -> Integer {
+        match Self::HALF {
+            true => Integer::from(&self.a * 2) + &self.b,
+            false => Integer::from(&self.a * 2),
+        }
+    }
+
+    /// The roots of unity: $\pm 1$, except for $i$ in $\mathbb{Z}[i]$ and the sixth roots of unity in
+    /// $\mathbb{Z}[\frac{1 + \sqrt{-3}}{2}]$. For $D < 0$ these are all of the units.
+    pub fn torsion_units() -> Vec<Self> {
+        let generator = match D {
+            -1 | -3 => Self::omega(),
+            _ => -Self::one(),
+        };
+        let mut units = vec![Self::one()];
+        let mut u = generator.clone();
+        while !u.is_one() {
+            units.push(u.clone());
+            u = u * generator.clone();
+        }
+        units
+    }
+
+    /// The fundamental unit $\varepsilon > 1$ of a real quadratic field, and `None` for $D < 0$.
+    ///
+    /// If $\varepsilon = x + y\omega$ then $\bar{\varepsilon} = \pm 1/\varepsilon$ is tiny, which makes $p/q$
+    /// with $\bar{\varepsilon} = \pm(p - q\omega)$ a very good approximation of $\omega$, so it's a convergent of
+    /// the continued fraction of $\omega$. The convergents grow, so the first one that gives a unit gives
+    /// $\varepsilon$, and that happens within the first period.
+    pub fn fundamental_unit() -> Option<Self> {
+        if D < 0 {
+            return None;
+        }
+        let (p, q) = match Self::HALF {
+            true => (Integer::from(1), Integer::from(2)),
+            false => (Integer::new(), Integer::from(1)),
+        };
+        let cf = quadratic_continued_fraction(&p, &q, &Integer::from(D));
+        cf.convergents(cf.preperiod().len() + cf.period().len())
+            .into_iter()
+            .map(|c| Self::new(c.numer().clone(), Integer::from(-c.denom())))
+            .find(|u| u.norm().abs() == 1)
+            .map(|u| u.conjugate())
+    }
+
+    /// Whether this is the normal form among its associates, see the module docs.
+    fn is_normal(&self) -> bool {
+        match D {
+            -1 | -3 => self.a > 0 && self.b >= 0,
+            _ => self.a > 0 || (self.a == 0 && self.b > 0),
+        }
+    }
+}
+
+/// The part of `QuadraticInteger` that's only for $\mathbb{Z}[i]$.
+impl QuadraticInteger<-1> {
+    /// Factors $z \neq 0$ into a unit times normal Gaussian primes with multiplicities, sorted by the rational
+    /// prime under them. Over $p$ the primes are $1 + i$ for $p = 2$, $p$ itself for $p \equiv 3 \pmod 4$, and
+    /// $\pi = \gcd(p, t + i)$ and $\bar{\pi}$ for $p \equiv 1 \pmod 4$, with $t^2 \equiv -1 \pmod p$. Panics if
+    /// $z = 0$.
+    pub fn factor(&self) -> (Self, Vec<(Self, usize)>) {
+        assert!(!self.is_zero(), "cannot factor zero");
+        let mut z = self.clone();
+        let mut factors = vec![];
+        let mut divide_out = |z: &mut Self, pi: Self| {
+            let mut e = 0;
+            while let Some(w) = z.div_exact(&pi) {
+                *z = w;
+                e += 1;
+            }
+            if e > 0 {
+                factors.push((pi, e));
+            }
+        };
+        for (p, _) in factor_integer(&self.norm()) {
+            let rem = p.mod_u(4);
+            if rem == 2 {
+                divide_out(&mut z, Self::new(Integer::from(1), Integer::from(1)));
+            } else if rem == 3 {
+                divide_out(&mut z, Self::from(p));
+            } else {
+                // t = c^((p - 1)/4) for a nonresidue c is a square root of -1
+                let mut c = Integer::from(2);
+                while c.jacobi(&p) != -1 {
+                    c += 1;
+                }
+                let e = Integer::from(&p - 1_u32) >> 2;
+                let t = c.pow_mod(&e, &p).unwrap();
+                let pi = euclid_gcd(&Self::from(p), &Self::new(t, Integer::from(1)));
+                let pi_bar = pi.conjugate().normalize();
+                divide_out(&mut z, pi);
+                divide_out(&mut z, pi_bar);
+            }
+        }
+        (z, factors)
+    }
+}
+
+impl<const D: i64> From<Integer> for QuadraticInteger<D> {
+    fn from(a: Integer) -> Self {
+        Self::new(a, Integer::new())
+    }
+}
+
+impl<const D: i64> Zero for QuadraticInteger<D> {
+    fn zero() -> Self {
+        Self::new(Integer::new(), Integer::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.a == 0 && self.b == 0
+    }
+}
+
+impl<const D: i64> One for QuadraticInteger<D> {
+    fn one() -> Self {
+        Self::new(Integer::from(1), Integer::new())
+    }
+
+    fn is_one(&self) -> bool {
+        self.a == 1 && self.b == 0
+    }
+}
+
+impl<const D: i64> Add for QuadraticInteger<D> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.a + rhs.a, self.b + rhs.b)
+    }
+}
+
+impl<const D: i64> Neg for QuadraticInteger<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.a, -self.b)
+    }
+}
+
+impl<const D: i64> Sub for QuadraticInteger<D> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.a - rhs.a, self.b - rhs.b)
+    }
+}
+
+/// $(a + b\omega)(c + d\omega) = ac + (ad + bc)\omega + bd\omega^2$, with $\omega^2 = D$ or
+/// $\omega^2 = \omega + \frac{D - 1}{4}$.
+impl<const D: i64> Mul for QuadraticInteger<D> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (c, d) = Self::omega_squared();
+        let bd = Integer::from(&self.b * &rhs.b);
+        let a = Integer::from(&self.a * &rhs.a) + Integer::from(&bd * &c);
+        let b = Integer::from(&self.a * &rhs.b) + Integer::from(&self.b * &rhs.a) + bd * d;
+        Self::new(a, b)
+    }
+}
+
+impl<const D: i64> ModType<QuadraticInteger<D>> for QuadraticInteger<D> {
+    fn mod_mul(lhs: Self, rhs: Self) -> Self {
+        lhs * rhs
+    }
+}
+
+impl<const D: i64> ModType<Integer> for QuadraticInteger<D> {
+    fn mod_mul(r: Integer, m: Self) -> Self {
+        Self::new(Integer::from(&r * &m.a), r * m.b)
+    }
+}
+
+impl<const D: i64> Monoid for QuadraticInteger<D> {}
+impl<const D: i64> CommRing for QuadraticInteger<D> {}
+
+/// $x/y = x\bar{y}/N(y)$, which is integral when $N(y)$ divides both components of $x\bar{y}$.
+impl<const D: i64> IntegralDomain for QuadraticInteger<D> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        let n = other.norm();
+        if n == 0 {
+            return None;
+        }
+        let x = self.clone() * other.conjugate();
+        (x.a.is_divisible(&n) && x.b.is_divisible(&n)).then(|| Self::new(x.a / &n, x.b / n))
+    }
+
+    fn normal_unit(&self) -> Self {
+        if self.is_zero() {
+            return Self::one();
+        }
+        Self::torsion_units()
+            .into_iter()
+            .find(|u| (self.clone() * u.conjugate()).is_normal())
+            .unwrap()
+    }
+
+    fn is_unit(&self) -> bool {
+        self.norm().abs() == 1
+    }
+}
+
+impl<const D: i64> GcdDomain for QuadraticInteger<D> {
+    fn gcd(&self, other: &Self) -> Self {
+        let () = Self::ASSERT_EUCLIDEAN;
+        euclid_gcd(self, other)
+    }
+}
+
+/// Rounds $x/y = x\bar{y}/N(y)$ to a lattice point $q\_0$, and then searches the boxes of quotients
+/// $q\_0 + i + j\omega$ with $|i|, |j| \leq k$ for $k = 1, 2, 4, \ldots$ until one of them has a remainder with
+/// $|N(r)| < |N(y)|$, and takes the smallest remainder in that box. Such a quotient exists since $D$ is
+/// norm-Euclidean, so the search stops. In imaginary fields the norm is a distance and the first box has the
+/// nearest point, but in real fields it's indefinite and a good quotient can be several steps away ($D = 19$).
+impl<const D: i64> EuclideanDomain for QuadraticInteger<D> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let () = Self::ASSERT_EUCLIDEAN;
+        assert!(!other.is_zero(), "divide by zero");
+        let n = other.norm();
+        let bound = n.clone().abs();
+        let x = self.clone() * other.conjugate();
+        let (a, b) = (x.a.div_rem_round(n.clone()).0, x.b.div_rem_round(n).0);
+        let mut k = 1_i64;
+        loop {
+            let mut best: Option<(Self, Self, Integer)> = None;
+            for i in -k..=k {
+                for j in -k..=k {
+                    let q = Self::new(Integer::from(&a + i), Integer::from(&b + j));
+                    let r = self.clone() - q.clone() * other.clone();
+                    let norm = r.norm().abs();
+                    if best.as_ref().is_none_or(|(_, _, s)| norm < *s) {
+                        best = Some((q, r, norm));
+                    }
+                }
+            }
+            match best.unwrap() {
+                (q, r, norm) if norm < bound => return (q, r),
+                _ => k *= 2,
+            }
+        }
+    }
+
+    fn norm(&self) -> Integer {
+        QuadraticInteger::norm(self).abs()
+    }
+}
+
+/// Writes $a + b\omega$, with $\omega$ as `i` for $D = -1$, `ω` for $D \equiv 1 \pmod 4$ and `√D` otherwise.
+impl<const D: i64> Display for QuadraticInteger<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let omega = match D {
+            -1 => "i".to_string(),
+            _ if Self::HALF => "ω".to_string(),
+            _ => format!("√{}", D),
+        };
+        let term = |b: &Integer| match b.to_i8() {
+            Some(1) => omega.clone(),
+            _ => format!("{}{}", b, omega),
+        };
+        match (self.a == 0, self.b == 0) {
+            (_, true) => write!(f, "{}", self.a),
+            (true, false) if self.b == -1 => write!(f, "-{}", omega),
+            (true, false) => write!(f, "{}", term(&self.b)),
+            (false, false) if self.b < 0 => {
+                write!(f, "{} - {}", self.a, term(&Integer::from(-&self.b)))
+            }
+            (false, false) => write!(f, "{} + {}", self.a, term(&self.b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::module::pow;
+    use rug::rand::RandState;
+
+    fn q<const D: i64>(a: i64, b: i64) -> QuadraticInteger<D> {
+        QuadraticInteger::new(Integer::from(a), Integer::from(b))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // in Z[sqrt(-5)], 6 = 2 * 3 = (1 + sqrt(-5))(1 - sqrt(-5))
+        let (x, y) = (q::<-5>(1, 1), q::<-5>(1, -1));
+        assert_eq!(x.clone() * y.clone(), q(6, 0));
+        assert_eq!(x.norm(), 6);
+        assert_eq!(x.trace(), 2);
+        assert_eq!(x.conjugate(), y);
+        assert_eq!(IntegralDomain::div_exact(&q(6, 0), &x), Some(y.clone()));
+        assert_eq!(IntegralDomain::div_exact(&q::<-5>(6, 0), &q(4, 0)), None);
+        assert_eq!(QuadraticInteger::<-5>::discriminant(), -20);
+        // w = (1 + sqrt(5))/2 satisfies w^2 = w + 1, and w(1 - w) = -1
+        let w = QuadraticInteger::<5>::omega();
+        assert_eq!(w.clone() * w.clone(), w.clone() + q(1, 0));
+        assert_eq!(w.conjugate(), q(1, -1));
+        assert_eq!(w.norm(), -1);
+        assert_eq!(QuadraticInteger::<5>::discriminant(), 5);
+        assert_eq!(QuadraticInteger::<-3>::omega().norm(), 1);
+
+        assert_eq!(q::<-1>(3, -2).to_string(), "3 - 2i");
+        assert_eq!(q::<-1>(0, -1).to_string(), "-i");
+        assert_eq!(q::<-5>(1, 2).to_string(), "1 + 2√-5");
+        assert_eq!(q::<5>(0, 1).to_string(), "ω");
+        assert_eq!(q::<2>(-4, 0).to_string(), "-4");
+    }
+
+    #[test]
+    fn test_units() {
+        assert_eq!(GaussianInteger::torsion_units().len(), 4);
+        assert_eq!(QuadraticInteger::<-3>::torsion_units().len(), 6);
+        assert_eq!(QuadraticInteger::<-5>::torsion_units().len(), 2);
+        assert!(GaussianInteger::fundamental_unit().is_none());
+        assert!(q::<-1>(0, 1).is_unit() && !q::<-1>(1, 1).is_unit());
+
+        assert_eq!(QuadraticInteger::<3>::fundamental_unit(), Some(q(2, 1)));
+        assert_eq!(QuadraticInteger::<5>::fundamental_unit(), Some(q(0, 1)));
+        // (3 + sqrt(13))/2 and (39 + 5 sqrt(61))/2
+        assert_eq!(QuadraticInteger::<13>::fundamental_unit(), Some(q(1, 1)));
+        assert_eq!(QuadraticInteger::<61>::fundamental_unit(), Some(q(17, 5)));
+        assert_eq!(
+            QuadraticInteger::<94>::fundamental_unit(),
+            Some(q(2143295, 221064))
+        );
+        let eps = QuadraticInteger::<2>::fundamental_unit().unwrap();
+        assert_eq!(pow(eps.clone(), 5), q(41, 29));
+        assert!(pow(eps, 5).is_unit());
+    }
+
+    #[test]
+    fn test_normal() {
+        // the associates of 2 + 3i are 2 + 3i, -3 + 2i, -2 - 3i, 3 - 2i
+        assert_eq!(q::<-1>(-3, 2).normalize(), q(2, 3));
+        assert_eq!(q::<-1>(0, -5).normalize(), q(5, 0));
+        assert_eq!(q::<-3>(0, 1).normalize(), q(1, 0));
+        assert_eq!(q::<-3>(-2, -1).normalize(), q(2, 1));
+        assert_eq!(q::<2>(0, -1).normalize(), q(0, 1));
+    }
+
+    fn check_euclidean<const D: i64>(rand: &mut RandState) {
+        let bound = Integer::from(2000);
+        let mut random = || Integer::from(bound.random_below_ref(rand)) - 1000_u32;
+        for _ in 0..200 {
+            let x = QuadraticInteger::<D>::new(random(), random());
+            let y = QuadraticInteger::<D>::new(random(), random());
+            if y.is_zero() {
+                continue;
+            }
+            let (q, r) = EuclideanDomain::div_rem(&x, &y);
+            assert_eq!(q * y.clone() + r.clone(), x);
+            assert!(
+                EuclideanDomain::norm(&r) < EuclideanDomain::norm(&y),
+                "D = {}",
+                D
+            );
+        }
+    }
+
+    #[test]
+    fn test_euclidean() {
+        let mut rand = RandState::new();
+        check_euclidean::<-1>(&mut rand);
+        check_euclidean::<-2>(&mut rand);
+        check_euclidean::<-3>(&mut rand);
+        check_euclidean::<-7>(&mut rand);
+        check_euclidean::<-11>(&mut rand);
+        check_euclidean::<2>(&mut rand);
+        check_euclidean::<3>(&mut rand);
+        check_euclidean::<5>(&mut rand);
+        check_euclidean::<6>(&mut rand);
+        check_euclidean::<7>(&mut rand);
+        check_euclidean::<11>(&mut rand);
+        check_euclidean::<13>(&mut rand);
+        check_euclidean::<17>(&mut rand);
+        check_euclidean::<19>(&mut rand);
+        check_euclidean::<21>(&mut rand);
+        check_euclidean::<29>(&mut rand);
+        check_euclidean::<33>(&mut rand);
+        check_euclidean::<37>(&mut rand);
+        check_euclidean::<41>(&mut rand);
+        check_euclidean::<57>(&mut rand);
+        check_euclidean::<73>(&mut rand);
+
+        // the nearest quotients aren't good enough for these
+        let (x, y) = (q::<19>(-12, -12), q::<19>(-8, 0));
+        let (quotient, r) = EuclideanDomain::div_rem(&x, &y);
+        assert_eq!(quotient * y.clone() + r.clone(), x);
+        assert!(EuclideanDomain::norm(&r) < EuclideanDomain::norm(&y));
+        let (x, y) = (q::<57>(-10, -7), q::<57>(-10, 5));
+        let (quotient, r) = EuclideanDomain::div_rem(&x, &y);
+        assert_eq!(quotient * y.clone() + r.clone(), x);
+        assert!(EuclideanDomain::norm(&r) < EuclideanDomain::norm(&y));
+
+        // gcd(11 + 3i, 1 + 8i) = 2 + i
+        assert_eq!(GcdDomain::gcd(&q::<-1>(11, 3), &q(1, 8)), q(2, 1));
+        assert_eq!(GcdDomain::gcd(&q::<2>(7, 0), &q(3, 1)), q(3, 1));
+    }
+
+    #[test]
+    fn test_factor() {
+        // 5 = -i (2 + i)(1 + 2i)
+        let (unit, factors) = q::<-1>(5, 0).factor();
+        assert_eq!(unit, q(0, -1));
+        assert_eq!(factors, vec![(q(2, 1), 1), (q(1, 2), 1)]);
+
+        // 2 = -i (1 + i)^2, and 3 stays prime
+        let (unit, factors) = q::<-1>(-18, 0).factor();
+        assert_eq!(unit, q(0, 1));
+        assert_eq!(factors, vec![(q(1, 1), 2), (q(3, 0), 2)]);
+
+        let z = q::<-1>(1234, -5678);
+        let (unit, factors) = z.factor();
+        assert!(unit.is_unit());
+        let mut product = unit;
+        for (pi, e) in factors {
+            assert!(pi.is_normal());
+            let n = pi.norm();
+            assert!(n.is_probably_prime(30) != rug::integer::IsPrime::No || n.is_perfect_square());
+            product = product * pow(pi, e);
+        }
+        assert_eq!(product, z);
+    }
+}
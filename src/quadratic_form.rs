@@ -0,0 +1,415 @@
+//! Binary quadratic forms
+//!
+//! A form $f = (a, b, c)$ is $ax^2 + bxy + cy^2$, with discriminant $D = b^2 - 4ac$. $\mathrm{SL}\_2(\mathbb{Z})$ acts
+//! on forms by substitution, and the classes of primitive positive definite forms of discriminant $D < 0$ make up
+//! the form class group, which is the class group of the order of discriminant $D$ in $\mathbb{Q}(\sqrt{D})$. So
+//! for fundamental $D$ this is a way to the class group of an imaginary quadratic field without `dedekind`.
+//!
+//! Every positive definite form is equivalent to exactly one reduced form, with $|b| \leq a \leq c$ and $b \geq 0$
+//! if $|b| = a$ or $a = c$. Reduced forms have $a \leq \sqrt{|D|/3}$, so there are finitely many of them, and the
+//! class number $h(D)$ is their count.
+//!
+//! Composition is Shanks' NUCOMP: Dirichlet composition gives a form $(A, B, C)$ with $A \approx |D|$, and instead
+//! of reducing it from there, a partial Euclidean algorithm stopped at about $|D/4|^{1/4}$ picks a short vector of
+//! its lattice directly, which leaves a form that's reduced or a step or two away from it.
+//!
+//! TODO: indefinite forms ($D > 0$) have cycles of reduced forms instead of a single one, and aren't handled.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::quadratic_form::BinaryQuadraticForm;
+//! use bored_algebra::Integer;
+//!
+//! let f = |a: i64, b: i64, c: i64| BinaryQuadraticForm::new(Integer::from(a), Integer::from(b), Integer::from(c));
+//! let d = Integer::from(-23);
+//! assert_eq!(BinaryQuadraticForm::class_number(&d), 3);
+//! // (2, 1, 3) has order 3, and (2, -1, 3) is its inverse
+//! let g = f(2, 1, 3);
+//! assert_eq!(g.clone() * g.clone(), f(2, -1, 3));
+//! assert_eq!(g.clone() * g.inverse(), BinaryQuadraticForm::identity(&d));
+//! assert!(f(6, 5, 2).is_equivalent(&f(2, -1, 3)));
+//! // 13 = (±2)^2 + (±3)^2 = (±3)^2 + (±2)^2, and 7 isn't a sum of two squares
+//! assert_eq!(f(1, 0, 1).representations(&Integer::from(13)).len(), 8);
+//! assert_eq!(f(1, 0, 1).represent(&Integer::from(7)), None);
+//! ```
+use crate::Integer;
+use rug::ops::RemRounding;
+
+use core::fmt::{self, Display};
+use core::ops::Mul;
+
+/// The form $ax^2 + bxy + cy^2$.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BinaryQuadraticForm {
+    a: Integer,
+    b: Integer,
+    c: Integer,
+}
+
+impl BinaryQuadraticForm {
+    pub fn new(a: Integer, b: Integer, c: Integer) -> Self {
+        Self { a, b, c }
+    }
+
+    /// The principal form of discriminant $D$: $x^2 + xy + \frac{1 - D}{4} y^2$ or $x^2 - \frac{D}{4} y^2$. Panics
+    /// if $D$ isn't a negative discriminant.
+    pub fn identity(d: &Integer) -> Self {
+        check_discriminant(d);
+        let b = Integer::from(d.is_odd());
+        let c = (Integer::from(&b * &b) - d) / 4;
+        Self::new(Integer::from(1), b, c)
+    }
+
+    /// $(a, b, c)$.
+    pub fn coefficients(&self) -> (&Integer, &Integer, &Integer) {
+        (&self.a, &self.b, &self.c)
+    }
+
+    /// $D = b^2 - 4ac$.
+    pub fn discriminant(&self) -> Integer {
+        Integer::from(&self.b * &self.b) - Integer::from(&self.a * &self.c) * 4
+    }
+
+    /// $f(x, y)$.
+    pub fn eval(&self, x: &Integer, y: &Integer) -> Integer {
+        Integer::from(&self.a * x) * x
+            + Integer::from(&self.b * x) * y
+            + Integer::from(&self.c * y) * y
+    }
+
+    /// Whether $\gcd(a, b, c) = 1$.
+    pub fn is_primitive(&self) -> bool {
+        Integer::from(self.a.gcd_ref(&self.b)).gcd(&self.c) == 1
+    }
+
+    /// Whether $f(x, y) > 0$ for all $(x, y) \neq (0, 0)$, which is $D < 0$ and $a > 0$.
+    pub fn is_positive_definite(&self) -> bool {
+        self.discriminant() < 0 && self.a > 0
+    }
+
+    pub fn is_reduced(&self) -> bool {
+        let b = self.b.clone().abs();
+        self.is_positive_definite()
+            && b <= self.a
+            && self.a <= self.c
+            && (self.b >= 0 || (b != self.a && self.a != self.c))
+    }
+
+    /// The reduced form equivalent to $f$. Panics if $f$ isn't positive definite.
+    pub fn reduce(&self) -> Self {
+        assert!(
+            self.is_positive_definite(),
+            "only positive definite forms can be reduced"
+        );
+        let d = self.discriminant();
+        let (mut a, mut b, mut c) = (self.a.clone(), self.b.clone(), self.c.clone());
+        loop {
+            // (x, y) -> (x - qy, y) takes b into (-a, a]
+            if b <= -a.clone() || b > a {
+                let two_a = Integer::from(&a * 2);
+                let mut r = b.clone().rem_euc(&two_a);
+                if r > a {
+                    r -= &two_a;
+                }
+                b = r;
+                c = (Integer::from(&b * &b) - &d) / Integer::from(&a * 4);
+            }
+            // (x, y) -> (-y, x) swaps a and c
+            if a > c {
+                (a, c) = (c, a);
+                b = -b;
+                continue;
+            }
+            if a == c && b < 0 {
+                b = -b;
+            }
+            return Self::new(a, b, c);
+        }
+    }
+
+    /// Whether $f$ and $g$ are properly equivalent, so in the same class. Panics if they aren't positive definite.
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        self.reduce() == other.reduce()
+    }
+
+    /// The inverse class, $(a, -b, c)$, reduced.
+    pub fn inverse(&self) -> Self {
+        Self::new(self.a.clone(), Integer::from(-&self.b), self.c.clone()).reduce()
+    }
+
+    /// The composition of two primitive positive definite forms of the same discriminant, reduced, by NUCOMP.
+    ///
+    /// With $a\_1 \leq a\_2$, $s = (b\_1 + b\_2)/2$, $d\_1 = \gcd(a\_1, a\_2, s)$, $v\_i = a\_i/d\_1$ and $r$ from
+    /// Dirichlet's conditions, the composite is $(A, B, C) = (v\_1v\_2, b\_2 + 2v\_2r, \ldots)$, which is
+    /// $N(xA + y\beta)/A$ on the lattice spanned by $A$ and $\beta = (B + \sqrt{D})/2$. The extended Euclidean
+    /// algorithm on $v\_1, r$ gives remainders $R = xv\_1 + yr$, and then $xA + y\beta$ has real part
+    /// $v\_2R + yb\_2/2$, so while $R$ shrinks and $y$ grows the vector gets shorter. Stopping at
+    /// $R \approx |D/4|^{1/4}$ and using two consecutive vectors as the new basis gives
+    /// $$
+    /// a = \frac{v\_2R^2 + b\_2Ry + d\_1c\_2y^2}{v\_1}, \quad
+    /// b = \frac{2v\_2RR' + b\_2(Ry' + R'y) + 2d\_1c\_2yy'}{v\_1}.
+    /// $$
+    pub fn compose(&self, other: &Self) -> Self {
+        let d = self.discriminant();
+        assert_eq!(d, other.discriminant(), "forms of different discriminants");
+        assert!(
+            self.is_positive_definite() && other.is_positive_definite(),
+            "only positive definite forms can be composed"
+        );
+        let (f1, f2) = match self.a <= other.a {
+            true => (self, other),
+            false => (other, self),
+        };
+        let (a1, b2, c2, a2) = (&f1.a, &f2.b, &f2.c, &f2.a);
+        let s: Integer = Integer::from(&f1.b + b2) / 2;
+        let n = Integer::from(b2 - &s);
+
+        // Dirichlet composition (Cohen, Algorithm 5.4.7)
+        let (y1, dd) = match a2.is_divisible(a1) {
+            true => (Integer::new(), a1.clone()),
+            false => {
+                let (g, u, _) = a2.clone().extended_gcd(a1.clone(), Integer::new());
+                (u, g)
+            }
+        };
+        let (x2, y2, d1) = match s.is_divisible(&dd) {
+            true => (Integer::new(), Integer::from(-1), dd),
+            false => {
+                let (g, x, y) = s.clone().extended_gcd(dd, Integer::new());
+                (x, -y, g)
+            }
+        };
+        let v1 = Integer::from(a1 / &d1);
+        let v2 = Integer::from(a2 / &d1);
+        let r = (y1 * y2 * &n - x2 * c2).div_rem_euc(v1.clone()).1;
+
+        // the partial Euclidean algorithm, keeping R = x v1 + y r for the last two remainders
+        let quarter: Integer = Integer::from(d.abs_ref()) / 4;
+        let bound = quarter.root(4);
+        let (mut prev, mut cur) = (
+            (v1.clone(), Integer::from(1), Integer::new()),
+            (r, Integer::new(), Integer::from(1)),
+        );
+        while cur.0 > bound {
+            let q = Integer::from(&prev.0 / &cur.0);
+            let next = (
+                Integer::from(&prev.0 - &q * &cur.0),
+                Integer::from(&prev.1 - &q * &cur.1),
+                Integer::from(&prev.2 - &q * &cur.2),
+            );
+            prev = cur;
+            cur = next;
+        }
+        // the new basis xA + y beta, x'A + y' beta needs xy' - x'y = 1
+        let (rr, x, y) = cur;
+        let (mut rr2, x2, mut y2) = prev;
+        if Integer::from(&x * &y2) - Integer::from(&x2 * &y) < 0 {
+            rr2 = -rr2;
+            y2 = -y2;
+        }
+        let dc = Integer::from(&d1 * c2);
+        let a = (Integer::from(&v2 * &rr) * &rr
+            + Integer::from(b2 * &rr) * &y
+            + Integer::from(&dc * &y) * &y)
+            / &v1;
+        let b = (Integer::from(&v2 * &rr) * &rr2 * 2
+            + b2 * (Integer::from(&rr * &y2) + Integer::from(&rr2 * &y))
+            + Integer::from(&dc * &y) * &y2 * 2)
+            / &v1;
+        let c = (Integer::from(&b * &b) - &d) / Integer::from(&a * 4);
+        Self::new(a, b, c).reduce()
+    }
+
+    /// $f^k$, for any integer $k$.
+    pub fn pow(&self, k: &Integer) -> Self {
+        let base = match *k < 0 {
+            true => self.inverse(),
+            false => self.reduce(),
+        };
+        let mut result = Self::identity(&self.discriminant());
+        for i in (0..k.significant_bits()).rev() {
+            result = result.compose(&result);
+            if k.get_bit(i) {
+                result = result.compose(&base);
+            }
+        }
+        result
+    }
+
+    /// Some $(x, y)$ with $f(x, y) = n$, if there is one. See `representations`.
+    pub fn represent(&self, n: &Integer) -> Option<(Integer, Integer)> {
+        self.representations(n).into_iter().next()
+    }
+
+    /// All $(x, y)$ with $f(x, y) = n$, ordered by $y$ and then $x$, for positive definite $f$. There are finitely
+    /// many since $4af(x, y) = (2ax + by)^2 - Dy^2$, so $y^2 \leq 4an/|D|$, and for each $y$ it's a quadratic in
+    /// $x$.
+    pub fn representations(&self, n: &Integer) -> Vec<(Integer, Integer)> {
+        assert!(
+            self.is_positive_definite(),
+            "only positive definite forms have finitely many representations"
+        );
+        let d = self.discriminant();
+        let bound: Integer = Integer::from(&self.a * n) * 4 / Integer::from(-&d);
+        let bound = bound.sqrt();
+        let two_a = Integer::from(&self.a * 2);
+        let mut solutions = vec![];
+        let mut y = Integer::from(-&bound);
+        while y <= bound {
+            // a x^2 + (by) x + (c y^2 - n) = 0 has discriminant D y^2 + 4an
+            let disc: Integer = Integer::from(&d * &y) * &y + Integer::from(&self.a * n) * 4;
+            if disc >= 0 && disc.is_perfect_square() {
+                let root = disc.sqrt();
+                let by = Integer::from(&self.b * &y);
+                let mut xs = vec![-Integer::from(&by + &root), Integer::from(&root - &by)];
+                xs.dedup();
+                for x in xs {
+                    if x.is_divisible(&two_a) {
+                        solutions.push((x / &two_a, y.clone()));
+                    }
+                }
+            }
+            y += 1;
+        }
+        solutions
+    }
+
+    /// The primitive reduced forms of discriminant $D < 0$, ordered by $a$ and then $b$. Panics if $D$ isn't a
+    /// negative discriminant.
+    pub fn reduced_forms(d: &Integer) -> Vec<Self> {
+        check_discriminant(d);
+        let mut forms = vec![];
+        let mut a = Integer::from(1);
+        // 3a^2 <= |D|
+        while Integer::from(&a * &a) * 3 <= Integer::from(-d) {
+            let mut b = Integer::from(1 - &a);
+            while b <= a {
+                let numerator = Integer::from(&b * &b) - d;
+                let four_a = Integer::from(&a * 4);
+                if numerator.is_divisible(&four_a) {
+                    let f = Self::new(a.clone(), b.clone(), numerator / four_a);
+                    if f.is_reduced() && f.is_primitive() {
+                        forms.push(f);
+                    }
+                }
+                b += 1;
+            }
+            a += 1;
+        }
+        forms
+    }
+
+    /// The class number $h(D)$, the number of classes of primitive positive definite forms of discriminant $D$.
+    pub fn class_number(d: &Integer) -> usize {
+        Self::reduced_forms(d).len()
+    }
+}
+
+fn check_discriminant(d: &Integer) {
+    let r = d.mod_u(4);
+    assert!(
+        *d < 0 && (r == 0 || r == 1),
+        "{} isn't a negative discriminant",
+        d
+    );
+}
+
+/// Composition of classes, see `compose`.
+impl Mul for BinaryQuadraticForm {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.compose(&rhs)
+    }
+}
+
+impl Display for BinaryQuadraticForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.a, self.b, self.c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn form(a: i64, b: i64, c: i64) -> BinaryQuadraticForm {
+        BinaryQuadraticForm::new(Integer::from(a), Integer::from(b), Integer::from(c))
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert!(form(2, 1, 3).is_reduced());
+        assert!(!form(2, -2, 3).is_reduced());
+        assert_eq!(form(6, 5, 2).reduce(), form(2, -1, 3));
+        assert_eq!(form(2, -2, 3).reduce(), form(2, 2, 3));
+        assert_eq!(form(3, -2, 3).reduce(), form(3, 2, 3));
+        // x^2 + y^2 under (x, y) -> (2x + 3y, x + 2y)
+        assert_eq!(form(5, 16, 13).reduce(), form(1, 0, 1));
+        assert!(form(5, 16, 13).is_equivalent(&form(1, 0, 1)));
+        // (2, 1, 3) and (2, -1, 3) are improperly equivalent, but not properly
+        assert!(!form(2, 1, 3).is_equivalent(&form(2, -1, 3)));
+        assert_eq!(form(1, 0, 1).to_string(), "(1, 0, 1)");
+    }
+
+    #[test]
+    fn test_class_number() {
+        let h = |d: i64| BinaryQuadraticForm::class_number(&Integer::from(d));
+        assert_eq!(h(-3), 1);
+        assert_eq!(h(-4), 1);
+        assert_eq!(h(-20), 2);
+        assert_eq!(h(-23), 3);
+        assert_eq!(h(-56), 4);
+        assert_eq!(h(-84), 4);
+        assert_eq!(h(-163), 1);
+        assert_eq!(h(-47), 5);
+        // non-fundamental: the order Z[3i] has class number 2
+        assert_eq!(h(-36), 2);
+        let forms = BinaryQuadraticForm::reduced_forms(&Integer::from(-20));
+        assert_eq!(forms, vec![form(1, 0, 5), form(2, 2, 3)]);
+    }
+
+    #[test]
+    fn test_compose() {
+        for d in [-23, -56, -84, -104, -71, -4 * 1009, -4 * 10007, -1000099] {
+            let d = Integer::from(d);
+            let forms = BinaryQuadraticForm::reduced_forms(&d);
+            let h = Integer::from(forms.len());
+            let e = BinaryQuadraticForm::identity(&d);
+            for f in forms.iter() {
+                assert_eq!(f.clone() * e.clone(), *f);
+                assert_eq!(f.clone() * f.inverse(), e);
+                assert_eq!(f.pow(&h), e);
+                assert_eq!(f.pow(&Integer::from(-1)), f.inverse());
+            }
+            // composition is a group law on the classes
+            for f in forms.iter().take(6) {
+                for g in forms.iter().take(6) {
+                    let fg = f.clone() * g.clone();
+                    assert!(fg.is_reduced() && fg.discriminant() == d);
+                    assert_eq!(fg, g.clone() * f.clone());
+                    for k in forms.iter().take(6) {
+                        assert_eq!(fg.clone() * k.clone(), f.clone() * (g.clone() * k.clone()));
+                    }
+                }
+            }
+        }
+        // C4 for D = -56: (3, 2, 5) generates
+        let g = form(3, 2, 5);
+        assert_eq!(g.clone() * g.clone(), form(2, 0, 7));
+        assert_eq!(g.pow(&Integer::from(3)), form(3, -2, 5));
+    }
+
+    #[test]
+    fn test_represent() {
+        let sum_of_squares = form(1, 0, 1);
+        assert_eq!(sum_of_squares.representations(&Integer::from(25)).len(), 12);
+        assert_eq!(sum_of_squares.represent(&Integer::from(21)), None);
+        // p = x^2 + 5y^2 for p = 1, 9 mod 20, and 2x^2 + 2xy + 3y^2 for p = 3, 7 mod 20
+        assert!(form(1, 0, 5).represent(&Integer::from(29)).is_some());
+        assert!(form(1, 0, 5).represent(&Integer::from(23)).is_none());
+        let (x, y) = form(2, 2, 3).represent(&Integer::from(23)).unwrap();
+        assert_eq!(form(2, 2, 3).eval(&x, &y), 23);
+        assert!(form(2, 2, 3).represent(&Integer::from(29)).is_none());
+    }
+}
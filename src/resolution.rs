@@ -0,0 +1,363 @@
+//! Free resolutions and graded Betti numbers
+//!
+//! For a finitely generated graded module $M$ over $R = F[x\_0, \ldots, x\_{n-1}]$ (with every variable in degree 1),
+//! a minimal free resolution is an exact sequence
+//! $$
+//! 0 \leftarrow M \leftarrow F\_0 \xleftarrow{d\_1} F\_1 \xleftarrow{d\_2} F\_2 \leftarrow \cdots \leftarrow F\_k \leftarrow 0
+//! $$
+//! where $F\_i = \bigoplus\_j R(-j)^{\beta\_{i,j}}$ and every $d\_i$ has entries in the maximal ideal
+//! $(x\_0, \ldots, x\_{n-1})$. The numbers $\beta\_{i,j}$ do not depend on the choice of minimal resolution,
+//! and are the graded Betti numbers of $M$.
+//!
+//! The resolution is computed one step at a time: the syzygies of the current generators come from
+//! Schreyer's algorithm (see `groebner::syzygies`), and are then cut down to a minimal generating set,
+//! going through them by degree and throwing away any that lie in the span of the ones already kept.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::mpoly::MPolynomial;
+//! use bored_algebra::resolution::Resolution;
+//! use bored_algebra::Rational;
+//!
+//! type P = MPolynomial<Rational>;
+//! // the twisted cubic, (xz - y^2, xw - yz, yw - z^2)
+//! let gens = vec![
+//!     P::from(vec![(vec![1, 0, 1], Rational::from(1)), (vec![0, 2], Rational::from(-1))]),
+//!     P::from(vec![(vec![1, 0, 0, 1], Rational::from(1)), (vec![0, 1, 1], Rational::from(-1))]),
+//!     P::from(vec![(vec![0, 1, 0, 1], Rational::from(1)), (vec![0, 0, 2], Rational::from(-1))]),
+//! ];
+//! let res = Resolution::of_ideal(&gens).unwrap();
+//! assert_eq!(
+//!     res.betti().to_string(),
+//!     "       0 1 2\ntotal: 1 3 2\n    0: 1 . .\n    1: . 3 2\n"
+//! );
+//! ```
+use crate::error::ArithmeticError;
+use crate::free::Free;
+use crate::groebner::{module_groebner_basis, module_normal_form, syzygies, Vector};
+use crate::matrix::Matrix;
+use crate::module::Homo;
+use crate::mpoly::{GRevLex, MPolynomial, MonomialOrder};
+use crate::structure::Field;
+use core::fmt::{self, Display};
+use std::collections::BTreeMap;
+
+use num_traits::identities::Zero;
+
+/// A minimal graded free resolution $F\_0 \leftarrow F\_1 \leftarrow \cdots \leftarrow F\_k$.
+#[derive(Clone, Debug)]
+pub struct Resolution<F, O = GRevLex> {
+    degrees: Vec<Vec<i64>>, // degrees of the basis elements of each F_i
+    maps: Vec<Matrix<MPolynomial<F, O>>>, // maps[i] is d_{i+1}: F_{i+1} -> F_i
+}
+
+/// The degree of `v`, where the $k$th basis vector has degree `shifts[k]`. Returns `None` for zero,
+/// and an error if `v` is not homogeneous.
+fn vector_degree<F: Field, O: MonomialOrder>(
+    v: &Vector<F, O>,
+    shifts: &[i64],
+) -> Result<Option<i64>, ArithmeticError> {
+    let mut degree = None;
+    for (k, p) in v.coords().iter().enumerate() {
+        for (m, _) in p.terms() {
+            let d = m.deg() as i64 + shifts.get(k).copied().ok_or(ArithmeticError::OutOfBounds)?;
+            match degree {
+                None => degree = Some(d),
+                Some(e) if e != d => return Err(ArithmeticError::NotHomogeneous),
+                _ => (),
+            }
+        }
+    }
+    Ok(degree)
+}
+
+/// A minimal set of generators of the submodule generated by the homogeneous elements `gens`,
+/// sorted by degree, along with their degrees.
+#[allow(clippy::type_complexity)]
+fn minimize<F: Field, O: MonomialOrder>(
+    gens: Vec<Vector<F, O>>,
+    shifts: &[i64],
+) -> Result<(Vec<Vector<F, O>>, Vec<i64>), ArithmeticError> {
+    let mut graded = vec![];
+    for g in gens {
+        if let Some(d) = vector_degree(&g, shifts)? {
+            graded.push((d, g));
+        }
+    }
+    graded.sort_by_key(|(d, _)| *d);
+
+    let mut kept = vec![];
+    let mut degrees = vec![];
+    let mut basis = vec![];
+    for (d, g) in graded {
+        if !module_normal_form(&g, &basis).is_zero() {
+            kept.push(g);
+            degrees.push(d);
+            basis = module_groebner_basis(&kept);
+        }
+    }
+    Ok((kept, degrees))
+}
+
+impl<F: Field, O: MonomialOrder> Resolution<F, O> {
+    /// The minimal free resolution of $R/I$, where $I$ is the ideal generated by `gens`. Returns
+    /// `ArithmeticError::NotHomogeneous` if any generator is not homogeneous.
+    pub fn of_ideal(gens: &[MPolynomial<F, O>]) -> Result<Self, ArithmeticError> {
+        let columns = gens
+            .iter()
+            .map(|f| Free::from(vec![f.clone()]))
+            .collect::<Vec<Vector<F, O>>>();
+        Self::resolve(columns, vec![0])
+    }
+
+    /// The minimal free resolution of the cokernel of `presentation`, a map $R^m \to R^n$
+    /// where the $k$th basis vector of $R^n$ has degree `degrees[k]`. The columns of `presentation`
+    /// need to be homogeneous.
+    ///
+    /// $F\_0$ is taken to be $R^n$, so this is only minimal if the generators of $R^n$ are a minimal
+    /// generating set of the cokernel (for example, if no entry of `presentation` is a nonzero constant).
+    pub fn of_module(
+        presentation: &Matrix<MPolynomial<F, O>>,
+        degrees: &[i64],
+    ) -> Result<Self, ArithmeticError> {
+        if degrees.len() != presentation.rows() {
+            return Err(ArithmeticError::OutOfBounds);
+        }
+        Self::resolve(presentation.columns(), degrees.to_vec())
+    }
+
+    fn resolve(columns: Vec<Vector<F, O>>, degrees: Vec<i64>) -> Result<Self, ArithmeticError> {
+        let mut res = Self {
+            degrees: vec![degrees],
+            maps: vec![],
+        };
+        let (mut current, mut current_degrees) = minimize(columns, &res.degrees[0])?;
+        // by Hilbert's syzygy theorem, this stops after at most (number of variables) steps
+        while !current.is_empty() {
+            let rank = res.degrees.last().unwrap().len();
+            res.maps.push(Matrix::from_columns(rank, &current));
+            let next = minimize(syzygies(&current), &current_degrees)?;
+            res.degrees.push(current_degrees);
+            (current, current_degrees) = next;
+        }
+        Ok(res)
+    }
+
+    /// The length $k$ of the resolution, the index of the last nonzero free module.
+    pub fn length(&self) -> usize {
+        self.maps.len()
+    }
+
+    /// The rank of the free module $F\_i$.
+    pub fn rank(&self, i: usize) -> usize {
+        self.degrees.get(i).map(|d| d.len()).unwrap_or(0)
+    }
+
+    /// The degrees of the basis elements of $F\_i$.
+    pub fn degrees(&self, i: usize) -> &[i64] {
+        self.degrees.get(i).map(|d| d.as_slice()).unwrap_or(&[])
+    }
+
+    /// The matrix of the differential $d\_i: F\_i \to F\_{i-1}$, for $1 \leq i \leq$ `length()`.
+    pub fn matrix(&self, i: usize) -> &Matrix<MPolynomial<F, O>> {
+        &self.maps[i - 1]
+    }
+
+    /// The differentials $d\_1, d\_2, \ldots, d\_k$ as homomorphisms.
+    #[allow(clippy::type_complexity)]
+    pub fn homos(&self) -> Vec<Homo<MPolynomial<F, O>, Vector<F, O>, Vector<F, O>>>
+    where
+        F: 'static,
+    {
+        self.maps.iter().map(|a| a.clone().into_homo()).collect()
+    }
+
+    /// The graded Betti numbers $\beta\_{i,j}$.
+    pub fn betti(&self) -> BettiTable {
+        let mut betti = BTreeMap::new();
+        for (i, degrees) in self.degrees.iter().enumerate() {
+            for d in degrees {
+                *betti.entry((i, *d)).or_insert(0) += 1;
+            }
+        }
+        BettiTable { betti }
+    }
+}
+
+/// Graded Betti numbers $\beta\_{i,j}$, the number of basis elements of $F\_i$ in degree $j$.
+/// Printing one gives the table in the same layout as Macaulay2's `betti`, where column $i$ and row $r$
+/// holds $\beta\_{i, i + r}$, and zeros are shown as dots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BettiTable {
+    betti: BTreeMap<(usize, i64), usize>,
+}
+
+impl BettiTable {
+    /// $\beta\_{i,j}$.
+    pub fn get(&self, i: usize, j: i64) -> usize {
+        self.betti.get(&(i, j)).copied().unwrap_or(0)
+    }
+
+    /// The total Betti number $\beta\_i = \sum\_j \beta\_{i,j}$, the rank of $F\_i$.
+    pub fn total(&self, i: usize) -> usize {
+        self.betti
+            .iter()
+            .filter(|((k, _), _)| *k == i)
+            .map(|(_, b)| b)
+            .sum()
+    }
+
+    /// The largest $i$ with a nonzero Betti number.
+    pub fn length(&self) -> usize {
+        self.betti.keys().map(|(i, _)| *i).max().unwrap_or(0)
+    }
+}
+
+impl Display for BettiTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<i64> = self.betti.keys().map(|(i, j)| j - *i as i64).collect();
+        let (low, high) = match (rows.iter().min(), rows.iter().max()) {
+            (Some(low), Some(high)) => (*low, *high),
+            _ => (0, 0),
+        };
+        let columns = self.length() + 1;
+
+        // each line is a label followed by one entry per column
+        let mut lines: Vec<(String, Vec<String>)> = vec![
+            (String::new(), (0..columns).map(|i| i.to_string()).collect()),
+            (
+                "total:".to_string(),
+                (0..columns).map(|i| self.total(i).to_string()).collect(),
+            ),
+        ];
+        for r in low..=high {
+            lines.push((
+                format!("{}:", r),
+                (0..columns)
+                    .map(|i| match self.get(i, i as i64 + r) {
+                        0 => ".".to_string(),
+                        b => b.to_string(),
+                    })
+                    .collect(),
+            ));
+        }
+
+        let label_width = lines.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| lines.iter().map(|(_, e)| e[i].len()).max().unwrap_or(0))
+            .collect();
+        for (label, entries) in lines {
+            write!(f, "{:>width$}", label, width = label_width)?;
+            for (e, w) in entries.iter().zip(widths.iter()) {
+                write!(f, " {:>width$}", e, width = w)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::poly;
+    use crate::Rational;
+
+    type P = MPolynomial<Rational>;
+
+    /// checks d_i d_{i+1} = 0
+    fn assert_complex(res: &Resolution<Rational>) {
+        for i in 1..res.length() {
+            assert!((res.matrix(i).clone() * res.matrix(i + 1).clone()).is_zero());
+        }
+    }
+
+    #[test]
+    fn test_koszul() {
+        let gens = vec![P::var(0), P::var(1), P::var(2)];
+        let res = Resolution::of_ideal(&gens).unwrap();
+        assert_eq!(res.length(), 3);
+        assert_eq!(
+            (0..4).map(|i| res.rank(i)).collect::<Vec<usize>>(),
+            vec![1, 3, 3, 1]
+        );
+        assert_eq!(res.degrees(2), &[2, 2, 2]);
+        assert_complex(&res);
+        assert_eq!(
+            res.betti().to_string(),
+            "       0 1 2 3\ntotal: 1 3 3 1\n    0: 1 3 3 1\n"
+        );
+    }
+
+    #[test]
+    fn test_twisted_cubic() {
+        let gens = vec![
+            poly(vec![(vec![1, 0, 1], 1), (vec![0, 2], -1)]),
+            poly(vec![(vec![1, 0, 0, 1], 1), (vec![0, 1, 1], -1)]),
+            poly(vec![(vec![0, 1, 0, 1], 1), (vec![0, 0, 2], -1)]),
+        ];
+        let res = Resolution::of_ideal(&gens).unwrap();
+        let betti = res.betti();
+        assert_eq!(betti.get(1, 2), 3);
+        assert_eq!(betti.get(2, 3), 2);
+        assert_eq!(betti.total(2), 2);
+        assert_eq!(res.length(), 2);
+        assert_complex(&res);
+        // the homomorphisms compose to zero too
+        let homos = res.homos();
+        let v = Free::from(vec![P::var(3), P::var(0)]);
+        let mut maps = homos.into_iter();
+        let d1 = maps.next().unwrap();
+        let d2 = maps.next().unwrap();
+        assert!(d2.compose(d1).apply(v).is_zero());
+    }
+
+    #[test]
+    fn test_non_minimal_generators() {
+        // (x^2, xy, x^2 + xy, y^3, x^3) is minimally generated by x^2, xy, y^3
+        let gens = vec![
+            poly(vec![(vec![2], 1)]),
+            poly(vec![(vec![1, 1], 1)]),
+            poly(vec![(vec![2], 1), (vec![1, 1], 1)]),
+            poly(vec![(vec![0, 3], 1)]),
+            poly(vec![(vec![3], 1)]),
+        ];
+        let res = Resolution::of_ideal(&gens).unwrap();
+        assert_eq!(res.degrees(1), &[2, 2, 3]);
+        assert_eq!(res.degrees(2), &[3, 4]);
+        assert_eq!(
+            res.betti().to_string(),
+            "       0 1 2\ntotal: 1 3 2\n    0: 1 . .\n    1: . 2 1\n    2: . 1 1\n"
+        );
+        assert_complex(&res);
+    }
+
+    #[test]
+    fn test_module() {
+        // coker of (x y) : R(-1)^2 -> R, the residue field again but as a module
+        let presentation = Matrix::from(vec![vec![P::var(0), P::var(1)]]);
+        let res = Resolution::of_module(&presentation, &[0]).unwrap();
+        assert_eq!(
+            res.betti().to_string(),
+            "       0 1 2\ntotal: 1 2 1\n    0: 1 2 1\n"
+        );
+
+        // a shifted free module has no syzygies
+        let presentation = Matrix::<P>::zero(2, 0);
+        let res = Resolution::of_module(&presentation, &[1, 3]).unwrap();
+        assert_eq!(res.length(), 0);
+        assert_eq!(
+            res.betti().to_string(),
+            "       0\ntotal: 2\n    1: 1\n    2: .\n    3: 1\n"
+        );
+    }
+
+    #[test]
+    fn test_not_homogeneous() {
+        let gens: Vec<P> = vec![poly(vec![(vec![2], 1), (vec![1], 1)])];
+        assert!(matches!(
+            Resolution::of_ideal(&gens),
+            Err(ArithmeticError::NotHomogeneous)
+        ));
+    }
+}
@@ -0,0 +1,601 @@
+//! Algebraic structures
+//!
+//! `RingType`, `FieldType` and `AbGroupType` in `module` are aliases, so anything with the right operators gets
+//! them, whether or not the operators satisfy the axioms (`i64` is a `FieldType`). The traits here have to be
+//! implemented on purpose, and they follow the usual chain
+//! $$
+//! \text{commutative rings} \supset \text{integral domains} \supset \text{gcd domains} \supset
+//! \text{Euclidean domains} \supset \text{fields},
+//! $$
+//! so generic code can ask for the structure it actually uses (a gcd, say) and only get types where that makes
+//! sense. `Monoid` and `Group` are written multiplicatively, since additive groups are `AbGroupType`, but
+//! `Additive` turns the additive group of a ring into a `Group`, and `Unit` is for the group of units.
+//!
+//! The implementations here:
+//! * `Integer` is a Euclidean domain, with $|a|$ as the norm, and `Rational` is a field.
+//! * `Zmod<N>`, `ZmodDyn` and `GaloisField<P>` are commutative rings. `Zmod<N>` is a `Field` when $N$ is prime,
+//!   which is checked at compile time: using it as one for composite $N$ doesn't build.
+//! * `Polynomial<R>` is an integral domain or gcd domain when $R$ is, and a Euclidean domain over a field.
+//! * `MPolynomial<R, O>` is a commutative ring.
+//!
+//! Polynomial division, Grobner bases, ideals and everything built on them ask for a `Field`, so they don't
+//! accept `i64`:
+//! ```compile_fail
+//! use bored_algebra::poly::Polynomial;
+//!
+//! let f = Polynomial::<i64>::from(vec![1, 2, 1]);
+//! let _ = f.gcd(&Polynomial::from(vec![1, 1]));
+//! ```
+//!
+//! Since the gcd is only defined up to units, integral domains pick a normal form for each class of associates
+//! with `normal_unit`: nonnegative integers, polynomials with a normal leading coefficient (so monic over a
+//! field). `gcd` gives normal results.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::structure::{ext_gcd, EuclideanDomain, GcdDomain};
+//! use bored_algebra::poly::Polynomial;
+//! use bored_algebra::zmod::Zmod;
+//! use bored_algebra::Integer;
+//!
+//! let (a, b) = (Integer::from(240), Integer::from(-46));
+//! assert_eq!(GcdDomain::gcd(&a, &b), 2);
+//! let (g, s, t) = ext_gcd(&a, &b);
+//! assert_eq!(g, Integer::from(&s * &a) + Integer::from(&t * &b));
+//!
+//! // gcd((x - 1)(x - 2), (x - 1)(x - 3)) = x - 1 over Z/7
+//! type F = Zmod<7>;
+//! let f = Polynomial::from(vec![F::new(2), F::new(4), F::new(1)]);
+//! let g = Polynomial::from(vec![F::new(3), F::new(3), F::new(1)]);
+//! assert_eq!(GcdDomain::gcd(&f, &g), Polynomial::from(vec![F::new(6), F::new(1)]));
+//! assert_eq!(f.norm(), 2);
+//! ```
+use crate::error::ArithmeticError;
+use crate::galois::GaloisField;
+use crate::module::RingType;
+use crate::mpoly::{MPolynomial, MonomialOrder};
+use crate::poly::Polynomial;
+use crate::zmod::{Zmod, ZmodDyn};
+use crate::{Integer, Rational};
+
+use core::fmt::Debug;
+use core::ops::{Div, Mul};
+use num_traits::identities::{One, Zero};
+
+/// A monoid, written multiplicatively: `Mul` is associative and `one()` is the identity.
+pub trait Monoid: Clone + Debug + Eq + Mul<Output = Self> + One {}
+
+/// A group, written multiplicatively.
+pub trait Group: Monoid {
+    fn inv(&self) -> Self;
+}
+
+/// A commutative ring with 1.
+pub trait CommRing: RingType + Monoid {}
+
+/// A commutative ring without zero divisors.
+pub trait IntegralDomain: CommRing {
+    /// $a/b$ if $b$ divides $a$, and `None` otherwise (and for $b = 0$).
+    fn div_exact(&self, other: &Self) -> Option<Self>;
+
+    /// The unit $u$ that makes $a/u$ the normal form of $a$ among its associates. It's 1 for zero, and the
+    /// default is 1 for everything, which is always allowed but doesn't normalize anything.
+    fn normal_unit(&self) -> Self {
+        Self::one()
+    }
+
+    /// The normal form of `self`, see `normal_unit`.
+    fn normalize(&self) -> Self {
+        self.div_exact(&self.normal_unit()).unwrap()
+    }
+
+    fn is_unit(&self) -> bool {
+        Self::one().div_exact(self).is_some()
+    }
+}
+
+/// An integral domain where any two elements have a gcd.
+pub trait GcdDomain: IntegralDomain {
+    /// The normal greatest common divisor, which is zero only for $\gcd(0, 0)$.
+    fn gcd(&self, other: &Self) -> Self;
+
+    /// The normal least common multiple.
+    fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let g = self.gcd(other);
+        (self.div_exact(&g).unwrap() * other.clone()).normalize()
+    }
+}
+
+/// An integral domain with division with remainder.
+pub trait EuclideanDomain: GcdDomain {
+    /// $(q, r)$ with `self` $= q \cdot$ `other` $+ r$ and $r = 0$ or $N(r) < N($`other`$)$. Panics if `other`
+    /// is zero.
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+
+    /// The Euclidean function $N$. It only needs to mean anything for nonzero elements.
+    fn norm(&self) -> Integer;
+}
+
+/// A field. Every field is a Euclidean domain, with zero remainders.
+pub trait Field: EuclideanDomain + Div<Output = Self> {
+    /// The inverse, or `DivideByZero` for zero.
+    fn inv(&self) -> Result<Self, ArithmeticError>;
+}
+
+/// The additive group of a ring, written multiplicatively to make it a `Group`: `Additive(a) * Additive(b)` is
+/// `Additive(a + b)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Additive<R>(pub R);
+
+/// A unit of an integral domain, as an element of the group of units $R^\times$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unit<R>(R);
+
+impl<R: IntegralDomain> Unit<R> {
+    /// `a` as a unit, or `None` if it isn't one.
+    pub fn new(a: R) -> Option<Self> {
+        a.is_unit().then_some(Unit(a))
+    }
+
+    pub fn value(&self) -> &R {
+        &self.0
+    }
+}
+
+/// The normal gcd of $a$ and $b$ by the Euclidean algorithm, for implementing `GcdDomain`.
+pub fn euclid_gcd<R: EuclideanDomain>(a: &R, b: &R) -> R {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while !b.is_zero() {
+        let r = a.div_rem(&b).1;
+        (a, b) = (b, r);
+    }
+    a.normalize()
+}
+
+/// The extended Euclidean algorithm: $(g, s, t)$ with $g = sa + tb$ the normal gcd of $a$ and $b$.
+pub fn ext_gcd<R: EuclideanDomain>(a: &R, b: &R) -> (R, R, R) {
+    let (mut r0, mut r1) = (a.clone(), b.clone());
+    let (mut s0, mut s1) = (R::one(), R::zero());
+    let (mut t0, mut t1) = (R::zero(), R::one());
+    while !r1.is_zero() {
+        let (q, r) = r0.div_rem(&r1);
+        let s = s0 - q.clone() * s1.clone();
+        let t = t0 - q * t1.clone();
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+    let u = r0.normal_unit();
+    let normal = |x: R| x.div_exact(&u).unwrap();
+    (normal(r0), normal(s0), normal(t0))
+}
+
+impl<R: CommRing> Mul for Additive<R> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self {
+        Additive(self.0 + rhs.0)
+    }
+}
+
+impl<R: CommRing> One for Additive<R> {
+    fn one() -> Self {
+        Additive(R::zero())
+    }
+}
+
+impl<R: CommRing> Monoid for Additive<R> {}
+
+impl<R: CommRing> Group for Additive<R> {
+    fn inv(&self) -> Self {
+        Additive(-self.0.clone())
+    }
+}
+
+impl<R: IntegralDomain> Mul for Unit<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Unit(self.0 * rhs.0)
+    }
+}
+
+impl<R: IntegralDomain> One for Unit<R> {
+    fn one() -> Self {
+        Unit(R::one())
+    }
+}
+
+impl<R: IntegralDomain> Monoid for Unit<R> {}
+
+impl<R: IntegralDomain> Group for Unit<R> {
+    fn inv(&self) -> Self {
+        Unit(R::one().div_exact(&self.0).unwrap())
+    }
+}
+
+impl Monoid for Integer {}
+impl CommRing for Integer {}
+
+/// The normal integers are the nonnegative ones.
+impl IntegralDomain for Integer {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (*other != 0 && self.is_divisible(other)).then(|| Integer::from(self / other))
+    }
+
+    fn normal_unit(&self) -> Self {
+        match *self < 0 {
+            true => Integer::from(-1),
+            false => Integer::from(1),
+        }
+    }
+}
+
+impl GcdDomain for Integer {
+    fn gcd(&self, other: &Self) -> Self {
+        Integer::from(self.gcd_ref(other))
+    }
+}
+
+/// Division with a nonnegative remainder.
+impl EuclideanDomain for Integer {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(*other != 0, "divide by zero");
+        self.clone().div_rem_euc(other.clone())
+    }
+
+    fn norm(&self) -> Integer {
+        self.clone().abs()
+    }
+}
+
+impl Monoid for Rational {}
+impl CommRing for Rational {}
+
+/// The normal rationals are 0 and 1.
+impl IntegralDomain for Rational {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| Rational::from(self / other))
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Rational::from(1),
+            false => self.clone(),
+        }
+    }
+}
+
+impl GcdDomain for Rational {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Rational::new(),
+            false => Rational::from(1),
+        }
+    }
+}
+
+impl EuclideanDomain for Rational {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "divide by zero");
+        (Rational::from(self / other), Rational::new())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+impl Field for Rational {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        match self.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => Ok(self.clone().recip()),
+        }
+    }
+}
+
+impl<const N: u64> Monoid for Zmod<N> {}
+impl<const N: u64> CommRing for Zmod<N> {}
+
+/// Only for prime $N$, which is checked when this gets compiled.
+impl<const N: u64> IntegralDomain for Zmod<N> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        let () = Self::ASSERT_PRIME;
+        (!other.is_zero()).then(|| *self / *other)
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => *self,
+        }
+    }
+}
+
+impl<const N: u64> GcdDomain for Zmod<N> {
+    fn gcd(&self, other: &Self) -> Self {
+        let () = Self::ASSERT_PRIME;
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl<const N: u64> EuclideanDomain for Zmod<N> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let () = Self::ASSERT_PRIME;
+        (*self / *other, Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+impl<const N: u64> Field for Zmod<N> {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        let () = Self::ASSERT_PRIME;
+        match self.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => self.inverse(),
+        }
+    }
+}
+
+impl Monoid for ZmodDyn {}
+impl CommRing for ZmodDyn {}
+
+impl<const P: u64> Monoid for GaloisField<P> {}
+impl<const P: u64> CommRing for GaloisField<P> {}
+
+impl<const P: u64> IntegralDomain for GaloisField<P> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.clone() / other.clone())
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => self.clone(),
+        }
+    }
+}
+
+impl<const P: u64> GcdDomain for GaloisField<P> {
+    fn gcd(&self, other: &Self) -> Self {
+        match self.is_zero() && other.is_zero() {
+            true => Self::zero(),
+            false => Self::one(),
+        }
+    }
+}
+
+impl<const P: u64> EuclideanDomain for GaloisField<P> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self.clone() / other.clone(), Self::zero())
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(!self.is_zero() as u8)
+    }
+}
+
+/// The inverse comes from the extended Euclidean algorithm against the modulus, see `GaloisField::inverse`.
+impl<const P: u64> Field for GaloisField<P> {
+    fn inv(&self) -> Result<Self, ArithmeticError> {
+        match self.is_zero() {
+            true => Err(ArithmeticError::DivideByZero),
+            false => self.inverse(),
+        }
+    }
+}
+
+impl<R: CommRing, O: MonomialOrder> Monoid for MPolynomial<R, O> {}
+impl<R: CommRing, O: MonomialOrder> CommRing for MPolynomial<R, O> {}
+
+impl<R: CommRing> Monoid for Polynomial<R> {}
+impl<R: CommRing> CommRing for Polynomial<R> {}
+
+/// The normal polynomials are the ones with a normal leading coefficient.
+impl<R: IntegralDomain> IntegralDomain for Polynomial<R> {
+    fn div_exact(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        if self.deg() < other.deg() {
+            return self.is_zero().then(Self::zero);
+        }
+        let n = other.deg() as usize;
+        let lead = other.lead_coeff();
+        let divisor = other.coeffs_take(n + 1);
+        let mut r = self.coeffs_take(self.deg() as usize + 1);
+        let mut q = vec![R::zero(); r.len() - n];
+        for k in (0..q.len()).rev() {
+            let c = r[k + n].div_exact(&lead)?;
+            for (j, d) in divisor.iter().enumerate() {
+                r[k + j] = r[k + j].clone() - c.clone() * d.clone();
+            }
+            q[k] = c;
+        }
+        r.iter().all(Zero::is_zero).then(|| Polynomial::from(q))
+    }
+
+    fn normal_unit(&self) -> Self {
+        Polynomial::from(vec![self.lead_coeff().normal_unit()])
+    }
+}
+
+/// The gcd of the coefficients.
+fn content<R: GcdDomain>(f: &Polynomial<R>) -> R {
+    f.coeffs_take(f.deg() as usize + 1)
+        .iter()
+        .fold(R::zero(), |acc, c| acc.gcd(c))
+}
+
+/// $f/c$, for a common divisor $c \neq 0$ of the coefficients.
+fn divide_coeffs<R: GcdDomain>(f: &Polynomial<R>, c: &R) -> Polynomial<R> {
+    let coeffs = f.coeffs_take(f.deg() as usize + 1);
+    Polynomial::from(
+        coeffs
+            .iter()
+            .map(|a| a.div_exact(c).unwrap())
+            .collect::<Vec<R>>(),
+    )
+}
+
+/// The primitive part, which is zero for zero.
+fn primitive<R: GcdDomain>(f: &Polynomial<R>) -> Polynomial<R> {
+    match f.is_zero() {
+        true => f.clone(),
+        false => divide_coeffs(f, &content(f)),
+    }
+}
+
+/// The pseudo-remainder of $a$ by $b \neq 0$: the remainder of $\mathrm{lc}(b)^k a$ for a $k$ that makes the
+/// division work over $R$.
+fn pseudo_rem<R: CommRing>(a: &Polynomial<R>, b: &Polynomial<R>) -> Polynomial<R> {
+    let lead = Polynomial::from(vec![b.lead_coeff()]);
+    let mut r = a.clone();
+    while !r.is_zero() && r.deg() >= b.deg() {
+        let mut shift = vec![R::zero(); (r.deg() - b.deg()) as usize];
+        shift.push(r.lead_coeff());
+        r = r * lead.clone() - Polynomial::from(shift) * b.clone();
+    }
+    r
+}
+
+/// Gauss's lemma: the gcd is the gcd of the contents times the gcd of the primitive parts, and the second one
+/// comes from the primitive remainder sequence.
+impl<R: GcdDomain> GcdDomain for Polynomial<R> {
+    fn gcd(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return (self.clone() + other.clone()).normalize();
+        }
+        let c = content(self).gcd(&content(other));
+        let (mut a, mut b) = (primitive(self), primitive(other));
+        if a.deg() < b.deg() {
+            (a, b) = (b, a);
+        }
+        while !b.is_zero() {
+            let r = primitive(&pseudo_rem(&a, &b));
+            (a, b) = (b, r);
+        }
+        (a * Polynomial::from(vec![c])).normalize()
+    }
+}
+
+/// Long division, with the degree as the norm.
+impl<F: Field> EuclideanDomain for Polynomial<F> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Polynomial::div_rem(self, other)
+    }
+
+    fn norm(&self) -> Integer {
+        Integer::from(self.deg())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::zpoly;
+
+    #[test]
+    fn test_integer() {
+        let (a, b) = (Integer::from(-12), Integer::from(18));
+        assert_eq!(GcdDomain::gcd(&a, &b), 6);
+        assert_eq!(GcdDomain::lcm(&a, &b), 36);
+        assert_eq!(a.normalize(), 12);
+        assert_eq!(
+            IntegralDomain::div_exact(&a, &Integer::from(4)),
+            Some(Integer::from(-3))
+        );
+        assert_eq!(IntegralDomain::div_exact(&a, &Integer::from(5)), None);
+        assert_eq!(IntegralDomain::div_exact(&a, &Integer::new()), None);
+        assert!(Integer::from(-1).is_unit() && !Integer::from(2).is_unit());
+        assert_eq!(
+            EuclideanDomain::div_rem(&Integer::from(-7), &Integer::from(3)),
+            (Integer::from(-3), Integer::from(2))
+        );
+        let (g, s, t) = ext_gcd(&a, &b);
+        assert_eq!(g, 6);
+        assert_eq!(s * a + t * b, 6);
+        assert_eq!(euclid_gcd(&Integer::new(), &Integer::from(-5)), 5);
+    }
+
+    #[test]
+    fn test_rational() {
+        let a = Rational::from((-3, 4));
+        assert_eq!(Field::inv(&a).unwrap(), Rational::from((-4, 3)));
+        assert!(Field::inv(&Rational::new()).is_err());
+        assert_eq!(a.normalize(), 1);
+        assert_eq!(
+            IntegralDomain::div_exact(&a, &Rational::from(3)),
+            Some(Rational::from((-1, 4)))
+        );
+        assert_eq!(GcdDomain::gcd(&a, &Rational::new()), 1);
+    }
+
+    #[test]
+    fn test_polynomial() {
+        // over Z: gcd(6x^2 - 6, 4x^2 + 8x + 4) = 2(x + 1)
+        let f = zpoly(vec![-6, 0, 6]);
+        let g = zpoly(vec![4, 8, 4]);
+        assert_eq!(GcdDomain::gcd(&f, &g), zpoly(vec![2, 2]));
+        assert_eq!(f.lcm(&g), zpoly(vec![-12, -12, 12, 12]));
+        assert_eq!(f.div_exact(&zpoly(vec![2, 2])), Some(zpoly(vec![-3, 3])));
+        assert_eq!(f.div_exact(&zpoly(vec![4, 4])), None);
+        assert_eq!(zpoly(vec![1, -2]).normalize(), zpoly(vec![-1, 2]));
+        assert_eq!(
+            GcdDomain::gcd(&zpoly(vec![0]), &zpoly(vec![-3])),
+            zpoly(vec![3])
+        );
+        // over Z/7
+        type F = Zmod<7>;
+        let p = |c: Vec<u64>| Polynomial::from(c.into_iter().map(F::new).collect::<Vec<_>>());
+        let (a, b) = (p(vec![1, 0, 1]), p(vec![3, 1]));
+        let (g, s, t) = ext_gcd(&a, &b);
+        assert!(g.is_one());
+        assert_eq!(s * a.clone() + t * b.clone(), g);
+        assert_eq!(
+            GcdDomain::gcd(&(a.clone() * b.clone()), &(b.clone() * b.clone())),
+            p(vec![3, 1])
+        );
+        assert_eq!(
+            euclid_gcd(&(a.clone() * b.clone()), &(a.clone() * a)),
+            p(vec![1, 0, 1])
+        );
+        assert_eq!(F::new(3).inv().unwrap(), F::new(5));
+        assert!(F::zero().inv().is_err());
+    }
+
+    #[test]
+    fn test_groups() {
+        let a = Additive(Integer::from(5));
+        assert_eq!(
+            a.clone() * Additive(Integer::from(-2)),
+            Additive(Integer::from(3))
+        );
+        assert_eq!(a.clone() * a.inv(), Additive::one());
+        assert!(Unit::new(Integer::from(2)).is_none());
+        let u = Unit::new(Integer::from(-1)).unwrap();
+        assert_eq!(u.clone() * u.clone(), Unit::one());
+        type F = Zmod<7>;
+        let v = Unit::new(F::new(3)).unwrap();
+        assert_eq!(v.inv().value(), &F::new(5));
+        assert!(Unit::new(F::zero()).is_none());
+        // the units of Q[x] are the nonzero constants
+        let c = Polynomial::from(vec![Rational::from((2, 3))]);
+        assert_eq!(
+            Unit::new(c).unwrap().inv().value(),
+            &Polynomial::from(vec![Rational::from((3, 2))])
+        );
+        assert!(Unit::new(Polynomial::from(vec![Rational::from(1), Rational::from(1)])).is_none());
+    }
+}
@@ -0,0 +1,34 @@
+//! Fixtures shared by the tests of the other modules.
+use crate::mpoly::MPolynomial;
+use crate::poly::Polynomial;
+use crate::{Integer, Rational};
+
+/// A multivariate polynomial over $\mathbb{Q}$ from its terms, as (exponents, coefficient) pairs.
+pub fn poly<P: From<Vec<(Vec<u32>, Rational)>>>(terms: Vec<(Vec<u32>, i64)>) -> P {
+    P::from(
+        terms
+            .into_iter()
+            .map(|(m, c)| (m, Rational::from(c)))
+            .collect::<Vec<(Vec<u32>, Rational)>>(),
+    )
+}
+
+/// A multivariate polynomial over $\mathbb{Z}$ from its terms, as (exponents, coefficient) pairs.
+pub fn zmpoly(terms: Vec<(Vec<u32>, i64)>) -> MPolynomial<Integer> {
+    MPolynomial::from(
+        terms
+            .into_iter()
+            .map(|(m, c)| (m, Integer::from(c)))
+            .collect::<Vec<(Vec<u32>, Integer)>>(),
+    )
+}
+
+/// A polynomial over $\mathbb{Z}$ from its coefficients, constant term first.
+pub fn zpoly(v: Vec<i64>) -> Polynomial<Integer> {
+    Polynomial::from(ints(v))
+}
+
+/// The `Integer`s with these values.
+pub fn ints(v: Vec<i64>) -> Vec<Integer> {
+    v.into_iter().map(Integer::from).collect()
+}
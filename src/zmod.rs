@@ -0,0 +1,656 @@
+//! Integers mod n
+//!
+//! `Zmod<N>` is $\mathbb{Z}/N$ for a modulus known at compile time, and `ZmodDyn` is $\mathbb{Z}/n$ for a modulus
+//! that's only known at runtime (from user input, say).
+//!
+//! `Zmod<N>` keeps elements for odd $N$ in Montgomery form: $a$ is stored as $aR \bmod N$ with $R = 2^{64}$, so a
+//! product needs two word multiplications and no 128-bit division. Even moduli are stored as they are.
+//!
+//! `ZmodDyn` is backed by `rug::Integer`. Since `Zero::zero()` and `One::one()` can't know the modulus, they give
+//! elements without one, which take on the modulus of whatever they're combined with. Combining elements with
+//! two different moduli panics.
+//!
+//! Both implement `Div`, so they're a `FieldType`, but it's only a field for a prime modulus: dividing by a
+//! non-unit panics. Use `inverse` to get an error instead.
+//!
+//! # Example:
+//! ```
+//! use bored_algebra::zmod::{Zmod, ZmodDyn};
+//! use bored_algebra::Integer;
+//!
+//! type F = Zmod<7>;
+//! assert_eq!(F::new(3) * F::new(5), F::new(1));
+//! assert_eq!(F::new(3).inverse().unwrap(), F::new(5));
+//! assert!(Zmod::<8>::new(4).inverse().is_err());
+//!
+//! let n = (Integer::from(1) << 127) - 1; // a Mersenne prime
+//! let a = ZmodDyn::new(Integer::from(2), &n);
+//! assert_eq!(a.pow(&(n.clone() - 1)).value(), &1);
+//! ```
+use crate::error::ArithmeticError;
+use crate::module::ModType;
+use crate::ntheory::units;
+use crate::Integer;
+
+use core::fmt;
+use core::fmt::{Debug, Display};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use num_traits::identities::{One, Zero};
+use rug::ops::RemRounding;
+
+/// $N^{-1} \bmod 2^{64}$ for odd $N$, by Newton's method: each step doubles the number of correct bits, and
+/// $N$ is its own inverse mod 8.
+const fn inverse_mod_word(n: u64) -> u64 {
+    let mut inv = n;
+    let mut k = 0;
+    while k < 5 {
+        inv = inv.wrapping_mul(2_u64.wrapping_sub(n.wrapping_mul(inv)));
+        k += 1;
+    }
+    inv
+}
+
+/// $a b \bmod n$ without overflowing.
+const fn mul_mod_word(a: u64, b: u64, n: u64) -> u64 {
+    (a as u128 * b as u128 % n as u128) as u64
+}
+
+/// Miller-Rabin with the first twelve primes as bases, which is deterministic for $n < 2^{64}$. It's a `const fn`
+/// so that primality of a modulus can be checked at compile time.
+pub(crate) const fn is_prime_word(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < BASES.len() {
+        if n.is_multiple_of(BASES[i]) {
+            return n == BASES[i];
+        }
+        i += 1;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    i = 0;
+    'bases: while i < BASES.len() {
+        // x = a^d mod n
+        let (mut x, mut base, mut e) = (1, BASES[i], d);
+        while e > 0 {
+            if e & 1 == 1 {
+                x = mul_mod_word(x, base, n);
+            }
+            base = mul_mod_word(base, base, n);
+            e >>= 1;
+        }
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod_word(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+/// An element of $\mathbb{Z}/N$, for $0 < N < 2^{64}$.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Zmod<const N: u64> {
+    repr: u64, // aR mod N for odd N, a for even N
+}
+
+impl<const N: u64> Zmod<N> {
+    const MONTGOMERY: bool = N % 2 == 1;
+    const N_INV: u64 = inverse_mod_word(N);
+    /// $R^2 \bmod N$, for moving into Montgomery form.
+    const R2: u64 = {
+        let r = (1_u128 << 64) % N as u128;
+        (r * r % N as u128) as u64
+    };
+    /// Evaluating this fails to compile for composite $N$, which is how `Zmod<N>` is only a `Field` (see
+    /// `structure`) for prime $N$.
+    pub(crate) const ASSERT_PRIME: () =
+        assert!(is_prime_word(N), "Z/N is only a field for prime N");
+
+    /// Montgomery reduction: $tR^{-1} \bmod N$, for $t < NR$.
+    fn redc(t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(Self::N_INV);
+        // t - mN is divisible by R, and their low words cancel
+        let (th, mh) = ((t >> 64) as u64, ((m as u128 * N as u128) >> 64) as u64);
+        match th >= mh {
+            true => th - mh,
+            false => th.wrapping_sub(mh).wrapping_add(N),
+        }
+    }
+
+    /// The residue of $a$.
+    pub fn new(a: u64) -> Self {
+        let a = a % N;
+        match Self::MONTGOMERY {
+            true => Self {
+                repr: Self::redc(a as u128 * Self::R2 as u128),
+            },
+            false => Self { repr: a },
+        }
+    }
+
+    /// The representative in $[0, N)$.
+    pub fn value(&self) -> u64 {
+        match Self::MONTGOMERY {
+            true => Self::redc(self.repr as u128),
+            false => self.repr,
+        }
+    }
+
+    pub fn modulus() -> u64 {
+        N
+    }
+
+    /// `self` to the power of $e$, by repeated squaring.
+    pub fn pow(&self, mut e: u64) -> Self {
+        let (mut base, mut acc) = (*self, Self::one());
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        acc
+    }
+
+    /// The inverse, by the extended Euclidean algorithm. Fails with `NotUnit` if `self` shares a factor with $N$.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        let (mut r0, mut r1) = (N as i128, self.value() as i128);
+        let (mut t0, mut t1) = (0_i128, 1_i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        match r0 == 1 || N == 1 {
+            true => Ok(Self::new(t0.rem_euclid(N as i128) as u64)),
+            false => Err(ArithmeticError::NotUnit),
+        }
+    }
+
+    pub fn is_unit(&self) -> bool {
+        self.inverse().is_ok()
+    }
+
+    /// The order of `self` in the unit group, or `None` if it's not a unit. See `ntheory::units`.
+    pub fn multiplicative_order(&self) -> Option<u64> {
+        units::multiplicative_order(&Integer::from(self.value()), &Integer::from(N))
+            .map(|k| k.to_u64().unwrap())
+    }
+
+    /// The smallest primitive root, or `None` if the unit group isn't cyclic.
+    pub fn primitive_root() -> Option<Self> {
+        units::primitive_root(&Integer::from(N)).map(|g| Self::from(&g))
+    }
+
+    /// The smallest $x \geq 0$ with `base`$^x$ = `self`, or `None` if there isn't one or they aren't units.
+    pub fn discrete_log(&self, base: &Self) -> Option<u64> {
+        units::discrete_log(
+            &Integer::from(base.value()),
+            &Integer::from(self.value()),
+            &Integer::from(N),
+        )
+        .map(|x| x.to_u64().unwrap())
+    }
+
+    /// The Carmichael function $\lambda(N)$, the exponent of the unit group.
+    pub fn carmichael_lambda() -> u64 {
+        units::carmichael_lambda(&Integer::from(N))
+            .to_u64()
+            .unwrap()
+    }
+}
+
+impl<const N: u64> From<u64> for Zmod<N> {
+    fn from(a: u64) -> Self {
+        Self::new(a)
+    }
+}
+
+impl<const N: u64> From<i64> for Zmod<N> {
+    fn from(a: i64) -> Self {
+        Self::new((a as i128).rem_euclid(N as i128) as u64)
+    }
+}
+
+impl<const N: u64> From<&Integer> for Zmod<N> {
+    fn from(a: &Integer) -> Self {
+        Self::new(Integer::from(a.rem_euc(N)).to_u64().unwrap())
+    }
+}
+
+impl<const N: u64> Zero for Zmod<N> {
+    fn zero() -> Self {
+        Self { repr: 0 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.repr == 0
+    }
+}
+
+impl<const N: u64> One for Zmod<N> {
+    fn one() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const N: u64> Add for Zmod<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Montgomery form is additive, so this is the same either way
+        let (s, overflow) = self.repr.overflowing_add(rhs.repr);
+        Self {
+            repr: match overflow || s >= N {
+                true => s.wrapping_sub(N),
+                false => s,
+            },
+        }
+    }
+}
+
+impl<const N: u64> Neg for Zmod<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            repr: match self.repr {
+                0 => 0,
+                a => N - a,
+            },
+        }
+    }
+}
+
+impl<const N: u64> Sub for Zmod<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const N: u64> Mul for Zmod<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let t = self.repr as u128 * rhs.repr as u128;
+        Self {
+            repr: match Self::MONTGOMERY {
+                true => Self::redc(t),
+                false => (t % N as u128) as u64,
+            },
+        }
+    }
+}
+
+/// Panics if `rhs` isn't a unit.
+impl<const N: u64> Div for Zmod<N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("divide by a non-unit")
+    }
+}
+
+impl<const N: u64> ModType<Zmod<N>> for Zmod<N> {
+    fn mod_mul(r: Zmod<N>, m: Zmod<N>) -> Zmod<N> {
+        r * m
+    }
+}
+
+/// $\mathbb{Z}/N$ as a $\mathbb{Z}$-module.
+impl<const N: u64> ModType<Integer> for Zmod<N> {
+    fn mod_mul(r: Integer, m: Zmod<N>) -> Zmod<N> {
+        Zmod::from(&r) * m
+    }
+}
+
+impl<const N: u64> Debug for Zmod<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mod {}", self.value(), N)
+    }
+}
+
+impl<const N: u64> Display for Zmod<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// An element of $\mathbb{Z}/n$ for a modulus $n$ chosen at runtime, or an integer still waiting for a modulus
+/// (see the module docs).
+#[derive(Clone, Debug)]
+pub struct ZmodDyn {
+    value: Integer,   // in [0, modulus), unless the modulus is 0
+    modulus: Integer, // 0 for no modulus yet
+}
+
+impl ZmodDyn {
+    /// The residue of $a$ mod $n$. Panics unless $n > 0$.
+    pub fn new(a: Integer, n: &Integer) -> Self {
+        assert!(*n > 0, "the modulus needs to be positive");
+        Self {
+            value: a.rem_euc(n),
+            modulus: n.clone(),
+        }
+    }
+
+    /// The representative in $[0, n)$, or the plain integer if there's no modulus yet.
+    pub fn value(&self) -> &Integer {
+        &self.value
+    }
+
+    /// The modulus, or `None` for elements made by `zero()` and `one()` that haven't met one yet.
+    pub fn modulus(&self) -> Option<&Integer> {
+        match self.modulus.is_zero() {
+            true => None,
+            false => Some(&self.modulus),
+        }
+    }
+
+    /// The modulus shared by `self` and `other`, or 0 if neither has one. Panics if they're different.
+    fn common_modulus(&self, other: &Self) -> Integer {
+        match (self.modulus.is_zero(), other.modulus.is_zero()) {
+            (true, _) => other.modulus.clone(),
+            (_, true) => self.modulus.clone(),
+            _ => {
+                assert_eq!(
+                    self.modulus, other.modulus,
+                    "elements have different moduli"
+                );
+                self.modulus.clone()
+            }
+        }
+    }
+
+    fn with_modulus(value: Integer, modulus: Integer) -> Self {
+        match modulus.is_zero() {
+            true => Self { value, modulus },
+            false => Self {
+                value: value.rem_euc(&modulus),
+                modulus,
+            },
+        }
+    }
+
+    /// `self` to the power of $e \geq 0$. Panics if there's no modulus and $e$ doesn't fit in a `u32`.
+    pub fn pow(&self, e: &Integer) -> Self {
+        match self.modulus.is_zero() {
+            true => Self::with_modulus(
+                rug::ops::Pow::pow(self.value.clone(), e.to_u32().expect("exponent too big")),
+                Integer::new(),
+            ),
+            false => Self {
+                value: Integer::from(self.value.pow_mod_ref(e, &self.modulus).unwrap()),
+                modulus: self.modulus.clone(),
+            },
+        }
+    }
+
+    /// The inverse, failing with `NotUnit` if `self` shares a factor with the modulus. Without a modulus, only
+    /// $\pm 1$ are units.
+    pub fn inverse(&self) -> Result<Self, ArithmeticError> {
+        if self.modulus.is_zero() {
+            return match self.value == 1 || self.value == -1 {
+                true => Ok(self.clone()),
+                false => Err(ArithmeticError::NotUnit),
+            };
+        }
+        match self.value.invert_ref(&self.modulus) {
+            Some(inv) => Ok(Self {
+                value: Integer::from(inv),
+                modulus: self.modulus.clone(),
+            }),
+            None if self.modulus == 1 => Ok(self.clone()),
+            None => Err(ArithmeticError::NotUnit),
+        }
+    }
+
+    pub fn is_unit(&self) -> bool {
+        self.inverse().is_ok()
+    }
+
+    /// The order of `self` in the unit group, or `None` if it's not a unit. Panics if there's no modulus.
+    pub fn multiplicative_order(&self) -> Option<Integer> {
+        units::multiplicative_order(&self.value, self.modulus().expect("no modulus"))
+    }
+
+    /// The smallest primitive root mod $n$, or `None` if the unit group isn't cyclic.
+    pub fn primitive_root(n: &Integer) -> Option<Self> {
+        units::primitive_root(n).map(|g| Self::new(g, n))
+    }
+
+    /// The smallest $x \geq 0$ with `base`$^x$ = `self`, or `None` if there isn't one or they aren't units. Panics if
+    /// neither has a modulus.
+    pub fn discrete_log(&self, base: &Self) -> Option<Integer> {
+        let n = self.common_modulus(base);
+        assert!(!n.is_zero(), "no modulus");
+        units::discrete_log(&base.value, &self.value, &n)
+    }
+}
+
+impl PartialEq for ZmodDyn {
+    fn eq(&self, other: &Self) -> bool {
+        let m = self.common_modulus(other);
+        match m.is_zero() {
+            true => self.value == other.value,
+            false => Integer::from(&self.value - &other.value).is_divisible(&m),
+        }
+    }
+}
+
+impl Eq for ZmodDyn {}
+
+impl Zero for ZmodDyn {
+    fn zero() -> Self {
+        Self {
+            value: Integer::new(),
+            modulus: Integer::new(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl One for ZmodDyn {
+    fn one() -> Self {
+        Self {
+            value: Integer::from(1),
+            modulus: Integer::new(),
+        }
+    }
+}
+
+impl Add for ZmodDyn {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let m = self.common_modulus(&rhs);
+        Self::with_modulus(self.value + rhs.value, m)
+    }
+}
+
+impl Neg for ZmodDyn {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::with_modulus(-self.value, self.modulus)
+    }
+}
+
+impl Sub for ZmodDyn {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let m = self.common_modulus(&rhs);
+        Self::with_modulus(self.value - rhs.value, m)
+    }
+}
+
+impl Mul for ZmodDyn {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let m = self.common_modulus(&rhs);
+        Self::with_modulus(self.value * rhs.value, m)
+    }
+}
+
+/// Panics if `rhs` isn't a unit.
+impl Div for ZmodDyn {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        // the inverse needs to know the modulus, which might only come from self
+        let m = self.common_modulus(&rhs);
+        let rhs = Self::with_modulus(rhs.value, m);
+        self * rhs.inverse().expect("divide by a non-unit")
+    }
+}
+
+impl ModType<ZmodDyn> for ZmodDyn {
+    fn mod_mul(r: ZmodDyn, m: ZmodDyn) -> ZmodDyn {
+        r * m
+    }
+}
+
+/// $\mathbb{Z}/n$ as a $\mathbb{Z}$-module.
+impl ModType<Integer> for ZmodDyn {
+    fn mod_mul(r: Integer, m: ZmodDyn) -> ZmodDyn {
+        ZmodDyn::with_modulus(r * m.value, m.modulus)
+    }
+}
+
+impl Display for ZmodDyn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::poly::Polynomial;
+
+    // the largest prime below 2^64, to check the Montgomery reduction near overflow
+    const BIG: u64 = 18446744073709551557;
+
+    #[test]
+    fn test_montgomery() {
+        let m = Integer::from(BIG);
+        let samples = [
+            0,
+            1,
+            2,
+            12345678901234567,
+            BIG - 1,
+            BIG - 2,
+            1 << 63,
+            u64::MAX,
+        ];
+        for &a in samples.iter() {
+            assert_eq!(Zmod::<BIG>::new(a).value(), a % BIG);
+            for &b in samples.iter() {
+                let (x, y) = (Zmod::<BIG>::new(a), Zmod::<BIG>::new(b));
+                let expected = |c: Integer| c.rem_euc(&m).to_u64().unwrap();
+                assert_eq!((x * y).value(), expected(Integer::from(a) * b));
+                assert_eq!((x + y).value(), expected(Integer::from(a) + b));
+                assert_eq!((x - y).value(), expected(Integer::from(a) - b));
+            }
+        }
+        // Fermat, and an inverse
+        let x = Zmod::<BIG>::new(3);
+        assert_eq!(x.pow(BIG - 1), Zmod::one());
+        assert_eq!(x * x.inverse().unwrap(), Zmod::one());
+        assert_eq!(x / x, Zmod::one());
+    }
+
+    #[test]
+    fn test_zmod() {
+        type Z12 = Zmod<12>;
+        assert_eq!(Z12::new(7) * Z12::new(5), Z12::new(11));
+        assert_eq!(Z12::from(-1_i64), Z12::new(11));
+        assert_eq!(Z12::new(5).inverse().unwrap(), Z12::new(5));
+        assert!(matches!(
+            Z12::new(6).inverse(),
+            Err(ArithmeticError::NotUnit)
+        ));
+        assert!(!Z12::new(0).is_unit());
+        assert_eq!(-Z12::new(0), Z12::zero());
+        assert_eq!(Z12::mod_mul(Integer::from(-25), Z12::new(2)), Z12::new(10));
+        assert_eq!(format!("{} {:?}", Z12::new(14), Z12::new(3)), "2 3 mod 12");
+        // the units mod 12 are Z/2 x Z/2
+        assert_eq!(Z12::carmichael_lambda(), 2);
+        assert_eq!(Z12::primitive_root(), None);
+        assert_eq!(Z12::new(7).multiplicative_order(), Some(2));
+        assert_eq!(Zmod::<13>::primitive_root(), Some(Zmod::new(2)));
+        assert_eq!(Zmod::<13>::new(3).discrete_log(&Zmod::new(2)), Some(4));
+        // Z/1 is the zero ring, where 0 is a unit
+        assert_eq!(Zmod::<1>::one(), Zmod::<1>::zero());
+        assert!(Zmod::<1>::zero().is_unit());
+
+        // x^2 + 1 = (x + 2)(x + 3) over Z/5
+        type F = Zmod<5>;
+        let f = Polynomial::from(vec![F::new(1), F::zero(), F::new(1)]);
+        let g = Polynomial::from(vec![F::new(2), F::new(1)]);
+        assert_eq!(
+            f.div_rem(&g),
+            (
+                Polynomial::from(vec![F::new(3), F::new(1)]),
+                Polynomial::zero()
+            )
+        );
+    }
+
+    #[test]
+    fn test_zmod_dyn() {
+        let n = Integer::from(35);
+        let a = ZmodDyn::new(Integer::from(-1), &n);
+        assert_eq!(a.value(), &34);
+        assert_eq!(a.modulus(), Some(&n));
+        // zero and one pick up the modulus
+        assert_eq!(a.clone() + ZmodDyn::one(), ZmodDyn::zero());
+        assert_eq!((a.clone() * ZmodDyn::one()).modulus(), Some(&n));
+        assert_eq!(ZmodDyn::one() - ZmodDyn::one(), ZmodDyn::zero());
+        assert_eq!(ZmodDyn::one() / a.clone(), a);
+        let b = ZmodDyn::new(Integer::from(12), &n);
+        assert_eq!(b.inverse().unwrap() * b.clone(), ZmodDyn::one());
+        assert!(ZmodDyn::new(Integer::from(14), &n).inverse().is_err());
+        assert_eq!(b.pow(&Integer::from(24)), ZmodDyn::one()); // phi(35) = 24
+        assert_eq!(b.multiplicative_order(), Some(Integer::from(12)));
+        assert_eq!(
+            b.pow(&Integer::from(5)).discrete_log(&b),
+            Some(Integer::from(5))
+        );
+        assert_eq!(ZmodDyn::primitive_root(&n), None);
+        assert_eq!(ZmodDyn::mod_mul(Integer::from(3), b).value(), &1);
+        // the same thing as Zmod<35>
+        let c = ZmodDyn::new(Integer::from(17), &n) * ZmodDyn::new(Integer::from(29), &n);
+        assert_eq!(c.value(), &Zmod::<35>::new(17 * 29).value());
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn test_different_moduli() {
+        let _ = ZmodDyn::new(Integer::from(1), &Integer::from(3))
+            + ZmodDyn::new(Integer::from(1), &Integer::from(5));
+    }
+}